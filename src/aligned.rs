@@ -0,0 +1,182 @@
+//! Storage with a guaranteed minimum alignment.
+//!
+//! SIMD kernels and DMA engines often require their buffers to start at a
+//! 16-, 32- or 64-byte boundary. [`Aligned`] raises the alignment of any value
+//! to that of a marker type such as [`Align32`], which can be used either to
+//! align the backing slice of a [`SliceDeque`] or, via [`AlignedArrayDeque`],
+//! the backing array of an [`ArrayDeque`].
+//!
+//! Only the start of the backing storage is aligned. The slices returned by
+//! `as_slices()` begin at the first element of the deque, so they are only
+//! aligned if that element is stored at an aligned offset, such as when the
+//! deque was filled from empty with `push_back()`.
+//!
+//! [`SliceDeque`]: crate::SliceDeque
+//! [`ArrayDeque`]: crate::ArrayDeque
+
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use crate::ArrayDeque;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A marker type specifying the alignment of an [`Aligned`] value.
+///
+/// This trait is sealed and cannot be implemented outside this crate.
+pub trait Alignment: sealed::Sealed + Copy + Default + fmt::Debug {
+    /// The alignment in bytes.
+    const ALIGN: usize;
+}
+
+macro_rules! alignments {
+    ($($(#[$attr:meta])* $name:ident = $align:literal;)*) => {
+        $(
+            $(#[$attr])*
+            #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+            #[repr(align($align))]
+            pub struct $name;
+
+            impl sealed::Sealed for $name {}
+
+            impl Alignment for $name {
+                const ALIGN: usize = $align;
+            }
+        )*
+    };
+}
+
+alignments! {
+    /// Aligns a value to a 16-byte boundary.
+    Align16 = 16;
+    /// Aligns a value to a 32-byte boundary.
+    Align32 = 32;
+    /// Aligns a value to a 64-byte boundary.
+    Align64 = 64;
+}
+
+/// A value whose address is aligned to at least `A::ALIGN` bytes.
+///
+/// `Aligned` dereferences to the value it wraps.
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::{aligned::{Align32, Aligned}, SliceDeque};
+/// let mut storage: Aligned<Align32, [f32; 16]> = Aligned::new([0.0; 16]);
+/// let mut deque = SliceDeque::new_in(&mut storage[..]);
+///
+/// for i in 0..8 {
+///     deque.push_back(i as f32).unwrap();
+/// }
+///
+/// let (front, _) = deque.as_slices();
+/// assert_eq!(front.as_ptr() as usize % 32, 0);
+/// ```
+#[derive(Clone, Copy, Default)]
+#[repr(C)]
+pub struct Aligned<A, T>
+where
+    A: Alignment,
+{
+    _align: [A; 0],
+    value: T,
+}
+
+/// An [`ArrayDeque`] whose backing array is aligned to at least `A::ALIGN`
+/// bytes.
+///
+/// The deque is accessed through `Deref` and `DerefMut`.
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::aligned::{Align64, AlignedArrayDeque};
+/// let mut deque: AlignedArrayDeque<u8, 256, Align64> = AlignedArrayDeque::default();
+///
+/// deque.push_back(1).unwrap();
+/// deque.push_back(2).unwrap();
+///
+/// let (front, _) = deque.as_slices();
+/// assert_eq!(front, &[1, 2]);
+/// assert_eq!(front.as_ptr() as usize % 64, 0);
+/// ```
+pub type AlignedArrayDeque<T, const N: usize, A> = Aligned<A, ArrayDeque<T, N>>;
+
+impl<A, T> Aligned<A, T>
+where
+    A: Alignment,
+{
+    /// Wraps `value`, aligning it to at least `A::ALIGN` bytes.
+    #[inline]
+    pub const fn new(value: T) -> Self {
+        Aligned { _align: [], value }
+    }
+
+    /// Consumes the `Aligned`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<A, T> Deref for Aligned<A, T>
+where
+    A: Alignment,
+{
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<A, T> DerefMut for Aligned<A, T>
+where
+    A: Alignment,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<A, T> fmt::Debug for Aligned<A, T>
+where
+    A: Alignment,
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::mem;
+
+    #[test]
+    fn alignment_is_raised() {
+        assert_eq!(mem::align_of::<Aligned<Align16, u8>>(), 16);
+        assert_eq!(mem::align_of::<Aligned<Align64, [u8; 3]>>(), 64);
+        assert_eq!(mem::size_of::<Aligned<Align32, [u8; 40]>>(), 64);
+    }
+
+    #[test]
+    fn array_deque_items_are_aligned() {
+        let mut deques: [AlignedArrayDeque<u8, 3, Align32>; 4] = Default::default();
+
+        for deque in deques.iter_mut() {
+            deque.push_back(0).unwrap();
+            let (front, _) = deque.as_slices();
+            assert_eq!(front.as_ptr() as usize % 32, 0);
+        }
+    }
+}
@@ -1,13 +1,36 @@
 //! A double-ended queue with fixed capacity, backed by an array.
 
+use core::{
+    cell::Cell,
+    cmp::Ordering,
+    fmt,
+    iter::Chain,
+    ops::{Deref, DerefMut},
+    slice,
+};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::concat::{Concat, Join};
+#[cfg(feature = "stats")]
+use crate::DequeStats;
 use crate::{
+    format::{DebugInternals, DisplaySeparated, HexDump},
     meta::{Meta, MetaLayout},
-    BaseDeque, CapacityError, DequeDrain, DequeIter,
+    snapshot::{self, LoadError, Persist},
+    BaseDeque, CapacityError, Deque, DequeCursor, DequeDrain, DequeDrainChunks, DequeExtractIf,
+    DequeHalf, DequeIter, DequePopGuard, DequeSlot, DequeTruncateGuard, LayoutInfo, Savepoint,
+    TryExtendError,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub(crate) struct ArrayMeta<const N: usize> {
     layout: MetaLayout,
+    generation: usize,
+    #[cfg(feature = "stats")]
+    stats: DequeStats,
 }
 
 impl<const N: usize> Meta for ArrayMeta<N> {
@@ -23,7 +46,38 @@ impl<const N: usize> Meta for ArrayMeta<N> {
 
     #[inline(always)]
     fn set_layout(&mut self, layout: MetaLayout) {
+        #[cfg(feature = "stats")]
+        let old_len = self.len();
+
         self.layout = layout;
+
+        #[cfg(feature = "stats")]
+        {
+            let new_len = self.len();
+            self.stats.record_len_change(old_len, new_len);
+        }
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline(always)]
+    fn stats(&self) -> &DequeStats {
+        &self.stats
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline(always)]
+    fn stats_mut(&mut self) -> &mut DequeStats {
+        &mut self.stats
+    }
+
+    #[inline(always)]
+    fn generation(&self) -> usize {
+        self.generation
+    }
+
+    #[inline(always)]
+    fn set_generation(&mut self, generation: usize) {
+        self.generation = generation;
     }
 }
 
@@ -34,13 +88,16 @@ impl<const N: usize> Meta for ArrayMeta<N> {
 /// All values are stored inline; that is, the size of of `ArrayDeque<T, N>` is
 /// *at least* `size_of::<[T; N]>()`, regardless of the number of elements
 /// currently stored in the deque.
-#[derive(Clone, Debug)]
+// `repr(C)` places the backing array at the start of the struct, which
+// `AlignedArrayDeque` relies on to align it.
+#[derive(Clone)]
+#[repr(C)]
 pub struct ArrayDeque<T, const N: usize>
 where
     T: Default,
 {
-    meta: ArrayMeta<N>,
     items: [T; N],
+    meta: ArrayMeta<N>,
 }
 
 impl<T, const N: usize> BaseDeque<T> for ArrayDeque<T, N>
@@ -55,7 +112,7 @@ where
     }
 
     #[inline(always)]
-    fn meta_mut(&mut self) -> &mut Self::Meta {
+    fn raw_meta_mut(&mut self) -> &mut Self::Meta {
         &mut self.meta
     }
 
@@ -65,16 +122,97 @@ where
     }
 
     #[inline(always)]
-    fn items_mut(&mut self) -> &mut [T] {
+    fn raw_items_mut(&mut self) -> &mut [T] {
         &mut self.items
     }
 
+    #[inline(always)]
+    fn raw_meta_and_items_mut(&mut self) -> (&mut Self::Meta, &mut [T]) {
+        (&mut self.meta, &mut self.items)
+    }
+
     #[inline(always)]
     fn capacity(&self) -> usize {
         N
     }
 }
 
+impl<T, const N: usize> Deque<T> for ArrayDeque<T, N>
+where
+    T: Default,
+{
+    #[inline]
+    fn capacity(&self) -> usize {
+        BaseDeque::capacity(self)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        BaseDeque::len(self)
+    }
+
+    #[inline]
+    fn front(&self) -> Option<&T> {
+        BaseDeque::front(self)
+    }
+
+    #[inline]
+    fn back(&self) -> Option<&T> {
+        BaseDeque::back(self)
+    }
+
+    #[inline]
+    fn as_slices(&self) -> (&[T], &[T]) {
+        BaseDeque::as_slices(self)
+    }
+
+    #[inline]
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        BaseDeque::as_mut_slices(self)
+    }
+
+    #[inline]
+    fn push_front(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        BaseDeque::push_front(self, item)
+    }
+
+    #[inline]
+    fn push_back(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        BaseDeque::push_back(self, item)
+    }
+
+    #[inline]
+    fn pop_front(&mut self) -> Option<T> {
+        BaseDeque::pop_front(self)
+    }
+
+    #[inline]
+    fn pop_back(&mut self) -> Option<T> {
+        BaseDeque::pop_back(self)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        BaseDeque::clear(self)
+    }
+
+    #[inline]
+    fn truncate_front(&mut self, len: usize) {
+        BaseDeque::truncate_front(self, len)
+    }
+
+    #[inline]
+    fn spare_slices_mut(&mut self) -> (&mut [T], &mut [T]) {
+        BaseDeque::spare_slices_mut(self)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn commit_back(&mut self, n: usize) {
+        ArrayDeque::commit_back(self, n)
+    }
+}
+
 impl<T, const N: usize> Default for ArrayDeque<T, N>
 where
     T: Default,
@@ -85,36 +223,55 @@ where
     }
 }
 
+impl<T, const N: usize> fmt::Debug for ArrayDeque<T, N>
+where
+    T: fmt::Debug + Default,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
 impl<T, const N: usize> PartialEq for ArrayDeque<T, N>
 where
     T: PartialEq + Default,
 {
     fn eq(&self, other: &Self) -> bool {
-        let mut it_other = other.iter();
-
-        for item_self in self.iter() {
-            let item_other = match it_other.next() {
-                Some(x) => x,
-                None => return false,
-            };
-
-            if item_self != item_other {
-                return false;
-            }
-        }
-
-        it_other.next().is_none()
+        BaseDeque::eq_elements(self, other)
     }
 }
 
 impl<T, const N: usize> Eq for ArrayDeque<T, N> where T: PartialEq + Default {}
 
+/// Appends the elements of an iterator to the back of the deque.
+///
+/// # Panics
+///
+/// Panics if the deque becomes full before the iterator is exhausted. Use
+/// `try_extend()` to handle this case without panicking.
+impl<T, const N: usize> Extend<T> for ArrayDeque<T, N>
+where
+    T: Default,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        BaseDeque::extend_expect(self, iter);
+    }
+}
+
 impl<T, const N: usize> ArrayDeque<T, N>
 where
     T: Default,
 {
     /// Constructs a new, empty `ArrayDeque<T, N>`.
     ///
+    /// Every slot of the backing array is initialized with `T::default()`.
+    /// For large deques of `Copy` elements, [`new_copied`] is faster.
+    ///
+    /// [`new_copied`]: ArrayDeque::new_copied
+    ///
     /// # Example
     ///
     /// ```
@@ -129,11 +286,63 @@ where
         ArrayDeque {
             meta: ArrayMeta {
                 layout: MetaLayout::Empty,
+                generation: 0,
+                #[cfg(feature = "stats")]
+                stats: DequeStats::new(),
             },
             items: [(); N].map(|_| Default::default()),
         }
     }
 
+    /// Appends an element to the back of the deque and returns the deque, for
+    /// building a deque in a single expression.
+    ///
+    /// If the deque is full, the element is returned in a `CapacityError` and
+    /// the deque is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let deque: ArrayDeque<u32, 4> = ArrayDeque::new().with_back(2)?.with_back(3)?;
+    ///
+    /// assert_eq!(deque.as_slices(), (&[2, 3][..], &[][..]));
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    pub fn with_back(mut self, item: T) -> Result<Self, CapacityError<T>> {
+        BaseDeque::push_back(&mut self, item)?;
+        Ok(self)
+    }
+
+    /// Prepends an element to the front of the deque and returns the deque,
+    /// for building a deque in a single expression.
+    ///
+    /// If the deque is full, the element is returned in a `CapacityError` and
+    /// the deque is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let deque: ArrayDeque<u32, 4> = ArrayDeque::new().with_back(2)?.with_front(1)?;
+    ///
+    /// assert_eq!(deque.front(), Some(&1));
+    /// assert_eq!(deque.back(), Some(&2));
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    pub fn with_front(mut self, item: T) -> Result<Self, CapacityError<T>> {
+        BaseDeque::push_front(&mut self, item)?;
+        Ok(self)
+    }
+
     /// Returns the maximum number of elements the deque may hold.
     ///
     /// This has the same value as the const generic parameter `N`.
@@ -334,6 +543,83 @@ where
         BaseDeque::back_mut(self)
     }
 
+    /// Returns mutable references to the first and last elements in the
+    /// deque at the same time.
+    ///
+    /// If the deque contains fewer than two elements, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+    ///
+    /// deque.try_extend([1, 2, 3].iter().copied()).unwrap();
+    ///
+    /// if let Some((head, tail)) = deque.ends_mut() {
+    ///     *head += 10;
+    ///     *tail += 20;
+    /// }
+    ///
+    /// assert_eq!(deque.as_slices(), (&[11, 2, 23][..], &[][..]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn ends_mut(&mut self) -> Option<(&mut T, &mut T)> {
+        BaseDeque::ends_mut(self)
+    }
+
+    /// Returns a reference to the element `index` positions from the back of
+    /// the deque, where index 0 is the last element.
+    ///
+    /// If `index` is out of bounds, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+    ///
+    /// deque.try_extend([10, 20, 30].iter().copied()).unwrap();
+    ///
+    /// assert_eq!(deque.get_from_back(0), Some(&30));
+    /// assert_eq!(deque.get_from_back(2), Some(&10));
+    /// assert_eq!(deque.get_from_back(3), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn get_from_back(&self, index: usize) -> Option<&T> {
+        BaseDeque::get_from_back(self, index)
+    }
+
+    /// Returns a mutable reference to the element `index` positions from the
+    /// back of the deque, where index 0 is the last element.
+    ///
+    /// If `index` is out of bounds, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+    ///
+    /// deque.try_extend([10, 20, 30].iter().copied()).unwrap();
+    ///
+    /// if let Some(reading) = deque.get_from_back_mut(1) {
+    ///     *reading += 5;
+    /// }
+    ///
+    /// assert_eq!(deque.as_slices(), (&[10, 25, 30][..], &[][..]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn get_from_back_mut(&mut self, index: usize) -> Option<&mut T> {
+        BaseDeque::get_from_back_mut(self, index)
+    }
+
     /// Returns a pair of slices which contain, in order, the elements of the
     /// `ArrayDeque`.
     ///
@@ -402,10 +688,16 @@ where
         BaseDeque::as_mut_slices(self)
     }
 
-    /// Prepends an element to the deque.
+    /// Returns a pair of slices of [`Cell`]s which contain, in order, the
+    /// elements of the `ArrayDeque`.
     ///
-    /// If the deque is at capacity, an `Err` containing the pushed value is
-    /// returned.
+    /// Because the returned slices are shared, several closures may hold them
+    /// at once and mutate the elements through [`Cell::set`] or
+    /// [`Cell::replace`].
+    ///
+    /// [`Cell`]: core::cell::Cell
+    /// [`Cell::set`]: core::cell::Cell::set
+    /// [`Cell::replace`]: core::cell::Cell::replace
     ///
     /// # Example
     ///
@@ -413,31 +705,51 @@ where
     /// # use holodeque::{ArrayDeque, CapacityError};
     /// # fn main() {
     /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
     ///
-    /// deque.push_front(1)?;
-    /// deque.push_front(2)?;
-    /// deque.push_front(3)?;
+    /// deque.push_back(1)?;
+    /// deque.push_back(2)?;
+    /// deque.push_front(0)?;
     ///
-    /// assert_eq!(deque.front(), Some(&3));
-    /// assert_eq!(deque.back(), Some(&1));
+    /// let (first, second) = deque.as_slices_of_cells();
+    /// let double = |i: usize| {
+    ///     let cell = first.iter().chain(second).nth(i).unwrap();
+    ///     cell.set(cell.get() * 2);
+    /// };
+    /// let bump_all = || first.iter().chain(second).for_each(|c| c.set(c.get() + 1));
     ///
-    /// // Another element would exceed capacity, so this fails.
-    /// let err = deque.push_front(4).unwrap_err();
-    /// assert_eq!(err.into_inner(), 4);
+    /// double(2);
+    /// bump_all();
+    ///
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2, 5]);
     /// # Ok(())
     /// # })().unwrap();
     /// # }
     /// ```
     #[inline]
-    pub fn push_front(&mut self, item: T) -> Result<(), CapacityError<T>> {
-        BaseDeque::push_front(self, item)
+    pub fn as_slices_of_cells(&mut self) -> (&[Cell<T>], &[Cell<T>]) {
+        BaseDeque::as_slices_of_cells(self)
     }
 
-    /// Appends an element to the deque.
+    /// Returns a pair of mutable slices which contain, in order, the unused
+    /// slots of the `ArrayDeque`.
     ///
-    /// If the deque is at capacity, an `Err` containing the pushed value is
-    /// returned.
+    /// The slots are ordered as they would be filled by repeated calls to
+    /// [`push_back`]. After writing to some prefix of the slots, call
+    /// [`commit_back`] to append them to the deque.
+    ///
+    /// Unused slots normally contain the default value of `T`, but may contain
+    /// stale elements if a draining iterator was leaked, if elements of a type
+    /// which does not need to be dropped were removed by [`clear`] or
+    /// [`truncate`], or if elements were removed by [`pop_front_copied`] or
+    /// [`pop_back_copied`].
+    ///
+    /// [`push_back`]: ArrayDeque::push_back
+    /// [`commit_back`]: ArrayDeque::commit_back
+    /// [`clear`]: ArrayDeque::clear
+    /// [`truncate`]: ArrayDeque::truncate
+    /// [`pop_front_copied`]: ArrayDeque::pop_front_copied
+    /// [`pop_back_copied`]: ArrayDeque::pop_back_copied
     ///
     /// # Example
     ///
@@ -445,88 +757,105 @@ where
     /// # use holodeque::{ArrayDeque, CapacityError};
     /// # fn main() {
     /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
+    /// let mut deque: ArrayDeque<u32, 5> = ArrayDeque::new();
     ///
     /// deque.push_back(1)?;
     /// deque.push_back(2)?;
     /// deque.push_back(3)?;
+    /// deque.pop_front();
     ///
-    /// assert_eq!(deque.front(), Some(&1));
-    /// assert_eq!(deque.back(), Some(&3));
+    /// let (first, second) = deque.spare_slices_mut();
+    /// assert_eq!((first.len(), second.len()), (2, 1));
     ///
-    /// // Another element would exceed capacity, so this fails.
-    /// let err = deque.push_front(4).unwrap_err();
-    /// assert_eq!(err.into_inner(), 4);
+    /// first[0] = 4;
+    /// first[1] = 5;
+    /// second[0] = 6;
+    /// deque.commit_back(3);
+    ///
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [2, 3, 4, 5, 6]);
     /// # Ok(())
     /// # })().unwrap();
     /// # }
     /// ```
     #[inline]
-    pub fn push_back(&mut self, item: T) -> Result<(), CapacityError<T>> {
-        BaseDeque::push_back(self, item)
+    pub fn spare_slices_mut(&mut self) -> (&mut [T], &mut [T]) {
+        BaseDeque::spare_slices_mut(self)
     }
 
-    /// Removes and returns the first element of the deque.
+    /// Appends the first `n` unused slots to the back of the deque.
     ///
-    /// If the deque is empty, `None` is returned.
+    /// This is used together with [`spare_slices_mut`] to fill the deque
+    /// in place. The appended elements are whatever values the slots contain.
+    ///
+    /// [`spare_slices_mut`]: ArrayDeque::spare_slices_mut
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` exceeds the number of unused slots.
     ///
     /// # Example
     ///
     /// ```
-    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # use holodeque::ArrayDeque;
     /// # fn main() {
-    /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
-    ///
-    /// deque.push_back(1)?;
-    /// deque.push_back(2)?;
-    /// deque.push_back(3)?;
+    /// let mut deque: ArrayDeque<u8, 4> = ArrayDeque::new();
     ///
-    /// assert_eq!(deque.pop_front(), Some(1));
-    /// assert_eq!(deque.pop_front(), Some(2));
-    /// assert_eq!(deque.pop_front(), Some(3));
-    /// assert_eq!(deque.pop_front(), None);
+    /// let (first, _) = deque.spare_slices_mut();
+    /// first[..2].copy_from_slice(b"hi");
+    /// deque.commit_back(2);
     ///
-    /// # Ok(())
-    /// # })().unwrap();
+    /// assert_eq!(deque.as_slices().0, b"hi");
     /// # }
     /// ```
     #[inline]
-    pub fn pop_front(&mut self) -> Option<T> {
-        BaseDeque::pop_front(self)
+    #[track_caller]
+    pub fn commit_back(&mut self, n: usize) {
+        if BaseDeque::commit_back(self, n).is_none() {
+            panic!(
+                "cannot commit {} slots; only {} are unused",
+                n,
+                self.capacity() - self.len()
+            );
+        }
     }
 
-    /// Removes and returns the last element of the deque.
+    /// Reserves a slot at the back of the deque, returning a guard which
+    /// provides mutable access to it.
     ///
-    /// If the deque is empty, `None` is returned.
+    /// The value in the slot becomes the back element of the deque only when
+    /// [`SlotGuard::commit`] is called. If the guard is dropped instead, the
+    /// deque is left unchanged. This allows an element to be built in place,
+    /// possibly fallibly, without exposing a partially built element.
+    ///
+    /// If the deque is full, `None` is returned.
     ///
     /// # Example
     ///
     /// ```
-    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # use holodeque::ArrayDeque;
     /// # fn main() {
-    /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
+    /// let mut deque: ArrayDeque<[u8; 4], 2> = ArrayDeque::new();
     ///
-    /// deque.push_back(1)?;
-    /// deque.push_back(2)?;
-    /// deque.push_back(3)?;
+    /// let mut slot = deque.reserve_back_slot().unwrap();
+    /// slot[0] = 1;
+    /// drop(slot);
+    /// assert!(deque.is_empty());
     ///
-    /// assert_eq!(deque.pop_back(), Some(3));
-    /// assert_eq!(deque.pop_back(), Some(2));
-    /// assert_eq!(deque.pop_back(), Some(1));
-    /// assert_eq!(deque.pop_back(), None);
-    ///
-    /// # Ok(())
-    /// # })().unwrap();
+    /// let mut slot = deque.reserve_back_slot().unwrap();
+    /// slot.copy_from_slice(&[1, 2, 3, 4]);
+    /// slot.commit();
+    /// assert_eq!(deque.back(), Some(&[1, 2, 3, 4]));
     /// # }
     /// ```
     #[inline]
-    pub fn pop_back(&mut self) -> Option<T> {
-        BaseDeque::pop_back(self)
+    pub fn reserve_back_slot(&mut self) -> Option<SlotGuard<'_, T, N>> {
+        DequeSlot::back(self).map(|inner| SlotGuard { inner })
     }
 
-    /// Clears the `ArrayDeque`, removing all values.
+    /// Prepends an element to the deque.
+    ///
+    /// If the deque is at capacity, an `Err` containing the pushed value is
+    /// returned.
     ///
     /// # Example
     ///
@@ -534,60 +863,62 @@ where
     /// # use holodeque::{ArrayDeque, CapacityError};
     /// # fn main() {
     /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut deque: ArrayDeque<usize, 8> = ArrayDeque::new();
+    /// let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
     ///
-    /// for i in 0..deque.capacity() / 2 {
-    ///     deque.push_front(i)?;
-    ///     deque.push_back(i)?;
-    /// }
+    /// deque.push_front(1)?;
+    /// deque.push_front(2)?;
+    /// deque.push_front(3)?;
     ///
-    /// assert_eq!(deque.len(), 8);
-    /// deque.clear();
-    /// assert!(deque.is_empty());
+    /// assert_eq!(deque.front(), Some(&3));
+    /// assert_eq!(deque.back(), Some(&1));
     ///
+    /// // Another element would exceed capacity, so this fails.
+    /// let err = deque.push_front(4).unwrap_err();
+    /// assert_eq!(err.into_inner(), 4);
     /// # Ok(())
     /// # })().unwrap();
     /// # }
     /// ```
     #[inline]
-    pub fn clear(&mut self) {
-        BaseDeque::clear(self)
+    pub fn push_front(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        BaseDeque::push_front(self, item)
     }
 
-    /// Shortens the `ArrayDeque`, keeping the first `len` elements and dropping
-    /// the rest.
+    /// Prepends an element to the deque, panicking if the deque is full.
     ///
-    /// If `len` is greater than the `ArrayDeque`'s current length, this has no
-    /// effect.
+    /// This is a convenience for code in which overflowing the deque is a
+    /// logic error. Prefer [`push_front`] where a full deque is an expected
+    /// condition.
+    ///
+    /// [`push_front`]: ArrayDeque::push_front
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deque is at capacity.
     ///
     /// # Example
     ///
     /// ```
-    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # use holodeque::ArrayDeque;
     /// # fn main() {
-    /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut deque: ArrayDeque<usize, 8> = ArrayDeque::new();
-    ///
-    /// deque.push_back(5)?;
-    /// deque.push_back(10)?;
-    /// deque.push_back(15)?;
-    /// deque.push_back(20)?;
-    /// deque.push_back(25)?;
+    /// let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
     ///
-    /// assert_eq!(deque.len(), 5);
-    /// deque.truncate(2);
-    /// assert_eq!(deque.len(), 2);
+    /// deque.push_front_expect(1);
+    /// deque.push_front_expect(2);
     ///
-    /// # Ok(())
-    /// # })().unwrap();
+    /// assert_eq!(deque.front(), Some(&2));
     /// # }
     /// ```
     #[inline]
-    pub fn truncate(&mut self, len: usize) {
-        BaseDeque::truncate(self, len)
+    #[track_caller]
+    pub fn push_front_expect(&mut self, item: T) {
+        BaseDeque::push_front_expect(self, item)
     }
 
-    /// Returns an iterator over the elements of the deque.
+    /// Prepends an element to the deque, returning a mutable reference to it.
+    ///
+    /// If the deque is at capacity, an `Err` containing the pushed value is
+    /// returned.
     ///
     /// # Example
     ///
@@ -595,43 +926,54 @@ where
     /// # use holodeque::{ArrayDeque, CapacityError};
     /// # fn main() {
     /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut deque: ArrayDeque<&str, 5> = ArrayDeque::new();
-    ///
-    /// deque.push_back("ideas")?;
-    /// deque.push_front("green")?;
-    /// deque.push_back("sleep")?;
-    /// deque.push_front("colorless")?;
-    /// deque.push_back("furiously")?;
+    /// let mut deque: ArrayDeque<(u32, &str), 3> = ArrayDeque::new();
     ///
-    /// let sentence = deque.iter().cloned().collect::<Vec<_>>();
+    /// let (id, _) = deque.push_front_mut((1, "pending"))?;
+    /// *id += 100;
     ///
-    /// assert_eq!(
-    ///     sentence,
-    ///     &["colorless", "green", "ideas", "sleep", "furiously"],
-    /// );
+    /// assert_eq!(deque.front(), Some(&(101, "pending")));
     /// # Ok(())
     /// # })().unwrap();
     /// # }
     /// ```
     #[inline]
-    pub fn iter(&self) -> Iter<'_, T, N> {
-        Iter::new(self)
+    pub fn push_front_mut(&mut self, item: T) -> Result<&mut T, CapacityError<T>> {
+        BaseDeque::push_front_mut(self, item)
     }
 
-    /// Drains `n` elements from the front of the deque.
+    /// Prepends an element produced by `f` to the deque, returning a mutable
+    /// reference to it.
     ///
-    /// If `n` exceeds `self.len()`, `None` is returned.
+    /// `f` is only called if the deque has room for another element. If the
+    /// deque is at capacity, `None` is returned instead.
     ///
-    /// When this method is called, `n` elements are immediately removed from
-    /// the front of the deque. If the returned iterator is dropped before
-    /// yielding all its items, they are dropped along with it.
+    /// # Example
     ///
-    /// If the returned iterator is leaked (e.g. with [`mem::forget`]), the
-    /// drained elements will not be dropped immediately. They may be dropped as
-    /// a result of subsequent operations on the deque; otherwise, they will be
-    /// dropped when the deque itself is dropped.
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<String, 1> = ArrayDeque::new();
     ///
-    /// [`mem::forget`]: https://doc.rust-lang.org/stable/core/mem/fn.forget.html
+    /// let s = deque.push_front_with(|| String::from("hello")).unwrap();
+    /// s.push_str(", world");
+    /// assert_eq!(deque.front().map(String::as_str), Some("hello, world"));
+    ///
+    /// // The deque is full, so the closure is never called.
+    /// assert!(deque.push_front_with(|| unreachable!()).is_none());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn push_front_with<F>(&mut self, f: F) -> Option<&mut T>
+    where
+        F: FnOnce() -> T,
+    {
+        BaseDeque::push_front_with(self, f)
+    }
+
+    /// Appends an element to the deque.
+    ///
+    /// If the deque is at capacity, an `Err` containing the pushed value is
+    /// returned.
     ///
     /// # Example
     ///
@@ -639,777 +981,5778 @@ where
     /// # use holodeque::{ArrayDeque, CapacityError};
     /// # fn main() {
     /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut deque: ArrayDeque<u32, 5> = ArrayDeque::new();
+    /// let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
     ///
-    /// deque.push_back(0)?;
     /// deque.push_back(1)?;
     /// deque.push_back(2)?;
     /// deque.push_back(3)?;
-    /// deque.push_back(4)?;
-    ///
-    /// let mut drain = deque.drain_front(3).unwrap();
     ///
-    /// assert_eq!(drain.next(), Some(0));
-    /// assert_eq!(drain.next(), Some(1));
-    /// assert_eq!(drain.next(), Some(2));
-    /// assert_eq!(drain.next(), None);
-    /// drop(drain);
+    /// assert_eq!(deque.front(), Some(&1));
+    /// assert_eq!(deque.back(), Some(&3));
     ///
-    /// assert_eq!(deque.len(), 2);
+    /// // Another element would exceed capacity, so this fails.
+    /// let err = deque.push_front(4).unwrap_err();
+    /// assert_eq!(err.into_inner(), 4);
     /// # Ok(())
     /// # })().unwrap();
     /// # }
     /// ```
     #[inline]
-    pub fn drain_front(&mut self, n: usize) -> Option<DrainFront<'_, T, N>> {
-        DrainFront::new(self, n)
+    pub fn push_back(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        BaseDeque::push_back(self, item)
     }
 
-    /// Drains `n` elements from the back of the deque.
+    /// Appends an element to the deque, panicking if the deque is full.
     ///
-    /// If `n` exceeds `self.len()`, `None` is returned.
+    /// This is a convenience for code in which overflowing the deque is a
+    /// logic error. Prefer [`push_back`] where a full deque is an expected
+    /// condition.
     ///
-    /// When this method is called, `n` elements are immediately removed from
-    /// the back of the deque. If the returned iterator is dropped before
-    /// yielding all its items, they are dropped along with it.
+    /// [`push_back`]: ArrayDeque::push_back
     ///
-    /// If the returned iterator is leaked (e.g. with [`mem::forget`]), the
-    /// drained elements will not be dropped immediately. They may be dropped as
-    /// a result of subsequent operations on the deque; otherwise, they will be
-    /// dropped when the deque itself is dropped.
+    /// # Panics
     ///
-    /// [`mem::forget`]: https://doc.rust-lang.org/stable/core/mem/fn.forget.html
+    /// Panics if the deque is at capacity.
     ///
     /// # Example
     ///
     /// ```
-    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # use holodeque::ArrayDeque;
     /// # fn main() {
-    /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut deque: ArrayDeque<u32, 5> = ArrayDeque::new();
+    /// let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
     ///
-    /// deque.push_back(0)?;
-    /// deque.push_back(1)?;
-    /// deque.push_back(2)?;
-    /// deque.push_back(3)?;
-    /// deque.push_back(4)?;
+    /// deque.push_back_expect(1);
+    /// deque.push_back_expect(2);
     ///
-    /// let mut drain = deque.drain_back(3).unwrap();
+    /// assert_eq!(deque.back(), Some(&2));
+    /// # }
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn push_back_expect(&mut self, item: T) {
+        BaseDeque::push_back_expect(self, item)
+    }
+
+    /// Appends an element to the deque, returning a mutable reference to it.
     ///
-    /// assert_eq!(drain.next(), Some(4));
-    /// assert_eq!(drain.next(), Some(3));
-    /// assert_eq!(drain.next(), Some(2));
-    /// assert_eq!(drain.next(), None);
-    /// drop(drain);
+    /// If the deque is at capacity, an `Err` containing the pushed value is
+    /// returned.
     ///
-    /// assert_eq!(deque.len(), 2);
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<(u32, &str), 3> = ArrayDeque::new();
+    ///
+    /// let entry = deque.push_back_mut((1, "pending"))?;
+    /// entry.1 = "done";
+    ///
+    /// assert_eq!(deque.back(), Some(&(1, "done")));
     /// # Ok(())
     /// # })().unwrap();
     /// # }
     /// ```
     #[inline]
-    pub fn drain_back(&mut self, n: usize) -> Option<DrainBack<'_, T, N>> {
-        DrainBack::new(self, n)
+    pub fn push_back_mut(&mut self, item: T) -> Result<&mut T, CapacityError<T>> {
+        BaseDeque::push_back_mut(self, item)
     }
-}
-
-/// An immutable iterator over an `ArrayDeque<T, N>`.
-///
-/// This struct is created by the [`iter`] method on [`ArrayDeque`].
-///
-/// [`iter`]: ArrayDeque::iter
-pub struct Iter<'a, T, const N: usize>
-where
-    T: Default,
-{
-    inner: DequeIter<'a, ArrayDeque<T, N>, T>,
-}
 
-impl<'a, T, const N: usize> Iter<'a, T, N>
-where
-    T: Default,
-{
+    /// Appends an element produced by `f` to the deque, returning a mutable
+    /// reference to it.
+    ///
+    /// `f` is only called if the deque has room for another element. If the
+    /// deque is at capacity, `None` is returned instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<String, 1> = ArrayDeque::new();
+    ///
+    /// let s = deque.push_back_with(|| String::from("hello")).unwrap();
+    /// s.push_str(", world");
+    /// assert_eq!(deque.back().map(String::as_str), Some("hello, world"));
+    ///
+    /// // The deque is full, so the closure is never called.
+    /// assert!(deque.push_back_with(|| unreachable!()).is_none());
+    /// # }
+    /// ```
     #[inline]
-    fn new(deque: &'a ArrayDeque<T, N>) -> Iter<'a, T, N> {
-        Iter {
-            inner: DequeIter::new(deque),
-        }
+    pub fn push_back_with<F>(&mut self, f: F) -> Option<&mut T>
+    where
+        F: FnOnce() -> T,
+    {
+        BaseDeque::push_back_with(self, f)
     }
-}
-
-impl<'a, T, const N: usize> Iterator for Iter<'a, T, N>
-where
-    T: Default,
-{
-    type Item = &'a T;
 
+    /// Appends an element to the back of the deque, unless it is equal to the
+    /// current back element.
+    ///
+    /// Returns `Ok(true)` if the element was appended and `Ok(false)` if it
+    /// was skipped. This coalesces runs of repeated values, such as unchanged
+    /// sensor readings.
+    ///
+    /// If the element is not skipped and the deque is full, an `Err`
+    /// containing `item` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+    ///
+    /// for reading in [20, 20, 21, 21, 21, 20].iter().copied() {
+    ///     deque.push_back_dedup(reading).unwrap();
+    /// }
+    ///
+    /// assert_eq!(deque.as_slices(), (&[20, 21, 20][..], &[][..]));
+    /// # }
+    /// ```
     #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next()
+    pub fn push_back_dedup(&mut self, item: T) -> Result<bool, CapacityError<T>>
+    where
+        T: PartialEq,
+    {
+        BaseDeque::push_back_dedup(self, item)
     }
-}
 
-impl<'a, T, const N: usize> DoubleEndedIterator for Iter<'a, T, N>
-where
-    T: Default,
-{
-    #[inline]
-    fn next_back(&mut self) -> Option<Self::Item> {
-        self.inner.next_back()
+    /// Appends the items of an iterator to the deque until it is full.
+    ///
+    /// If the deque becomes full before the iterator is exhausted, an `Err` is
+    /// returned containing the number of items that were appended and the
+    /// first item that did not fit. The remaining items are left in the
+    /// iterator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, TryExtendError};
+    /// # fn main() {
+    /// # (|| -> Result<(), TryExtendError<_>> {
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+    ///
+    /// deque.try_extend(vec![1, 2])?;
+    /// assert_eq!(deque.len(), 2);
+    ///
+    /// let err = deque.try_extend(3..10).unwrap_err();
+    /// assert_eq!(err.accepted(), 2);
+    /// assert_eq!(err.into_first_rejected(), 5);
+    /// assert!(deque.is_full());
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), TryExtendError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        BaseDeque::try_extend(self, iter)
     }
-}
 
-/// A draining iterator which removes elements from the front of an
-/// `ArrayDeque<T, N>`.
-///
-/// This struct is created by the [`drain_front`] method on [`ArrayDeque`].
-///
+    /// Prepends the items of an iterator to the deque, keeping their order.
+    ///
+    /// After this call, the first item of the iterator is the front element
+    /// of the deque, followed by the other items and then by the elements
+    /// previously in the deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deque becomes full before the iterator is exhausted. The
+    /// items prepended before then are kept, in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+    /// deque.push_back(4).unwrap();
+    ///
+    /// deque.extend_front(1..=3);
+    /// assert!(deque.iter().copied().eq(1..=4));
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn extend_front<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        BaseDeque::extend_front_expect(self, iter)
+    }
+
+    /// Prepends the items of an iterator to the deque, keeping their order,
+    /// until it is full.
+    ///
+    /// If the deque becomes full before the iterator is exhausted, an `Err` is
+    /// returned containing the number of items that were prepended and the
+    /// first item that did not fit. The prepended items are in the same order
+    /// as in the iterator, ahead of the elements previously in the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+    /// deque.push_back(9).unwrap();
+    ///
+    /// let err = deque.try_extend_front(1..).unwrap_err();
+    /// assert_eq!(err.accepted(), 3);
+    /// assert_eq!(err.into_first_rejected(), 4);
+    /// assert!(deque.iter().copied().eq([1, 2, 3, 9]));
+    /// # }
+    /// ```
+    pub fn try_extend_front<I>(&mut self, iter: I) -> Result<(), TryExtendError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        BaseDeque::try_extend_front(self, iter)
+    }
+
+    /// Appends the items of an iterator to the deque until it is full, and
+    /// returns the number of items that did not fit.
+    ///
+    /// Unlike [`try_extend`], this consumes the whole iterator: the items
+    /// which did not fit are dropped and counted. With the `alloc` feature,
+    /// [`collect_overflow`] returns them instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+    ///
+    /// assert_eq!(deque.count_overflow(1..=3), 0);
+    /// assert_eq!(deque.count_overflow(4..=10), 6);
+    /// assert!(deque.iter().copied().eq(1..=4));
+    /// # }
+    /// ```
+    ///
+    /// [`try_extend`]: ArrayDeque::try_extend
+    /// [`collect_overflow`]: ArrayDeque::collect_overflow
+    pub fn count_overflow<I>(&mut self, iter: I) -> usize
+    where
+        I: IntoIterator<Item = T>,
+    {
+        BaseDeque::count_overflow(self, iter)
+    }
+
+    /// Removes and returns the first element of the deque.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
+    ///
+    /// deque.push_back(1)?;
+    /// deque.push_back(2)?;
+    /// deque.push_back(3)?;
+    ///
+    /// assert_eq!(deque.pop_front(), Some(1));
+    /// assert_eq!(deque.pop_front(), Some(2));
+    /// assert_eq!(deque.pop_front(), Some(3));
+    /// assert_eq!(deque.pop_front(), None);
+    ///
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        BaseDeque::pop_front(self)
+    }
+
+    /// Returns a guard which provides mutable access to the first element of
+    /// the deque, and removes it only when committed.
+    ///
+    /// Calling [`PopGuard::commit`] or [`PopGuard::into_inner`] removes the
+    /// element. If the guard is dropped instead, the element stays at the
+    /// front of the deque. Unlike popping an element and pushing it back on
+    /// failure, this cannot fail for lack of capacity, and the element keeps
+    /// its place ahead of the others.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut inbox: ArrayDeque<&str, 4> = ArrayDeque::new();
+    /// inbox.try_extend(["retry me", "ok"].iter().copied()).unwrap();
+    ///
+    /// fn handle(msg: &str) -> Result<(), ()> {
+    ///     if msg == "ok" { Ok(()) } else { Err(()) }
+    /// }
+    ///
+    /// let msg = inbox.pop_front_guard().unwrap();
+    /// if handle(*msg).is_ok() {
+    ///     msg.commit();
+    /// }
+    /// assert_eq!(inbox.len(), 2);
+    ///
+    /// inbox.rotate_to_front(1);
+    /// let msg = inbox.pop_front_guard().unwrap();
+    /// if handle(*msg).is_ok() {
+    ///     msg.commit();
+    /// }
+    /// assert!(inbox.iter().eq(&["retry me"]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn pop_front_guard(&mut self) -> Option<PopGuard<'_, T, N>> {
+        DequePopGuard::front(self).map(|inner| PopGuard { inner })
+    }
+
+    /// Removes and returns the last element of the deque.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
+    ///
+    /// deque.push_back(1)?;
+    /// deque.push_back(2)?;
+    /// deque.push_back(3)?;
+    ///
+    /// assert_eq!(deque.pop_back(), Some(3));
+    /// assert_eq!(deque.pop_back(), Some(2));
+    /// assert_eq!(deque.pop_back(), Some(1));
+    /// assert_eq!(deque.pop_back(), None);
+    ///
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        BaseDeque::pop_back(self)
+    }
+
+    /// Removes the first element and returns a copy of it.
+    ///
+    /// Unlike [`pop_front`], this does not write the default value of `T`
+    /// into the vacated slot, which avoids a redundant store for large `Copy`
+    /// types. The stale value is left in place until it is overwritten.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// [`pop_front`]: ArrayDeque::pop_front
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<[u64; 32], 2> = ArrayDeque::new();
+    /// deque.push_back([7; 32]).unwrap();
+    ///
+    /// assert_eq!(deque.pop_front_copied(), Some([7; 32]));
+    /// assert_eq!(deque.pop_front_copied(), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn pop_front_copied(&mut self) -> Option<T>
+    where
+        T: Copy,
+    {
+        BaseDeque::pop_front_copied(self)
+    }
+
+    /// Removes the last element and returns a copy of it.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// See [`pop_front_copied`] for details.
+    ///
+    /// [`pop_front_copied`]: ArrayDeque::pop_front_copied
+    #[inline]
+    pub fn pop_back_copied(&mut self) -> Option<T>
+    where
+        T: Copy,
+    {
+        BaseDeque::pop_back_copied(self)
+    }
+
+    /// Removes and returns the first element equal to `item`.
+    ///
+    /// The elements between the removed element and the nearer end of the
+    /// deque are shifted to fill the gap, so this takes time proportional to
+    /// the position of the element plus the distance to the nearer end.
+    ///
+    /// If no element is equal to `item`, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut tasks: ArrayDeque<u32, 4> = ArrayDeque::new();
+    /// tasks.push_back(10)?;
+    /// tasks.push_back(11)?;
+    /// tasks.push_back(12)?;
+    ///
+    /// assert_eq!(tasks.remove_item(&11), Some(11));
+    /// assert_eq!(tasks.remove_item(&11), None);
+    /// assert_eq!(tasks.as_slices(), (&[10, 12][..], &[][..]));
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn remove_item(&mut self, item: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        BaseDeque::remove_item(self, item)
+    }
+
+    /// Returns `true` if the deque contains an element equal to `item`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<char, 4> = ArrayDeque::new();
+    /// deque.push_back('b')?;
+    /// deque.push_front('a')?;
+    ///
+    /// assert!(deque.contains(&'a'));
+    /// assert!(!deque.contains(&'c'));
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn contains(&self, item: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        BaseDeque::contains(self, item)
+    }
+
+    /// Returns the index of the first element equal to `item`, counting from
+    /// the front of the deque.
+    ///
+    /// If no element is equal to `item`, `None` is returned. To search a
+    /// byte deque, [`find_byte`] may be faster.
+    ///
+    /// [`find_byte`]: ArrayDeque::find_byte
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<char, 4> = ArrayDeque::new();
+    /// deque.push_back('b')?;
+    /// deque.push_back('c')?;
+    /// deque.push_front('a')?;
+    ///
+    /// assert_eq!(deque.position_of(&'c'), Some(2));
+    /// assert_eq!(deque.position_of(&'d'), None);
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn position_of(&self, item: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        BaseDeque::position_of(self, item)
+    }
+
+    /// Returns `true` if `other` holds the same elements as this deque, in
+    /// the same cyclic order.
+    ///
+    /// The two deques are equal up to rotation: one of them can be turned
+    /// into the other by repeatedly moving its front element to the back.
+    /// This takes time linear in the length of the deques and does not
+    /// allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut before: ArrayDeque<char, 4> = ArrayDeque::new();
+    /// before.try_extend("abcd".chars()).unwrap();
+    ///
+    /// let mut after = before.clone();
+    /// after.round_robin();
+    /// assert!(after.iter().eq(&['b', 'c', 'd', 'a']));
+    ///
+    /// assert!(after.cyclic_eq(&before));
+    /// assert!(after != before);
+    /// # }
+    /// ```
+    pub fn cyclic_eq<D>(&self, other: &D) -> bool
+    where
+        D: Deque<T> + ?Sized,
+        T: PartialEq,
+    {
+        BaseDeque::cyclic_eq(self, other)
+    }
+
+    /// Clears the `ArrayDeque`, removing all values.
+    ///
+    /// If `T` does not need to be dropped, this takes constant time, as the
+    /// removed elements are left in the backing storage to be overwritten.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<usize, 8> = ArrayDeque::new();
+    ///
+    /// for i in 0..deque.capacity() / 2 {
+    ///     deque.push_front(i)?;
+    ///     deque.push_back(i)?;
+    /// }
+    ///
+    /// assert_eq!(deque.len(), 8);
+    /// deque.clear();
+    /// assert!(deque.is_empty());
+    ///
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        BaseDeque::clear(self)
+    }
+
+    /// Shortens the `ArrayDeque`, keeping the first `len` elements and dropping
+    /// the rest.
+    ///
+    /// If `len` is greater than the `ArrayDeque`'s current length, this has no
+    /// effect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<usize, 8> = ArrayDeque::new();
+    ///
+    /// deque.push_back(5)?;
+    /// deque.push_back(10)?;
+    /// deque.push_back(15)?;
+    /// deque.push_back(20)?;
+    /// deque.push_back(25)?;
+    ///
+    /// assert_eq!(deque.len(), 5);
+    /// deque.truncate(2);
+    /// assert_eq!(deque.len(), 2);
+    ///
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        BaseDeque::truncate(self, len)
+    }
+
+    /// Returns a guard which truncates the deque back to its current length
+    /// when dropped, unless [`keep`] is called.
+    ///
+    /// This undoes speculative pushes to the back of the deque on every path
+    /// out of a scope, including early returns and panics.
+    ///
+    /// [`keep`]: TruncateGuard::keep
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut path: ArrayDeque<u8, 8> = ArrayDeque::new();
+    /// path.push_back(0).unwrap();
+    ///
+    /// {
+    ///     let mut attempt = path.truncate_guard();
+    ///     attempt.push_back(1).unwrap();
+    ///     attempt.push_back(2).unwrap();
+    ///     // Dead end: dropping the guard removes 1 and 2.
+    /// }
+    ///
+    /// let mut attempt = path.truncate_guard();
+    /// attempt.push_back(3).unwrap();
+    /// attempt.keep();
+    ///
+    /// assert!(path.iter().copied().eq([0, 3]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn truncate_guard(&mut self) -> TruncateGuard<'_, T, N> {
+        TruncateGuard {
+            inner: DequeTruncateGuard::new(self),
+        }
+    }
+
+    /// Shortens the `ArrayDeque`, keeping the last `len` elements and dropping
+    /// the rest.
+    ///
+    /// If `len` is greater than the `ArrayDeque`'s current length, this has no
+    /// effect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<usize, 8> = ArrayDeque::new();
+    ///
+    /// deque.push_back(5)?;
+    /// deque.push_back(10)?;
+    /// deque.push_back(15)?;
+    ///
+    /// deque.truncate_front(2);
+    /// assert_eq!(deque.front(), Some(&10));
+    /// assert_eq!(deque.len(), 2);
+    ///
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn truncate_front(&mut self, len: usize) {
+        BaseDeque::truncate_front(self, len)
+    }
+
+    /// Returns `true` if the first elements of the deque are equal to
+    /// `prefix`.
+    ///
+    /// An empty `prefix` matches any deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut rx: ArrayDeque<u8, 16> = ArrayDeque::new();
+    /// rx.try_extend(b"GET /".iter().copied()).unwrap();
+    ///
+    /// assert!(rx.starts_with(b"GET"));
+    /// assert!(!rx.starts_with(b"PUT"));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn starts_with(&self, prefix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        BaseDeque::starts_with(self, prefix)
+    }
+
+    /// Returns `true` if the last elements of the deque are equal to
+    /// `suffix`.
+    ///
+    /// An empty `suffix` matches any deque.
+    #[inline]
+    pub fn ends_with(&self, suffix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        BaseDeque::ends_with(self, suffix)
+    }
+
+    /// Removes the first elements of the deque if they are equal to `prefix`,
+    /// returning `true` if they were removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut rx: ArrayDeque<u8, 16> = ArrayDeque::new();
+    /// rx.try_extend(b"OK\r\n".iter().copied()).unwrap();
+    ///
+    /// assert!(!rx.strip_prefix(b"ERR"));
+    /// assert!(rx.strip_prefix(b"OK"));
+    /// assert!(rx.strip_suffix(b"\r\n"));
+    /// assert!(rx.is_empty());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn strip_prefix(&mut self, prefix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        BaseDeque::strip_prefix(self, prefix)
+    }
+
+    /// Removes the last elements of the deque if they are equal to `suffix`,
+    /// returning `true` if they were removed.
+    #[inline]
+    pub fn strip_suffix(&mut self, suffix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        BaseDeque::strip_suffix(self, suffix)
+    }
+
+    /// Moves elements from the front of the deque into `dst`, in order,
+    /// returning the number of elements moved.
+    ///
+    /// As many elements are moved as fit in `dst`; any remaining elements stay
+    /// in the deque. If `dst` is at least as long as the deque, the deque is
+    /// left empty. This makes the contents contiguous in a single pass without
+    /// rotating the backing storage, at the cost of a scratch buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u8, 4> = ArrayDeque::new();
+    /// deque.push_back(2).unwrap();
+    /// deque.push_back(3).unwrap();
+    /// deque.push_front(1).unwrap();
+    ///
+    /// let mut scratch = [0; 8];
+    /// let n = deque.linearize_into(&mut scratch);
+    ///
+    /// assert_eq!(&scratch[..n], &[1, 2, 3]);
+    /// assert!(deque.is_empty());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn linearize_into(&mut self, dst: &mut [T]) -> usize {
+        BaseDeque::linearize_into(self, dst)
+    }
+
+    /// Clones elements from the front of the deque into `dst`, in order,
+    /// returning the number of elements cloned.
+    ///
+    /// Unlike [`linearize_into`], this leaves the deque unchanged.
+    ///
+    /// [`linearize_into`]: ArrayDeque::linearize_into
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u8, 4> = ArrayDeque::new();
+    /// deque.push_back(2).unwrap();
+    /// deque.push_back(3).unwrap();
+    /// deque.push_front(1).unwrap();
+    ///
+    /// let mut scratch = [0; 2];
+    /// assert_eq!(deque.clone_into_slice(&mut scratch), 2);
+    ///
+    /// assert_eq!(scratch, [1, 2]);
+    /// assert_eq!(deque.len(), 3);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn clone_into_slice(&self, dst: &mut [T]) -> usize
+    where
+        T: Clone,
+    {
+        BaseDeque::clone_into_slice(self, dst)
+    }
+
+    /// Rotates the deque so that the element at logical index `index` becomes
+    /// the first element.
+    ///
+    /// The elements before `index` are moved, in order, to the back of the
+    /// deque. This is equivalent to rotating the deque left by `index`
+    /// positions. The deque is made contiguous first, so this may also change
+    /// its layout in the backing storage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > self.len()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 8> = ArrayDeque::new();
+    ///
+    /// deque.try_extend([0, 1, 2, 3, 4].iter().copied()).unwrap();
+    ///
+    /// let next = deque.iter().position(|&task| task == 3).unwrap();
+    /// deque.rotate_to_front(next);
+    ///
+    /// assert_eq!(deque.as_slices(), (&[3, 4, 0, 1, 2][..], &[][..]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn rotate_to_front(&mut self, index: usize) {
+        BaseDeque::rotate_to_front(self, index)
+    }
+
+    /// Moves the front element to the back of the deque, returning a mutable
+    /// reference to the new front element, or `None` if the deque is empty.
+    ///
+    /// This takes constant time, and unlike a [`pop_front`] followed by a
+    /// [`push_back`], it cannot fail for lack of capacity. If the deque holds
+    /// a single element, that element is returned.
+    ///
+    /// [`pop_front`]: ArrayDeque::pop_front
+    /// [`push_back`]: ArrayDeque::push_back
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut tasks: ArrayDeque<&str, 4> = ArrayDeque::new();
+    /// tasks.try_extend(["net", "ui", "disk"].iter().copied()).unwrap();
+    ///
+    /// assert_eq!(tasks.round_robin(), Some(&mut "ui"));
+    /// assert_eq!(tasks.round_robin(), Some(&mut "disk"));
+    /// assert!(tasks.iter().eq(&["disk", "net", "ui"]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn round_robin(&mut self) -> Option<&mut T> {
+        BaseDeque::round_robin(self)
+    }
+
+    /// Reorders the deque such that the element at logical index `index` is
+    /// at its final sorted position, returning the elements before it, the
+    /// element itself and the elements after it.
+    ///
+    /// This behaves like [`slice::select_nth_unstable`]: every element
+    /// before `index` is less than or equal to it and every element after is
+    /// greater than or equal to it. The deque is made contiguous first, so
+    /// this may also change its layout in the backing storage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= self.len()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 8> = ArrayDeque::new();
+    ///
+    /// deque.try_extend([7, 1, 9, 4, 3].iter().copied()).unwrap();
+    ///
+    /// let (_, median, _) = deque.select_nth_unstable(2);
+    /// assert_eq!(*median, 4);
+    /// assert_eq!(deque.len(), 5);
+    /// # }
+    /// ```
+    ///
+    /// [`slice::select_nth_unstable`]: https://doc.rust-lang.org/stable/std/primitive.slice.html#method.select_nth_unstable
+    #[inline]
+    pub fn select_nth_unstable(&mut self, index: usize) -> (&mut [T], &mut T, &mut [T])
+    where
+        T: Ord,
+    {
+        BaseDeque::select_nth_unstable_by(self, index, T::cmp)
+    }
+
+    /// Reorders the deque with a comparator function such that the element at
+    /// logical index `index` is at its final sorted position.
+    ///
+    /// See [`select_nth_unstable`](Self::select_nth_unstable) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= self.len()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<f32, 8> = ArrayDeque::new();
+    ///
+    /// deque.try_extend([0.5, 2.0, -1.0, 1.5].iter().copied()).unwrap();
+    ///
+    /// let (_, max, _) = deque.select_nth_unstable_by(3, |a, b| a.total_cmp(b));
+    /// assert_eq!(*max, 2.0);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn select_nth_unstable_by<F>(
+        &mut self,
+        index: usize,
+        compare: F,
+    ) -> (&mut [T], &mut T, &mut [T])
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        BaseDeque::select_nth_unstable_by(self, index, compare)
+    }
+
+    /// Reorders the deque with a key extraction function such that the
+    /// element at logical index `index` is at its final sorted position.
+    ///
+    /// See [`select_nth_unstable`](Self::select_nth_unstable) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= self.len()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<i32, 8> = ArrayDeque::new();
+    ///
+    /// deque.try_extend([-8, 3, -1, 5].iter().copied()).unwrap();
+    ///
+    /// let (_, nearest, _) = deque.select_nth_unstable_by_key(0, |x| x.abs());
+    /// assert_eq!(*nearest, -1);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn select_nth_unstable_by_key<K, F>(
+        &mut self,
+        index: usize,
+        mut f: F,
+    ) -> (&mut [T], &mut T, &mut [T])
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        BaseDeque::select_nth_unstable_by(self, index, |a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Returns a [`Savepoint`] recording the current contents of the deque.
+    ///
+    /// Passing the savepoint to [`rollback_to`] later removes every element
+    /// pushed to either end of the deque since the savepoint was created.
+    ///
+    /// Pushing to either end, including by extending the deque, keeps the
+    /// savepoint valid. Any other modification invalidates it, including
+    /// popping, removing or inserting elements, mutable access to elements,
+    /// rotating, sorting and editing through a cursor. The one exception is
+    /// a savepoint of an empty deque, which can always be rolled back to.
+    ///
+    /// [`rollback_to`]: ArrayDeque::rollback_to
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<char, 8> = ArrayDeque::new();
+    /// deque.push_back('a')?;
+    ///
+    /// let savepoint = deque.savepoint();
+    /// deque.push_back('b')?;
+    /// deque.push_front('c')?;
+    ///
+    /// deque.rollback_to(savepoint);
+    /// assert_eq!(deque.iter().collect::<String>(), "a");
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn savepoint(&self) -> Savepoint {
+        BaseDeque::savepoint(self)
+    }
+
+    /// Removes every element pushed since `savepoint` was created, restoring
+    /// the deque to its contents at that time.
+    ///
+    /// The removed elements are dropped in place, without moving the
+    /// remaining elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deque has been modified other than by pushing since the
+    /// savepoint was created, as described in [`savepoint`]. If the savepoint
+    /// was created by a different deque, this may not be detected, and the
+    /// contents of the deque are unspecified.
+    ///
+    /// [`savepoint`]: ArrayDeque::savepoint
+    #[inline]
+    pub fn rollback_to(&mut self, savepoint: Savepoint) {
+        BaseDeque::rollback_to(self, savepoint)
+    }
+
+    /// Returns the usage statistics of the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// let mut deque: ArrayDeque<u32, 2> = ArrayDeque::new();
+    /// deque.push_back(1).unwrap();
+    /// deque.push_back(2).unwrap();
+    /// assert!(deque.push_back(3).is_err());
+    /// deque.pop_front();
+    ///
+    /// let stats = deque.stats();
+    /// assert_eq!(stats.high_water_mark(), 2);
+    /// assert_eq!(stats.pushes(), 2);
+    /// assert_eq!(stats.pops(), 1);
+    /// assert_eq!(stats.rejected_pushes(), 1);
+    /// ```
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn stats(&self) -> &DequeStats {
+        self.meta.stats()
+    }
+
+    /// Resets the usage statistics of the deque.
+    ///
+    /// The counters are set to zero and the high-water mark is set to the
+    /// current number of elements.
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn reset_stats(&mut self) {
+        BaseDeque::reset_stats(self)
+    }
+
+    /// Returns an iterator over the elements of the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<&str, 5> = ArrayDeque::new();
+    ///
+    /// deque.push_back("ideas")?;
+    /// deque.push_front("green")?;
+    /// deque.push_back("sleep")?;
+    /// deque.push_front("colorless")?;
+    /// deque.push_back("furiously")?;
+    ///
+    /// let sentence = deque.iter().cloned().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     sentence,
+    ///     &["colorless", "green", "ideas", "sleep", "furiously"],
+    /// );
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T, N> {
+        Iter::new(self)
+    }
+
+    /// Drains `n` elements from the front of the deque.
+    ///
+    /// If `n` exceeds `self.len()`, `None` is returned.
+    ///
+    /// When this method is called, `n` elements are immediately removed from
+    /// the front of the deque. If the returned iterator is dropped before
+    /// yielding all its items, they are dropped along with it.
+    ///
+    /// If the returned iterator is leaked (e.g. with [`mem::forget`]), the
+    /// drained elements will not be dropped immediately. They may be dropped as
+    /// a result of subsequent operations on the deque; otherwise, they will be
+    /// dropped when the deque itself is dropped.
+    ///
+    /// [`mem::forget`]: https://doc.rust-lang.org/stable/core/mem/fn.forget.html
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<u32, 5> = ArrayDeque::new();
+    ///
+    /// deque.push_back(0)?;
+    /// deque.push_back(1)?;
+    /// deque.push_back(2)?;
+    /// deque.push_back(3)?;
+    /// deque.push_back(4)?;
+    ///
+    /// let mut drain = deque.drain_front(3).unwrap();
+    ///
+    /// assert_eq!(drain.next(), Some(0));
+    /// assert_eq!(drain.next(), Some(1));
+    /// assert_eq!(drain.next(), Some(2));
+    /// assert_eq!(drain.next(), None);
+    /// drop(drain);
+    ///
+    /// assert_eq!(deque.len(), 2);
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn drain_front(&mut self, n: usize) -> Option<DrainFront<'_, T, N>> {
+        DrainFront::new(self, n)
+    }
+
+    /// Drains `n` elements from the back of the deque.
+    ///
+    /// If `n` exceeds `self.len()`, `None` is returned.
+    ///
+    /// When this method is called, `n` elements are immediately removed from
+    /// the back of the deque. If the returned iterator is dropped before
+    /// yielding all its items, they are dropped along with it.
+    ///
+    /// If the returned iterator is leaked (e.g. with [`mem::forget`]), the
+    /// drained elements will not be dropped immediately. They may be dropped as
+    /// a result of subsequent operations on the deque; otherwise, they will be
+    /// dropped when the deque itself is dropped.
+    ///
+    /// [`mem::forget`]: https://doc.rust-lang.org/stable/core/mem/fn.forget.html
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<u32, 5> = ArrayDeque::new();
+    ///
+    /// deque.push_back(0)?;
+    /// deque.push_back(1)?;
+    /// deque.push_back(2)?;
+    /// deque.push_back(3)?;
+    /// deque.push_back(4)?;
+    ///
+    /// let mut drain = deque.drain_back(3).unwrap();
+    ///
+    /// assert_eq!(drain.next(), Some(4));
+    /// assert_eq!(drain.next(), Some(3));
+    /// assert_eq!(drain.next(), Some(2));
+    /// assert_eq!(drain.next(), None);
+    /// drop(drain);
+    ///
+    /// assert_eq!(deque.len(), 2);
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn drain_back(&mut self, n: usize) -> Option<DrainBack<'_, T, N>> {
+        DrainBack::new(self, n)
+    }
+
+    /// Returns an iterator which removes elements from the front of the deque
+    /// in arrays of `C` elements.
+    ///
+    /// Each call to `next` removes one array's worth of elements. Iteration
+    /// stops when fewer than `C` elements remain; those are left in the deque,
+    /// so that they can be completed by elements pushed later.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `C` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut samples: ArrayDeque<i16, 8> = ArrayDeque::new();
+    /// samples.try_extend(0..7).unwrap();
+    ///
+    /// let mut chunks = samples.drain_chunks::<3>();
+    /// assert_eq!(chunks.next(), Some([0, 1, 2]));
+    /// assert_eq!(chunks.next(), Some([3, 4, 5]));
+    /// assert_eq!(chunks.next(), None);
+    ///
+    /// assert_eq!(samples.len(), 1);
+    /// # }
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn drain_chunks<const C: usize>(&mut self) -> DrainChunks<'_, T, N, C> {
+        DrainChunks {
+            inner: DequeDrainChunks::new(self),
+        }
+    }
+
+    /// Retains only the elements for which `f` returns `true`, removing the
+    /// rest.
+    ///
+    /// The retained elements keep their order. See [`retain_mut`] for the
+    /// behavior if `f` panics.
+    ///
+    /// [`retain_mut`]: ArrayDeque::retain_mut
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 8> = ArrayDeque::new();
+    /// deque.try_extend(1..=6).unwrap();
+    ///
+    /// deque.retain(|&x| x % 3 != 0);
+    /// assert!(deque.iter().copied().eq([1, 2, 4, 5]));
+    /// # }
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        BaseDeque::retain_mut(self, |item| f(item))
+    }
+
+    /// Retains only the elements for which `f` returns `true`, removing the
+    /// rest. `f` may modify the elements it is passed.
+    ///
+    /// The retained elements keep their order.
+    ///
+    /// If `f` panics, the deque keeps the elements `f` has already retained,
+    /// followed by the element `f` panicked on and all elements not yet
+    /// passed to `f`. The elements `f` has already rejected are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 8> = ArrayDeque::new();
+    /// deque.try_extend([5, 1, 8, 2].iter().copied()).unwrap();
+    ///
+    /// deque.retain_mut(|x| {
+    ///     *x *= 10;
+    ///     *x > 20
+    /// });
+    /// assert!(deque.iter().copied().eq([50, 80]));
+    /// # }
+    /// ```
+    pub fn retain_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        BaseDeque::retain_mut(self, f)
+    }
+
+    /// Returns an iterator which removes and yields the elements for which
+    /// `pred` returns `true`, in order.
+    ///
+    /// `pred` may modify the elements it is passed. If the iterator is
+    /// dropped before it is exhausted, the elements not yet passed to `pred`
+    /// are kept. The remaining elements keep their order.
+    ///
+    /// If `pred` panics, the deque keeps every element which has not been
+    /// extracted, including the element `pred` panicked on. If the iterator
+    /// is leaked, for example with [`mem::forget`], default values may be
+    /// left in the deque in place of the extracted elements.
+    ///
+    /// [`mem::forget`]: core::mem::forget
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 8> = ArrayDeque::new();
+    /// deque.try_extend(1..=6).unwrap();
+    ///
+    /// let evens: Vec<u32> = deque.extract_if(|x| *x % 2 == 0).collect();
+    /// assert_eq!(evens, [2, 4, 6]);
+    /// assert!(deque.iter().copied().eq([1, 3, 5]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, N, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf {
+            inner: DequeExtractIf::new(self, pred),
+        }
+    }
+
+    /// Consumes the deque, returning a deque of the results of calling `f` on
+    /// each element, in order.
+    ///
+    /// If `f` panics, both the elements not yet mapped and those already
+    /// mapped are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u8, 4> = ArrayDeque::new();
+    /// deque.try_extend(b"abc".iter().copied()).unwrap();
+    ///
+    /// let upper = deque.map(|b| char::from(b.to_ascii_uppercase()));
+    /// assert!(upper.iter().eq(&['A', 'B', 'C']));
+    /// # }
+    /// ```
+    pub fn map<U, F>(mut self, mut f: F) -> ArrayDeque<U, N>
+    where
+        U: Default,
+        F: FnMut(T) -> U,
+    {
+        let mut mapped = ArrayDeque::new();
+
+        while let Some(item) = self.pop_front() {
+            mapped.push_back_expect(f(item));
+        }
+
+        mapped
+    }
+
+    /// Returns a cursor pointing at the front element of the deque.
+    ///
+    /// If the deque is empty, the cursor points at the "ghost" position
+    /// between the back and the front of the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<u32, 8> = ArrayDeque::new();
+    /// deque.push_back(1)?;
+    /// deque.push_back(3)?;
+    /// deque.push_back(4)?;
+    ///
+    /// let mut cursor = deque.cursor_front_mut();
+    /// cursor.move_next();
+    /// cursor.insert_before(2)?;
+    /// cursor.move_next();
+    /// assert_eq!(cursor.remove_current(), Some(4));
+    ///
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, N> {
+        CursorMut {
+            inner: DequeCursor::front(self),
+        }
+    }
+
+    /// Returns a cursor pointing at the back element of the deque.
+    ///
+    /// If the deque is empty, the cursor points at the "ghost" position
+    /// between the back and the front of the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<u32, 8> = ArrayDeque::new();
+    /// deque.push_back(1)?;
+    /// deque.push_back(2)?;
+    ///
+    /// let mut cursor = deque.cursor_back_mut();
+    /// *cursor.current().unwrap() *= 10;
+    /// cursor.insert_after(3)?;
+    ///
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 20, 3]);
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, N> {
+        CursorMut {
+            inner: DequeCursor::back(self),
+        }
+    }
+
+    /// Splits the deque into a handle to its front and a handle to its back.
+    ///
+    /// The [`FrontHandle`] can only inspect and remove elements at the front
+    /// of the deque, and the [`BackHandle`] can only inspect and add elements
+    /// at the back. Both handles may be used at the same time, which allows a
+    /// producer and a consumer within one thread to share the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 2> = ArrayDeque::new();
+    /// let (front, back) = deque.split();
+    ///
+    /// let produce = |item| back.push_back(item).is_ok();
+    /// let consume = || front.pop_front();
+    ///
+    /// assert!(produce(1));
+    /// assert!(produce(2));
+    /// assert_eq!(consume(), Some(1));
+    /// assert!(produce(3));
+    /// assert_eq!(consume(), Some(2));
+    /// assert_eq!(consume(), Some(3));
+    /// assert_eq!(consume(), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn split(&mut self) -> (FrontHandle<'_, T, N>, BackHandle<'_, T, N>) {
+        let (front, back) = DequeHalf::split(self);
+
+        (FrontHandle { inner: front }, BackHandle { inner: back })
+    }
+
+    /// Returns an adaptor which formats the physical layout of the deque.
+    ///
+    /// The `Debug` implementation of `ArrayDeque` prints only the elements of
+    /// the deque in logical order. The returned adaptor instead prints the
+    /// layout metadata and the entire backing array, including unused slots.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+    ///
+    /// deque.push_back(1)?;
+    /// deque.push_front(2)?;
+    ///
+    /// assert_eq!(format!("{:?}", deque), "[2, 1]");
+    /// assert_eq!(
+    ///     format!("{:?}", deque.debug_internals()),
+    ///     "DebugInternals { \
+    ///         layout: Wrapped { wrap_len: 1, gap_len: 2 }, \
+    ///         items: [1, 0, 0, 2] \
+    ///     }",
+    /// );
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn debug_internals(&self) -> DebugInternals<'_, T> {
+        BaseDeque::debug_internals(self)
+    }
+
+    /// Returns a [`LayoutInfo`] describing where the elements of the deque
+    /// are placed in the backing storage.
+    ///
+    /// Unlike [`debug_internals`](Self::debug_internals), the returned value
+    /// can be inspected programmatically, for example to check whether the
+    /// elements can be handed to a DMA transfer as a single buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+    ///
+    /// deque.push_back(1)?;
+    /// assert!(deque.layout_info().is_contiguous());
+    ///
+    /// deque.push_front(2)?;
+    /// let layout = deque.layout_info();
+    /// assert!(!layout.is_contiguous());
+    /// assert_eq!(layout.head(), 3);
+    /// assert_eq!(layout.wrap_len(), 1);
+    /// assert_eq!(layout.gap_len(), 2);
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn layout_info(&self) -> LayoutInfo {
+        BaseDeque::layout_info(self)
+    }
+
+    /// Returns an adaptor which displays the elements of the deque in order,
+    /// separated by `separator`.
+    ///
+    /// Formatting options are applied to each element individually.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, CapacityError};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut deque: ArrayDeque<f32, 4> = ArrayDeque::new();
+    ///
+    /// deque.push_back(0.5)?;
+    /// deque.push_back(1.0)?;
+    /// deque.push_front(0.25)?;
+    ///
+    /// assert_eq!(
+    ///     format!("{:.2}", deque.display_separated(", ")),
+    ///     "0.25, 0.50, 1.00",
+    /// );
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn display_separated<'s>(&'s self, separator: &'s str) -> DisplaySeparated<'s, T> {
+        BaseDeque::display_separated(self, separator)
+    }
+}
+
+impl<T, const N: usize> ArrayDeque<T, N>
+where
+    T: Copy + Default,
+{
+    /// Constructs a new, empty `ArrayDeque<T, N>` by copying a single default
+    /// value into every slot.
+    ///
+    /// This is equivalent to [`new`], but [`new`] calls `T::default()` once
+    /// per slot, which dominates construction time for large arrays. For
+    /// `Copy` types this constructor instead compiles to a bulk fill, such as
+    /// a `memset` for integers.
+    ///
+    /// [`new`]: ArrayDeque::new
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let deque: Box<ArrayDeque<u8, 65536>> = Box::new(ArrayDeque::new_copied());
+    ///
+    /// assert!(deque.is_empty());
+    /// assert_eq!(deque.capacity(), 65536);
+    /// # }
+    /// ```
+    pub fn new_copied() -> Self {
+        ArrayDeque {
+            meta: ArrayMeta {
+                layout: MetaLayout::Empty,
+                generation: 0,
+                #[cfg(feature = "stats")]
+                stats: DequeStats::new(),
+            },
+            items: [T::default(); N],
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T, const N: usize> ArrayDeque<T, N>
+where
+    T: Default + zeroize::Zeroize,
+{
+    /// Removes the first element and returns it, zeroizing the slot it
+    /// occupied.
+    ///
+    /// Unlike [`pop_front`], which leaves the default value of `T` in the
+    /// vacated slot, this overwrites the slot using [`Zeroize`], which is
+    /// guaranteed not to be optimized away.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// [`pop_front`]: ArrayDeque::pop_front
+    /// [`Zeroize`]: zeroize::Zeroize
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<[u8; 32], 4> = ArrayDeque::new();
+    /// deque.push_back([0x55; 32]).unwrap();
+    ///
+    /// assert_eq!(deque.pop_front_zeroize(), Some([0x55; 32]));
+    /// assert_eq!(deque.pop_front_zeroize(), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn pop_front_zeroize(&mut self) -> Option<T> {
+        BaseDeque::pop_front_zeroize(self)
+    }
+
+    /// Removes the last element and returns it, zeroizing the slot it
+    /// occupied.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// See [`pop_front_zeroize`] for details.
+    ///
+    /// [`pop_front_zeroize`]: ArrayDeque::pop_front_zeroize
+    #[inline]
+    pub fn pop_back_zeroize(&mut self) -> Option<T> {
+        BaseDeque::pop_back_zeroize(self)
+    }
+
+    /// Clears the deque, zeroizing each element in place before removing it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<[u8; 32], 4> = ArrayDeque::new();
+    /// deque.push_back([0x55; 32]).unwrap();
+    /// deque.push_front([0xaa; 32]).unwrap();
+    ///
+    /// deque.clear_zeroize();
+    /// assert!(deque.is_empty());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn clear_zeroize(&mut self) {
+        BaseDeque::clear_zeroize(self)
+    }
+}
+
+/// Zeroizes every slot of the backing storage, including unused ones, and
+/// leaves the deque empty.
+#[cfg(feature = "zeroize")]
+impl<T, const N: usize> zeroize::Zeroize for ArrayDeque<T, N>
+where
+    T: Default + zeroize::Zeroize,
+{
+    fn zeroize(&mut self) {
+        BaseDeque::zeroize_all(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const N: usize> ArrayDeque<T, N>
+where
+    T: Default,
+{
+    /// Appends the items of an iterator to the deque until it is full, and
+    /// returns the items that did not fit, in order.
+    ///
+    /// Unlike [`try_extend`], this consumes the whole iterator, so nothing is
+    /// silently lost and the returned items can be retried later. If every
+    /// item fits, the returned `Vec` is empty and does not allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+    /// deque.push_back(1).unwrap();
+    ///
+    /// let leftover = deque.collect_overflow(2..=6);
+    /// assert_eq!(leftover, [5, 6]);
+    /// assert!(deque.iter().copied().eq(1..=4));
+    /// # }
+    /// ```
+    ///
+    /// [`try_extend`]: ArrayDeque::try_extend
+    pub fn collect_overflow<I>(&mut self, iter: I) -> Vec<T>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        BaseDeque::collect_overflow(self, iter)
+    }
+
+    /// Concatenates the elements of the deque into a single value.
+    ///
+    /// This mirrors [`slice::concat`]: a deque of strings produces a `String`
+    /// and a deque of slices produces a `Vec`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<&str, 4> = ArrayDeque::new();
+    ///
+    /// deque.push_back("ERROR").unwrap();
+    /// deque.push_back(": ").unwrap();
+    /// deque.push_back("disk full").unwrap();
+    ///
+    /// assert_eq!(deque.concat(), "ERROR: disk full");
+    /// # }
+    /// ```
+    ///
+    /// [`slice::concat`]: https://doc.rust-lang.org/stable/std/primitive.slice.html#method.concat
+    pub fn concat<Item>(&self) -> <T as Concat<Item>>::Output
+    where
+        T: Concat<Item>,
+        Item: ?Sized,
+    {
+        let (front, back) = self.as_slices();
+
+        Concat::concat(front.iter().chain(back))
+    }
+
+    /// Joins the elements of the deque into a single value, placing `sep`
+    /// between each pair of elements.
+    ///
+    /// This mirrors [`slice::join`]: a deque of strings joined by a `&str`
+    /// produces a `String` and a deque of slices joined by a slice produces a
+    /// `Vec`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<&str, 4> = ArrayDeque::new();
+    ///
+    /// deque.push_back("ts=12").unwrap();
+    /// deque.push_back("level=warn").unwrap();
+    /// deque.push_back("temp=81").unwrap();
+    ///
+    /// assert_eq!(deque.join(" "), "ts=12 level=warn temp=81");
+    /// # }
+    /// ```
+    ///
+    /// [`slice::join`]: https://doc.rust-lang.org/stable/std/primitive.slice.html#method.join
+    pub fn join<Separator>(&self, sep: Separator) -> <T as Join<Separator>>::Output
+    where
+        T: Join<Separator>,
+    {
+        let (front, back) = self.as_slices();
+
+        Join::join(front.iter().chain(back), sep)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<T, const N: usize> ArrayDeque<T, N>
+where
+    T: Default,
+{
+    /// Moves all elements of `vec` to the back of the deque, in order.
+    ///
+    /// If the deque does not have room for every element of `vec`, nothing is
+    /// appended and an `Err` containing `vec` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// use smallvec::{smallvec, SmallVec};
+    ///
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+    ///
+    /// let tokens: SmallVec<[u32; 2]> = smallvec![1, 2, 3];
+    /// deque.extend_from_smallvec(tokens).unwrap();
+    ///
+    /// let more: SmallVec<[u32; 2]> = smallvec![4, 5];
+    /// let rejected = deque.extend_from_smallvec(more).unwrap_err();
+    /// assert_eq!(rejected.into_inner().as_slice(), [4, 5]);
+    /// assert_eq!(deque.len(), 3);
+    /// # }
+    /// ```
+    pub fn extend_from_smallvec<A>(
+        &mut self,
+        vec: smallvec::SmallVec<A>,
+    ) -> Result<(), CapacityError<smallvec::SmallVec<A>>>
+    where
+        A: smallvec::Array<Item = T>,
+    {
+        if vec.len() > self.capacity() - self.len() {
+            return Err(CapacityError { item: vec });
+        }
+
+        for item in vec {
+            self.push_back_expect(item);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<T, const N: usize> ArrayDeque<T, N>
+where
+    T: Default,
+{
+    /// Shuffles the elements of the deque in place.
+    ///
+    /// The shuffle is uniform over all permutations of the elements, and the
+    /// deque's layout in the backing storage is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+    /// deque.push_back(3).unwrap();
+    /// deque.push_back(4).unwrap();
+    /// deque.push_front(2).unwrap();
+    /// deque.push_front(1).unwrap();
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    /// deque.shuffle(&mut rng);
+    ///
+    /// assert_eq!(deque.len(), 4);
+    /// assert!((1..=4).all(|i| deque.iter().any(|&x| x == i)));
+    /// ```
+    #[inline]
+    pub fn shuffle<R>(&mut self, rng: &mut R)
+    where
+        R: rand::Rng + ?Sized,
+    {
+        BaseDeque::shuffle(self, rng)
+    }
+
+    /// Returns a reference to a randomly chosen element, or `None` if the
+    /// deque is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+    /// deque.push_back(3).unwrap();
+    /// deque.push_back(4).unwrap();
+    /// deque.push_front(2).unwrap();
+    /// deque.push_front(1).unwrap();
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    ///
+    /// let chosen = deque.choose(&mut rng).unwrap();
+    /// assert!((1..=4).contains(chosen));
+    /// ```
+    #[inline]
+    pub fn choose<R>(&self, rng: &mut R) -> Option<&T>
+    where
+        R: rand::Rng + ?Sized,
+    {
+        BaseDeque::choose(self, rng)
+    }
+
+    /// Returns a mutable reference to a randomly chosen element, or `None` if
+    /// the deque is empty.
+    ///
+    /// See [`choose`] for details.
+    ///
+    /// [`choose`]: ArrayDeque::choose
+    #[inline]
+    pub fn choose_mut<R>(&mut self, rng: &mut R) -> Option<&mut T>
+    where
+        R: rand::Rng + ?Sized,
+    {
+        BaseDeque::choose_mut(self, rng)
+    }
+}
+
+impl<T, const N: usize> ArrayDeque<T, N>
+where
+    T: Persist + Default,
+{
+    /// Writes a binary snapshot of the deque, passing the bytes to `write`.
+    ///
+    /// The elements are written from front to back, after a short header.
+    /// `write` is called with a few bytes at a time and should write all of
+    /// them; the first error it returns is passed on. See the [`snapshot`]
+    /// module for the format.
+    ///
+    /// [`snapshot`]: crate::snapshot
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deque holds more than `u32::MAX` elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() -> std::io::Result<()> {
+    /// use std::io::Write;
+    ///
+    /// let mut readings: ArrayDeque<i16, 8> = ArrayDeque::new();
+    /// readings.push_back(-40).unwrap();
+    /// readings.push_back(85).unwrap();
+    ///
+    /// let mut file = Vec::new();
+    /// readings.persist_to(|bytes| file.write_all(bytes))?;
+    ///
+    /// let mut reader = &file[..];
+    /// let restored: ArrayDeque<i16, 8> =
+    ///     ArrayDeque::load_from(|buf| std::io::Read::read_exact(&mut reader, buf)).unwrap();
+    /// assert_eq!(restored, readings);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn persist_to<W, E>(&self, write: W) -> Result<(), E>
+    where
+        W: FnMut(&[u8]) -> Result<(), E>,
+    {
+        snapshot::persist_to(self, write)
+    }
+
+    /// Constructs a deque from a binary snapshot written by [`persist_to`],
+    /// filling buffers with `read`.
+    ///
+    /// `read` should fill the whole buffer it is given. If the snapshot was
+    /// written with a different version of the format or element size, or
+    /// holds more than `N` elements, an error is returned.
+    ///
+    /// [`persist_to`]: ArrayDeque::persist_to
+    pub fn load_from<R, E>(read: R) -> Result<Self, LoadError<E>>
+    where
+        R: FnMut(&mut [u8]) -> Result<(), E>,
+    {
+        let mut deque = ArrayDeque::new();
+        snapshot::load_from(&mut deque, read)?;
+
+        Ok(deque)
+    }
+}
+
+impl<T, const K: usize, const N: usize> ArrayDeque<[T; K], N>
+where
+    [T; K]: Default,
+{
+    /// Returns an iterator over the elements of the arrays in the deque, from
+    /// front to back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut frames: ArrayDeque<[f32; 2], 4> = ArrayDeque::new();
+    /// frames.push_back([0.5, -0.5]).unwrap();
+    /// frames.push_back([0.25, -0.25]).unwrap();
+    ///
+    /// let peak = frames.iter_flat().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    /// assert_eq!(peak, 0.5);
+    /// assert_eq!(frames.iter_flat().len(), 4);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn iter_flat(&self) -> IterFlat<'_, T> {
+        let (front, back) = self.as_slices();
+
+        IterFlat {
+            inner: front.as_flattened().iter().chain(back.as_flattened()),
+        }
+    }
+
+    /// Returns an iterator which allows modifying the elements of the arrays
+    /// in the deque, from front to back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut frames: ArrayDeque<[f32; 2], 4> = ArrayDeque::new();
+    /// frames.push_back([0.5, -0.5]).unwrap();
+    /// frames.push_front([1.0, -1.0]).unwrap();
+    ///
+    /// frames.iter_flat_mut().for_each(|s| *s *= 0.5);
+    /// assert_eq!(frames.pop_front(), Some([0.5, -0.5]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn iter_flat_mut(&mut self) -> IterFlatMut<'_, T> {
+        let (front, back) = self.as_mut_slices();
+
+        IterFlatMut {
+            inner: front
+                .as_flattened_mut()
+                .iter_mut()
+                .chain(back.as_flattened_mut()),
+        }
+    }
+
+    /// Returns the elements of the arrays in the deque as a single slice, or
+    /// `None` if the deque is not contiguous.
+    ///
+    /// Calling [`rotate_to_front`] with an index of `0` first makes the deque
+    /// contiguous, so that this returns `Some`.
+    ///
+    /// [`rotate_to_front`]: ArrayDeque::rotate_to_front
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut frames: ArrayDeque<[i16; 2], 4> = ArrayDeque::new();
+    /// frames.push_back([1, 2]).unwrap();
+    /// frames.push_back([3, 4]).unwrap();
+    /// assert_eq!(frames.flatten_slices(), Some(&[1, 2, 3, 4][..]));
+    ///
+    /// frames.push_front([-1, 0]).unwrap();
+    /// assert_eq!(frames.flatten_slices(), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn flatten_slices(&self) -> Option<&[T]> {
+        match self.as_slices() {
+            (front, []) => Some(front.as_flattened()),
+            _ => None,
+        }
+    }
+}
+
+impl<A, B, const N: usize> ArrayDeque<(A, B), N>
+where
+    A: Default,
+    B: Default,
+{
+    /// Combines two deques of equal length into a deque of pairs, in order.
+    ///
+    /// If the deques have different lengths, they are returned unchanged in
+    /// an `Err`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut timestamps: ArrayDeque<u64, 4> = ArrayDeque::new();
+    /// let mut readings: ArrayDeque<f32, 4> = ArrayDeque::new();
+    /// timestamps.try_extend([100, 110].iter().copied()).unwrap();
+    /// readings.try_extend([0.5, 0.75].iter().copied()).unwrap();
+    ///
+    /// let samples = ArrayDeque::zip(timestamps, readings).unwrap();
+    /// assert!(samples.iter().eq(&[(100, 0.5), (110, 0.75)]));
+    ///
+    /// let (timestamps, readings) = samples.unzip();
+    /// assert!(timestamps.iter().eq(&[100, 110]));
+    /// assert!(readings.iter().eq(&[0.5, 0.75]));
+    /// # }
+    /// ```
+    // The inputs are handed back on error, so the error is as large as they
+    // are.
+    #[allow(clippy::result_large_err)]
+    pub fn zip(
+        mut first: ArrayDeque<A, N>,
+        mut second: ArrayDeque<B, N>,
+    ) -> Result<Self, (ArrayDeque<A, N>, ArrayDeque<B, N>)> {
+        if first.len() != second.len() {
+            return Err((first, second));
+        }
+
+        let mut pairs = ArrayDeque::new();
+        while let (Some(a), Some(b)) = (first.pop_front(), second.pop_front()) {
+            pairs.push_back_expect((a, b));
+        }
+
+        Ok(pairs)
+    }
+
+    /// Splits a deque of pairs into a deque of the first elements and a deque
+    /// of the second elements, in order.
+    ///
+    /// See [`zip`](ArrayDeque::zip) for an example.
+    pub fn unzip(mut self) -> (ArrayDeque<A, N>, ArrayDeque<B, N>) {
+        let mut first = ArrayDeque::new();
+        let mut second = ArrayDeque::new();
+
+        while let Some((a, b)) = self.pop_front() {
+            first.push_back_expect(a);
+            second.push_back_expect(b);
+        }
+
+        (first, second)
+    }
+}
+
+impl<const N: usize> ArrayDeque<u8, N> {
+    /// Returns the index of the first occurrence of `byte`, counting from the
+    /// front of the deque.
+    ///
+    /// Each of the two backing slices is scanned in bulk; with the `memchr`
+    /// feature, this uses SIMD instructions where available.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut rx: ArrayDeque<u8, 16> = ArrayDeque::new();
+    /// rx.try_extend(b"ping\npo".iter().copied()).unwrap();
+    ///
+    /// let end = rx.find_byte(b'\n').unwrap();
+    /// let line: Vec<u8> = rx.drain_front(end + 1).unwrap().collect();
+    /// assert_eq!(line, b"ping\n");
+    /// assert_eq!(rx.find_byte(b'\n'), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn find_byte(&self, byte: u8) -> Option<usize> {
+        crate::find_byte(self, byte)
+    }
+
+    /// Returns an adaptor which displays the contents of the deque as a hex
+    /// dump, in the style of `hexdump -C`.
+    ///
+    /// See [`HexDump`] for the format.
+    ///
+    /// [`HexDump`]: crate::format::HexDump
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut rx: ArrayDeque<u8, 32> = ArrayDeque::new();
+    /// rx.try_extend(b"\x02HELLO, WORLD!\r\n\x03".iter().copied()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     rx.hex_dump().to_string(),
+    ///     "00000000  02 48 45 4c 4c 4f 2c 20  57 4f 52 4c 44 21 0d 0a  |.HELLO, WORLD!..|\n\
+    ///      00000010  03                                                |.|",
+    /// );
+    /// # }
+    /// ```
+    #[inline]
+    pub fn hex_dump(&self) -> HexDump<'_> {
+        HexDump::new(self.as_slices())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const N: usize> ArrayDeque<u8, N> {
+    /// Reads bytes from `reader` into the back of the deque, returning the
+    /// number of bytes read.
+    ///
+    /// The unused space in the deque consists of at most two contiguous
+    /// regions, so at most two calls to [`Read::read`] are made. The second
+    /// call is only made if the first one fills its region completely. If the
+    /// second call fails, the bytes from the first call are kept and the
+    /// error is discarded.
+    ///
+    /// A return value of `Ok(0)` indicates either that the deque is full or
+    /// that `reader` has reached end-of-file.
+    ///
+    /// [`Read::read`]: std::io::Read::read
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() -> std::io::Result<()> {
+    /// let mut deque: ArrayDeque<u8, 4> = ArrayDeque::new();
+    /// let mut reader: &[u8] = b"abcdef";
+    ///
+    /// assert_eq!(deque.read_from(&mut reader)?, 4);
+    /// assert!(deque.is_full());
+    /// assert_eq!(deque.read_from(&mut reader)?, 0);
+    ///
+    /// deque.pop_front();
+    /// deque.pop_front();
+    /// assert_eq!(deque.read_from(&mut reader)?, 2);
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), b"cdef");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_from<R>(&mut self, reader: &mut R) -> std::io::Result<usize>
+    where
+        R: std::io::Read + ?Sized,
+    {
+        crate::io::read_from(self, reader)
+    }
+
+    /// Writes bytes from the front of the deque to `writer`, removing the
+    /// bytes that were written and returning their count.
+    ///
+    /// The contents of the deque consist of at most two contiguous regions, so
+    /// at most two calls to [`Write::write`] are made. The second call is only
+    /// made if the first one writes its region completely. If the second call
+    /// fails, the bytes from the first call are still removed and the error is
+    /// discarded.
+    ///
+    /// Bytes not accepted by `writer` remain at the front of the deque.
+    ///
+    /// [`Write::write`]: std::io::Write::write
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() -> std::io::Result<()> {
+    /// let mut deque: ArrayDeque<u8, 8> = ArrayDeque::new();
+    /// deque.try_extend(b"hello".iter().copied()).unwrap();
+    ///
+    /// // A fixed-size writer which only accepts three bytes.
+    /// let mut buf = [0; 3];
+    /// let mut writer = &mut buf[..];
+    ///
+    /// assert_eq!(deque.write_front_to(&mut writer)?, 3);
+    /// assert_eq!(&buf, b"hel");
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), b"lo");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_front_to<W>(&mut self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: std::io::Write + ?Sized,
+    {
+        crate::io::write_front_to(self, writer)
+    }
+}
+
+/// An immutable iterator over an `ArrayDeque<T, N>`.
+///
+/// This struct is created by the [`iter`] method on [`ArrayDeque`].
+///
+/// [`iter`]: ArrayDeque::iter
+pub struct Iter<'a, T, const N: usize>
+where
+    T: Default,
+{
+    inner: DequeIter<'a, ArrayDeque<T, N>, T>,
+}
+
+impl<'a, T, const N: usize> Iter<'a, T, N>
+where
+    T: Default,
+{
+    #[inline]
+    fn new(deque: &'a ArrayDeque<T, N>) -> Iter<'a, T, N> {
+        Iter {
+            inner: DequeIter::new(deque),
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for Iter<'a, T, N>
+where
+    T: Default,
+{
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for Iter<'a, T, N>
+where
+    T: Default,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+/// A draining iterator which removes elements from the front of an
+/// `ArrayDeque<T, N>`.
+///
+/// This struct is created by the [`drain_front`] method on [`ArrayDeque`].
+///
 /// [`drain_front`]: ArrayDeque::drain_front
 pub struct DrainFront<'a, T, const N: usize>
 where
     T: Default,
 {
-    inner: DequeDrain<'a, ArrayDeque<T, N>, T>,
+    inner: DequeDrain<'a, ArrayDeque<T, N>, T>,
+}
+
+impl<'a, T, const N: usize> DrainFront<'a, T, N>
+where
+    T: Default,
+{
+    #[inline]
+    fn new(deque: &'a mut ArrayDeque<T, N>, n: usize) -> Option<DrainFront<'a, T, N>> {
+        Some(DrainFront {
+            inner: DequeDrain::front(deque, n)?,
+        })
+    }
+
+    /// Returns the elements which have not yet been yielded as a pair of
+    /// slices, in front-to-back order.
+    ///
+    /// This allows the remaining elements to be processed in bulk; they are
+    /// still dropped along with the iterator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 6> = ArrayDeque::new();
+    /// deque.try_extend(0..6).unwrap();
+    ///
+    /// let mut drain = deque.drain_front(3).unwrap();
+    /// assert_eq!(drain.as_slices(), (&[0, 1, 2][..], &[][..]));
+    ///
+    /// drain.next();
+    /// assert_eq!(drain.as_slices(), (&[1, 2][..], &[][..]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.inner.as_slices()
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for DrainFront<'a, T, N>
+where
+    T: Default,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// A draining iterator which removes elements from the back of an
+/// `ArrayDeque<T, N>`.
+///
+/// This struct is created by the [`drain_back`] method on [`ArrayDeque`].
+///
+/// [`drain_back`]: ArrayDeque::drain_back
+pub struct DrainBack<'a, T, const N: usize>
+where
+    T: Default,
+{
+    inner: DequeDrain<'a, ArrayDeque<T, N>, T>,
+}
+
+impl<'a, T, const N: usize> DrainBack<'a, T, N>
+where
+    T: Default,
+{
+    #[inline]
+    fn new(deque: &'a mut ArrayDeque<T, N>, n: usize) -> Option<DrainBack<'a, T, N>> {
+        Some(DrainBack {
+            inner: DequeDrain::back(deque, n)?,
+        })
+    }
+
+    /// Returns the elements which have not yet been yielded as a pair of
+    /// slices, in front-to-back order. This is the reverse of the order in
+    /// which they are yielded.
+    ///
+    /// This allows the remaining elements to be processed in bulk; they are
+    /// still dropped along with the iterator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// # fn main() {
+    /// let mut deque: ArrayDeque<u32, 6> = ArrayDeque::new();
+    /// deque.try_extend(0..6).unwrap();
+    ///
+    /// let mut drain = deque.drain_back(3).unwrap();
+    /// assert_eq!(drain.as_slices(), (&[3, 4, 5][..], &[][..]));
+    ///
+    /// drain.next();
+    /// assert_eq!(drain.as_slices(), (&[3, 4][..], &[][..]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.inner.as_slices()
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for DrainBack<'a, T, N>
+where
+    T: Default,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// An iterator which removes elements from the front of an `ArrayDeque<T, N>`
+/// in arrays of `C` elements.
+///
+/// This struct is created by the [`drain_chunks`] method on [`ArrayDeque`].
+///
+/// [`drain_chunks`]: ArrayDeque::drain_chunks
+pub struct DrainChunks<'a, T, const N: usize, const C: usize>
+where
+    T: Default,
+{
+    inner: DequeDrainChunks<'a, ArrayDeque<T, N>, T, C>,
+}
+
+impl<'a, T, const N: usize, const C: usize> Iterator for DrainChunks<'a, T, N, C>
+where
+    T: Default,
+{
+    type Item = [T; C];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T, const N: usize, const C: usize> ExactSizeIterator for DrainChunks<'a, T, N, C> where
+    T: Default
+{
+}
+
+/// An immutable iterator over the elements of the arrays in an
+/// `ArrayDeque<[T; K], N>`.
+///
+/// This struct is created by the [`iter_flat`] method on [`ArrayDeque`].
+///
+/// [`iter_flat`]: ArrayDeque::iter_flat
+pub struct IterFlat<'a, T> {
+    inner: Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>,
+}
+
+impl<'a, T> Iterator for IterFlat<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterFlat<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterFlat<'a, T> {}
+
+/// A mutable iterator over the elements of the arrays in an
+/// `ArrayDeque<[T; K], N>`.
+///
+/// This struct is created by the [`iter_flat_mut`] method on [`ArrayDeque`].
+///
+/// [`iter_flat_mut`]: ArrayDeque::iter_flat_mut
+pub struct IterFlatMut<'a, T> {
+    inner: Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>,
+}
+
+impl<'a, T> Iterator for IterFlatMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterFlatMut<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterFlatMut<'a, T> {}
+
+/// An iterator which removes the elements of an `ArrayDeque<T, N>` for which
+/// a predicate returns `true`.
+///
+/// This struct is created by the [`extract_if`] method on [`ArrayDeque`].
+///
+/// [`extract_if`]: ArrayDeque::extract_if
+pub struct ExtractIf<'a, T, const N: usize, F>
+where
+    T: Default,
+{
+    inner: DequeExtractIf<'a, ArrayDeque<T, N>, T, F>,
+}
+
+impl<'a, T, const N: usize, F> Iterator for ExtractIf<'a, T, N, F>
+where
+    T: Default,
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// A cursor over a `ArrayDeque<T, N>` with editing operations.
+///
+/// A cursor points either at an element of the deque or at a "ghost"
+/// position between the back and the front. Moving past either end of the
+/// deque lands on the ghost position, and moving again wraps around to the
+/// other end.
+///
+/// Inserting or removing at the cursor shifts whichever side of the deque is
+/// shorter, so these operations take time proportional to the distance from
+/// the cursor to the nearer end of the deque.
+///
+/// This struct is created by the [`cursor_front_mut`] and [`cursor_back_mut`]
+/// methods on [`ArrayDeque`].
+///
+/// [`cursor_front_mut`]: ArrayDeque::cursor_front_mut
+/// [`cursor_back_mut`]: ArrayDeque::cursor_back_mut
+pub struct CursorMut<'a, T, const N: usize>
+where
+    T: Default,
+{
+    inner: DequeCursor<'a, ArrayDeque<T, N>, T>,
+}
+
+impl<'a, T, const N: usize> CursorMut<'a, T, N>
+where
+    T: Default,
+{
+    /// Returns the logical index of the element the cursor points at.
+    ///
+    /// If the cursor points at the ghost position, `None` is returned.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.inner.index()
+    }
+
+    /// Returns a mutable reference to the element the cursor points at.
+    ///
+    /// If the cursor points at the ghost position, `None` is returned.
+    #[inline]
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.inner.current()
+    }
+
+    /// Returns a mutable reference to the element after the cursor.
+    ///
+    /// If the cursor points at the ghost position, this is the front element.
+    /// If the cursor points at the back element, `None` is returned.
+    #[inline]
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        self.inner.peek_next()
+    }
+
+    /// Returns a mutable reference to the element before the cursor.
+    ///
+    /// If the cursor points at the ghost position, this is the back element.
+    /// If the cursor points at the front element, `None` is returned.
+    #[inline]
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        self.inner.peek_prev()
+    }
+
+    /// Moves the cursor to the next element.
+    ///
+    /// If the cursor points at the back element, it moves to the ghost
+    /// position. If it points at the ghost position, it moves to the front
+    /// element.
+    #[inline]
+    pub fn move_next(&mut self) {
+        self.inner.move_next()
+    }
+
+    /// Moves the cursor to the previous element.
+    ///
+    /// If the cursor points at the front element, it moves to the ghost
+    /// position. If it points at the ghost position, it moves to the back
+    /// element.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        self.inner.move_prev()
+    }
+
+    /// Inserts an element before the cursor.
+    ///
+    /// If the cursor points at the ghost position, the element is inserted at
+    /// the back of the deque. The cursor continues to point at the same
+    /// element.
+    ///
+    /// If the deque is full, an `Err` containing `item` is returned.
+    #[inline]
+    pub fn insert_before(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.inner.insert_before(item)
+    }
+
+    /// Inserts an element after the cursor.
+    ///
+    /// If the cursor points at the ghost position, the element is inserted at
+    /// the front of the deque. The cursor continues to point at the same
+    /// element.
+    ///
+    /// If the deque is full, an `Err` containing `item` is returned.
+    #[inline]
+    pub fn insert_after(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.inner.insert_after(item)
+    }
+
+    /// Removes and returns the element the cursor points at.
+    ///
+    /// The cursor moves to the element that followed the removed one, or to
+    /// the ghost position if the back element was removed. If the cursor
+    /// points at the ghost position, nothing is removed and `None` is
+    /// returned.
+    #[inline]
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.inner.remove_current()
+    }
+}
+
+/// The front element of a [`ArrayDeque`], which is removed from the deque only
+/// when committed.
+///
+/// The guard dereferences to the element. If the guard is dropped without
+/// calling [`commit`] or [`into_inner`], the element is left at the front of
+/// the deque, including any changes made through the guard.
+///
+/// This struct is created by the [`pop_front_guard`] method on
+/// [`ArrayDeque`].
+///
+/// [`commit`]: PopGuard::commit
+/// [`into_inner`]: PopGuard::into_inner
+/// [`pop_front_guard`]: ArrayDeque::pop_front_guard
+pub struct PopGuard<'a, T, const N: usize>
+where
+    T: Default,
+{
+    inner: DequePopGuard<'a, ArrayDeque<T, N>, T>,
+}
+
+impl<'a, T, const N: usize> PopGuard<'a, T, N>
+where
+    T: Default,
+{
+    /// Removes the element from the front of the deque and drops it.
+    #[inline]
+    pub fn commit(self) {
+        drop(self.inner.into_inner());
+    }
+
+    /// Removes the element from the front of the deque and returns it.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.inner.into_inner()
+    }
+}
+
+impl<'a, T, const N: usize> Deref for PopGuard<'a, T, N>
+where
+    T: Default,
+{
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.inner.get()
+    }
+}
+
+impl<'a, T, const N: usize> DerefMut for PopGuard<'a, T, N>
+where
+    T: Default,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        self.inner.get_mut()
+    }
+}
+
+/// A [`ArrayDeque`] which is truncated back to its original length when the guard
+/// is dropped, unless kept.
+///
+/// The guard dereferences to the deque, so elements can be pushed through it
+/// as usual. When the guard is dropped without calling [`keep`], every
+/// element beyond the length the deque had when the guard was created is
+/// removed from the back and dropped.
+///
+/// Only elements pushed to the back are undone. To also undo elements pushed
+/// to the front, use [`savepoint`] instead.
+///
+/// This struct is created by the [`truncate_guard`] method on [`ArrayDeque`].
+///
+/// [`keep`]: TruncateGuard::keep
+/// [`savepoint`]: ArrayDeque::savepoint
+/// [`truncate_guard`]: ArrayDeque::truncate_guard
+pub struct TruncateGuard<'a, T, const N: usize>
+where
+    T: Default,
+{
+    inner: DequeTruncateGuard<'a, ArrayDeque<T, N>, T>,
+}
+
+impl<'a, T, const N: usize> TruncateGuard<'a, T, N>
+where
+    T: Default,
+{
+    /// Returns the length the deque is truncated to when the guard is
+    /// dropped.
+    #[inline]
+    pub fn original_len(&self) -> usize {
+        self.inner.original_len()
+    }
+
+    /// Keeps the elements pushed while the guard was alive.
+    #[inline]
+    pub fn keep(self) {
+        self.inner.keep()
+    }
+}
+
+impl<'a, T, const N: usize> Deref for TruncateGuard<'a, T, N>
+where
+    T: Default,
+{
+    type Target = ArrayDeque<T, N>;
+
+    #[inline]
+    fn deref(&self) -> &ArrayDeque<T, N> {
+        self.inner.get()
+    }
+}
+
+impl<'a, T, const N: usize> DerefMut for TruncateGuard<'a, T, N>
+where
+    T: Default,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut ArrayDeque<T, N> {
+        self.inner.get_mut()
+    }
+}
+
+/// A reserved slot at the back of a [`ArrayDeque`], which becomes the back
+/// element of the deque only when committed.
+///
+/// The guard dereferences to the value in the slot, which starts out as the
+/// default value of `T`. If the guard is dropped without calling [`commit`],
+/// the slot is reset to the default value and the deque is left unchanged.
+///
+/// This struct is created by the [`reserve_back_slot`] method on
+/// [`ArrayDeque`].
+///
+/// [`commit`]: SlotGuard::commit
+/// [`reserve_back_slot`]: ArrayDeque::reserve_back_slot
+pub struct SlotGuard<'a, T, const N: usize>
+where
+    T: Default,
+{
+    inner: DequeSlot<'a, ArrayDeque<T, N>, T>,
+}
+
+impl<'a, T, const N: usize> SlotGuard<'a, T, N>
+where
+    T: Default,
+{
+    /// Appends the value in the slot to the back of the deque.
+    #[inline]
+    pub fn commit(self) {
+        self.inner.commit()
+    }
+}
+
+impl<'a, T, const N: usize> Deref for SlotGuard<'a, T, N>
+where
+    T: Default,
+{
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.inner.get()
+    }
+}
+
+impl<'a, T, const N: usize> DerefMut for SlotGuard<'a, T, N>
+where
+    T: Default,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        self.inner.get_mut()
+    }
+}
+
+/// A handle to the front of a split `ArrayDeque`.
+///
+/// This struct is created by the [`split`] method on [`ArrayDeque`].
+///
+/// [`split`]: ArrayDeque::split
+pub struct FrontHandle<'a, T, const N: usize>
+where
+    T: Default,
+{
+    inner: DequeHalf<'a, ArrayMeta<N>, T>,
+}
+
+impl<'a, T, const N: usize> FrontHandle<'a, T, N>
+where
+    T: Default,
+{
+    /// Returns the number of elements in the deque.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the deque is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.len() == 0
+    }
+
+    /// Returns a clone of the front element, or `None` if the deque is empty.
+    #[inline]
+    pub fn front(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.inner.front()
+    }
+
+    /// Removes the front element and returns it, or `None` if the deque is
+    /// empty.
+    #[inline]
+    pub fn pop_front(&self) -> Option<T> {
+        self.inner.pop_front()
+    }
+}
+
+/// A handle to the back of a split `ArrayDeque`.
+///
+/// This struct is created by the [`split`] method on [`ArrayDeque`].
+///
+/// [`split`]: ArrayDeque::split
+pub struct BackHandle<'a, T, const N: usize>
+where
+    T: Default,
+{
+    inner: DequeHalf<'a, ArrayMeta<N>, T>,
+}
+
+impl<'a, T, const N: usize> BackHandle<'a, T, N>
+where
+    T: Default,
+{
+    /// Returns the number of elements in the deque.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the deque is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.len() == 0
+    }
+
+    /// Returns `true` if the deque is full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.inner.len() == self.inner.capacity()
+    }
+
+    /// Returns a clone of the back element, or `None` if the deque is empty.
+    #[inline]
+    pub fn back(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.inner.back()
+    }
+
+    /// Appends an element to the back of the deque.
+    ///
+    /// If the deque is full, the element is returned in a [`CapacityError`].
+    #[inline]
+    pub fn push_back(&self, item: T) -> Result<(), CapacityError<T>> {
+        self.inner.push_back(item)
+    }
+}
+
+#[cfg(feature = "serde")]
+use core::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{
+    de::{
+        Deserialize, DeserializeSeed, Deserializer, Error, Expected, IgnoredAny, SeqAccess, Visitor,
+    },
+    ser::{Serialize, SerializeSeq, Serializer},
+};
+
+#[cfg(feature = "serde")]
+impl<T, const N: usize> serde::Serialize for ArrayDeque<T, N>
+where
+    T: Serialize + Default,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+
+        for element in self.iter() {
+            seq.serialize_element(element)?;
+        }
+
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub struct ExceededCapacity {
+    pub(crate) capacity: usize,
+}
+
+#[cfg(feature = "serde")]
+impl Expected for ExceededCapacity {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a sequence of at most {} elements",
+            self.capacity
+        )
+    }
+}
+
+/// Deserializes a deque from a sequence of at most `N` elements.
+///
+/// The `'de` lifetime of the deserializer is passed through to the elements,
+/// so types which borrow from the input, such as `&'de str` and `&'de [u8]`,
+/// can be deserialized without copying when the format supports it.
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::ArrayDeque;
+/// use serde::Deserialize;
+/// use serde::de::value::{BorrowedStrDeserializer, Error, SeqDeserializer};
+///
+/// let input = String::from("borrowed");
+/// let elements = [BorrowedStrDeserializer::<Error>::new(&input)];
+/// let deserializer = SeqDeserializer::<_, Error>::new(elements.iter().cloned());
+///
+/// let deque: ArrayDeque<&str, 4> = ArrayDeque::deserialize(deserializer)?;
+/// assert!(core::ptr::eq(deque.front().unwrap().as_ptr(), input.as_ptr()));
+/// # Ok::<(), Error>(())
+/// ```
+#[cfg(feature = "serde")]
+impl<'de, T, const N: usize> Deserialize<'de> for ArrayDeque<T, N>
+where
+    T: Deserialize<'de> + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ArrayDequeVisitor<T, const N: usize> {
+            phantom: core::marker::PhantomData<T>,
+        }
+
+        impl<'de, T, const N: usize> Visitor<'de> for ArrayDequeVisitor<T, N>
+        where
+            T: Deserialize<'de> + Default,
+        {
+            type Value = ArrayDeque<T, N>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a sequence of at most {} elements", N)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut deque = ArrayDeque::new();
+
+                while let Some(elem) = seq.next_element()? {
+                    deque.push_back(elem).map_err(|_| {
+                        A::Error::invalid_length(deque.len() + 1, &ExceededCapacity { capacity: N })
+                    })?;
+                }
+
+                Ok(deque)
+            }
+        }
+
+        deserializer.deserialize_seq(ArrayDequeVisitor {
+            phantom: PhantomData,
+        })
+    }
+
+    fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct InPlaceVisitor<'a, T, const N: usize>(&'a mut ArrayDeque<T, N>)
+        where
+            T: Default;
+
+        impl<'a, 'de, T, const N: usize> Visitor<'de> for InPlaceVisitor<'a, T, N>
+        where
+            T: Deserialize<'de> + Default,
+        {
+            type Value = ();
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a sequence of at most {} elements", N)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let deque = self.0;
+
+                // Deserialize into the existing elements first, so that any
+                // resources they own can be reused.
+                for index in 0..deque.len() {
+                    let place = BaseDeque::get_mut(deque, index).unwrap();
+
+                    if seq.next_element_seed(InPlaceSeed(place))?.is_none() {
+                        deque.truncate(index);
+                        return Ok(());
+                    }
+                }
+
+                while let Some(elem) = seq.next_element()? {
+                    deque.push_back(elem).map_err(|_| {
+                        A::Error::invalid_length(deque.len() + 1, &ExceededCapacity { capacity: N })
+                    })?;
+                }
+
+                Ok(())
+            }
+        }
+
+        deserializer.deserialize_seq(InPlaceVisitor(place))
+    }
+}
+
+/// A seed which deserializes a value in place.
+#[cfg(feature = "serde")]
+struct InPlaceSeed<'a, T>(&'a mut T);
+
+#[cfg(feature = "serde")]
+impl<'a, 'de, T> DeserializeSeed<'de> for InPlaceSeed<'a, T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize_in_place(deserializer, self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, const N: usize> ArrayDeque<T, N>
+where
+    T: Deserialize<'de> + Default,
+{
+    /// Extends the deque with the contents of a deserializer.
+    ///
+    /// The elements of the deserialized sequence are appended to the back of
+    /// the deque. If the deque becomes full, an error is returned and the
+    /// elements appended before the error remain in the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// use serde::de::value::{Error, SeqDeserializer};
+    ///
+    /// let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+    /// deque.push_back(1).unwrap();
+    ///
+    /// let deserializer = SeqDeserializer::<_, Error>::new([2u32, 3].iter().copied());
+    /// deque.extend_deserialize(deserializer)?;
+    ///
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn extend_deserialize<D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let extend = ExtendArrayDeque { deque: self };
+        extend.deserialize(deserializer)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+struct ExtendArrayDeque<'deque, T, const N: usize>
+where
+    T: Default,
+{
+    deque: &'deque mut ArrayDeque<T, N>,
+}
+
+#[cfg(feature = "serde")]
+impl<'deque, 'de, T, const N: usize> DeserializeSeed<'de> for ExtendArrayDeque<'deque, T, N>
+where
+    T: Deserialize<'de> + Default,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ExtendArrayDequeVisitor<'deque, T, const N: usize>
+        where
+            T: Default,
+        {
+            deque: &'deque mut ArrayDeque<T, N>,
+        }
+
+        impl<'deque, 'de, T, const N: usize> Visitor<'de> for ExtendArrayDequeVisitor<'deque, T, N>
+        where
+            T: Deserialize<'de> + Default,
+        {
+            type Value = ();
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    formatter,
+                    "a sequence of at most {} elements",
+                    N - self.deque.len()
+                )
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                while let Some(elem) = seq.next_element()? {
+                    self.deque.push_back(elem).map_err(|_| {
+                        A::Error::invalid_length(
+                            self.deque.len() + 1,
+                            &ExceededCapacity { capacity: N },
+                        )
+                    })?;
+                }
+
+                Ok(())
+            }
+        }
+
+        deserializer.deserialize_seq(ExtendArrayDequeVisitor { deque: self.deque })?;
+
+        Ok(())
+    }
+}
+
+/// A deque deserialized from a sequence which may be longer than its
+/// capacity.
+///
+/// The `Deserialize` implementation of `ArrayDeque` fails if the input holds
+/// more than `N` elements. Deserializing a `Truncating<ArrayDeque<T, N>>`
+/// instead keeps the first `N` elements and skips the rest, counting how many
+/// were discarded. Skipped elements are not deserialized into `T`, so they
+/// need not be valid values of `T`.
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::{array_deque::Truncating, ArrayDeque};
+/// use serde::Deserialize;
+/// use serde::de::value::{Error, SeqDeserializer};
+///
+/// let deserializer = SeqDeserializer::<_, Error>::new([1u32, 2, 3, 4, 5].iter().copied());
+/// let truncated = Truncating::<ArrayDeque<u32, 3>>::deserialize(deserializer)?;
+///
+/// assert_eq!(truncated.discarded(), 2);
+/// assert_eq!(truncated.into_inner().iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+/// # Ok::<(), Error>(())
+/// ```
+#[cfg(feature = "serde")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Truncating<D> {
+    deque: D,
+    discarded: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<D> Truncating<D> {
+    /// Returns a reference to the deserialized deque.
+    #[inline]
+    pub fn deque(&self) -> &D {
+        &self.deque
+    }
+
+    /// Returns the number of elements which did not fit in the deque.
+    #[inline]
+    pub fn discarded(&self) -> usize {
+        self.discarded
+    }
+
+    /// Returns `true` if any elements were discarded.
+    #[inline]
+    pub fn is_truncated(&self) -> bool {
+        self.discarded > 0
+    }
+
+    /// Consumes the wrapper, returning the deserialized deque.
+    #[inline]
+    pub fn into_inner(self) -> D {
+        self.deque
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, const N: usize> Deserialize<'de> for Truncating<ArrayDeque<T, N>>
+where
+    T: Deserialize<'de> + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TruncatingVisitor<T, const N: usize> {
+            phantom: PhantomData<T>,
+        }
+
+        impl<'de, T, const N: usize> Visitor<'de> for TruncatingVisitor<T, N>
+        where
+            T: Deserialize<'de> + Default,
+        {
+            type Value = Truncating<ArrayDeque<T, N>>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut deque = ArrayDeque::new();
+
+                while !deque.is_full() {
+                    match seq.next_element()? {
+                        Some(elem) => deque.push_back_expect(elem),
+                        None => break,
+                    }
+                }
+
+                let mut discarded = 0;
+                while seq.next_element::<IgnoredAny>()?.is_some() {
+                    discarded += 1;
+                }
+
+                Ok(Truncating { deque, discarded })
+            }
+        }
+
+        deserializer.deserialize_seq(TruncatingVisitor {
+            phantom: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<const N: usize> bytes::Buf for ArrayDeque<u8, N> {
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.as_slices().0
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        if DequeDrain::front(self, cnt).is_none() {
+            panic!("cannot advance past end of deque: {} > {}", cnt, self.len());
+        }
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<const N: usize> embedded_io::ErrorType for ArrayDeque<u8, N> {
+    type Error = CapacityError<()>;
+}
+
+#[cfg(feature = "embedded-io")]
+impl<const N: usize> embedded_io::Read for ArrayDeque<u8, N> {
+    /// Removes bytes from the front of the deque and copies them into `buf`.
+    ///
+    /// This never blocks. If the deque is empty, `Ok(0)` is returned.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(crate::io::copy_front_into(self, buf))
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<const N: usize> embedded_io::BufRead for ArrayDeque<u8, N> {
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        Ok(self.as_slices().0)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        DequeDrain::front(self, amt).unwrap();
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<const N: usize> embedded_io::Write for ArrayDeque<u8, N> {
+    /// Appends as many bytes from `buf` as will fit to the back of the deque.
+    ///
+    /// This never blocks. If `buf` is nonempty and the deque is full, an error
+    /// is returned.
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if !buf.is_empty() && self.is_full() {
+            return Err(CapacityError { item: () });
+        }
+
+        Ok(crate::io::copy_back_from(self, buf))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<const N: usize> embedded_io::ReadReady for ArrayDeque<u8, N> {
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_empty())
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<const N: usize> embedded_io::WriteReady for ArrayDeque<u8, N> {
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_full())
+    }
+}
+
+#[cfg(feature = "minicbor")]
+impl<C, T, const N: usize> minicbor::Encode<C> for ArrayDeque<T, N>
+where
+    T: minicbor::Encode<C> + Default,
+{
+    fn encode<W>(
+        &self,
+        e: &mut minicbor::Encoder<W>,
+        ctx: &mut C,
+    ) -> Result<(), minicbor::encode::Error<W::Error>>
+    where
+        W: minicbor::encode::Write,
+    {
+        crate::cbor::encode(self, e, ctx)
+    }
+}
+
+#[cfg(feature = "minicbor")]
+impl<'b, C, T, const N: usize> minicbor::Decode<'b, C> for ArrayDeque<T, N>
+where
+    T: minicbor::Decode<'b, C> + Default,
+{
+    fn decode(d: &mut minicbor::Decoder<'b>, ctx: &mut C) -> Result<Self, minicbor::decode::Error> {
+        let mut deque = ArrayDeque::new();
+        crate::cbor::extend_decode(&mut deque, d, ctx)?;
+
+        Ok(deque)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<T, const N: usize> schemars::JsonSchema for ArrayDeque<T, N>
+where
+    T: schemars::JsonSchema + Default,
+{
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        alloc::format!("ArrayDeque_up_to_size_{}_of_{}", N, T::schema_name()).into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "array",
+            "items": generator.subschema_for::<T>(),
+            "maxItems": N,
+        })
+    }
+}
+
+/// The worst-case size is that of a full deque: a varint length prefix
+/// followed by `N` elements of maximum size. This matches the encoding
+/// produced by the `Serialize` implementation when the `serde` feature is
+/// enabled.
+#[cfg(feature = "postcard")]
+impl<T, const N: usize> postcard::experimental::max_size::MaxSize for ArrayDeque<T, N>
+where
+    T: postcard::experimental::max_size::MaxSize + Default,
+{
+    const POSTCARD_MAX_SIZE: usize = T::POSTCARD_MAX_SIZE * N + varint_size(N);
+}
+
+/// Returns the number of bytes needed to encode `n` as a postcard varint.
+#[cfg(feature = "postcard")]
+const fn varint_size(n: usize) -> usize {
+    let bits = usize::BITS - n.leading_zeros();
+
+    // Each varint byte holds 7 bits, and zero still takes one byte.
+    if bits == 0 {
+        1
+    } else {
+        bits.div_ceil(7) as usize
+    }
+}
+
+/// Generates a deque of up to `N` elements, each pushed to a randomly chosen
+/// end, so that both linear and wrapped layouts are produced.
+#[cfg(feature = "arbitrary")]
+impl<'a, T, const N: usize> arbitrary::Arbitrary<'a> for ArrayDeque<T, N>
+where
+    T: arbitrary::Arbitrary<'a> + Default,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut deque = ArrayDeque::new();
+
+        for elem in u.arbitrary_iter::<(bool, T)>()? {
+            if deque.is_full() {
+                break;
+            }
+
+            match elem? {
+                (true, val) => deque.push_front(val).ok().unwrap(),
+                (false, val) => deque.push_back(val).ok().unwrap(),
+            }
+        }
+
+        Ok(deque)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> From<heapless::Vec<T, N>> for ArrayDeque<T, N>
+where
+    T: Default,
+{
+    fn from(vec: heapless::Vec<T, N>) -> Self {
+        let mut deque = ArrayDeque::new();
+        deque.extend(vec);
+        deque
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> From<heapless::Deque<T, N>> for ArrayDeque<T, N>
+where
+    T: Default,
+{
+    fn from(other: heapless::Deque<T, N>) -> Self {
+        let mut deque = ArrayDeque::new();
+        deque.extend(other);
+        deque
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> From<ArrayDeque<T, N>> for heapless::Vec<T, N>
+where
+    T: Default,
+{
+    fn from(mut deque: ArrayDeque<T, N>) -> Self {
+        let mut vec = heapless::Vec::new();
+
+        while let Some(item) = deque.pop_front() {
+            // Both containers have capacity N.
+            vec.push(item).ok().unwrap();
+        }
+
+        vec
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> From<ArrayDeque<T, N>> for heapless::Deque<T, N>
+where
+    T: Default,
+{
+    fn from(mut deque: ArrayDeque<T, N>) -> Self {
+        let mut other = heapless::Deque::new();
+
+        while let Some(item) = deque.pop_front() {
+            other.push_back(item).ok().unwrap();
+        }
+
+        other
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<T, const N: usize> From<arrayvec::ArrayVec<T, N>> for ArrayDeque<T, N>
+where
+    T: Default,
+{
+    fn from(vec: arrayvec::ArrayVec<T, N>) -> Self {
+        let mut deque = ArrayDeque::new();
+        deque.extend(vec);
+        deque
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<T, const N: usize> From<ArrayDeque<T, N>> for arrayvec::ArrayVec<T, N>
+where
+    T: Default,
+{
+    fn from(mut deque: ArrayDeque<T, N>) -> Self {
+        let mut vec = arrayvec::ArrayVec::new();
+
+        while let Some(item) = deque.pop_front() {
+            // Both containers have capacity N.
+            vec.push(item);
+        }
+
+        vec
+    }
+}
+
+#[cfg(feature = "tinyvec")]
+impl<T, const N: usize> From<tinyvec::ArrayVec<[T; N]>> for ArrayDeque<T, N>
+where
+    T: Default,
+{
+    fn from(vec: tinyvec::ArrayVec<[T; N]>) -> Self {
+        let mut deque = ArrayDeque::new();
+        deque.extend(vec);
+        deque
+    }
+}
+
+#[cfg(feature = "tinyvec")]
+impl<T, const N: usize> From<ArrayDeque<T, N>> for tinyvec::ArrayVec<[T; N]>
+where
+    T: Default,
+{
+    fn from(mut deque: ArrayDeque<T, N>) -> Self {
+        let mut vec = tinyvec::ArrayVec::new();
+
+        while let Some(item) = deque.pop_front() {
+            vec.push(item);
+        }
+
+        vec
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A, const N: usize> core::convert::TryFrom<smallvec::SmallVec<A>> for ArrayDeque<A::Item, N>
+where
+    A: smallvec::Array,
+    A::Item: Default,
+{
+    type Error = CapacityError<smallvec::SmallVec<A>>;
+
+    /// Moves the elements of `vec` into a new deque.
+    ///
+    /// If `vec` has more than `N` elements, an `Err` containing `vec` is
+    /// returned.
+    fn try_from(vec: smallvec::SmallVec<A>) -> Result<Self, Self::Error> {
+        let mut deque = ArrayDeque::new();
+        deque.extend_from_smallvec(vec)?;
+        Ok(deque)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A, const N: usize> From<ArrayDeque<A::Item, N>> for smallvec::SmallVec<A>
+where
+    A: smallvec::Array,
+    A::Item: Default,
+{
+    fn from(mut deque: ArrayDeque<A::Item, N>) -> Self {
+        let mut vec = smallvec::SmallVec::with_capacity(deque.len());
+
+        while let Some(item) = deque.pop_front() {
+            vec.push(item);
+        }
+
+        vec
+    }
+}
+
+/// Returns a [`Strategy`] which generates `ArrayDeque`s with elements drawn
+/// from `element`.
+///
+/// Both the number of elements and the physical position of the front element
+/// vary, so generated deques cover linear and wrapped layouts. Values shrink
+/// toward fewer elements and toward a front element at the start of the
+/// backing array.
+///
+/// [`Strategy`]: proptest::strategy::Strategy
+///
+/// # Example
+/// ```
+/// # use holodeque::array_deque::array_deque_strategy;
+/// use proptest::{
+///     arbitrary::any,
+///     strategy::{Strategy, ValueTree},
+///     test_runner::TestRunner,
+/// };
+///
+/// let strategy = array_deque_strategy::<_, 8>(any::<u8>());
+/// let deque = strategy.new_tree(&mut TestRunner::default()).unwrap().current();
+///
+/// assert!(deque.len() <= 8);
+/// ```
+#[cfg(feature = "proptest")]
+pub fn array_deque_strategy<S, const N: usize>(
+    element: S,
+) -> impl proptest::strategy::Strategy<Value = ArrayDeque<S::Value, N>>
+where
+    S: proptest::strategy::Strategy,
+    S::Value: Default,
+{
+    use proptest::strategy::Strategy;
+
+    let elements = proptest::collection::vec(element, 0..=N);
+    let head = 0..N.max(1);
+
+    (elements, head).prop_map(|(elements, head)| {
+        let mut deque = ArrayDeque::new();
+        let mut elements = elements.into_iter();
+
+        if let Some(first) = elements.next() {
+            deque.meta.set_layout(MetaLayout::Linear {
+                first: head,
+                len: core::num::NonZeroUsize::new(1).unwrap(),
+            });
+            deque.items[head] = first;
+        }
+
+        for elem in elements {
+            // The vector holds at most N elements.
+            deque.push_back(elem).ok().unwrap();
+        }
+
+        deque
+    })
+}
+
+#[cfg(any(feature = "quickcheck", test))]
+impl<T, const N: usize> quickcheck::Arbitrary for ArrayDeque<T, N>
+where
+    T: quickcheck::Arbitrary + Default,
+{
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        use crate::DequeEnd;
+
+        let mut deque = ArrayDeque::new();
+        let len = usize::arbitrary(g) % (N + 1);
+
+        for _ in 0..len {
+            let val = T::arbitrary(g);
+            match g.choose(&[DequeEnd::Front, DequeEnd::Back]).unwrap() {
+                DequeEnd::Front => deque.push_front(val).ok().unwrap(),
+                DequeEnd::Back => deque.push_back(val).ok().unwrap(),
+            }
+        }
+
+        deque
+    }
+
+    fn shrink(&self) -> alloc::boxed::Box<dyn Iterator<Item = Self>> {
+        if self.is_empty() {
+            alloc::boxed::Box::new(core::iter::empty())
+        } else {
+            let mut less_front = self.clone();
+            less_front.pop_front();
+
+            let mut less_back = self.clone();
+            less_back.pop_back();
+
+            alloc::boxed::Box::new(alloc::vec![less_front, less_back].into_iter())
+        }
+    }
 }
 
-impl<'a, T, const N: usize> DrainFront<'a, T, N>
-where
-    T: Default,
-{
-    #[inline]
-    fn new(deque: &'a mut ArrayDeque<T, N>, n: usize) -> Option<DrainFront<'a, T, N>> {
-        Some(DrainFront {
-            inner: DequeDrain::front(deque, n)?,
-        })
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::mem;
+
+    extern crate alloc;
+    use alloc::{format, rc::Rc, string::String, vec::Vec};
+
+    #[test]
+    fn empty_deque_has_zero_len() {
+        let d0: ArrayDeque<(), 0> = ArrayDeque::new();
+        assert_eq!(d0.len(), 0);
+
+        let d1: ArrayDeque<(), 1> = ArrayDeque::new();
+        assert_eq!(d1.len(), 0);
+
+        let d3: ArrayDeque<(), 3> = ArrayDeque::new();
+        assert_eq!(d3.len(), 0);
+    }
+
+    #[test]
+    fn empty_deque_front_is_none() {
+        let d0: ArrayDeque<(), 0> = ArrayDeque::new();
+        assert_eq!(d0.front(), None);
+
+        let d1: ArrayDeque<(), 1> = ArrayDeque::new();
+        assert_eq!(d1.front(), None);
+
+        let d3: ArrayDeque<(), 3> = ArrayDeque::new();
+        assert_eq!(d3.front(), None);
+    }
+
+    #[test]
+    fn empty_deque_back_is_none() {
+        let d0: ArrayDeque<(), 0> = ArrayDeque::new();
+        assert_eq!(d0.front(), None);
+
+        let d1: ArrayDeque<(), 1> = ArrayDeque::new();
+        assert_eq!(d1.front(), None);
+
+        let d3: ArrayDeque<(), 3> = ArrayDeque::new();
+        assert_eq!(d3.front(), None);
+    }
+
+    #[test]
+    fn zero_capacity_is_both_empty_and_full() {
+        let zero_cap: ArrayDeque<(), 0> = ArrayDeque::new();
+
+        assert!(zero_cap.is_empty());
+        assert!(zero_cap.is_full());
+    }
+
+    #[test]
+    fn push_zero_capacity_is_error() {
+        let mut zero_cap: ArrayDeque<(), 0> = ArrayDeque::new();
+
+        assert!(zero_cap.push_front(()).is_err());
+        assert!(zero_cap.push_back(()).is_err());
+    }
+
+    #[test]
+    fn pop_zero_capacity_is_none() {
+        let mut zero_cap: ArrayDeque<(), 0> = ArrayDeque::new();
+
+        assert!(zero_cap.pop_front().is_none());
+        assert!(zero_cap.pop_back().is_none());
+    }
+
+    #[test]
+    fn push_full_linear_is_error() {
+        let mut deque: ArrayDeque<(), 3> = ArrayDeque::new();
+
+        deque.push_front(()).unwrap();
+        deque.push_front(()).unwrap();
+        deque.push_front(()).unwrap();
+
+        assert!(deque.push_front(()).is_err());
+        assert!(deque.push_back(()).is_err());
+    }
+
+    #[test]
+    fn push_full_wrapped_is_error() {
+        let mut deque: ArrayDeque<(), 3> = ArrayDeque::new();
+
+        deque.push_front(()).unwrap();
+        deque.push_front(()).unwrap();
+        deque.push_back(()).unwrap();
+
+        assert!(deque.push_front(()).is_err());
+        assert!(deque.push_back(()).is_err());
+    }
+
+    #[test]
+    fn pop_empty_is_none() {
+        let mut deque: ArrayDeque<(), 3> = ArrayDeque::new();
+
+        assert!(deque.pop_front().is_none());
+        assert!(deque.pop_back().is_none());
+    }
+
+    #[test]
+    fn push_front_one_becomes_front_and_back() {
+        let mut deque: ArrayDeque<usize, 3> = ArrayDeque::new();
+
+        deque.push_front(42).unwrap();
+        assert_eq!(deque.front(), Some(&42));
+        assert_eq!(deque.back(), Some(&42));
+    }
+
+    #[test]
+    fn push_back_one_becomes_front_and_back() {
+        let mut deque: ArrayDeque<usize, 3> = ArrayDeque::new();
+
+        deque.push_back(42).unwrap();
+        assert_eq!(deque.front(), Some(&42));
+        assert_eq!(deque.back(), Some(&42));
+    }
+
+    #[test]
+    fn push_both_ends_front_back() {
+        let mut deque: ArrayDeque<&'static str, 3> = ArrayDeque::new();
+
+        deque.push_back("back").unwrap();
+        deque.push_front("front").unwrap();
+
+        assert_eq!(deque.front(), Some(&"front"));
+        assert_eq!(deque.back(), Some(&"back"));
+    }
+
+    #[test]
+    fn push_pop_front() {
+        let mut deque: ArrayDeque<&'static str, 3> = ArrayDeque::new();
+
+        deque.push_front("front").unwrap();
+        assert_eq!(deque.len(), 1);
+        assert_eq!(deque.pop_front(), Some("front"));
+        assert_eq!(deque.len(), 0);
+    }
+
+    #[test]
+    fn push_pop_back() {
+        let mut deque: ArrayDeque<&'static str, 3> = ArrayDeque::new();
+
+        deque.push_back("back").unwrap();
+        assert_eq!(deque.len(), 1);
+        assert_eq!(deque.pop_back(), Some("back"));
+        assert_eq!(deque.len(), 0);
+    }
+
+    #[test]
+    fn push_front_then_back() {
+        let mut deque: ArrayDeque<&'static str, 3> = ArrayDeque::new();
+
+        deque.push_front("front").unwrap();
+        assert_eq!(deque.len(), 1);
+        deque.push_back("back").unwrap();
+        assert_eq!(deque.len(), 2);
+
+        let mut pop_front_front = deque.clone();
+        let mut pop_front_back = deque.clone();
+        let mut pop_back_front = deque.clone();
+        let mut pop_back_back = deque.clone();
+
+        assert_eq!(pop_front_front.pop_front(), Some("front"));
+        assert_eq!(pop_front_front.pop_front(), Some("back"));
+
+        assert_eq!(pop_front_back.pop_front(), Some("front"));
+        assert_eq!(pop_front_back.pop_back(), Some("back"));
+
+        assert_eq!(pop_back_front.pop_back(), Some("back"));
+        assert_eq!(pop_back_front.pop_front(), Some("front"));
+
+        assert_eq!(pop_back_back.pop_back(), Some("back"));
+        assert_eq!(pop_back_back.pop_back(), Some("front"));
+    }
+
+    #[test]
+    fn push_back_then_front() {
+        let mut deque: ArrayDeque<&'static str, 3> = ArrayDeque::new();
+
+        deque.push_back("back").unwrap();
+        assert_eq!(deque.len(), 1);
+        deque.push_front("front").unwrap();
+        assert_eq!(deque.len(), 2);
+
+        let mut pop_front_front = deque.clone();
+        let mut pop_front_back = deque.clone();
+        let mut pop_back_front = deque.clone();
+        let mut pop_back_back = deque.clone();
+
+        assert_eq!(pop_front_front.pop_front(), Some("front"));
+        assert_eq!(pop_front_front.pop_front(), Some("back"));
+
+        assert_eq!(pop_front_back.pop_front(), Some("front"));
+        assert_eq!(pop_front_back.pop_back(), Some("back"));
+
+        assert_eq!(pop_back_front.pop_back(), Some("back"));
+        assert_eq!(pop_back_front.pop_front(), Some("front"));
+
+        assert_eq!(pop_back_back.pop_back(), Some("back"));
+        assert_eq!(pop_back_back.pop_back(), Some("front"));
+    }
+
+    #[test]
+    fn push_front_wraps_linear_at_start() {
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+
+        deque.push_back(1).unwrap();
+        deque.push_back(2).unwrap();
+        deque.push_front(0).unwrap();
+
+        assert_eq!(deque.front(), Some(&0));
+        assert_eq!(deque.back(), Some(&2));
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(deque.pop_front(), Some(0));
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_front(), Some(2));
+    }
+
+    #[test]
+    fn push_full_error_contains_item() {
+        let mut deque: ArrayDeque<u32, 1> = ArrayDeque::new();
+
+        deque.push_back(1).unwrap();
+        assert_eq!(deque.push_back(2), Err(CapacityError { item: 2 }));
+        assert_eq!(deque.push_front(3), Err(CapacityError { item: 3 }));
+    }
+
+    #[test]
+    fn push_mut_returns_pushed_slot() {
+        let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
+
+        *deque.push_back_mut(1).unwrap() += 10;
+        *deque.push_front_mut(2).unwrap() += 20;
+        *deque.push_front_mut(3).unwrap() += 30;
+
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [33, 22, 11]);
+        assert_eq!(deque.push_back_mut(4), Err(CapacityError { item: 4 }));
+    }
+
+    #[test]
+    fn push_with_panic_leaves_deque_unchanged() {
+        extern crate std;
+
+        let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
+        deque.push_back(1).unwrap();
+
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            let _ = deque.push_front_with(|| panic!("constructor failed"));
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(deque.len(), 1);
+        assert_eq!(deque.front(), Some(&1));
+    }
+
+    #[test]
+    fn split_front_clone_panic_keeps_element() {
+        extern crate std;
+
+        #[derive(Debug, Default, PartialEq)]
+        struct Unclonable(u32);
+
+        impl Clone for Unclonable {
+            fn clone(&self) -> Self {
+                panic!("clone failed");
+            }
+        }
+
+        let mut deque: ArrayDeque<Unclonable, 2> = ArrayDeque::new();
+        deque.push_back(Unclonable(1)).unwrap();
+        let (front, back) = deque.split();
+
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| back.back()));
+
+        assert!(result.is_err());
+        assert_eq!(front.pop_front(), Some(Unclonable(1)));
+    }
+
+    /// Builds a wrapped deque holding `0..6`, returning it along with clones
+    /// of its elements for tracking drops.
+    fn wrapped_rcs() -> (ArrayDeque<Rc<u32>, 6>, Vec<Rc<u32>>) {
+        let tracked: Vec<Rc<u32>> = (0..6).map(Rc::new).collect();
+
+        let mut deque = ArrayDeque::new();
+        deque.try_extend(tracked[2..].iter().cloned()).unwrap();
+        deque.push_front(Rc::clone(&tracked[1])).unwrap();
+        deque.push_front(Rc::clone(&tracked[0])).unwrap();
+        assert!(!deque.layout_info().is_contiguous());
+
+        (deque, tracked)
+    }
+
+    fn strong_counts(tracked: &[Rc<u32>]) -> Vec<usize> {
+        tracked.iter().map(Rc::strong_count).collect()
+    }
+
+    #[test]
+    fn retain_mut_panic_keeps_unprocessed() {
+        extern crate std;
+
+        let (mut deque, tracked) = wrapped_rcs();
+
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            deque.retain_mut(|x| match **x {
+                4 => panic!("predicate failed"),
+                x => x % 2 == 1,
+            });
+        }));
+
+        assert!(result.is_err());
+        assert!(deque.iter().map(|x| **x).eq([1, 3, 4, 5]));
+        assert_eq!(strong_counts(&tracked), [1, 2, 1, 2, 2, 2]);
+
+        deque.retain(|x| **x > 3);
+        assert!(deque.iter().map(|x| **x).eq([4, 5]));
+        drop(deque);
+        assert_eq!(strong_counts(&tracked), [1; 6]);
+    }
+
+    #[test]
+    fn extract_if_panic_keeps_unextracted() {
+        extern crate std;
+
+        let (mut deque, tracked) = wrapped_rcs();
+        let mut extracted = Vec::new();
+
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            let iter = deque.extract_if(|x| match **x {
+                4 => panic!("predicate failed"),
+                x => x % 2 == 0,
+            });
+
+            for item in iter {
+                extracted.push(item);
+            }
+        }));
+
+        assert!(result.is_err());
+        assert!(extracted.iter().map(|x| **x).eq([0, 2]));
+        assert!(deque.iter().map(|x| **x).eq([1, 3, 4, 5]));
+        assert_eq!(strong_counts(&tracked), [2; 6]);
+
+        // Dropping the iterator early keeps the unprocessed elements.
+        let mut iter = deque.extract_if(|_| true);
+        assert_eq!(iter.next().as_deref(), Some(&1));
+        drop(iter);
+        assert!(deque.iter().map(|x| **x).eq([3, 4, 5]));
+        assert_eq!(strong_counts(&tracked), [2, 1, 2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn map_panic_drops_all_elements() {
+        extern crate std;
+
+        let (deque, tracked) = wrapped_rcs();
+
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            deque.map(|x| match *x {
+                3 => panic!("mapping failed"),
+                _ => Some(x),
+            })
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(strong_counts(&tracked), [1; 6]);
+
+        let (deque, tracked) = wrapped_rcs();
+        let doubled = deque.map(|x| *x * 2);
+        assert!(doubled.iter().copied().eq([0, 2, 4, 6, 8, 10]));
+        assert_eq!(strong_counts(&tracked), [1; 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "push_back on a full deque (capacity is 1)")]
+    fn push_back_expect_full_panics() {
+        let mut deque: ArrayDeque<u32, 1> = ArrayDeque::new();
+
+        deque.push_back_expect(1);
+        deque.push_back_expect(2);
+    }
+
+    #[test]
+    fn drain_chunks_spans_wrap() {
+        let mut deque: ArrayDeque<String, 5> = ArrayDeque::new();
+        for s in ["c", "d", "e"].iter().copied() {
+            deque.push_back(s.into()).unwrap();
+        }
+        deque.push_front("b".into()).unwrap();
+        deque.push_front("a".into()).unwrap();
+        assert!(!deque.layout_info().is_contiguous());
+
+        let mut chunks = deque.drain_chunks::<2>();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.next().unwrap(), ["a", "b"]);
+        assert_eq!(chunks.next().unwrap(), ["c", "d"]);
+        assert_eq!(chunks.next(), None);
+
+        assert!(deque.iter().eq(["e"]));
+    }
+
+    #[test]
+    fn cyclic_eq_ignores_rotation() {
+        let mut a: ArrayDeque<u8, 5> = ArrayDeque::new();
+        let mut b: ArrayDeque<u8, 5> = ArrayDeque::new();
+        assert!(a.cyclic_eq(&b));
+
+        a.try_extend([1, 1, 2, 1, 3].iter().copied()).unwrap();
+        for _ in 0..5 {
+            b.clear();
+            b.try_extend(a.iter().copied()).unwrap();
+            a.round_robin();
+            assert!(a.cyclic_eq(&b) && b.cyclic_eq(&a));
+        }
+
+        b.clear();
+        b.try_extend([1, 1, 3, 1, 2].iter().copied()).unwrap();
+        assert!(!a.cyclic_eq(&b));
+        b.pop_back();
+        assert!(!a.cyclic_eq(&b));
+    }
+
+    #[test]
+    fn cyclic_eq_matches_naive_rotation() {
+        // Every pair of binary sequences of equal length, checked against
+        // trying each rotation in turn.
+        for len in 0..=8 {
+            let bit = |n: u32, i: usize| n >> (i % len) & 1;
+            for x in 0..1 << len {
+                for y in 0..1 << len {
+                    let mut a: ArrayDeque<u32, 8> = ArrayDeque::new();
+                    let mut b: ArrayDeque<u32, 8> = ArrayDeque::new();
+                    a.try_extend((0..len).map(|i| bit(x, i))).unwrap();
+                    b.try_extend((0..len).map(|i| bit(y, i))).unwrap();
+
+                    let naive =
+                        len == 0 || (0..len).any(|r| (0..len).all(|i| bit(x, i + r) == bit(y, i)));
+                    assert_eq!(a.cyclic_eq(&b), naive, "{:?} {:?}", a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn extend_front_keeps_order_across_wrap() {
+        let mut deque: ArrayDeque<u32, 6> = ArrayDeque::new();
+        deque.try_extend([0, 5, 6].iter().copied()).unwrap();
+        deque.pop_front();
+
+        // The new block spans the wrap, from the end of the backing array to
+        // its first slot.
+        deque.extend_front(2..5);
+        assert!(!deque.layout_info().is_contiguous());
+        assert!(deque.iter().copied().eq(2..=6));
+
+        let err = deque.try_extend_front([0, 1].iter().copied()).unwrap_err();
+        assert_eq!((err.accepted(), err.into_first_rejected()), (1, 1));
+        assert!(deque.iter().copied().eq([0, 2, 3, 4, 5, 6]));
+    }
+
+    #[test]
+    #[should_panic(expected = "extend_front on a full deque (capacity is 2)")]
+    fn extend_front_panics_when_full() {
+        let mut deque: ArrayDeque<u32, 2> = ArrayDeque::new();
+        deque.extend_front(0..3);
+    }
+
+    #[test]
+    fn overflow_is_reported_across_wrap() {
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+        deque.try_extend([0, 1, 2].iter().copied()).unwrap();
+        deque.truncate_front(1);
+
+        assert_eq!(deque.count_overflow(3..5), 0);
+        assert_eq!(deque.count_overflow(5..9), 3);
+        assert!(deque.iter().copied().eq(2..6));
+
+        #[cfg(feature = "alloc")]
+        {
+            deque.truncate_front(1);
+            assert_eq!(deque.collect_overflow(6..10), [9]);
+            assert!(deque.iter().copied().eq(5..9));
+            assert!(deque.collect_overflow(0..0).is_empty());
+        }
+    }
+
+    #[test]
+    fn zip_and_unzip_preserve_order() {
+        let mut first: ArrayDeque<u8, 3> = ArrayDeque::new();
+        first.try_extend([2, 3].iter().copied()).unwrap();
+        first.push_front(1).unwrap();
+        let mut second: ArrayDeque<char, 3> = ArrayDeque::new();
+        second.try_extend(['a', 'b'].iter().copied()).unwrap();
+
+        let (first, mut second) = ArrayDeque::zip(first, second).unwrap_err();
+        second.push_back('c').unwrap();
+
+        let pairs = ArrayDeque::zip(first, second).unwrap();
+        assert!(pairs.iter().copied().eq([(1, 'a'), (2, 'b'), (3, 'c')]));
+
+        let (first, second) = pairs.unzip();
+        assert!(first.iter().copied().eq([1, 2, 3]));
+        assert!(second.iter().copied().eq(['a', 'b', 'c']));
+    }
+
+    #[test]
+    fn pop_front_guard_removes_only_when_committed() {
+        let mut deque: ArrayDeque<u32, 2> = ArrayDeque::new();
+        assert!(deque.pop_front_guard().is_none());
+
+        deque.push_back(1).unwrap();
+        deque.push_back(2).unwrap();
+
+        *deque.pop_front_guard().unwrap() += 10;
+        assert!(deque.iter().copied().eq([11, 2]));
+
+        deque.pop_front_guard().unwrap().commit();
+        assert_eq!(deque.pop_front_guard().unwrap().into_inner(), 2);
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn truncate_guard_undoes_nested_pushes() {
+        fn search(path: &mut ArrayDeque<u32, 4>, depth: u32) -> bool {
+            let mut path = path.truncate_guard();
+            if path.push_back(depth).is_err() {
+                return false;
+            }
+
+            if depth == 3 || search(&mut path, depth + 1) {
+                path.keep();
+                return true;
+            }
+
+            false
+        }
+
+        let mut path: ArrayDeque<u32, 4> = ArrayDeque::new();
+        path.try_extend([8, 9].iter().copied()).unwrap();
+        path.pop_front();
+
+        assert!(!search(&mut path, 0));
+        assert!(path.iter().copied().eq([9]));
+
+        path.clear();
+        assert!(search(&mut path, 0));
+        assert!(path.iter().copied().eq(0..4));
+    }
+
+    #[test]
+    fn insert_and_remove_at_every_index_across_wrap() {
+        fn wrapped() -> (ArrayDeque<u32, 8>, Vec<u32>) {
+            let mut deque = ArrayDeque::new();
+            deque.try_extend(2..6).unwrap();
+            deque.push_front(1).unwrap();
+            deque.push_front(0).unwrap();
+            assert!(!deque.layout_info().is_contiguous());
+
+            (deque, (0..6).collect())
+        }
+
+        for index in 0..=6 {
+            let (mut deque, mut model) = wrapped();
+            let mut cursor = deque.cursor_front_mut();
+            for _ in 0..index {
+                cursor.move_next();
+            }
+
+            cursor.insert_before(99).unwrap();
+            model.insert(index, 99);
+            assert!(deque.iter().eq(&model), "insert at {}", index);
+
+            let (mut deque, mut model) = wrapped();
+            if index < 6 {
+                assert_eq!(
+                    BaseDeque::remove(&mut deque, index),
+                    Some(model.remove(index))
+                );
+                assert!(deque.iter().eq(&model), "remove at {}", index);
+            }
+        }
+    }
+
+    #[test]
+    fn remove_item_from_either_side_of_wrap() {
+        let mut deque: ArrayDeque<u32, 6> = ArrayDeque::new();
+        deque.try_extend([3, 4, 5, 4].iter().copied()).unwrap();
+        deque.push_front(2).unwrap();
+        deque.push_front(1).unwrap();
+        assert!(!deque.layout_info().is_contiguous());
+
+        assert_eq!(deque.remove_item(&2), Some(2));
+        assert_eq!(deque.remove_item(&4), Some(4));
+        assert!(deque.iter().copied().eq([1, 3, 5, 4]));
+        assert_eq!(deque.remove_item(&6), None);
+    }
+
+    #[test]
+    fn byte_search_and_eq_span_wrap() {
+        let mut a: ArrayDeque<u8, 6> = ArrayDeque::new();
+        a.try_extend(b"cdef".iter().copied()).unwrap();
+        a.push_front(b'b').unwrap();
+        a.push_front(b'a').unwrap();
+        assert!(!a.layout_info().is_contiguous());
+
+        assert_eq!(a.find_byte(b'b'), Some(1));
+        assert_eq!(a.find_byte(b'e'), Some(4));
+        assert_eq!(a.find_byte(b'z'), None);
+        assert!(a.contains(&b'f'));
+
+        let mut b: ArrayDeque<u8, 6> = ArrayDeque::new();
+        b.try_extend(b"abcdef".iter().copied()).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(b, a);
+
+        b.pop_back();
+        b.push_back(b'g').unwrap();
+        assert_ne!(a, b);
+        b.pop_back();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn round_robin_cycles_in_place() {
+        let mut deque: ArrayDeque<u8, 3> = ArrayDeque::new();
+        assert_eq!(deque.round_robin(), None);
+
+        deque.push_back(1).unwrap();
+        assert_eq!(deque.round_robin(), Some(&mut 1));
+
+        deque.push_back(2).unwrap();
+        for expected in [2, 1, 2] {
+            assert_eq!(deque.round_robin().copied(), Some(expected));
+        }
+        assert!(deque.iter().copied().eq([2, 1]));
+
+        deque.push_back(3).unwrap();
+        *deque.round_robin().unwrap() += 10;
+        assert!(deque.iter().copied().eq([11, 3, 2]));
+        assert_eq!(deque.len(), 3);
+    }
+
+    #[test]
+    fn iter_flat_spans_wrap() {
+        let mut deque: ArrayDeque<[u8; 2], 3> = ArrayDeque::new();
+        deque.push_back([3, 4]).unwrap();
+        deque.push_back([5, 6]).unwrap();
+        deque.push_front([1, 2]).unwrap();
+        assert_eq!(deque.flatten_slices(), None);
+
+        assert!(deque.iter_flat().copied().eq(1..=6));
+        assert!(deque.iter_flat().rev().copied().eq((1..=6).rev()));
+
+        deque.iter_flat_mut().for_each(|x| *x *= 10);
+        deque.rotate_to_front(0);
+        assert_eq!(deque.flatten_slices(), Some(&[10, 20, 30, 40, 50, 60][..]));
+    }
+
+    #[test]
+    fn strip_affixes_across_wrap() {
+        let mut deque: ArrayDeque<u8, 6> = ArrayDeque::new();
+        deque.try_extend(b"cdef".iter().copied()).unwrap();
+        deque.push_front(b'b').unwrap();
+        deque.push_front(b'a').unwrap();
+        assert!(!deque.layout_info().is_contiguous());
+
+        for n in 0..=6 {
+            assert!(deque.starts_with(&b"abcdef"[..n]));
+            assert!(deque.ends_with(&b"abcdef"[6 - n..]));
+        }
+        assert!(!deque.starts_with(b"abcdefg"));
+        assert!(!deque.ends_with(b"abcdez"));
+
+        assert!(deque.strip_prefix(b"abc"));
+        assert!(!deque.strip_suffix(b"ff"));
+        assert!(deque.strip_suffix(b"ef"));
+        assert!(deque.iter().eq(b"d"));
+    }
+
+    #[test]
+    fn with_back_and_front_build_in_order() {
+        let deque: ArrayDeque<u32, 3> = ArrayDeque::new()
+            .with_back(2)
+            .and_then(|d| d.with_front(1))
+            .and_then(|d| d.with_back(3))
+            .unwrap();
+        assert!(deque.iter().copied().eq([1, 2, 3]));
+
+        assert_eq!(deque.with_front(0).unwrap_err().into_inner(), 0);
+    }
+
+    #[test]
+    fn try_extend_zero_capacity() {
+        let mut deque: ArrayDeque<u32, 0> = ArrayDeque::new();
+
+        assert_eq!(deque.try_extend(None), Ok(()));
+
+        let err = deque.try_extend(Some(1)).unwrap_err();
+        assert_eq!(err.accepted(), 0);
+        assert_eq!(err.first_rejected(), &1);
+    }
+
+    #[test]
+    fn try_extend_leaves_remaining_items() {
+        let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
+        let mut iter = 0..10;
+
+        let err = deque.try_extend(&mut iter).unwrap_err();
+        assert_eq!(err.map(u64::from).into_first_rejected(), 3);
+        assert_eq!(iter.next(), Some(4));
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn extend_exact_size_fills_wrapped_spare() {
+        let mut deque: ArrayDeque<u32, 5> = ArrayDeque::new();
+        deque.push_back(0).unwrap();
+        deque.push_front(9).unwrap();
+
+        deque.extend([1, 2].iter().copied());
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [9, 0, 1, 2]);
+
+        let err = deque.try_extend([3, 4, 5].iter().copied()).unwrap_err();
+        assert_eq!(err.accepted(), 1);
+        assert_eq!(err.into_first_rejected(), 4);
+        assert!(deque.iter().copied().eq([9, 0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn extend_tolerates_wrong_size_hint() {
+        /// Yields `len` elements while claiming to yield exactly `hint`.
+        struct Liar {
+            len: u32,
+            hint: usize,
+        }
+
+        impl Iterator for Liar {
+            type Item = u32;
+
+            fn next(&mut self) -> Option<u32> {
+                self.hint = self.hint.saturating_sub(1);
+                self.len = self.len.checked_sub(1)?;
+                Some(self.len)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.hint, Some(self.hint))
+            }
+        }
+
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+        deque.extend(Liar { len: 2, hint: 3 });
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 0]);
+
+        deque.clear();
+        deque.try_extend(Liar { len: 3, hint: 1 }).unwrap();
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [2, 1, 0]);
+    }
+
+    #[test]
+    fn spare_slices_cover_unused_slots() {
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+        assert_eq!(deque.spare_slices_mut().0.len(), 4);
+
+        deque.push_front(1).unwrap();
+        let (first, second) = deque.spare_slices_mut();
+        assert_eq!((first.len(), second.len()), (0, 3));
+
+        deque.push_back(2).unwrap();
+        let (first, second) = deque.spare_slices_mut();
+        assert_eq!((first.len(), second.len()), (2, 0));
+
+        deque.commit_back(2);
+        assert!(deque.is_full());
+        assert_eq!(deque.spare_slices_mut(), (&mut [][..], &mut [][..]));
+    }
+
+    #[test]
+    fn commit_back_wraps() {
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+        deque.push_back(0).unwrap();
+        deque.push_back(0).unwrap();
+        deque.push_back(1).unwrap();
+        deque.drain_front(2).unwrap();
+
+        let (first, second) = deque.spare_slices_mut();
+        first[0] = 2;
+        second[0] = 3;
+        second[1] = 4;
+        deque.commit_back(3);
+
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+        assert_eq!(deque.back(), Some(&4));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot commit 2 slots; only 1 are unused")]
+    fn commit_back_too_many_panics() {
+        let mut deque: ArrayDeque<u32, 2> = ArrayDeque::new();
+        deque.push_back(0).unwrap();
+        deque.commit_back(2);
+    }
+
+    #[test]
+    fn clear_makes_empty() {
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+
+        deque.push_back(0).unwrap();
+        deque.push_back(1).unwrap();
+        deque.push_back(2).unwrap();
+        deque.push_back(3).unwrap();
+
+        assert_eq!(deque.len(), 4);
+        deque.clear();
+        assert!(deque.is_empty());
+
+        deque.push_front(0).unwrap();
+        deque.push_front(1).unwrap();
+        deque.push_front(2).unwrap();
+        deque.push_front(3).unwrap();
+
+        assert_eq!(deque.len(), 4);
+        deque.clear();
+        assert!(deque.is_empty());
+
+        deque.push_back(0).unwrap();
+        deque.push_back(1).unwrap();
+        deque.push_front(2).unwrap();
+        deque.push_front(3).unwrap();
+
+        assert_eq!(deque.len(), 4);
+        deque.clear();
+        assert!(deque.is_empty());
+
+        deque.push_front(0).unwrap();
+        deque.push_front(1).unwrap();
+        deque.push_back(2).unwrap();
+        deque.push_back(3).unwrap();
+
+        assert_eq!(deque.len(), 4);
+        deque.clear();
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn truncate_shorter_has_no_effect() {
+        let mut deque: ArrayDeque<u32, 5> = ArrayDeque::new();
+
+        deque.push_back(42).unwrap();
+        assert_eq!(deque.len(), 1);
+        deque.truncate(5);
+        assert_eq!(deque.len(), 1);
+    }
+
+    #[test]
+    fn truncate_longer_reduces_len() {
+        let mut deque: ArrayDeque<u32, 8> = ArrayDeque::new();
+
+        deque.push_back(5).unwrap();
+        deque.push_back(10).unwrap();
+        deque.push_back(15).unwrap();
+        deque.push_back(20).unwrap();
+        deque.push_back(25).unwrap();
+        deque.push_back(30).unwrap();
+        deque.push_back(35).unwrap();
+
+        assert_eq!(deque.len(), 7);
+        deque.truncate(4);
+        assert_eq!(deque.len(), 4);
+        assert_eq!(deque.front(), Some(&5));
+        assert_eq!(deque.back(), Some(&20));
+    }
+
+    #[test]
+    fn iter_zero_capacity() {
+        let deque: ArrayDeque<usize, 0> = ArrayDeque::new();
+        let mut iter = deque.iter();
+
+        assert!(iter.next().is_none());
+        assert!(iter.next_back().is_none());
+    }
+
+    #[test]
+    fn iter_forward() {
+        let mut deque: ArrayDeque<usize, 5> = ArrayDeque::new();
+        deque.push_back(0).unwrap();
+        deque.push_back(1).unwrap();
+        deque.push_back(2).unwrap();
+        deque.push_back(3).unwrap();
+        deque.push_back(4).unwrap();
+
+        let mut iter = deque.iter();
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&4));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iter_reverse() {
+        let mut deque: ArrayDeque<usize, 5> = ArrayDeque::new();
+        deque.push_back(4).unwrap();
+        deque.push_back(3).unwrap();
+        deque.push_back(2).unwrap();
+        deque.push_back(1).unwrap();
+        deque.push_back(0).unwrap();
+
+        let mut iter = deque.iter().rev();
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&4));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iter_alternate() {
+        let mut deque: ArrayDeque<usize, 5> = ArrayDeque::new();
+        deque.push_back(0).unwrap();
+        deque.push_back(1).unwrap();
+        deque.push_back(2).unwrap();
+        deque.push_back(3).unwrap();
+        deque.push_back(4).unwrap();
+
+        let mut iter = deque.iter();
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iter_has_same_order_as_slices() {
+        let mut deque: ArrayDeque<u32, 6> = ArrayDeque::new();
+
+        deque.push_front(3).unwrap();
+        deque.push_front(5).unwrap();
+        deque.push_front(7).unwrap();
+        deque.push_back(2).unwrap();
+        deque.push_back(4).unwrap();
+        deque.push_back(6).unwrap();
+
+        let from_slices = {
+            let mut v = Vec::new();
+
+            let (first, second) = deque.as_slices();
+            for &item in first.iter().chain(second.iter()) {
+                v.push(item);
+            }
+
+            v
+        };
+
+        let from_iter = deque.iter().copied().collect::<Vec<_>>();
+
+        assert_eq!(from_slices, from_iter);
+    }
+
+    #[test]
+    fn slices_wrapped_not_full() {
+        let mut deque: ArrayDeque<u32, 6> = ArrayDeque::new();
+
+        deque.push_back(2).unwrap();
+        deque.push_back(3).unwrap();
+        deque.push_front(1).unwrap();
+
+        assert_eq!(deque.as_slices(), (&[1][..], &[2, 3][..]));
+
+        let (first, second) = deque.as_mut_slices();
+        assert_eq!(first, &mut [1]);
+        assert_eq!(second, &mut [2, 3]);
+    }
+
+    #[test]
+    fn slices_and_mut_slices_are_eq() {
+        let mut deque: ArrayDeque<u32, 6> = ArrayDeque::new();
+
+        deque.push_front(3).unwrap();
+        deque.push_front(5).unwrap();
+        deque.push_front(7).unwrap();
+        deque.push_back(2).unwrap();
+        deque.push_back(4).unwrap();
+        deque.push_back(6).unwrap();
+
+        let (s1, s2) = deque.as_slices();
+        let v1 = Vec::from(s1);
+        let v2 = Vec::from(s2);
+
+        let (m1, m2) = deque.as_mut_slices();
+        assert_eq!(v1, m1);
+        assert_eq!(v2, m2);
     }
-}
 
-impl<'a, T, const N: usize> Iterator for DrainFront<'a, T, N>
-where
-    T: Default,
-{
-    type Item = T;
+    #[test]
+    fn drain_zero_capacity() {
+        let mut deque: ArrayDeque<(), 0> = ArrayDeque::new();
+        assert!(deque.drain_front(1).is_none());
+        assert!(deque.drain_back(1).is_none());
+        assert!(deque.drain_front(0).unwrap().next().is_none());
+        assert!(deque.drain_back(0).unwrap().next().is_none());
+    }
 
-    #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next()
+    #[test]
+    fn clear_and_truncate_run_destructors() {
+        let rc = Rc::new("refcount");
+
+        let mut deque: ArrayDeque<Rc<&'static str>, 4> = ArrayDeque::new();
+        for _ in 0..4 {
+            deque.push_back(rc.clone()).unwrap();
+        }
+        deque.truncate(1);
+        assert_eq!(Rc::strong_count(&rc), 2);
+        deque.clear();
+        assert_eq!(Rc::strong_count(&rc), 1);
     }
-}
 
-/// A draining iterator which removes elements from the back of an
-/// `ArrayDeque<T, N>`.
-///
-/// This struct is created by the [`drain_back`] method on [`ArrayDeque`].
-///
-/// [`drain_back`]: ArrayDeque::drain_back
-pub struct DrainBack<'a, T, const N: usize>
-where
-    T: Default,
-{
-    inner: DequeDrain<'a, ArrayDeque<T, N>, T>,
-}
+    #[test]
+    fn clear_and_truncate_skip_non_drop_elements() {
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+        deque.push_back(1).unwrap();
+        deque.push_back(2).unwrap();
+        deque.push_back(3).unwrap();
 
-impl<'a, T, const N: usize> DrainBack<'a, T, N>
-where
-    T: Default,
-{
-    #[inline]
-    fn new(deque: &'a mut ArrayDeque<T, N>, n: usize) -> Option<DrainBack<'a, T, N>> {
-        Some(DrainBack {
-            inner: DequeDrain::back(deque, n)?,
-        })
+        deque.truncate(2);
+        assert_eq!(deque.spare_slices_mut(), (&mut [3, 0][..], &mut [][..]));
+
+        deque.clear();
+        assert!(deque.is_empty());
+        deque.push_back(4).unwrap();
+        assert_eq!(deque.as_slices(), (&[4][..], &[][..]));
     }
-}
 
-impl<'a, T, const N: usize> Iterator for DrainBack<'a, T, N>
-where
-    T: Default,
-{
-    type Item = T;
+    #[test]
+    fn pop_copied_leaves_stale_values() {
+        let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
+        deque.push_back(1).unwrap();
+        deque.push_back(2).unwrap();
+        deque.push_front(0).unwrap();
 
-    #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next()
+        assert_eq!(deque.pop_front_copied(), Some(0));
+        assert_eq!(deque.pop_back_copied(), Some(2));
+        assert_eq!(deque.as_slices(), (&[1][..], &[][..]));
+        assert_eq!(deque.pop_back_copied(), Some(1));
+        assert_eq!(deque.pop_front_copied(), None);
+        assert!(deque.is_empty());
+
+        deque.push_back(3).unwrap();
+        assert_eq!(deque.as_slices(), (&[3][..], &[][..]));
     }
-}
 
-#[cfg(feature = "serde")]
-use core::{fmt, marker::PhantomData};
+    #[test]
+    fn linearize_into_wrapped() {
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+        deque.push_back(2).unwrap();
+        deque.push_back(3).unwrap();
+        deque.push_front(1).unwrap();
+        deque.push_front(0).unwrap();
 
-#[cfg(feature = "serde")]
-use serde::{
-    de::{Deserialize, Deserializer, Error, Expected, SeqAccess, Visitor},
-    ser::{Serialize, SerializeSeq, Serializer},
-};
+        let mut all = [0; 4];
+        assert_eq!(deque.clone_into_slice(&mut all), 4);
+        assert_eq!(all, [0, 1, 2, 3]);
 
-#[cfg(feature = "serde")]
-impl<T, const N: usize> serde::Serialize for ArrayDeque<T, N>
-where
-    T: Serialize + Default,
-{
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        let mut scratch = [9; 3];
+        assert_eq!(deque.linearize_into(&mut scratch), 3);
+        assert_eq!(scratch, [0, 1, 2]);
+        assert_eq!(deque.as_slices(), (&[3][..], &[][..]));
 
-        for element in self.iter() {
-            seq.serialize_element(element)?;
-        }
+        assert_eq!(deque.linearize_into(&mut scratch), 1);
+        assert_eq!(scratch, [3, 1, 2]);
+        assert!(deque.is_empty());
+    }
 
-        seq.end()
+    #[test]
+    fn swap_contents_with_slice_deque() {
+        let mut array: ArrayDeque<u32, 4> = ArrayDeque::new();
+        array.push_back(2).unwrap();
+        array.push_back(3).unwrap();
+        array.push_front(1).unwrap();
+        array.push_front(0).unwrap();
+
+        let mut slice = [0; 5];
+        let mut slice_deque = crate::SliceDeque::new_in(&mut slice);
+        slice_deque.push_back(10).unwrap();
+        slice_deque.push_front(9).unwrap();
+
+        array.swap_contents(&mut slice_deque).unwrap();
+        assert_eq!(array.iter().copied().collect::<Vec<_>>(), [9, 10]);
+        assert_eq!(
+            slice_deque.iter().copied().collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+
+        slice_deque.push_back(4).unwrap();
+        assert_eq!(array.swap_contents(&mut slice_deque), None);
+        assert_eq!(array.len(), 2);
+        assert_eq!(slice_deque.len(), 5);
+
+        slice_deque.pop_back();
+        slice_deque.swap_contents(&mut array).unwrap();
+        assert_eq!(array.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+        assert_eq!(slice_deque.iter().copied().collect::<Vec<_>>(), [9, 10]);
     }
-}
 
-#[cfg(feature = "serde")]
-#[doc(hidden)]
-pub struct ExceededCapacity {
-    capacity: usize,
-}
+    #[test]
+    fn transfer_to_between_wrapped_deques() {
+        let mut src: ArrayDeque<u32, 4> = ArrayDeque::new();
+        src.push_back(2).unwrap();
+        src.push_back(3).unwrap();
+        src.push_front(1).unwrap();
+        src.push_front(0).unwrap();
+
+        // The spare slots of `dst` are split as one slot at the end of the
+        // array and two at the start.
+        let mut dst: ArrayDeque<u32, 5> = ArrayDeque::new();
+        dst.extend([7, 8, 9, 10]);
+        dst.truncate_front(2);
+
+        assert_eq!(src.transfer_to(&mut dst, 10), Ok(3));
+        assert_eq!(dst.iter().copied().collect::<Vec<_>>(), [9, 10, 0, 1, 2]);
+        assert_eq!(src.iter().copied().collect::<Vec<_>>(), [3]);
+
+        assert_eq!(
+            src.transfer_to(&mut dst, 1),
+            Err(CapacityError { item: () })
+        );
+        assert_eq!(src.transfer_to(&mut dst, 0), Ok(0));
+        assert_eq!(src.len(), 1);
+    }
 
-#[cfg(feature = "serde")]
-impl Expected for ExceededCapacity {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            formatter,
-            "a sequence of at most {} elements",
-            self.capacity
-        )
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn transfer_to_and_from_vec_deque() {
+        use alloc::collections::VecDeque;
+
+        let mut array: ArrayDeque<u32, 3> = ArrayDeque::new();
+        array.extend([0, 1, 2]);
+        array.pop_front();
+        array.push_back(3).unwrap();
+
+        let mut vec_deque = VecDeque::from([7, 8]);
+        assert_eq!(array.transfer_to(&mut vec_deque, 10), Ok(3));
+        assert_eq!(vec_deque, [7, 8, 1, 2, 3]);
+        assert!(array.is_empty());
+
+        Deque::truncate_front(&mut vec_deque, 4);
+        assert_eq!(vec_deque.transfer_to(&mut array, 10), Ok(3));
+        assert!(array.iter().copied().eq([8, 1, 2]));
+        assert_eq!(vec_deque, [3]);
     }
-}
 
-#[cfg(feature = "serde")]
-impl<'de, T, const N: usize> Deserialize<'de> for ArrayDeque<T, N>
-where
-    T: Deserialize<'de> + Default,
-{
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        struct ArrayDequeVisitor<T, const N: usize> {
-            phantom: core::marker::PhantomData<T>,
-        }
+    #[test]
+    fn reserve_back_slot_wraps() {
+        let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
+        deque.push_back(1).unwrap();
+        deque.push_back(2).unwrap();
+        deque.push_back(3).unwrap();
+        assert!(deque.reserve_back_slot().is_none());
+        assert_eq!(deque.pop_front_copied(), Some(1));
 
-        impl<'de, T, const N: usize> Visitor<'de> for ArrayDequeVisitor<T, N>
-        where
-            T: Deserialize<'de> + Default,
-        {
-            type Value = ArrayDeque<T, N>;
+        let mut slot = deque.reserve_back_slot().unwrap();
+        assert_eq!(*slot, 0);
+        *slot = 4;
+        slot.commit();
 
-            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                write!(formatter, "a sequence of at most {} elements", N)
-            }
+        assert_eq!(deque.as_slices(), (&[2, 3][..], &[4][..]));
+        assert!(deque.reserve_back_slot().is_none());
+    }
 
-            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-            where
-                A: SeqAccess<'de>,
-            {
-                let mut deque = ArrayDeque::new();
+    #[test]
+    fn dropped_slot_is_reset() {
+        let rc = Rc::new("refcount");
+        let mut deque: ArrayDeque<Option<Rc<&'static str>>, 2> = ArrayDeque::new();
 
-                while let Some(elem) = seq.next_element()? {
-                    deque.push_back(elem).map_err(|_| {
-                        A::Error::invalid_length(deque.len() + 1, &ExceededCapacity { capacity: N })
-                    })?;
-                }
+        let mut slot = deque.reserve_back_slot().unwrap();
+        *slot = Some(rc.clone());
+        drop(slot);
 
-                Ok(deque)
-            }
-        }
+        assert!(deque.is_empty());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
 
-        deserializer.deserialize_seq(ArrayDequeVisitor {
-            phantom: PhantomData,
-        })
+    #[test]
+    fn push_back_dedup_full() {
+        let mut deque: ArrayDeque<u32, 2> = ArrayDeque::new();
+
+        assert_eq!(deque.push_back_dedup(1), Ok(true));
+        assert_eq!(deque.push_back_dedup(2), Ok(true));
+        assert_eq!(deque.push_back_dedup(2), Ok(false));
+        assert_eq!(deque.push_back_dedup(1).unwrap_err().into_inner(), 1);
     }
-}
 
-#[cfg(all(feature = "std", test))]
-impl<T, const N: usize> quickcheck::Arbitrary for ArrayDeque<T, N>
-where
-    T: quickcheck::Arbitrary + std::fmt::Debug + Default,
-{
-    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
-        use crate::DequeEnd;
+    #[test]
+    fn drain_as_slices_wrapped() {
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+        deque.push_back(2).unwrap();
+        deque.push_back(3).unwrap();
+        deque.push_front(1).unwrap();
+        deque.push_front(0).unwrap();
+
+        let mut drain = deque.drain_front(3).unwrap();
+        assert_eq!(drain.as_slices(), (&[0, 1][..], &[2][..]));
+        drain.next();
+        drain.next();
+        assert_eq!(drain.as_slices(), (&[2][..], &[][..]));
+        drop(drain);
+
+        deque.push_front(2).unwrap();
+        deque.push_front(1).unwrap();
+        let mut drain = deque.drain_back(3).unwrap();
+        assert_eq!(drain.as_slices(), (&[1][..], &[2, 3][..]));
+        drain.next();
+        drain.next();
+        assert_eq!(drain.as_slices(), (&[1][..], &[][..]));
+    }
+
+    #[test]
+    fn ends_mut_wrapped() {
+        let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
+        deque.push_back(0).unwrap();
+        assert_eq!(deque.ends_mut(), None);
+
+        deque.push_front(1).unwrap();
+        let (front, back) = deque.ends_mut().unwrap();
+        assert_eq!((*front, *back), (1, 0));
+
+        *front = 5;
+        *back = 6;
+        assert_eq!(deque.front(), Some(&5));
+        assert_eq!(deque.back(), Some(&6));
+    }
+
+    #[test]
+    fn get_from_back_wrapped() {
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+        deque.push_back(2).unwrap();
+        deque.push_front(1).unwrap();
+        deque.push_front(0).unwrap();
+
+        assert_eq!(deque.get_from_back(0), Some(&2));
+        assert_eq!(deque.get_from_back(1), Some(&1));
+        assert_eq!(deque.get_from_back(2), Some(&0));
+        assert_eq!(deque.get_from_back(3), None);
+        assert_eq!(deque.get_from_back(usize::MAX), None);
+
+        *deque.get_from_back_mut(2).unwrap() = 5;
+        assert_eq!(deque.front(), Some(&5));
+    }
+
+    #[test]
+    fn select_nth_unstable_wrapped() {
+        let mut deque: ArrayDeque<u32, 5> = ArrayDeque::new();
+        deque.push_back(4).unwrap();
+        deque.push_back(0).unwrap();
+        deque.push_front(3).unwrap();
+        deque.push_front(1).unwrap();
+        deque.push_front(2).unwrap();
+
+        let (less, nth, greater) = deque.select_nth_unstable(3);
+        assert_eq!(*nth, 3);
+        assert!(less.iter().all(|&x| x < 3));
+        assert_eq!(greater, [4]);
+
+        let mut sorted = [0; 5];
+        deque.clone_into_slice(&mut sorted);
+        sorted.sort_unstable();
+        assert_eq!(sorted, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn rotate_to_front_wrapped() {
+        let mut deque: ArrayDeque<u32, 5> = ArrayDeque::new();
+        deque.push_back(2).unwrap();
+        deque.push_back(3).unwrap();
+        deque.push_front(1).unwrap();
+        deque.push_front(0).unwrap();
+
+        deque.rotate_to_front(1);
+        assert!(deque.iter().copied().eq([1, 2, 3, 0]));
+
+        deque.rotate_to_front(4);
+        assert!(deque.iter().copied().eq([1, 2, 3, 0]));
+
+        deque.push_back(4).unwrap();
+        deque.rotate_to_front(3);
+        assert!(deque.iter().copied().eq([0, 4, 1, 2, 3]));
+    }
+
+    #[test]
+    #[should_panic(expected = "rotation index out of bounds")]
+    fn rotate_to_front_out_of_bounds() {
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+        deque.push_back(0).unwrap();
+
+        deque.rotate_to_front(2);
+    }
+
+    #[test]
+    fn drain_runs_destructors_when_consumed() {
+        let rc = Rc::new("refcount");
+
+        let mut deque: ArrayDeque<Rc<&'static str>, 3> = ArrayDeque::new();
+        deque.push_back(rc.clone()).unwrap();
+        deque.push_back(rc.clone()).unwrap();
+        deque.push_back(rc.clone()).unwrap();
+        let drain = deque.drain_front(3).unwrap();
+        drain.for_each(drop);
 
-        let mut deque = ArrayDeque::new();
-        let len = usize::arbitrary(g) % N;
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
 
-        for _ in 0..len {
-            let val = T::arbitrary(g);
-            match g.choose(&[DequeEnd::Front, DequeEnd::Back]).unwrap() {
-                DequeEnd::Front => deque.push_front(val).unwrap(),
-                DequeEnd::Back => deque.push_back(val).unwrap(),
-            }
-        }
+    #[test]
+    fn drain_runs_destructors_when_dropped() {
+        let rc = Rc::new("refcount");
 
-        deque
+        let mut deque: ArrayDeque<Rc<&'static str>, 3> = ArrayDeque::new();
+        deque.push_back(rc.clone()).unwrap();
+        deque.push_back(rc.clone()).unwrap();
+        deque.push_back(rc.clone()).unwrap();
+        let drain = deque.drain_front(3).unwrap();
+        drop(drain);
+
+        assert_eq!(Rc::strong_count(&rc), 1);
     }
 
-    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
-        if self.is_empty() {
-            Box::new(std::iter::empty())
-        } else {
-            let mut less_front = self.clone();
-            less_front.pop_front();
+    #[test]
+    fn drain_removes_elements_when_leaked() {
+        let mut deque: ArrayDeque<usize, 5> = ArrayDeque::new();
+        deque.push_back(0).unwrap();
+        deque.push_back(1).unwrap();
+        deque.push_back(2).unwrap();
+        deque.push_back(3).unwrap();
+        deque.push_back(4).unwrap();
 
-            let mut less_back = self.clone();
-            less_back.pop_back();
+        {
+            let mut from_front = deque.clone();
+            let drain = from_front.drain_front(3).unwrap();
+            mem::forget(drain);
+            assert_eq!(from_front.len(), 2);
+            let mut iter = from_front.iter();
+            assert_eq!(iter.next(), Some(&3));
+            assert_eq!(iter.next(), Some(&4));
+        }
 
-            Box::new(vec![less_front, less_back].into_iter())
+        {
+            let mut from_back = deque;
+            let drain = from_back.drain_back(3).unwrap();
+            mem::forget(drain);
+            assert_eq!(from_back.len(), 2);
+            let mut iter = from_back.iter();
+            assert_eq!(iter.next(), Some(&0));
+            assert_eq!(iter.next(), Some(&1));
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn debug_prints_logical_order() {
+        let mut deque: ArrayDeque<u32, 5> = ArrayDeque::new();
+        assert_eq!(format!("{:?}", deque), "[]");
 
-    use core::mem;
+        deque.push_front(1).unwrap();
+        deque.push_front(2).unwrap();
+        deque.push_back(3).unwrap();
+        assert_eq!(format!("{:?}", deque), "[2, 1, 3]");
+        assert_eq!(format!("{:#?}", deque), "[\n    2,\n    1,\n    3,\n]");
+    }
 
-    extern crate alloc;
-    use alloc::{rc::Rc, vec::Vec};
+    #[test]
+    fn debug_internals_prints_backing_array() {
+        let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
+        deque.push_back(7).unwrap();
+
+        assert_eq!(
+            format!("{:?}", deque.debug_internals()),
+            "DebugInternals { layout: Linear { first: 0, len: 1 }, items: [7, 0, 0] }",
+        );
+    }
 
     #[test]
-    fn empty_deque_has_zero_len() {
-        let d0: ArrayDeque<(), 0> = ArrayDeque::new();
-        assert_eq!(d0.len(), 0);
+    fn layout_info_empty_and_linear() {
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
 
-        let d1: ArrayDeque<(), 1> = ArrayDeque::new();
-        assert_eq!(d1.len(), 0);
+        let layout = deque.layout_info();
+        assert!(layout.is_empty());
+        assert!(layout.is_contiguous());
+        assert_eq!((layout.head(), layout.gap_len()), (0, 4));
 
-        let d3: ArrayDeque<(), 3> = ArrayDeque::new();
-        assert_eq!(d3.len(), 0);
+        deque.push_back(1).unwrap();
+        deque.push_back(2).unwrap();
+        deque.pop_front().unwrap();
+
+        let layout = deque.layout_info();
+        assert!(layout.is_contiguous());
+        assert_eq!(layout.head(), 1);
+        assert_eq!(layout.len(), 1);
+        assert_eq!(layout.wrap_len(), 0);
+        assert_eq!(layout.gap_len(), 3);
     }
 
     #[test]
-    fn empty_deque_front_is_none() {
-        let d0: ArrayDeque<(), 0> = ArrayDeque::new();
-        assert_eq!(d0.front(), None);
+    fn display_separated_across_wrap() {
+        let mut deque: ArrayDeque<&str, 4> = ArrayDeque::new();
+        assert_eq!(format!("{}", deque.display_separated(", ")), "");
+
+        deque.push_back("b").unwrap();
+        deque.push_back("c").unwrap();
+        deque.push_front("a").unwrap();
+        assert_eq!(
+            format!("{}", deque.display_separated(" -> ")),
+            "a -> b -> c"
+        );
+        assert_eq!(format!("{:>2}", deque.display_separated("|")), " a| b| c");
+    }
 
-        let d1: ArrayDeque<(), 1> = ArrayDeque::new();
-        assert_eq!(d1.front(), None);
+    #[test]
+    fn hex_dump_across_wrap() {
+        let mut deque: ArrayDeque<u8, 20> = ArrayDeque::new();
+        assert_eq!(format!("{}", deque.hex_dump()), "");
+
+        deque.try_extend(0x3c..0x4c).unwrap();
+        deque.push_front(0x7f).unwrap();
+        deque.push_front(b' ').unwrap();
+        assert!(!deque.layout_info().is_contiguous());
+
+        assert_eq!(
+            format!("{}", deque.hex_dump()),
+            "00000000  20 7f 3c 3d 3e 3f 40 41  42 43 44 45 46 47 48 49  | .<=>?@ABCDEFGHI|\n\
+             00000010  4a 4b                                             |JK|",
+        );
+    }
 
-        let d3: ArrayDeque<(), 3> = ArrayDeque::new();
-        assert_eq!(d3.front(), None);
+    #[cfg(feature = "std")]
+    #[test]
+    fn read_from_fills_both_spare_regions() {
+        let mut deque: ArrayDeque<u8, 6> = ArrayDeque::new();
+        deque.push_back(0).unwrap();
+        deque.push_back(0).unwrap();
+        deque.push_back(1).unwrap();
+        deque.pop_front();
+        deque.pop_front();
+
+        // Spare regions are 3..6 and 0..2.
+        let mut reader: &[u8] = &[2, 3, 4, 5, 6, 7];
+        assert_eq!(deque.read_from(&mut reader).unwrap(), 5);
+        assert!(deque.is_full());
+        assert_eq!(reader, &[7]);
+        assert_eq!(
+            deque.iter().copied().collect::<Vec<_>>(),
+            [1, 2, 3, 4, 5, 6]
+        );
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn empty_deque_back_is_none() {
-        let d0: ArrayDeque<(), 0> = ArrayDeque::new();
-        assert_eq!(d0.front(), None);
+    fn read_from_short_read_makes_one_call() {
+        struct OneByte(u8);
+
+        impl std::io::Read for OneByte {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                buf[0] = self.0;
+                self.0 += 1;
+                Ok(1)
+            }
+        }
 
-        let d1: ArrayDeque<(), 1> = ArrayDeque::new();
-        assert_eq!(d1.front(), None);
+        let mut deque: ArrayDeque<u8, 4> = ArrayDeque::new();
+        let mut reader = OneByte(10);
 
-        let d3: ArrayDeque<(), 3> = ArrayDeque::new();
-        assert_eq!(d3.front(), None);
+        assert_eq!(deque.read_from(&mut reader).unwrap(), 1);
+        assert_eq!(deque.read_from(&mut reader).unwrap(), 1);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [10, 11]);
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn zero_capacity_is_both_empty_and_full() {
-        let zero_cap: ArrayDeque<(), 0> = ArrayDeque::new();
+    fn write_front_to_writes_both_regions() {
+        let mut deque: ArrayDeque<u8, 4> = ArrayDeque::new();
+        deque.push_back(2).unwrap();
+        deque.push_back(3).unwrap();
+        deque.push_front(1).unwrap();
 
-        assert!(zero_cap.is_empty());
-        assert!(zero_cap.is_full());
+        let mut out = Vec::new();
+        assert_eq!(deque.write_front_to(&mut out).unwrap(), 3);
+        assert_eq!(out, [1, 2, 3]);
+        assert!(deque.is_empty());
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn push_zero_capacity_is_error() {
-        let mut zero_cap: ArrayDeque<(), 0> = ArrayDeque::new();
+    fn write_front_to_error_keeps_contents() {
+        struct Broken;
 
-        assert!(zero_cap.push_front(()).is_err());
-        assert!(zero_cap.push_back(()).is_err());
+        impl std::io::Write for Broken {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::BrokenPipe.into())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut deque: ArrayDeque<u8, 4> = ArrayDeque::new();
+        deque.push_back(1).unwrap();
+
+        assert!(deque.write_front_to(&mut Broken).is_err());
+        assert_eq!(deque.len(), 1);
     }
 
+    #[cfg(feature = "embedded-io")]
     #[test]
-    fn pop_zero_capacity_is_none() {
-        let mut zero_cap: ArrayDeque<(), 0> = ArrayDeque::new();
-
-        assert!(zero_cap.pop_front().is_none());
-        assert!(zero_cap.pop_back().is_none());
+    fn embedded_io_read_write() {
+        use embedded_io::{BufRead, Read, ReadReady, Write, WriteReady};
+
+        let mut deque: ArrayDeque<u8, 4> = ArrayDeque::new();
+        assert!(!deque.read_ready().unwrap());
+        assert!(deque.write_ready().unwrap());
+
+        assert_eq!(deque.write(b"abc").unwrap(), 3);
+        assert_eq!(deque.write(b"de").unwrap(), 1);
+        assert_eq!(deque.write(b"f"), Err(CapacityError { item: () }));
+        assert_eq!(deque.write(b"").unwrap(), 0);
+        assert!(!deque.write_ready().unwrap());
+
+        let mut buf = [0; 3];
+        assert_eq!(deque.read(&mut buf).unwrap(), 3);
+        assert_eq!(&buf, b"abc");
+
+        // The remaining byte is at the end of the backing array, so these
+        // wrap around.
+        deque.write_all(b"xy").unwrap();
+        assert_eq!(deque.fill_buf().unwrap(), b"d");
+        deque.consume(1);
+        assert_eq!(deque.read(&mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], b"xy");
+        assert!(!deque.read_ready().unwrap());
     }
 
+    #[cfg(feature = "bytes")]
     #[test]
-    fn push_full_linear_is_error() {
-        let mut deque: ArrayDeque<(), 3> = ArrayDeque::new();
+    fn bytes_buf_reads_across_wrap() {
+        use bytes::{Buf, BufMut};
 
-        deque.push_front(()).unwrap();
-        deque.push_front(()).unwrap();
-        deque.push_front(()).unwrap();
+        let mut deque: ArrayDeque<u8, 4> = ArrayDeque::new();
+        deque.try_extend(*b"abc").unwrap();
+        deque.advance(2);
 
-        assert!(deque.push_front(()).is_err());
-        assert!(deque.push_back(()).is_err());
+        let (mut first, mut second) = deque.spare_slices_mut();
+        first.put_u8(b'd');
+        second.put_slice(b"ef");
+        deque.commit_back(3);
+
+        assert_eq!(deque.remaining(), 4);
+        assert_eq!(deque.chunk(), b"cd");
+
+        let mut out = [0; 4];
+        deque.copy_to_slice(&mut out);
+        assert_eq!(&out, b"cdef");
+        assert!(!deque.has_remaining());
     }
 
+    #[cfg(feature = "bytes")]
     #[test]
-    fn push_full_wrapped_is_error() {
-        let mut deque: ArrayDeque<(), 3> = ArrayDeque::new();
+    #[should_panic(expected = "cannot advance past end of deque: 2 > 1")]
+    fn bytes_buf_advance_too_far_panics() {
+        use bytes::Buf;
 
-        deque.push_front(()).unwrap();
-        deque.push_front(()).unwrap();
-        deque.push_back(()).unwrap();
-
-        assert!(deque.push_front(()).is_err());
-        assert!(deque.push_back(()).is_err());
+        let mut deque: ArrayDeque<u8, 4> = ArrayDeque::new();
+        deque.push_back(0).unwrap();
+        deque.advance(2);
     }
 
     #[test]
-    fn pop_empty_is_none() {
-        let mut deque: ArrayDeque<(), 3> = ArrayDeque::new();
+    fn extend_appends_to_back() {
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+        deque.push_back(1).unwrap();
+        deque.extend([2, 3]);
 
-        assert!(deque.pop_front().is_none());
-        assert!(deque.pop_back().is_none());
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
     }
 
+    #[cfg(feature = "arbitrary")]
     #[test]
-    fn push_front_one_becomes_front_and_back() {
-        let mut deque: ArrayDeque<usize, 3> = ArrayDeque::new();
+    fn arbitrary_respects_capacity() {
+        use arbitrary::{Arbitrary, Unstructured};
 
-        deque.push_front(42).unwrap();
-        assert_eq!(deque.front(), Some(&42));
-        assert_eq!(deque.back(), Some(&42));
+        let bytes: Vec<u8> = (0..=255).collect();
+        let mut u = Unstructured::new(&bytes);
+
+        let deque = ArrayDeque::<u8, 4>::arbitrary(&mut u).unwrap();
+        assert!(deque.len() <= 4);
+
+        let deque = ArrayDeque::<u8, 0>::arbitrary(&mut u).unwrap();
+        assert!(deque.is_empty());
     }
 
+    #[cfg(feature = "proptest")]
     #[test]
-    fn push_back_one_becomes_front_and_back() {
-        let mut deque: ArrayDeque<usize, 3> = ArrayDeque::new();
+    fn proptest_strategy_covers_layouts() {
+        use proptest::{
+            arbitrary::any,
+            strategy::{Strategy, ValueTree},
+            test_runner::TestRunner,
+        };
 
-        deque.push_back(42).unwrap();
-        assert_eq!(deque.front(), Some(&42));
-        assert_eq!(deque.back(), Some(&42));
+        let strategy = array_deque_strategy::<_, 4>(any::<u8>());
+        let mut runner = TestRunner::deterministic();
+        let mut wrapped = false;
+
+        for _ in 0..256 {
+            let deque = strategy.new_tree(&mut runner).unwrap().current();
+            assert!(deque.len() <= 4);
+            wrapped |= !deque.as_slices().1.is_empty();
+        }
+
+        assert!(wrapped);
     }
 
+    #[cfg(feature = "proptest")]
     #[test]
-    fn push_both_ends_front_back() {
-        let mut deque: ArrayDeque<&'static str, 3> = ArrayDeque::new();
+    fn proptest_strategy_shrinks_to_empty() {
+        use proptest::{
+            arbitrary::any,
+            strategy::{Strategy, ValueTree},
+            test_runner::TestRunner,
+        };
 
-        deque.push_back("back").unwrap();
-        deque.push_front("front").unwrap();
+        let strategy = array_deque_strategy::<_, 4>(any::<u8>());
+        let mut tree = strategy.new_tree(&mut TestRunner::deterministic()).unwrap();
 
-        assert_eq!(deque.front(), Some(&"front"));
-        assert_eq!(deque.back(), Some(&"back"));
+        while tree.simplify() {}
+
+        assert!(tree.current().is_empty());
     }
 
+    #[cfg(feature = "rand")]
     #[test]
-    fn push_pop_front() {
-        let mut deque: ArrayDeque<&'static str, 3> = ArrayDeque::new();
+    fn shuffle_wrapped() {
+        use rand::{rngs::SmallRng, SeedableRng};
 
-        deque.push_front("front").unwrap();
-        assert_eq!(deque.len(), 1);
-        assert_eq!(deque.pop_front(), Some("front"));
-        assert_eq!(deque.len(), 0);
+        let mut rng = SmallRng::seed_from_u64(1);
+        let mut seen_reordered = false;
+
+        for _ in 0..16 {
+            let mut deque: ArrayDeque<u32, 6> = ArrayDeque::new();
+            deque.extend(3..6);
+            for i in (0..3).rev() {
+                deque.push_front(i).unwrap();
+            }
+
+            deque.shuffle(&mut rng);
+
+            let mut sorted: Vec<u32> = deque.iter().copied().collect();
+            seen_reordered |= sorted != [0, 1, 2, 3, 4, 5];
+            sorted.sort_unstable();
+            assert_eq!(sorted, [0, 1, 2, 3, 4, 5]);
+        }
+
+        assert!(seen_reordered);
     }
 
+    #[cfg(feature = "rand")]
     #[test]
-    fn push_pop_back() {
-        let mut deque: ArrayDeque<&'static str, 3> = ArrayDeque::new();
+    fn choose_empty() {
+        use rand::{rngs::SmallRng, SeedableRng};
 
-        deque.push_back("back").unwrap();
-        assert_eq!(deque.len(), 1);
-        assert_eq!(deque.pop_back(), Some("back"));
-        assert_eq!(deque.len(), 0);
+        let mut rng = SmallRng::seed_from_u64(1);
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
+
+        assert_eq!(deque.choose(&mut rng), None);
+        assert_eq!(deque.choose_mut(&mut rng), None);
+
+        deque.push_front(7).unwrap();
+        *deque.choose_mut(&mut rng).unwrap() += 1;
+        assert_eq!(deque.choose(&mut rng), Some(&8));
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn push_front_then_back() {
-        let mut deque: ArrayDeque<&'static str, 3> = ArrayDeque::new();
+    fn deserialize_borrowed() {
+        use serde_test::assert_de_tokens;
 
-        deque.push_front("front").unwrap();
-        assert_eq!(deque.len(), 1);
-        deque.push_back("back").unwrap();
-        assert_eq!(deque.len(), 2);
+        let mut deque: ArrayDeque<&str, 4> = ArrayDeque::new();
+        deque.push_back("zero").unwrap();
+        deque.push_back("copy").unwrap();
 
-        let mut pop_front_front = deque.clone();
-        let mut pop_front_back = deque.clone();
-        let mut pop_back_front = deque.clone();
-        let mut pop_back_back = deque.clone();
+        assert_de_tokens(
+            &deque,
+            &[
+                Token::Seq { len: Some(2) },
+                Token::BorrowedStr("zero"),
+                Token::BorrowedStr("copy"),
+                Token::SeqEnd,
+            ],
+        );
 
-        assert_eq!(pop_front_front.pop_front(), Some("front"));
-        assert_eq!(pop_front_front.pop_front(), Some("back"));
+        let mut deque: ArrayDeque<&[u8], 4> = ArrayDeque::new();
+        deque.push_back(b"\x00\x01").unwrap();
 
-        assert_eq!(pop_front_back.pop_front(), Some("front"));
-        assert_eq!(pop_front_back.pop_back(), Some("back"));
+        assert_de_tokens(
+            &deque,
+            &[
+                Token::Seq { len: Some(1) },
+                Token::BorrowedBytes(b"\x00\x01"),
+                Token::SeqEnd,
+            ],
+        );
+    }
 
-        assert_eq!(pop_back_front.pop_back(), Some("back"));
-        assert_eq!(pop_back_front.pop_front(), Some("front"));
+    #[test]
+    #[should_panic = "push_back on a full deque (capacity is 2)"]
+    fn extend_past_capacity_panics() {
+        let mut deque: ArrayDeque<u32, 2> = ArrayDeque::new();
+        deque.extend([1, 2, 3]);
+    }
 
-        assert_eq!(pop_back_back.pop_back(), Some("back"));
-        assert_eq!(pop_back_back.pop_back(), Some("front"));
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn heapless_round_trip() {
+        let vec: heapless::Vec<u32, 4> = heapless::Vec::from_slice(&[1, 2, 3]).unwrap();
+
+        let mut deque = ArrayDeque::from(vec);
+        deque.push_front(0).unwrap();
+
+        let other = heapless::Deque::from(deque);
+        assert!(other.iter().copied().eq(0..4));
+
+        let mut deque = ArrayDeque::from(other);
+        deque.pop_front();
+        let vec = heapless::Vec::from(deque);
+        assert_eq!(vec, [1, 2, 3]);
     }
 
+    #[cfg(feature = "arrayvec")]
     #[test]
-    fn push_back_then_front() {
-        let mut deque: ArrayDeque<&'static str, 3> = ArrayDeque::new();
+    fn arrayvec_round_trip() {
+        let vec: arrayvec::ArrayVec<u32, 4> = (1..4).collect();
 
-        deque.push_back("back").unwrap();
-        assert_eq!(deque.len(), 1);
-        deque.push_front("front").unwrap();
-        assert_eq!(deque.len(), 2);
+        let mut deque = ArrayDeque::from(vec);
+        deque.push_front(0).unwrap();
+        let vec = arrayvec::ArrayVec::from(deque);
+        assert_eq!(vec.as_slice(), [0, 1, 2, 3]);
+
+        // Vectors of a different capacity go through `try_extend()`.
+        let mut deque: ArrayDeque<u32, 2> = ArrayDeque::new();
+        let err = deque.try_extend(vec).unwrap_err();
+        assert_eq!(err.accepted(), 2);
+        assert_eq!(err.into_first_rejected(), 2);
+    }
 
-        let mut pop_front_front = deque.clone();
-        let mut pop_front_back = deque.clone();
-        let mut pop_back_front = deque.clone();
-        let mut pop_back_back = deque.clone();
+    #[cfg(feature = "tinyvec")]
+    #[test]
+    fn tinyvec_round_trip() {
+        let vec: tinyvec::ArrayVec<[u32; 4]> = tinyvec::array_vec!([u32; 4] => 1, 2, 3);
+
+        let mut deque = ArrayDeque::from(vec);
+        deque.push_front(0).unwrap();
+        let vec = tinyvec::ArrayVec::from(deque);
+        assert_eq!(vec.as_slice(), [0, 1, 2, 3]);
+    }
 
-        assert_eq!(pop_front_front.pop_front(), Some("front"));
-        assert_eq!(pop_front_front.pop_front(), Some("back"));
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn smallvec_round_trip() {
+        let vec: smallvec::SmallVec<[u32; 2]> = smallvec::smallvec![1, 2, 3];
 
-        assert_eq!(pop_front_back.pop_front(), Some("front"));
-        assert_eq!(pop_front_back.pop_back(), Some("back"));
+        use core::convert::TryFrom;
 
-        assert_eq!(pop_back_front.pop_back(), Some("back"));
-        assert_eq!(pop_back_front.pop_front(), Some("front"));
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::try_from(vec).unwrap();
+        deque.push_front(0).unwrap();
+        let vec = smallvec::SmallVec::<[u32; 2]>::from(deque);
+        assert_eq!(vec.as_slice(), [0, 1, 2, 3]);
 
-        assert_eq!(pop_back_back.pop_back(), Some("back"));
-        assert_eq!(pop_back_back.pop_back(), Some("front"));
+        let err = ArrayDeque::<u32, 3>::try_from(vec).unwrap_err();
+        assert_eq!(err.into_inner().len(), 4);
     }
 
+    #[cfg(feature = "minicbor")]
     #[test]
-    fn clear_makes_empty() {
+    fn minicbor_round_trip() {
         let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
-
-        deque.push_back(0).unwrap();
-        deque.push_back(1).unwrap();
         deque.push_back(2).unwrap();
         deque.push_back(3).unwrap();
-
-        assert_eq!(deque.len(), 4);
-        deque.clear();
-        assert!(deque.is_empty());
-
-        deque.push_front(0).unwrap();
         deque.push_front(1).unwrap();
-        deque.push_front(2).unwrap();
-        deque.push_front(3).unwrap();
 
-        assert_eq!(deque.len(), 4);
-        deque.clear();
-        assert!(deque.is_empty());
+        let mut buf = [0u8; 16];
+        minicbor::encode(&deque, &mut buf[..]).unwrap();
+        assert_eq!(buf[..4], [0x83, 0x01, 0x02, 0x03]);
 
-        deque.push_back(0).unwrap();
-        deque.push_back(1).unwrap();
-        deque.push_front(2).unwrap();
-        deque.push_front(3).unwrap();
+        let decoded: ArrayDeque<u32, 4> = minicbor::decode(&buf).unwrap();
+        assert_eq!(decoded, deque);
+    }
 
-        assert_eq!(deque.len(), 4);
-        deque.clear();
-        assert!(deque.is_empty());
+    #[cfg(feature = "minicbor")]
+    #[test]
+    fn minicbor_decode_checks_capacity() {
+        // [1, 2, 3]
+        let definite = [0x83, 0x01, 0x02, 0x03];
+        assert!(minicbor::decode::<ArrayDeque<u32, 2>>(&definite).is_err());
 
-        deque.push_front(0).unwrap();
-        deque.push_front(1).unwrap();
-        deque.push_back(2).unwrap();
-        deque.push_back(3).unwrap();
+        // [_ 1, 2, 3]
+        let indefinite = [0x9f, 0x01, 0x02, 0x03, 0xff];
+        assert!(minicbor::decode::<ArrayDeque<u32, 2>>(&indefinite).is_err());
 
-        assert_eq!(deque.len(), 4);
-        deque.clear();
-        assert!(deque.is_empty());
+        let deque: ArrayDeque<u32, 3> = minicbor::decode(&indefinite).unwrap();
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
     }
 
+    #[cfg(feature = "schemars")]
     #[test]
-    fn truncate_shorter_has_no_effect() {
-        let mut deque: ArrayDeque<u32, 5> = ArrayDeque::new();
+    fn json_schema_has_max_items() {
+        let schema = schemars::schema_for!(ArrayDeque<u32, 8>);
 
-        deque.push_back(42).unwrap();
-        assert_eq!(deque.len(), 1);
-        deque.truncate(5);
-        assert_eq!(deque.len(), 1);
+        assert_eq!(schema.get("type").unwrap(), "array");
+        assert_eq!(schema.get("maxItems").unwrap(), 8);
+        assert!(schema.get("items").is_some());
     }
 
+    #[cfg(all(feature = "postcard", feature = "serde"))]
     #[test]
-    fn truncate_longer_reduces_len() {
-        let mut deque: ArrayDeque<u32, 8> = ArrayDeque::new();
+    fn postcard_max_size_bounds_full_deque() {
+        use postcard::experimental::max_size::MaxSize;
 
-        deque.push_back(5).unwrap();
-        deque.push_back(10).unwrap();
-        deque.push_back(15).unwrap();
-        deque.push_back(20).unwrap();
-        deque.push_back(25).unwrap();
-        deque.push_back(30).unwrap();
-        deque.push_back(35).unwrap();
+        assert_eq!(varint_size(0), 1);
+        assert_eq!(varint_size(127), 1);
+        assert_eq!(varint_size(128), 2);
 
-        assert_eq!(deque.len(), 7);
-        deque.truncate(4);
-        assert_eq!(deque.len(), 4);
-        assert_eq!(deque.front(), Some(&5));
-        assert_eq!(deque.back(), Some(&20));
+        let mut deque: ArrayDeque<u32, 200> = ArrayDeque::new();
+        for _ in 0..200 {
+            deque.push_back(u32::MAX).unwrap();
+        }
+
+        let mut buf = [0u8; ArrayDeque::<u32, 200>::POSTCARD_MAX_SIZE];
+        let used = postcard::to_slice(&deque, &mut buf).unwrap();
+        assert_eq!(used.len(), buf.len());
     }
 
+    #[cfg(feature = "zeroize")]
     #[test]
-    fn iter_zero_capacity() {
-        let deque: ArrayDeque<usize, 0> = ArrayDeque::new();
-        let mut iter = deque.iter();
+    fn zeroize_overwrites_slots() {
+        use zeroize::Zeroize;
 
-        assert!(iter.next().is_none());
-        assert!(iter.next_back().is_none());
+        let mut deque: ArrayDeque<u8, 4> = ArrayDeque::new();
+        deque.extend([1, 2, 3]);
+
+        assert_eq!(deque.pop_front_zeroize(), Some(1));
+        assert_eq!(deque.pop_back_zeroize(), Some(3));
+        assert_eq!(deque.items, [0, 2, 0, 0]);
+
+        deque.clear_zeroize();
+        assert!(deque.is_empty());
+        assert_eq!(deque.items, [0; 4]);
+
+        // Zeroize must also cover slots that do not hold the default value.
+        deque.items = [9; 4];
+        deque.push_back(7).unwrap();
+        deque.zeroize();
+        assert!(deque.is_empty());
+        assert_eq!(deque.items, [0; 4]);
     }
 
     #[test]
-    fn iter_forward() {
-        let mut deque: ArrayDeque<usize, 5> = ArrayDeque::new();
-        deque.push_back(0).unwrap();
+    fn cursor_wraps_through_ghost() {
+        let mut deque: ArrayDeque<u32, 4> = ArrayDeque::new();
         deque.push_back(1).unwrap();
         deque.push_back(2).unwrap();
-        deque.push_back(3).unwrap();
-        deque.push_back(4).unwrap();
 
-        let mut iter = deque.iter();
-        assert_eq!(iter.next(), Some(&0));
-        assert_eq!(iter.next(), Some(&1));
-        assert_eq!(iter.next(), Some(&2));
-        assert_eq!(iter.next(), Some(&3));
-        assert_eq!(iter.next(), Some(&4));
-        assert_eq!(iter.next(), None);
+        let mut cursor = deque.cursor_back_mut();
+        assert_eq!(cursor.index(), Some(1));
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 2));
+
+        // At the ghost position, insert_before appends and insert_after
+        // prepends.
+        cursor.insert_before(3).unwrap();
+        cursor.insert_after(0).unwrap();
+        assert_eq!(cursor.index(), None);
+        assert!(cursor.insert_after(5).is_err());
+
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 0));
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [0, 1, 2]);
     }
 
     #[test]
-    fn iter_reverse() {
-        let mut deque: ArrayDeque<usize, 5> = ArrayDeque::new();
-        deque.push_back(4).unwrap();
-        deque.push_back(3).unwrap();
-        deque.push_back(2).unwrap();
-        deque.push_back(1).unwrap();
-        deque.push_back(0).unwrap();
+    fn cursor_on_empty_deque() {
+        let mut deque: ArrayDeque<u32, 2> = ArrayDeque::new();
 
-        let mut iter = deque.iter().rev();
-        assert_eq!(iter.next(), Some(&0));
-        assert_eq!(iter.next(), Some(&1));
-        assert_eq!(iter.next(), Some(&2));
-        assert_eq!(iter.next(), Some(&3));
-        assert_eq!(iter.next(), Some(&4));
-        assert_eq!(iter.next(), None);
+        let mut cursor = deque.cursor_front_mut();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.peek_next(), None);
+        cursor.insert_after(1).unwrap();
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+
+        let mut cursor = deque.cursor_back_mut();
+        assert_eq!(cursor.current(), Some(&mut 1));
     }
 
+    #[cfg(feature = "stats")]
     #[test]
-    fn iter_alternate() {
-        let mut deque: ArrayDeque<usize, 5> = ArrayDeque::new();
-        deque.push_back(0).unwrap();
+    fn stats_count_every_change() {
+        let mut deque: ArrayDeque<u8, 4> = ArrayDeque::new();
+        deque.extend(0..4);
+        assert!(deque.push_front(9).is_err());
+        assert!(deque.push_back_with(|| 9).is_none());
+
+        deque.truncate(1);
+        deque.clear();
         deque.push_back(1).unwrap();
-        deque.push_back(2).unwrap();
-        deque.push_back(3).unwrap();
-        deque.push_back(4).unwrap();
 
-        let mut iter = deque.iter();
-        assert_eq!(iter.next(), Some(&0));
-        assert_eq!(iter.next_back(), Some(&4));
-        assert_eq!(iter.next(), Some(&1));
-        assert_eq!(iter.next_back(), Some(&3));
-        assert_eq!(iter.next(), Some(&2));
-        assert_eq!(iter.next_back(), None);
-        assert_eq!(iter.next(), None);
+        let stats = *deque.stats();
+        assert_eq!(stats.high_water_mark(), 4);
+        assert_eq!(stats.pushes(), 5);
+        assert_eq!(stats.pops(), 4);
+        assert_eq!(stats.rejected_pushes(), 2);
+
+        deque.reset_stats();
+        assert_eq!(deque.stats().high_water_mark(), 1);
+        assert_eq!(deque.stats().pushes(), 0);
     }
 
     #[test]
-    fn iter_has_same_order_as_slices() {
-        let mut deque: ArrayDeque<u32, 6> = ArrayDeque::new();
-
-        deque.push_front(3).unwrap();
-        deque.push_front(5).unwrap();
-        deque.push_front(7).unwrap();
+    fn rollback_across_wrap() {
+        let mut deque: ArrayDeque<u8, 4> = ArrayDeque::new();
+        deque.push_back(1).unwrap();
         deque.push_back(2).unwrap();
-        deque.push_back(4).unwrap();
-        deque.push_back(6).unwrap();
+        deque.pop_front();
 
-        let from_slices = {
-            let mut v = Vec::new();
-
-            let (first, second) = deque.as_slices();
-            for &item in first.iter().chain(second.iter()) {
-                v.push(item);
-            }
-
-            v
-        };
+        let savepoint = deque.savepoint();
+        deque.push_front(1).unwrap();
+        deque.push_front(0).unwrap();
+        deque.push_back(3).unwrap();
+        assert!(deque.is_full());
 
-        let from_iter = deque.iter().copied().collect::<Vec<_>>();
+        deque.rollback_to(savepoint);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [2]);
 
-        assert_eq!(from_slices, from_iter);
+        let empty = ArrayDeque::<u8, 4>::new().savepoint();
+        deque.rollback_to(empty);
+        assert!(deque.is_empty());
     }
 
     #[test]
-    fn slices_and_mut_slices_are_eq() {
-        let mut deque: ArrayDeque<u32, 6> = ArrayDeque::new();
-
-        deque.push_front(3).unwrap();
-        deque.push_front(5).unwrap();
-        deque.push_front(7).unwrap();
+    #[should_panic(expected = "savepoint is not valid")]
+    fn rollback_after_pop_panics() {
+        let mut deque: ArrayDeque<u8, 4> = ArrayDeque::new();
+        deque.push_back(1).unwrap();
         deque.push_back(2).unwrap();
-        deque.push_back(4).unwrap();
-        deque.push_back(6).unwrap();
-
-        let (s1, s2) = deque.as_slices();
-        let v1 = Vec::from(s1);
-        let v2 = Vec::from(s2);
 
-        let (m1, m2) = deque.as_mut_slices();
-        assert_eq!(v1, m1);
-        assert_eq!(v2, m2);
+        let savepoint = deque.savepoint();
+        deque.pop_back();
+        deque.rollback_to(savepoint);
     }
 
     #[test]
-    fn drain_zero_capacity() {
-        let mut deque: ArrayDeque<(), 0> = ArrayDeque::new();
-        assert!(deque.drain_front(1).is_none());
-        assert!(deque.drain_back(1).is_none());
-        assert!(deque.drain_front(0).unwrap().next().is_none());
-        assert!(deque.drain_back(0).unwrap().next().is_none());
+    #[should_panic(expected = "savepoint is not valid")]
+    fn rollback_after_cursor_insert_panics() {
+        let mut deque: ArrayDeque<u8, 8> = ArrayDeque::new();
+        deque.try_extend([1, 2, 3, 4]).unwrap();
+
+        let savepoint = deque.savepoint();
+        let mut cursor = deque.cursor_front_mut();
+        cursor.move_next();
+        cursor.insert_before(99).unwrap();
+        deque.rollback_to(savepoint);
     }
 
     #[test]
-    fn drain_runs_destructors_when_consumed() {
-        let rc = Rc::new("refcount");
+    #[should_panic(expected = "savepoint is not valid")]
+    fn rollback_after_rotate_panics() {
+        let mut deque: ArrayDeque<u8, 8> = ArrayDeque::new();
+        deque.try_extend([1, 2, 3, 4]).unwrap();
 
-        let mut deque: ArrayDeque<Rc<&'static str>, 3> = ArrayDeque::new();
-        deque.push_back(rc.clone()).unwrap();
-        deque.push_back(rc.clone()).unwrap();
-        deque.push_back(rc.clone()).unwrap();
-        let drain = deque.drain_front(3).unwrap();
-        drain.for_each(drop);
-
-        assert_eq!(Rc::strong_count(&rc), 1);
+        let savepoint = deque.savepoint();
+        deque.rotate_to_front(2);
+        deque.push_back(5).unwrap();
+        deque.rollback_to(savepoint);
     }
 
     #[test]
-    fn drain_runs_destructors_when_dropped() {
-        let rc = Rc::new("refcount");
-
-        let mut deque: ArrayDeque<Rc<&'static str>, 3> = ArrayDeque::new();
-        deque.push_back(rc.clone()).unwrap();
-        deque.push_back(rc.clone()).unwrap();
-        deque.push_back(rc.clone()).unwrap();
-        let drain = deque.drain_front(3).unwrap();
-        drop(drain);
+    fn rollback_after_extend_front() {
+        let mut deque: ArrayDeque<u8, 8> = ArrayDeque::new();
+        deque.try_extend([3, 4]).unwrap();
+
+        let savepoint = deque.savepoint();
+        deque.try_extend_front([1, 2]).unwrap();
+        deque.try_extend([5, 6]).unwrap();
+        assert_eq!(
+            deque.iter().copied().collect::<Vec<_>>(),
+            [1, 2, 3, 4, 5, 6]
+        );
 
-        assert_eq!(Rc::strong_count(&rc), 1);
+        deque.rollback_to(savepoint);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [3, 4]);
     }
 
     #[test]
-    fn drain_removes_elements_when_leaked() {
-        let mut deque: ArrayDeque<usize, 5> = ArrayDeque::new();
-        deque.push_back(0).unwrap();
-        deque.push_back(1).unwrap();
-        deque.push_back(2).unwrap();
-        deque.push_back(3).unwrap();
-        deque.push_back(4).unwrap();
+    fn split_handles_share_deque() {
+        let mut deque: ArrayDeque<String, 3> = ArrayDeque::new();
+        deque.push_back("a".into()).unwrap();
+        deque.push_back("b".into()).unwrap();
+        deque.pop_front();
 
         {
-            let mut from_front = deque.clone();
-            let drain = from_front.drain_front(3).unwrap();
-            mem::forget(drain);
-            assert_eq!(from_front.len(), 2);
-            let mut iter = from_front.iter();
-            assert_eq!(iter.next(), Some(&3));
-            assert_eq!(iter.next(), Some(&4));
+            let (front, back) = deque.split();
+            assert_eq!(front.front().as_deref(), Some("b"));
+
+            back.push_back("c".into()).unwrap();
+            back.push_back("d".into()).unwrap();
+            assert!(back.is_full());
+            assert!(back.push_back("e".into()).is_err());
+            assert_eq!(back.back().as_deref(), Some("d"));
+
+            assert_eq!(front.pop_front().as_deref(), Some("b"));
+            assert_eq!(front.len(), 2);
+            assert_eq!(back.len(), 2);
         }
 
-        {
-            let mut from_back = deque;
-            let drain = from_back.drain_back(3).unwrap();
-            mem::forget(drain);
-            assert_eq!(from_back.len(), 2);
-            let mut iter = from_back.iter();
-            assert_eq!(iter.next(), Some(&0));
-            assert_eq!(iter.next(), Some(&1));
-        }
+        assert_eq!(deque.iter().collect::<Vec<_>>(), ["c", "d"]);
     }
 
     #[cfg(feature = "serde")]
@@ -1417,6 +6760,7 @@ mod tests {
 
     #[cfg(feature = "serde")]
     #[test]
+    #[allow(clippy::useless_conversion)]
     fn serialize_deserialize() {
         let mut deque: ArrayDeque<String, 10> = ArrayDeque::new();
         deque.push_back("jumps".into()).unwrap();
@@ -1447,8 +6791,123 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_in_place_reuses_elements() {
+        use serde::de::value::{Error, SeqDeserializer};
+
+        type StrSeq =
+            SeqDeserializer<core::iter::Copied<core::slice::Iter<'static, &'static str>>, Error>;
+
+        fn seq(items: &'static [&'static str]) -> StrSeq {
+            SeqDeserializer::new(items.iter().copied())
+        }
+
+        let mut deque: ArrayDeque<String, 4> = ArrayDeque::new();
+        for _ in 0..3 {
+            deque.push_back(String::with_capacity(64)).unwrap();
+        }
+
+        ArrayDeque::deserialize_in_place(seq(&["a", "b"]), &mut deque).unwrap();
+        assert_eq!(deque.iter().collect::<Vec<_>>(), ["a", "b"]);
+        assert!(deque.iter().all(|s| s.capacity() >= 64));
+
+        ArrayDeque::deserialize_in_place(seq(&["c", "d", "e", "f"]), &mut deque).unwrap();
+        assert_eq!(deque.iter().collect::<Vec<_>>(), ["c", "d", "e", "f"]);
+
+        let err = ArrayDeque::deserialize_in_place(seq(&["g"; 5]), &mut deque).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid length 5, expected a sequence of at most 4 elements"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn extend_deserialize_appends() {
+        use serde::de::value::{Error, SeqDeserializer};
+
+        let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
+        deque.push_back(1).unwrap();
+
+        let deserializer = SeqDeserializer::<_, Error>::new([2u32].iter().copied());
+        deque.extend_deserialize(deserializer).unwrap();
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2]);
+
+        let deserializer = SeqDeserializer::<_, Error>::new([3u32, 4].iter().copied());
+        let err = deque.extend_deserialize(deserializer).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid length 4, expected a sequence of at most 3 elements"
+        );
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn truncating_counts_discarded() {
+        use serde_test::assert_de_tokens;
+
+        let mut deque: ArrayDeque<String, 2> = ArrayDeque::new();
+        deque.push_back("a".into()).unwrap();
+        deque.push_back("b".into()).unwrap();
+
+        // The discarded elements are skipped without being deserialized, so
+        // they need not be strings.
+        assert_de_tokens(
+            &Truncating {
+                deque: deque.clone(),
+                discarded: 2,
+            },
+            &[
+                Token::Seq { len: Some(4) },
+                Token::Str("a"),
+                Token::Str("b"),
+                Token::U32(3),
+                Token::Seq { len: None },
+                Token::SeqEnd,
+                Token::SeqEnd,
+            ],
+        );
+
+        deque.pop_back();
+        assert_de_tokens(
+            &Truncating {
+                deque,
+                discarded: 0,
+            },
+            &[Token::Seq { len: Some(1) }, Token::Str("a"), Token::SeqEnd],
+        );
+    }
+
     #[cfg(feature = "std")]
     quickcheck::quickcheck! {
+        fn qc_cursor_edits_match_vec(deque: ArrayDeque<u8, 16>, ops: Vec<(u8, u8)>) -> bool {
+            let mut deque = deque;
+            let mut model: Vec<u8> = deque.iter().copied().collect();
+
+            for (pos, item) in ops {
+                let index = pos as usize % (model.len() + 1);
+
+                let mut cursor = deque.cursor_front_mut();
+                for _ in 0..index {
+                    cursor.move_next();
+                }
+
+                if item % 2 == 0 {
+                    if cursor.insert_before(item).is_ok() {
+                        model.insert(index, item);
+                    }
+                } else if index < model.len()
+                    && cursor.remove_current() != Some(model.remove(index))
+                {
+                    return false;
+                }
+            }
+
+            deque.iter().copied().collect::<Vec<_>>() == model
+        }
+
         fn qc_front_unchanged_when_back_popped(deque: ArrayDeque<u8, 128>) -> bool {
             if deque.len() <= 1 {
                 // pop_back() would remove front.
@@ -0,0 +1,299 @@
+//! Asynchronous adapters for using a deque in `futures` pipelines.
+//!
+//! An [`AsyncDeque`] is a fixed-capacity buffer which can be split into a
+//! [`DequeSink`], which pushes items to the back and applies backpressure when
+//! the deque is full, and a [`DequeStream`], which pops items from the front.
+//! Tasks waiting on either half are woken through their [`Waker`]s, so no
+//! particular executor is required.
+//!
+//! Both halves borrow the `AsyncDeque`, which is not `Sync`, so they must be
+//! polled from the same thread, as is the case with single-threaded and
+//! embedded executors.
+//!
+//! # Example
+//!
+//! ```
+//! # use holodeque::async_deque::AsyncDeque;
+//! use futures::{executor::block_on, future::join, SinkExt, StreamExt};
+//!
+//! let mut deque: AsyncDeque<u32, 2> = AsyncDeque::new();
+//! let (mut sink, stream) = deque.split();
+//!
+//! let produce = async move {
+//!     for i in 0..8 {
+//!         sink.send(i).await.unwrap();
+//!     }
+//!     // Dropping the sink ends the stream.
+//! };
+//!
+//! let (_, received) = block_on(join(produce, stream.collect::<Vec<_>>()));
+//! assert_eq!(received, [0, 1, 2, 3, 4, 5, 6, 7]);
+//! ```
+//!
+//! [`Waker`]: core::task::Waker
+
+use core::{
+    cell::{Cell, RefCell},
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use futures_core::Stream;
+use futures_sink::Sink;
+
+use crate::{ArrayDeque, CapacityError};
+
+/// A fixed-capacity deque shared between a [`DequeSink`] and a
+/// [`DequeStream`].
+pub struct AsyncDeque<T, const N: usize>
+where
+    T: Default,
+{
+    deque: RefCell<ArrayDeque<T, N>>,
+    // Woken when an item is pushed or the sink is closed.
+    stream_waker: Cell<Option<Waker>>,
+    // Woken when an item is popped.
+    sink_waker: Cell<Option<Waker>>,
+    closed: Cell<bool>,
+}
+
+impl<T, const N: usize> AsyncDeque<T, N>
+where
+    T: Default,
+{
+    /// Constructs a new, empty `AsyncDeque<T, N>`.
+    pub fn new() -> Self {
+        AsyncDeque {
+            deque: RefCell::new(ArrayDeque::new()),
+            stream_waker: Cell::new(None),
+            sink_waker: Cell::new(None),
+            closed: Cell::new(false),
+        }
+    }
+
+    /// Returns the maximum number of elements the deque may hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of elements in the deque.
+    pub fn len(&self) -> usize {
+        self.deque.borrow().len()
+    }
+
+    /// Returns `true` if the deque is empty.
+    pub fn is_empty(&self) -> bool {
+        self.deque.borrow().is_empty()
+    }
+
+    /// Splits the deque into a sink, which pushes items to the back, and a
+    /// stream, which pops items from the front.
+    ///
+    /// The stream ends once the sink has been closed or dropped and every
+    /// buffered item has been popped.
+    pub fn split(&mut self) -> (DequeSink<'_, T, N>, DequeStream<'_, T, N>) {
+        self.closed.set(false);
+
+        (DequeSink { shared: self }, DequeStream { shared: self })
+    }
+
+    /// Consumes the `AsyncDeque`, returning the buffered items.
+    pub fn into_inner(self) -> ArrayDeque<T, N> {
+        self.deque.into_inner()
+    }
+
+    fn close(&self) {
+        self.closed.set(true);
+        wake(&self.stream_waker);
+    }
+}
+
+impl<T, const N: usize> Default for AsyncDeque<T, N>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        AsyncDeque::new()
+    }
+}
+
+fn wake(waker: &Cell<Option<Waker>>) {
+    if let Some(waker) = waker.take() {
+        waker.wake();
+    }
+}
+
+/// The pushing half of a split [`AsyncDeque`].
+///
+/// Items are appended to the back of the deque. When the deque is full,
+/// [`poll_ready`] returns `Pending` until the [`DequeStream`] pops an item.
+/// Closing or dropping the sink ends the stream once the deque is drained.
+///
+/// [`poll_ready`]: Sink::poll_ready
+pub struct DequeSink<'a, T, const N: usize>
+where
+    T: Default,
+{
+    shared: &'a AsyncDeque<T, N>,
+}
+
+impl<'a, T, const N: usize> Sink<T> for DequeSink<'a, T, N>
+where
+    T: Default,
+{
+    type Error = CapacityError<T>;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if self.shared.deque.borrow().is_full() {
+            self.shared.sink_waker.set(Some(cx.waker().clone()));
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    /// Pushes `item` to the back of the deque.
+    ///
+    /// If [`poll_ready`](Sink::poll_ready) was not called first and the deque
+    /// is full, the item is returned in a [`CapacityError`].
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        self.shared.deque.borrow_mut().push_back(item)?;
+        wake(&self.shared.stream_waker);
+
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Items are available to the stream as soon as they are pushed.
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.shared.close();
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<'a, T, const N: usize> Drop for DequeSink<'a, T, N>
+where
+    T: Default,
+{
+    fn drop(&mut self) {
+        self.shared.close();
+    }
+}
+
+/// The popping half of a split [`AsyncDeque`].
+///
+/// Items are popped from the front of the deque. When the deque is empty, the
+/// stream returns `Pending` until the [`DequeSink`] pushes an item, or ends if
+/// the sink has been closed.
+pub struct DequeStream<'a, T, const N: usize>
+where
+    T: Default,
+{
+    shared: &'a AsyncDeque<T, N>,
+}
+
+impl<'a, T, const N: usize> Stream for DequeStream<'a, T, N>
+where
+    T: Default,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let popped = self.shared.deque.borrow_mut().pop_front();
+
+        match popped {
+            Some(item) => {
+                wake(&self.shared.sink_waker);
+                Poll::Ready(Some(item))
+            }
+
+            None if self.shared.closed.get() => Poll::Ready(None),
+
+            None => {
+                self.shared.stream_waker.set(Some(cx.waker().clone()));
+                Poll::Pending
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.shared.len();
+
+        if self.shared.closed.get() {
+            (len, Some(len))
+        } else {
+            (len, None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    use futures::{
+        executor::block_on,
+        future::{join, poll_fn},
+        SinkExt, StreamExt,
+    };
+
+    #[test]
+    fn backpressure_when_full() {
+        let mut deque: AsyncDeque<u32, 1> = AsyncDeque::new();
+        let (mut sink, mut stream) = deque.split();
+
+        block_on(async {
+            sink.send(1).await.unwrap();
+
+            let ready = poll_fn(|cx| Poll::Ready(Pin::new(&mut sink).poll_ready(cx))).await;
+            assert!(ready.is_pending());
+
+            assert_eq!(stream.next().await, Some(1));
+
+            let ready = poll_fn(|cx| Poll::Ready(Pin::new(&mut sink).poll_ready(cx))).await;
+            assert!(matches!(ready, Poll::Ready(Ok(()))));
+        });
+    }
+
+    #[test]
+    fn start_send_without_ready_fails_when_full() {
+        let mut deque: AsyncDeque<u32, 1> = AsyncDeque::new();
+        let (mut sink, _stream) = deque.split();
+
+        Pin::new(&mut sink).start_send(1).unwrap();
+        let err = Pin::new(&mut sink).start_send(2).unwrap_err();
+        assert_eq!(err.into_inner(), 2);
+    }
+
+    #[test]
+    fn stream_ends_after_close() {
+        let mut deque: AsyncDeque<u32, 4> = AsyncDeque::new();
+
+        {
+            let (mut sink, stream) = deque.split();
+
+            let produce = async move {
+                sink.send(1).await.unwrap();
+                sink.send(2).await.unwrap();
+                sink.close().await.unwrap();
+            };
+
+            let (_, received) = block_on(join(produce, stream.collect::<Vec<_>>()));
+            assert_eq!(received, [1, 2]);
+        }
+
+        // The deque can be split again after both halves are dropped.
+        let (mut sink, mut stream) = deque.split();
+        block_on(sink.send(3)).unwrap();
+        drop(sink);
+        assert_eq!(block_on(stream.next()), Some(3));
+        assert_eq!(block_on(stream.next()), None);
+    }
+}
@@ -0,0 +1,558 @@
+//! Bipartite buffers with fixed capacity, backed by an array or a slice.
+//!
+//! A bip buffer is a ring buffer which only ever hands out contiguous
+//! regions. Writers [`grant`] a region of free space, fill it in place and
+//! [`commit`] however much of it they used; readers [`read`] the oldest
+//! contiguous run of committed elements and [`release`] them once processed.
+//! This suits DMA engines and codecs, which need a single slice rather than
+//! the pair of slices returned by a wrapped deque.
+//!
+//! The buffer holds up to two regions of committed elements: region A, which
+//! is read first, and region B, which begins at the start of the backing
+//! storage once there is no room left after region A. When region A has been
+//! fully released, region B takes its place.
+//!
+//! [`grant`]: BipBuffer::grant
+//! [`commit`]: BipBuffer::commit
+//! [`read`]: BipBuffer::read
+//! [`release`]: BipBuffer::release
+
+use core::{fmt, ops::Range};
+
+/// The region which an outstanding grant will extend when committed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum GrantTarget {
+    /// The grant begins where region A ends, or holds the first elements of
+    /// region A if it is empty when the grant is committed.
+    A,
+    /// The grant begins where region B ends.
+    B,
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct BipState {
+    /// The committed elements which are read first.
+    a: Range<usize>,
+    /// The end of the committed elements at the start of the storage, which
+    /// are read after region A. Region B is inactive if this is zero.
+    b_end: usize,
+    /// The region handed out by the most recent grant, if it has not yet
+    /// been committed, and the region it extends.
+    grant: Option<(Range<usize>, GrantTarget)>,
+}
+
+pub(crate) trait BaseBipBuffer<T> {
+    fn state(&self) -> &BipState;
+
+    fn state_mut(&mut self) -> &mut BipState;
+
+    fn items(&self) -> &[T];
+
+    fn items_mut(&mut self) -> &mut [T];
+
+    fn capacity(&self) -> usize {
+        self.items().len()
+    }
+
+    fn len(&self) -> usize {
+        self.state().a.len() + self.state().b_end
+    }
+
+    fn grant(&mut self, n: usize) -> Option<&mut [T]> {
+        let capacity = self.capacity();
+        let state = self.state_mut();
+
+        if state.a.is_empty() && state.b_end == 0 {
+            // Start over at the beginning to make all space available.
+            state.a = 0..0;
+        }
+
+        let (start, target) = if state.b_end > 0 {
+            // Region B may only grow up to the start of region A.
+            (state.a.start - state.b_end >= n).then_some((state.b_end, GrantTarget::B))?
+        } else if capacity - state.a.end >= n {
+            (state.a.end, GrantTarget::A)
+        } else if state.a.start >= n {
+            (0, GrantTarget::B)
+        } else {
+            return None;
+        };
+
+        state.grant = Some((start..start + n, target));
+        Some(&mut self.items_mut()[start..start + n])
+    }
+
+    fn commit(&mut self, n: usize) {
+        let state = self.state_mut();
+        let (grant, target) = state
+            .grant
+            .take()
+            .expect("commit called without an outstanding grant");
+
+        assert!(
+            n <= grant.len(),
+            "committed length (is {}) should be <= granted length (is {})",
+            n,
+            grant.len(),
+        );
+
+        match target {
+            GrantTarget::A => {
+                // If region A was released entirely after the grant, the
+                // grant becomes the new region A where it stands.
+                if state.a.is_empty() {
+                    state.a = grant.start..grant.start;
+                }
+                state.a.end += n;
+            }
+
+            GrantTarget::B => state.b_end = grant.start + n,
+        }
+    }
+
+    fn read(&self) -> &[T] {
+        &self.items()[self.state().a.clone()]
+    }
+
+    fn read_mut(&mut self) -> &mut [T] {
+        let a = self.state().a.clone();
+        &mut self.items_mut()[a]
+    }
+
+    fn release(&mut self, n: usize) {
+        let state = self.state_mut();
+
+        assert!(
+            n <= state.a.len(),
+            "released length (is {}) should be <= readable length (is {})",
+            n,
+            state.a.len(),
+        );
+
+        state.a.start += n;
+
+        if state.a.is_empty() {
+            state.a = 0..state.b_end;
+            state.b_end = 0;
+
+            // Region B has become region A, so a grant which would have
+            // extended region B now extends region A.
+            if let Some((_, target)) = &mut state.grant {
+                *target = GrantTarget::A;
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        *self.state_mut() = BipState::default();
+    }
+}
+
+/// A bip buffer with fixed capacity, backed by an array.
+///
+/// The capacity of the buffer is determined by the generic parameter `N`.
+///
+/// Elements are never dropped or overwritten when they are released; they
+/// remain in the backing storage until a later grant hands them out again.
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::BipBuffer;
+/// let mut buf: BipBuffer<u8, 8> = BipBuffer::new();
+///
+/// // A producer, such as a DMA transfer, fills part of a granted region.
+/// let region = buf.grant(8).unwrap();
+/// region[..6].copy_from_slice(b"abcdef");
+/// buf.commit(6);
+///
+/// assert_eq!(buf.read(), b"abcdef");
+/// buf.release(4);
+///
+/// // There is no room after the committed data, so the next grant starts
+/// // at the beginning of the buffer.
+/// buf.grant(3).unwrap().copy_from_slice(b"xyz");
+/// buf.commit(3);
+///
+/// assert_eq!(buf.read(), b"ef");
+/// buf.release(2);
+/// assert_eq!(buf.read(), b"xyz");
+/// ```
+#[derive(Clone)]
+pub struct BipBuffer<T, const N: usize>
+where
+    T: Default,
+{
+    items: [T; N],
+    state: BipState,
+}
+
+impl<T, const N: usize> BaseBipBuffer<T> for BipBuffer<T, N>
+where
+    T: Default,
+{
+    #[inline(always)]
+    fn state(&self) -> &BipState {
+        &self.state
+    }
+
+    #[inline(always)]
+    fn state_mut(&mut self) -> &mut BipState {
+        &mut self.state
+    }
+
+    #[inline(always)]
+    fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    #[inline(always)]
+    fn items_mut(&mut self) -> &mut [T] {
+        &mut self.items
+    }
+}
+
+impl<T, const N: usize> Default for BipBuffer<T, N>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        BipBuffer::new()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for BipBuffer<T, N>
+where
+    T: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BipBuffer")
+            .field("readable", &self.read())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<T, const N: usize> BipBuffer<T, N>
+where
+    T: Default,
+{
+    /// Constructs a new, empty `BipBuffer<T, N>`.
+    pub fn new() -> Self {
+        BipBuffer {
+            items: [(); N].map(|_| Default::default()),
+            state: BipState::default(),
+        }
+    }
+
+    /// Returns the maximum number of elements the buffer may hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of committed elements which have not been
+    /// released.
+    #[inline]
+    pub fn len(&self) -> usize {
+        BaseBipBuffer::len(self)
+    }
+
+    /// Returns `true` if the buffer contains no committed elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reserves a contiguous region of `n` elements for writing, returning
+    /// it as a mutable slice.
+    ///
+    /// The region contains stale or default values. Writes to it become
+    /// readable once they are passed to [`commit`]. A new grant replaces any
+    /// earlier grant which was not committed.
+    ///
+    /// If there is no contiguous region of `n` free elements, `None` is
+    /// returned.
+    ///
+    /// [`commit`]: BipBuffer::commit
+    #[inline]
+    pub fn grant(&mut self, n: usize) -> Option<&mut [T]> {
+        BaseBipBuffer::grant(self, n)
+    }
+
+    /// Makes the first `n` elements of the most recent grant readable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no outstanding grant, or if `n` is greater than the
+    /// length of the grant.
+    #[inline]
+    pub fn commit(&mut self, n: usize) {
+        BaseBipBuffer::commit(self, n)
+    }
+
+    /// Returns the oldest contiguous run of committed elements.
+    ///
+    /// This may not contain every committed element: once it has been fully
+    /// released, any elements committed at the start of the buffer become
+    /// readable.
+    #[inline]
+    pub fn read(&self) -> &[T] {
+        BaseBipBuffer::read(self)
+    }
+
+    /// Returns the oldest contiguous run of committed elements as a mutable
+    /// slice.
+    #[inline]
+    pub fn read_mut(&mut self) -> &mut [T] {
+        BaseBipBuffer::read_mut(self)
+    }
+
+    /// Releases the first `n` elements returned by [`read`], freeing their
+    /// space for future grants.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the length of the slice returned by
+    /// [`read`].
+    ///
+    /// [`read`]: BipBuffer::read
+    #[inline]
+    pub fn release(&mut self, n: usize) {
+        BaseBipBuffer::release(self, n)
+    }
+
+    /// Releases all committed elements and discards any outstanding grant.
+    #[inline]
+    pub fn clear(&mut self) {
+        BaseBipBuffer::clear(self)
+    }
+}
+
+/// A bip buffer with fixed capacity, backed by a slice.
+///
+/// The capacity of the buffer is determined by the length of the slice.
+pub struct SliceBipBuffer<'a, T>
+where
+    T: Default,
+{
+    items: &'a mut [T],
+    state: BipState,
+}
+
+impl<'a, T> BaseBipBuffer<T> for SliceBipBuffer<'a, T>
+where
+    T: Default,
+{
+    #[inline(always)]
+    fn state(&self) -> &BipState {
+        &self.state
+    }
+
+    #[inline(always)]
+    fn state_mut(&mut self) -> &mut BipState {
+        &mut self.state
+    }
+
+    #[inline(always)]
+    fn items(&self) -> &[T] {
+        self.items
+    }
+
+    #[inline(always)]
+    fn items_mut(&mut self) -> &mut [T] {
+        self.items
+    }
+}
+
+impl<'a, T> fmt::Debug for SliceBipBuffer<'a, T>
+where
+    T: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SliceBipBuffer")
+            .field("readable", &self.read())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<'a, T> SliceBipBuffer<'a, T>
+where
+    T: Default,
+{
+    /// Creates an empty `SliceBipBuffer` backed by the provided slice.
+    ///
+    /// The elements in the slice are left in place, and are handed out by
+    /// grants as stale values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceBipBuffer;
+    /// let mut slice = [0u8; 8];
+    /// let buf = SliceBipBuffer::new_in(&mut slice);
+    ///
+    /// assert!(buf.is_empty());
+    /// assert_eq!(buf.capacity(), 8);
+    /// ```
+    pub fn new_in(slice: &'a mut [T]) -> SliceBipBuffer<'a, T> {
+        SliceBipBuffer {
+            items: slice,
+            state: BipState::default(),
+        }
+    }
+
+    /// Returns the maximum number of elements the buffer may hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns the number of committed elements which have not been
+    /// released.
+    #[inline]
+    pub fn len(&self) -> usize {
+        BaseBipBuffer::len(self)
+    }
+
+    /// Returns `true` if the buffer contains no committed elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reserves a contiguous region of `n` elements for writing, returning
+    /// it as a mutable slice.
+    ///
+    /// See [`BipBuffer::grant`] for details.
+    #[inline]
+    pub fn grant(&mut self, n: usize) -> Option<&mut [T]> {
+        BaseBipBuffer::grant(self, n)
+    }
+
+    /// Makes the first `n` elements of the most recent grant readable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no outstanding grant, or if `n` is greater than the
+    /// length of the grant.
+    #[inline]
+    pub fn commit(&mut self, n: usize) {
+        BaseBipBuffer::commit(self, n)
+    }
+
+    /// Returns the oldest contiguous run of committed elements.
+    #[inline]
+    pub fn read(&self) -> &[T] {
+        BaseBipBuffer::read(self)
+    }
+
+    /// Returns the oldest contiguous run of committed elements as a mutable
+    /// slice.
+    #[inline]
+    pub fn read_mut(&mut self) -> &mut [T] {
+        BaseBipBuffer::read_mut(self)
+    }
+
+    /// Releases the first `n` elements returned by [`read`], freeing their
+    /// space for future grants.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the length of the slice returned by
+    /// [`read`].
+    ///
+    /// [`read`]: SliceBipBuffer::read
+    #[inline]
+    pub fn release(&mut self, n: usize) {
+        BaseBipBuffer::release(self, n)
+    }
+
+    /// Releases all committed elements and discards any outstanding grant.
+    #[inline]
+    pub fn clear(&mut self) {
+        BaseBipBuffer::clear(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_to_region_b() {
+        let mut buf: BipBuffer<u32, 6> = BipBuffer::new();
+
+        buf.grant(4).unwrap().copy_from_slice(&[1, 2, 3, 4]);
+        buf.commit(4);
+        buf.release(2);
+
+        // Two elements free after region A, two before it.
+        assert!(buf.grant(3).is_none());
+        buf.grant(2).unwrap().copy_from_slice(&[5, 6]);
+        buf.commit(2);
+        buf.grant(2).unwrap().copy_from_slice(&[7, 8]);
+        buf.commit(1);
+        assert_eq!(buf.len(), 5);
+
+        // Region B cannot grow into region A.
+        assert!(buf.grant(2).is_none());
+
+        assert_eq!(buf.read(), &[3, 4, 5, 6]);
+        buf.release(4);
+        assert_eq!(buf.read(), &[7]);
+        buf.release(1);
+        assert!(buf.is_empty());
+
+        // An empty buffer makes its whole capacity available again.
+        assert_eq!(buf.grant(6).map(|r| r.len()), Some(6));
+    }
+
+    #[test]
+    fn uncommitted_grant_is_discarded() {
+        let mut slice = [0; 4];
+        let mut buf = SliceBipBuffer::new_in(&mut slice);
+
+        buf.grant(4).unwrap().fill(9);
+        buf.grant(2).unwrap();
+        buf.commit(0);
+        assert!(buf.is_empty());
+        assert!(buf.read().is_empty());
+    }
+
+    #[test]
+    fn commit_after_releasing_everything() {
+        let mut buf: BipBuffer<u8, 8> = BipBuffer::new();
+
+        buf.grant(4).unwrap().copy_from_slice(b"abcd");
+        buf.commit(4);
+        buf.grant(2).unwrap().copy_from_slice(b"ef");
+        buf.release(4);
+        buf.commit(2);
+
+        assert_eq!(buf.len(), 2);
+        assert_eq!(buf.read(), b"ef");
+
+        // The same, with the grant at the start of the storage.
+        buf.release(2);
+        buf.grant(8).unwrap();
+        buf.commit(7);
+        buf.release(3);
+        buf.grant(2).unwrap().copy_from_slice(b"gh");
+        buf.release(4);
+        buf.commit(2);
+
+        assert_eq!(buf.len(), 2);
+        assert_eq!(buf.read(), b"gh");
+    }
+
+    #[test]
+    #[should_panic]
+    fn commit_more_than_granted() {
+        let mut buf: BipBuffer<u32, 4> = BipBuffer::new();
+
+        buf.grant(2).unwrap();
+        buf.commit(3);
+    }
+}
@@ -0,0 +1,410 @@
+//! A double-ended queue of bits.
+
+use core::{
+    fmt,
+    ops::{Bound, Range, RangeBounds},
+};
+
+use crate::{meta::Meta, slice_deque::SliceMeta, CapacityError};
+
+const WORD_BITS: usize = u32::BITS as usize;
+
+/// A double-ended queue of booleans with fixed capacity, packed into `WORDS`
+/// 32-bit words.
+///
+/// Each element occupies a single bit, so a `BitDeque` uses an eighth of the
+/// memory of an `ArrayDeque<bool, N>`. Const generic arithmetic is not yet
+/// stable, so the storage is sized in words: a `BitDeque<WORDS>` holds up to
+/// `32 * WORDS` bits by default, and [`with_capacity`] may set a smaller
+/// capacity, which is useful for keeping exactly the last `n` flags.
+///
+/// [`with_capacity`]: BitDeque::with_capacity
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::bit_deque::BitDeque;
+/// // Record the outcome of the last 10 requests.
+/// let mut outcomes: BitDeque<1> = BitDeque::with_capacity(10);
+///
+/// for i in 0..25 {
+///     outcomes.push_back_overwrite(i % 3 != 0);
+/// }
+///
+/// assert_eq!(outcomes.len(), 10);
+/// assert_eq!(outcomes.count_ones(), 6);
+/// // Successes among the 5 most recent requests.
+/// assert_eq!(outcomes.count_ones_in(5..), 3);
+/// ```
+#[derive(Clone)]
+pub struct BitDeque<const WORDS: usize> {
+    meta: SliceMeta,
+    words: [u32; WORDS],
+}
+
+impl<const WORDS: usize> BitDeque<WORDS> {
+    /// Constructs a new, empty `BitDeque` with a capacity of `32 * WORDS`
+    /// bits.
+    pub fn new() -> Self {
+        BitDeque::with_capacity(WORDS * WORD_BITS)
+    }
+
+    /// Constructs a new, empty `BitDeque` with a capacity of `bits` bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is greater than `32 * WORDS`.
+    pub fn with_capacity(bits: usize) -> Self {
+        assert!(
+            bits <= WORDS * WORD_BITS,
+            "capacity (is {}) should be <= 32 * WORDS (is {})",
+            bits,
+            WORDS * WORD_BITS,
+        );
+
+        BitDeque {
+            meta: SliceMeta::empty(bits),
+            words: [0; WORDS],
+        }
+    }
+
+    /// Returns the maximum number of bits the deque may hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.meta.capacity()
+    }
+
+    /// Returns the number of bits in the deque.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.meta.len()
+    }
+
+    /// Returns `true` if the deque is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the deque is full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    fn bit(&self, physical: usize) -> bool {
+        self.words[physical / WORD_BITS] & (1 << (physical % WORD_BITS)) != 0
+    }
+
+    fn set_bit(&mut self, physical: usize, value: bool) {
+        let word = &mut self.words[physical / WORD_BITS];
+        let mask = 1 << (physical % WORD_BITS);
+
+        if value {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+    }
+
+    fn physical_index(&self, index: usize) -> Option<usize> {
+        let (front, wrap) = self.meta.as_ranges();
+
+        if index < front.len() {
+            Some(front.start + index)
+        } else if index - front.len() < wrap.len() {
+            Some(wrap.start + (index - front.len()))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the bit at logical position `index`, or `None` if `index` is
+    /// out of bounds.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        self.physical_index(index)
+            .map(|physical| self.bit(physical))
+    }
+
+    /// Sets the bit at logical position `index`, returning its previous
+    /// value, or `None` if `index` is out of bounds.
+    pub fn set(&mut self, index: usize, value: bool) -> Option<bool> {
+        let physical = self.physical_index(index)?;
+        let old = self.bit(physical);
+        self.set_bit(physical, value);
+
+        Some(old)
+    }
+
+    /// Returns the first bit, or `None` if the deque is empty.
+    pub fn front(&self) -> Option<bool> {
+        self.meta.front().map(|physical| self.bit(physical))
+    }
+
+    /// Returns the last bit, or `None` if the deque is empty.
+    pub fn back(&self) -> Option<bool> {
+        self.meta.back().map(|physical| self.bit(physical))
+    }
+
+    /// Prepends a bit to the deque.
+    ///
+    /// If the deque is full, the bit is returned in a [`CapacityError`].
+    pub fn push_front(&mut self, bit: bool) -> Result<(), CapacityError<bool>> {
+        match self.meta.reserve_front() {
+            Some(physical) => {
+                self.set_bit(physical, bit);
+                Ok(())
+            }
+
+            None => Err(CapacityError { item: bit }),
+        }
+    }
+
+    /// Appends a bit to the deque.
+    ///
+    /// If the deque is full, the bit is returned in a [`CapacityError`].
+    pub fn push_back(&mut self, bit: bool) -> Result<(), CapacityError<bool>> {
+        match self.meta.reserve_back() {
+            Some(physical) => {
+                self.set_bit(physical, bit);
+                Ok(())
+            }
+
+            None => Err(CapacityError { item: bit }),
+        }
+    }
+
+    /// Appends a bit to the deque, removing and returning the first bit if
+    /// the deque is full.
+    ///
+    /// If the deque has a capacity of zero, `bit` itself is returned.
+    pub fn push_back_overwrite(&mut self, bit: bool) -> Option<bool> {
+        let evicted = if self.is_full() {
+            self.pop_front()
+        } else {
+            None
+        };
+
+        match self.push_back(bit) {
+            Ok(()) => evicted,
+            Err(e) => Some(e.into_inner()),
+        }
+    }
+
+    /// Removes and returns the first bit, or `None` if the deque is empty.
+    pub fn pop_front(&mut self) -> Option<bool> {
+        self.meta.free_front().map(|physical| self.bit(physical))
+    }
+
+    /// Removes and returns the last bit, or `None` if the deque is empty.
+    pub fn pop_back(&mut self) -> Option<bool> {
+        self.meta.free_back().map(|physical| self.bit(physical))
+    }
+
+    /// Removes all bits from the deque.
+    pub fn clear(&mut self) {
+        // Bits need no cleanup, so the drain of freed indices is discarded.
+        let _ = self.meta.clear();
+    }
+
+    /// Counts the set bits in a contiguous physical range of the storage.
+    fn count_ones_physical(&self, range: Range<usize>) -> usize {
+        if range.is_empty() {
+            return 0;
+        }
+
+        let (first_word, last_word) = (range.start / WORD_BITS, (range.end - 1) / WORD_BITS);
+
+        (first_word..=last_word)
+            .map(|w| {
+                let lo = if w == first_word {
+                    range.start % WORD_BITS
+                } else {
+                    0
+                };
+                let hi = if w == last_word {
+                    (range.end - 1) % WORD_BITS + 1
+                } else {
+                    WORD_BITS
+                };
+
+                // Mask off the bits below `lo` and at or above `hi`.
+                let mask = (u32::MAX >> (WORD_BITS - (hi - lo))) << lo;
+                (self.words[w] & mask).count_ones() as usize
+            })
+            .sum()
+    }
+
+    /// Returns the number of set bits in the deque.
+    pub fn count_ones(&self) -> usize {
+        let (front, wrap) = self.meta.as_ranges();
+
+        self.count_ones_physical(front) + self.count_ones_physical(wrap)
+    }
+
+    /// Returns the number of unset bits in the deque.
+    pub fn count_zeros(&self) -> usize {
+        self.len() - self.count_ones()
+    }
+
+    /// Returns the number of set bits in the given logical range of the
+    /// deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or its start is greater than its
+    /// end.
+    pub fn count_ones_in<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<usize>,
+    {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+
+        assert!(
+            start <= end && end <= len,
+            "range {}..{} out of bounds for deque of length {}",
+            start,
+            end,
+            len,
+        );
+
+        let (front, wrap) = self.meta.as_ranges();
+        let split = front.len();
+
+        let in_front = start.min(split)..end.min(split);
+        let in_wrap = start.max(split) - split..end.max(split) - split;
+
+        self.count_ones_physical(front.start + in_front.start..front.start + in_front.end)
+            + self.count_ones_physical(wrap.start + in_wrap.start..wrap.start + in_wrap.end)
+    }
+
+    /// Returns a front-to-back iterator over the bits of the deque.
+    pub fn iter(&self) -> Iter<'_, WORDS> {
+        Iter {
+            deque: self,
+            range: 0..self.len(),
+        }
+    }
+}
+
+impl<const WORDS: usize> Default for BitDeque<WORDS> {
+    fn default() -> Self {
+        BitDeque::new()
+    }
+}
+
+impl<const WORDS: usize> fmt::Debug for BitDeque<WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// An iterator over the bits of a [`BitDeque`].
+///
+/// This struct is created by the [`iter`] method on [`BitDeque`].
+///
+/// [`iter`]: BitDeque::iter
+#[derive(Clone)]
+pub struct Iter<'a, const WORDS: usize> {
+    deque: &'a BitDeque<WORDS>,
+    range: Range<usize>,
+}
+
+impl<'a, const WORDS: usize> Iterator for Iter<'a, WORDS> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        let index = self.range.next()?;
+        self.deque.get(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a, const WORDS: usize> DoubleEndedIterator for Iter<'a, WORDS> {
+    fn next_back(&mut self) -> Option<bool> {
+        let index = self.range.next_back()?;
+        self.deque.get(index)
+    }
+}
+
+impl<'a, const WORDS: usize> ExactSizeIterator for Iter<'a, WORDS> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn push_pop_both_ends() {
+        let mut deque: BitDeque<1> = BitDeque::with_capacity(3);
+
+        deque.push_back(true).unwrap();
+        deque.push_front(false).unwrap();
+        deque.push_back(true).unwrap();
+        assert!(deque.push_front(true).unwrap_err().into_inner());
+        assert_eq!(deque.iter().collect::<Vec<_>>(), [false, true, true]);
+
+        assert_eq!(deque.pop_back(), Some(true));
+        assert_eq!(deque.pop_front(), Some(false));
+        assert_eq!(deque.front(), Some(true));
+        assert_eq!(deque.back(), Some(true));
+        deque.clear();
+        assert_eq!(deque.pop_front(), None);
+    }
+
+    #[test]
+    fn counts_across_words_and_wrap() {
+        let mut deque: BitDeque<3> = BitDeque::with_capacity(70);
+        let expected: Vec<bool> = (0..100).map(|i| i % 3 == 0 || i % 7 == 0).collect();
+
+        for bit in expected.iter().copied() {
+            deque.push_back_overwrite(bit);
+        }
+
+        let window = &expected[30..];
+        assert_eq!(deque.iter().collect::<Vec<_>>(), window);
+        assert_eq!(deque.count_ones(), window.iter().filter(|&&b| b).count());
+
+        for (start, end) in [(0, 70), (0, 0), (5, 40), (33, 69), (69, 70)]
+            .iter()
+            .copied()
+        {
+            assert_eq!(
+                deque.count_ones_in(start..end),
+                window[start..end].iter().filter(|&&b| b).count(),
+                "range {}..{}",
+                start,
+                end,
+            );
+        }
+    }
+
+    #[test]
+    fn set_and_get() {
+        let mut deque: BitDeque<1> = BitDeque::new();
+        assert_eq!(deque.capacity(), 32);
+
+        for _ in 0..4 {
+            deque.push_front(false).unwrap();
+        }
+        assert_eq!(deque.set(2, true), Some(false));
+        assert_eq!(deque.get(2), Some(true));
+        assert_eq!(deque.set(4, true), None);
+        assert_eq!(deque.count_zeros(), 3);
+    }
+}
@@ -0,0 +1,551 @@
+//! A fixed-capacity ring with a single writer and independent readers.
+
+use core::fmt;
+#[cfg(all(feature = "atomics", target_has_atomic = "64"))]
+use core::sync::atomic::{fence, AtomicBool, AtomicU64, Ordering};
+
+#[cfg(all(feature = "atomics", target_has_atomic = "64"))]
+use crate::spsc::AtomicItem;
+use crate::{ArrayDeque, BaseDeque};
+
+/// An error returned by [`Broadcast::recv`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecvError {
+    /// The subscriber has received every element sent so far.
+    Empty,
+
+    /// The subscriber fell behind, and this many elements were overwritten
+    /// before it could receive them.
+    ///
+    /// The subscriber has been moved to the oldest element still in the
+    /// ring, so the next call to `recv` succeeds.
+    Lagged(u64),
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecvError::Empty => f.write_str("no elements to receive"),
+            RecvError::Lagged(n) => write!(f, "subscriber lagged by {} elements", n),
+        }
+    }
+}
+
+impl core::error::Error for RecvError {}
+
+/// A reader's position in a [`Broadcast`].
+///
+/// A subscriber is the sequence number of the next element it will receive.
+/// It holds no reference to the ring, so subscribers can be stored anywhere
+/// and copied freely; each copy advances independently. A subscriber must
+/// only be used with the ring that created it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Subscriber {
+    next: u64,
+}
+
+impl Subscriber {
+    /// Returns the sequence number of the next element this subscriber will
+    /// receive.
+    #[inline]
+    pub fn next_seq(&self) -> u64 {
+        self.next
+    }
+}
+
+/// A ring holding the `N` most recently sent elements, which any number of
+/// subscribers read at their own pace.
+///
+/// Sending to a full ring overwrites the oldest element. Every element is
+/// numbered in the order it was sent, and each [`Subscriber`] records the
+/// number of the next element it will receive. A subscriber which falls more
+/// than `N` elements behind observes [`RecvError::Lagged`] with the number of
+/// elements it missed, then continues from the oldest element still held.
+///
+/// This is similar to `tokio::sync::broadcast`, but requires neither `std`
+/// nor an async runtime: the ring is a plain value, and receiving borrows
+/// elements from it rather than cloning them. As sending requires exclusive
+/// access, a `Broadcast` cannot be sent to while other threads receive from
+/// it; for that, use
+#[cfg_attr(
+    all(feature = "atomics", target_has_atomic = "64"),
+    doc = "[`AtomicBroadcast`]."
+)]
+#[cfg_attr(
+    not(all(feature = "atomics", target_has_atomic = "64")),
+    doc = "`AtomicBroadcast`, behind the `atomics` feature."
+)]
+///
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::broadcast::{Broadcast, RecvError};
+/// let mut ring: Broadcast<u32, 2> = Broadcast::new();
+/// let mut fast = ring.subscribe();
+/// let mut slow = ring.subscribe();
+///
+/// ring.send(1);
+/// assert_eq!(ring.recv(&mut fast), Ok(&1));
+///
+/// ring.send(2);
+/// ring.send(3);
+/// assert_eq!(ring.recv(&mut fast), Ok(&2));
+/// assert_eq!(ring.recv(&mut fast), Ok(&3));
+/// assert_eq!(ring.recv(&mut fast), Err(RecvError::Empty));
+///
+/// assert_eq!(ring.recv(&mut slow), Err(RecvError::Lagged(1)));
+/// assert_eq!(ring.recv(&mut slow), Ok(&2));
+/// ```
+#[derive(Clone)]
+pub struct Broadcast<T, const N: usize>
+where
+    T: Default,
+{
+    items: ArrayDeque<T, N>,
+    /// The sequence number of the front element.
+    first: u64,
+}
+
+impl<T, const N: usize> Broadcast<T, N>
+where
+    T: Default,
+{
+    /// Constructs a new, empty `Broadcast<T, N>`.
+    pub fn new() -> Self {
+        Broadcast {
+            items: ArrayDeque::new(),
+            first: 0,
+        }
+    }
+
+    /// Returns the number of elements the ring holds before it starts
+    /// overwriting them.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of elements currently held by the ring.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if the ring holds no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the sequence number the next element sent will receive.
+    #[inline]
+    pub fn next_seq(&self) -> u64 {
+        self.first + self.items.len() as u64
+    }
+
+    /// Appends an element, overwriting the oldest element if the ring is
+    /// full.
+    ///
+    /// The overwritten element is returned. If the capacity is zero, `item`
+    /// itself is returned, although it is still assigned a sequence number.
+    pub fn send(&mut self, item: T) -> Option<T> {
+        let evicted = match self.items.push_back(item) {
+            Ok(()) => return None,
+            Err(e) if N == 0 => e.into_inner(),
+            Err(e) => {
+                let oldest = self.items.pop_front();
+                self.items.push_back_expect(e.into_inner());
+                oldest.unwrap()
+            }
+        };
+
+        self.first += 1;
+        Some(evicted)
+    }
+
+    /// Returns a subscriber which receives elements sent after this call.
+    #[inline]
+    pub fn subscribe(&self) -> Subscriber {
+        Subscriber {
+            next: self.next_seq(),
+        }
+    }
+
+    /// Returns a subscriber which starts at the oldest element held by the
+    /// ring.
+    #[inline]
+    pub fn subscribe_oldest(&self) -> Subscriber {
+        Subscriber { next: self.first }
+    }
+
+    /// Returns the number of elements `subscriber` has yet to receive, or
+    /// `None` if it has lagged behind the oldest element.
+    pub fn pending(&self, subscriber: &Subscriber) -> Option<usize> {
+        if subscriber.next < self.first {
+            return None;
+        }
+
+        Some((self.next_seq() - subscriber.next) as usize)
+    }
+
+    /// Receives the next element for `subscriber`, advancing it.
+    ///
+    /// Returns [`RecvError::Empty`] if `subscriber` has received every
+    /// element, and [`RecvError::Lagged`] if elements it had not yet
+    /// received were overwritten.
+    pub fn recv(&self, subscriber: &mut Subscriber) -> Result<&T, RecvError> {
+        if subscriber.next < self.first {
+            let missed = self.first - subscriber.next;
+            subscriber.next = self.first;
+
+            return Err(RecvError::Lagged(missed));
+        }
+
+        let index = (subscriber.next - self.first) as usize;
+        let physical = BaseDeque::physical_index(&self.items, index).ok_or(RecvError::Empty)?;
+        subscriber.next += 1;
+
+        Ok(&BaseDeque::items(&self.items)[physical])
+    }
+}
+
+impl<T, const N: usize> Default for Broadcast<T, N>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for Broadcast<T, N>
+where
+    T: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Broadcast")
+            .field("first_seq", &self.first)
+            .field("items", &self.items)
+            .finish()
+    }
+}
+
+/// A ring holding the `N` most recently sent elements, which one thread sends
+/// to while any number of threads receive from it.
+///
+/// This behaves like [`Broadcast`], but every operation takes a shared
+/// reference, so the ring can be placed in a `static`. The single [`Sender`]
+/// is obtained with [`sender`]; receivers only need a [`Subscriber`] and a
+/// shared reference to the ring. Neither side ever waits for the other.
+///
+/// Each slot is guarded by its own sequence lock: the sender marks the slot
+/// before and after overwriting it, and a receiver which overlaps the write
+/// discards what it read and reports that it lagged. As this crate forbids
+/// `unsafe` code, each slot is itself an atomic, so elements are limited to
+/// types implementing [`AtomicItem`], and are received by value.
+///
+/// [`sender`]: AtomicBroadcast::sender
+///
+/// # Example
+///
+/// ```
+/// use holodeque::broadcast::{AtomicBroadcast, RecvError};
+///
+/// static READINGS: AtomicBroadcast<u16, 4> = AtomicBroadcast::new();
+///
+/// let mut sender = READINGS.sender().unwrap();
+/// let mut display = READINGS.subscribe();
+///
+/// std::thread::scope(|s| {
+///     s.spawn(move || {
+///         for reading in [310, 312, 309] {
+///             sender.send(reading);
+///         }
+///     });
+/// });
+///
+/// assert_eq!(READINGS.recv(&mut display), Ok(310));
+/// assert_eq!(READINGS.recv(&mut display), Ok(312));
+/// assert_eq!(READINGS.recv(&mut display), Ok(309));
+/// assert_eq!(READINGS.recv(&mut display), Err(RecvError::Empty));
+/// ```
+#[cfg(all(feature = "atomics", target_has_atomic = "64"))]
+pub struct AtomicBroadcast<T, const N: usize>
+where
+    T: AtomicItem,
+{
+    /// The sequence number the next element sent will receive.
+    next: AtomicU64,
+    has_sender: AtomicBool,
+    /// For each slot, twice the sequence number of the element it holds plus
+    /// two, or that minus one while the element is being written. Zero if the
+    /// slot has never been written.
+    stamps: [AtomicU64; N],
+    slots: [T::Atomic; N],
+}
+
+#[cfg(all(feature = "atomics", target_has_atomic = "64"))]
+impl<T, const N: usize> AtomicBroadcast<T, N>
+where
+    T: AtomicItem,
+{
+    /// Creates an empty ring.
+    ///
+    /// This is a `const fn`, so the ring can be placed in a `static`.
+    pub const fn new() -> AtomicBroadcast<T, N> {
+        AtomicBroadcast {
+            next: AtomicU64::new(0),
+            has_sender: AtomicBool::new(false),
+            stamps: [const { AtomicU64::new(0) }; N],
+            slots: [T::INIT; N],
+        }
+    }
+
+    /// Returns the number of elements the ring holds before it starts
+    /// overwriting them.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the sequence number the next element sent will receive.
+    #[inline]
+    pub fn next_seq(&self) -> u64 {
+        self.next.load(Ordering::Acquire)
+    }
+
+    /// Returns the sender of the ring.
+    ///
+    /// A ring has only one sender; subsequent calls return `None`.
+    #[inline]
+    pub fn sender(&self) -> Option<Sender<'_, T, N>> {
+        if self.has_sender.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+
+        Some(Sender { ring: self })
+    }
+
+    /// Returns a subscriber which receives elements sent after this call.
+    #[inline]
+    pub fn subscribe(&self) -> Subscriber {
+        Subscriber {
+            next: self.next_seq(),
+        }
+    }
+
+    /// Returns a subscriber which starts at the oldest element held by the
+    /// ring.
+    #[inline]
+    pub fn subscribe_oldest(&self) -> Subscriber {
+        Subscriber {
+            next: self.next_seq().saturating_sub(N as u64),
+        }
+    }
+
+    /// Receives the next element for `subscriber`, advancing it.
+    ///
+    /// Returns [`RecvError::Empty`] if `subscriber` has received every
+    /// element, and [`RecvError::Lagged`] if elements it had not yet
+    /// received were overwritten, including while this call was reading
+    /// them.
+    pub fn recv(&self, subscriber: &mut Subscriber) -> Result<T, RecvError> {
+        let seq = subscriber.next;
+        let complete = 2 * seq + 2;
+
+        if N > 0 {
+            let index = (seq % N as u64) as usize;
+            let stamp = self.stamps[index].load(Ordering::Acquire);
+            if stamp < complete {
+                // The element has not been sent, or is still being written.
+                return Err(RecvError::Empty);
+            }
+
+            if stamp == complete {
+                let item = T::load(&self.slots[index]);
+
+                fence(Ordering::Acquire);
+                if self.stamps[index].load(Ordering::Relaxed) == complete {
+                    subscriber.next += 1;
+                    return Ok(item);
+                }
+            }
+        } else if seq >= self.next_seq() {
+            return Err(RecvError::Empty);
+        }
+
+        // The element was overwritten. The element in the slot being written
+        // now, if any, is lost as well, so the subscriber skips at least one.
+        let oldest = self.next_seq().saturating_sub(N as u64).max(seq + 1);
+        subscriber.next = oldest;
+
+        Err(RecvError::Lagged(oldest - seq))
+    }
+}
+
+#[cfg(all(feature = "atomics", target_has_atomic = "64"))]
+impl<T, const N: usize> Default for AtomicBroadcast<T, N>
+where
+    T: AtomicItem,
+{
+    fn default() -> Self {
+        AtomicBroadcast::new()
+    }
+}
+
+/// The sender of an [`AtomicBroadcast`].
+///
+/// This struct is created by the [`sender`] method on [`AtomicBroadcast`].
+///
+/// [`sender`]: AtomicBroadcast::sender
+#[cfg(all(feature = "atomics", target_has_atomic = "64"))]
+pub struct Sender<'b, T, const N: usize>
+where
+    T: AtomicItem,
+{
+    ring: &'b AtomicBroadcast<T, N>,
+}
+
+#[cfg(all(feature = "atomics", target_has_atomic = "64"))]
+impl<'b, T, const N: usize> Sender<'b, T, N>
+where
+    T: AtomicItem,
+{
+    /// Appends an element, overwriting the oldest element if the ring is
+    /// full.
+    ///
+    /// The overwritten element is returned. If the capacity is zero, `item`
+    /// itself is returned, although it is still assigned a sequence number.
+    pub fn send(&mut self, item: T) -> Option<T> {
+        let ring = self.ring;
+        // Only the sender modifies the sequence number.
+        let seq = ring.next.load(Ordering::Relaxed);
+
+        let evicted = if N == 0 {
+            Some(item)
+        } else {
+            let index = (seq % N as u64) as usize;
+            let evicted = (seq >= N as u64).then(|| T::load(&ring.slots[index]));
+
+            ring.stamps[index].store(2 * seq + 1, Ordering::Relaxed);
+            fence(Ordering::Release);
+            T::store(&ring.slots[index], item);
+            ring.stamps[index].store(2 * seq + 2, Ordering::Release);
+
+            evicted
+        };
+
+        ring.next.store(seq + 1, Ordering::Release);
+
+        evicted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscribers_advance_independently() {
+        let mut ring: Broadcast<u32, 3> = Broadcast::new();
+        let mut early = ring.subscribe();
+
+        for i in 0..5 {
+            assert_eq!(ring.send(i), i.checked_sub(3));
+        }
+
+        let mut late = ring.subscribe_oldest();
+        assert_eq!(ring.pending(&late), Some(3));
+        assert_eq!(ring.pending(&early), None);
+
+        assert_eq!(ring.recv(&mut early), Err(RecvError::Lagged(2)));
+        let copy = early;
+        for i in 2..5 {
+            assert_eq!(ring.recv(&mut early), Ok(&i));
+            assert_eq!(ring.recv(&mut late), Ok(&i));
+        }
+        assert_eq!(ring.recv(&mut early), Err(RecvError::Empty));
+        assert_eq!(ring.pending(&copy), Some(3));
+    }
+
+    #[test]
+    fn zero_capacity_always_lags() {
+        let mut ring: Broadcast<u32, 0> = Broadcast::new();
+        let mut sub = ring.subscribe();
+
+        assert_eq!(ring.send(7), Some(7));
+        assert_eq!(ring.next_seq(), 1);
+        assert_eq!(ring.recv(&mut sub), Err(RecvError::Lagged(1)));
+        assert_eq!(ring.recv(&mut sub), Err(RecvError::Empty));
+    }
+
+    #[cfg(all(feature = "atomics", target_has_atomic = "64"))]
+    #[test]
+    fn atomic_subscribers_advance_independently() {
+        let ring: AtomicBroadcast<u32, 3> = AtomicBroadcast::new();
+        let mut sender = ring.sender().unwrap();
+        assert!(ring.sender().is_none());
+        let mut early = ring.subscribe();
+
+        for i in 0..5 {
+            assert_eq!(sender.send(i), i.checked_sub(3));
+        }
+
+        let mut late = ring.subscribe_oldest();
+        assert_eq!(ring.recv(&mut early), Err(RecvError::Lagged(2)));
+        for i in 2..5 {
+            assert_eq!(ring.recv(&mut early), Ok(i));
+            assert_eq!(ring.recv(&mut late), Ok(i));
+        }
+        assert_eq!(ring.recv(&mut early), Err(RecvError::Empty));
+    }
+
+    #[cfg(all(feature = "atomics", target_has_atomic = "64"))]
+    #[test]
+    fn atomic_zero_capacity_always_lags() {
+        let ring: AtomicBroadcast<u32, 0> = AtomicBroadcast::new();
+        let mut sub = ring.subscribe();
+
+        assert_eq!(ring.sender().unwrap().send(7), Some(7));
+        assert_eq!(ring.recv(&mut sub), Err(RecvError::Lagged(1)));
+        assert_eq!(ring.recv(&mut sub), Err(RecvError::Empty));
+    }
+
+    #[cfg(all(feature = "atomics", target_has_atomic = "64", feature = "std"))]
+    #[test]
+    fn concurrent_receivers_see_sent_elements() {
+        const COUNT: u64 = 10_000;
+
+        let ring: AtomicBroadcast<u64, 4> = AtomicBroadcast::new();
+        let mut sender = ring.sender().unwrap();
+
+        std::thread::scope(|s| {
+            s.spawn(move || {
+                for i in 0..COUNT {
+                    sender.send(i);
+                }
+            });
+
+            for _ in 0..2 {
+                s.spawn(|| {
+                    let mut subscriber = ring.subscribe_oldest();
+                    while subscriber.next_seq() < COUNT {
+                        let seq = subscriber.next_seq();
+
+                        // Element `i` is sent with sequence number `i`, so a
+                        // torn or misplaced read would not match.
+                        match ring.recv(&mut subscriber) {
+                            Ok(item) => assert_eq!(item, seq),
+                            Err(RecvError::Lagged(n)) => {
+                                assert!(n > 0);
+                                assert_eq!(subscriber.next_seq(), seq + n);
+                            }
+                            Err(RecvError::Empty) => core::hint::spin_loop(),
+                        }
+                    }
+                });
+            }
+        });
+    }
+}
@@ -0,0 +1,83 @@
+//! CBOR support via `minicbor`.
+
+use minicbor::{
+    data::Type,
+    decode::{self, Decoder},
+    encode::{self, Encoder, Write},
+    Decode, Encode,
+};
+
+use crate::BaseDeque;
+
+/// Encodes the contents of `deque` as a definite-length CBOR array.
+pub(crate) fn encode<D, T, C, W>(
+    deque: &D,
+    e: &mut Encoder<W>,
+    ctx: &mut C,
+) -> Result<(), encode::Error<W::Error>>
+where
+    D: BaseDeque<T>,
+    T: Encode<C> + Default,
+    W: Write,
+{
+    let (first, second) = deque.as_slices();
+
+    e.array(deque.len() as u64)?;
+
+    for item in first.iter().chain(second) {
+        item.encode(e, ctx)?;
+    }
+
+    Ok(())
+}
+
+/// Decodes a CBOR array, appending its elements to the back of `deque`.
+///
+/// Both definite- and indefinite-length arrays are accepted. If the array has
+/// more elements than the deque has room for, an error is returned; the
+/// elements decoded up to that point remain in the deque.
+pub(crate) fn extend_decode<'b, D, T, C>(
+    deque: &mut D,
+    d: &mut Decoder<'b>,
+    ctx: &mut C,
+) -> Result<(), decode::Error>
+where
+    D: BaseDeque<T>,
+    T: Decode<'b, C> + Default,
+{
+    let position = d.position();
+
+    match d.array()? {
+        Some(len) => {
+            if len > (deque.capacity() - deque.len()) as u64 {
+                return Err(capacity_exceeded().at(position));
+            }
+
+            for _ in 0..len {
+                // The length check above guarantees there is room.
+                deque.push_back(T::decode(d, ctx)?).ok().unwrap();
+            }
+        }
+
+        None => {
+            while d.datatype()? != Type::Break {
+                let position = d.position();
+
+                if deque.is_full() {
+                    return Err(capacity_exceeded().at(position));
+                }
+
+                deque.push_back(T::decode(d, ctx)?).ok().unwrap();
+            }
+
+            // Consume the break marker.
+            d.set_position(d.position() + 1);
+        }
+    }
+
+    Ok(())
+}
+
+fn capacity_exceeded() -> decode::Error {
+    decode::Error::message("array length exceeds deque capacity")
+}
@@ -0,0 +1,173 @@
+//! Flattening a deque of strings or slices into a single value.
+//!
+//! These traits back the `concat()` and `join()` methods of [`ArrayDeque`]
+//! and [`SliceDeque`], mirroring the methods of the same names on slices.
+//!
+//! [`ArrayDeque`]: crate::ArrayDeque
+//! [`SliceDeque`]: crate::SliceDeque
+
+use alloc::{string::String, vec::Vec};
+use core::borrow::Borrow;
+
+/// An element type which can be concatenated into a single value of type
+/// `Self::Output`.
+///
+/// This is implemented for element types which borrow as `str`, producing a
+/// `String`, and for element types which borrow as `[V]`, producing a
+/// `Vec<V>`.
+pub trait Concat<Item: ?Sized> {
+    /// The type of the concatenated value.
+    type Output;
+
+    /// Concatenates `items` in order.
+    fn concat<'a, I>(items: I) -> Self::Output
+    where
+        I: Iterator<Item = &'a Self> + Clone,
+        Self: 'a;
+}
+
+/// An element type which can be joined into a single value of type
+/// `Self::Output`, with a separator of type `Separator` between each pair of
+/// elements.
+pub trait Join<Separator> {
+    /// The type of the joined value.
+    type Output;
+
+    /// Joins `items` in order, placing `sep` between each pair of elements.
+    fn join<'a, I>(items: I, sep: Separator) -> Self::Output
+    where
+        I: Iterator<Item = &'a Self> + Clone,
+        Self: 'a;
+}
+
+impl<S> Concat<str> for S
+where
+    S: Borrow<str>,
+{
+    type Output = String;
+
+    fn concat<'a, I>(items: I) -> String
+    where
+        I: Iterator<Item = &'a Self> + Clone,
+        Self: 'a,
+    {
+        let len = items.clone().map(|s| s.borrow().len()).sum();
+        let mut out = String::with_capacity(len);
+
+        for s in items {
+            out.push_str(s.borrow());
+        }
+
+        out
+    }
+}
+
+impl<S, V> Concat<V> for S
+where
+    S: Borrow<[V]>,
+    V: Clone,
+{
+    type Output = Vec<V>;
+
+    fn concat<'a, I>(items: I) -> Vec<V>
+    where
+        I: Iterator<Item = &'a Self> + Clone,
+        Self: 'a,
+    {
+        let len = items.clone().map(|s| s.borrow().len()).sum();
+        let mut out = Vec::with_capacity(len);
+
+        for s in items {
+            out.extend_from_slice(s.borrow());
+        }
+
+        out
+    }
+}
+
+impl<S> Join<&str> for S
+where
+    S: Borrow<str>,
+{
+    type Output = String;
+
+    fn join<'a, I>(items: I, sep: &str) -> String
+    where
+        I: Iterator<Item = &'a Self> + Clone,
+        Self: 'a,
+    {
+        let count = items.clone().count();
+        let len = items.clone().map(|s| s.borrow().len()).sum::<usize>()
+            + sep.len() * count.saturating_sub(1);
+        let mut out = String::with_capacity(len);
+
+        for (i, s) in items.enumerate() {
+            if i > 0 {
+                out.push_str(sep);
+            }
+            out.push_str(s.borrow());
+        }
+
+        out
+    }
+}
+
+impl<S, V> Join<&[V]> for S
+where
+    S: Borrow<[V]>,
+    V: Clone,
+{
+    type Output = Vec<V>;
+
+    fn join<'a, I>(items: I, sep: &[V]) -> Vec<V>
+    where
+        I: Iterator<Item = &'a Self> + Clone,
+        Self: 'a,
+    {
+        let count = items.clone().count();
+        let len = items.clone().map(|s| s.borrow().len()).sum::<usize>()
+            + sep.len() * count.saturating_sub(1);
+        let mut out = Vec::with_capacity(len);
+
+        for (i, s) in items.enumerate() {
+            if i > 0 {
+                out.extend_from_slice(sep);
+            }
+            out.extend_from_slice(s.borrow());
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ArrayDeque;
+
+    use alloc::{string::String, vec, vec::Vec};
+
+    #[test]
+    fn wrapped_strings() {
+        let mut deque: ArrayDeque<String, 3> = ArrayDeque::new();
+        deque.push_back(String::from("b")).unwrap();
+        deque.push_back(String::from("c")).unwrap();
+        deque.push_front(String::from("a")).unwrap();
+
+        assert_eq!(deque.concat(), "abc");
+        assert_eq!(deque.join(", "), "a, b, c");
+    }
+
+    #[test]
+    fn slices() {
+        let mut deque: ArrayDeque<Vec<u8>, 3> = ArrayDeque::new();
+        assert_eq!(deque.concat(), Vec::<u8>::new());
+        assert_eq!(deque.join(&[0][..]), Vec::<u8>::new());
+
+        deque.push_back(vec![1, 2]).unwrap();
+        deque.push_back(vec![]).unwrap();
+        deque.push_back(vec![3]).unwrap();
+
+        assert_eq!(deque.concat(), [1, 2, 3]);
+        assert_eq!(deque.join(&[0][..]), [1, 2, 0, 0, 3]);
+    }
+}
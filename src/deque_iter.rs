@@ -0,0 +1,215 @@
+//! Iterators over any [`Deque`].
+//!
+//! Each deque type has its own iterator types, such as
+//! [`array_deque::Iter`](crate::array_deque::Iter), which generic code cannot
+//! name without knowing the deque type. The iterators in this module are
+//! returned by the provided methods of the [`Deque`] trait, so functions
+//! generic over the trait can return them.
+//!
+//! # Example
+//!
+//! ```
+//! use core::iter::Rev;
+//!
+//! use holodeque::{deque_iter, ArrayDeque, Deque, SliceDeque};
+//!
+//! fn newest_first<D: Deque<u8>>(deque: &D) -> Rev<deque_iter::Iter<'_, u8>> {
+//!     deque.iter().rev()
+//! }
+//!
+//! let mut array: ArrayDeque<u8, 4> = ArrayDeque::new();
+//! array.extend([1, 2]);
+//! assert!(newest_first(&array).eq(&[2, 1]));
+//!
+//! let mut slice = [0; 2];
+//! let mut slice_deque = SliceDeque::new_in(&mut slice);
+//! slice_deque.push_back(3).unwrap();
+//! assert!(newest_first(&slice_deque).eq(&[3]));
+//! ```
+
+use core::{iter::Chain, marker::PhantomData, slice};
+
+use crate::Deque;
+
+/// An immutable iterator over any [`Deque`].
+///
+/// This struct is created by the [`iter`] method on [`Deque`].
+///
+/// [`iter`]: Deque::iter
+#[derive(Clone)]
+pub struct Iter<'a, T> {
+    inner: Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub(crate) fn new((front, back): (&'a [T], &'a [T])) -> Iter<'a, T> {
+        Iter {
+            inner: front.iter().chain(back),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+/// A mutable iterator over any [`Deque`].
+///
+/// This struct is created by the [`iter_mut`] method on [`Deque`].
+///
+/// [`iter_mut`]: Deque::iter_mut
+pub struct IterMut<'a, T> {
+    inner: Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>,
+}
+
+impl<'a, T> IterMut<'a, T> {
+    pub(crate) fn new((front, back): (&'a mut [T], &'a mut [T])) -> IterMut<'a, T> {
+        IterMut {
+            inner: front.iter_mut().chain(back),
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+/// A draining iterator which removes every element of any [`Deque`].
+///
+/// Elements are removed as they are yielded. When the iterator is dropped,
+/// any elements it did not yield are removed and dropped, leaving the deque
+/// empty.
+///
+/// This struct is created by the [`drain`] method on [`Deque`].
+///
+/// [`drain`]: Deque::drain
+pub struct Drain<'a, D, T>
+where
+    D: Deque<T> + ?Sized,
+{
+    deque: &'a mut D,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, D, T> Drain<'a, D, T>
+where
+    D: Deque<T> + ?Sized,
+{
+    pub(crate) fn new(deque: &'a mut D) -> Drain<'a, D, T> {
+        Drain {
+            deque,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, D, T> Iterator for Drain<'a, D, T>
+where
+    D: Deque<T> + ?Sized,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deque.pop_front()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len(), Some(self.deque.len()))
+    }
+}
+
+impl<'a, D, T> DoubleEndedIterator for Drain<'a, D, T>
+where
+    D: Deque<T> + ?Sized,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.deque.pop_back()
+    }
+}
+
+impl<'a, D, T> ExactSizeIterator for Drain<'a, D, T> where D: Deque<T> + ?Sized {}
+
+impl<'a, D, T> Drop for Drain<'a, D, T>
+where
+    D: Deque<T> + ?Sized,
+{
+    fn drop(&mut self) {
+        self.deque.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ArrayDeque, Deque, SliceDeque};
+
+    fn sum_and_drain<D: Deque<u32>>(deque: &mut D) -> u32 {
+        deque.iter_mut().for_each(|item| *item *= 10);
+        let sum = deque.iter().rev().sum();
+
+        let mut drain = deque.drain();
+        assert_eq!(drain.len(), 3);
+        assert_eq!(drain.next_back(), Some(30));
+        sum
+    }
+
+    #[test]
+    fn generic_iterators_span_wrap() {
+        let mut array: ArrayDeque<u32, 3> = ArrayDeque::new();
+        array.extend([0, 1, 2]);
+        array.pop_front();
+        array.push_back(3).unwrap();
+
+        assert_eq!(sum_and_drain(&mut array), 60);
+        assert!(array.is_empty());
+
+        let mut slice = [0; 4];
+        let mut slice_deque = SliceDeque::new_in(&mut slice);
+        slice_deque.push_back(2).unwrap();
+        slice_deque.push_back(3).unwrap();
+        slice_deque.push_front(1).unwrap();
+
+        assert_eq!(sum_and_drain(&mut slice_deque), 60);
+        assert!(slice_deque.is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+//! A pair of deques which alternate between being filled and drained.
+
+use core::fmt;
+
+use crate::ArrayDeque;
+
+/// Two deques with alternating roles: one is filled while the other is
+/// drained.
+///
+/// This is the usual arrangement for block processing, as with audio or DMA
+/// transfers: a producer appends to the filling side while a consumer works
+/// through the draining side, and once the consumer is done the roles are
+/// exchanged with [`swap`]. Swapping only flips an index, so it takes
+/// constant time regardless of `N`.
+///
+/// [`swap`]: DoubleBuffer::swap
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::double_buffer::DoubleBuffer;
+/// let mut blocks: DoubleBuffer<i16, 4> = DoubleBuffer::new();
+///
+/// blocks.filling_mut().try_extend([1, 2, 3, 4].iter().copied()).unwrap();
+/// assert!(blocks.swap_if_drained());
+///
+/// blocks.filling_mut().push_back(5).unwrap();
+/// let block: Vec<i16> = blocks.draining_mut().drain_front(4).unwrap().collect();
+/// assert_eq!(block, [1, 2, 3, 4]);
+///
+/// assert!(blocks.swap_if_drained());
+/// assert_eq!(blocks.draining().front(), Some(&5));
+/// ```
+#[derive(Clone)]
+pub struct DoubleBuffer<T, const N: usize>
+where
+    T: Default,
+{
+    buffers: [ArrayDeque<T, N>; 2],
+    /// The index of the filling side in `buffers`.
+    filling: usize,
+}
+
+impl<T, const N: usize> DoubleBuffer<T, N>
+where
+    T: Default,
+{
+    /// Constructs a new `DoubleBuffer<T, N>` with both sides empty.
+    pub fn new() -> Self {
+        DoubleBuffer {
+            buffers: [ArrayDeque::new(), ArrayDeque::new()],
+            filling: 0,
+        }
+    }
+
+    /// Returns the maximum number of elements each side may hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns a reference to the side being filled.
+    #[inline]
+    pub fn filling(&self) -> &ArrayDeque<T, N> {
+        &self.buffers[self.filling]
+    }
+
+    /// Returns a mutable reference to the side being filled.
+    #[inline]
+    pub fn filling_mut(&mut self) -> &mut ArrayDeque<T, N> {
+        &mut self.buffers[self.filling]
+    }
+
+    /// Returns a reference to the side being drained.
+    #[inline]
+    pub fn draining(&self) -> &ArrayDeque<T, N> {
+        &self.buffers[1 - self.filling]
+    }
+
+    /// Returns a mutable reference to the side being drained.
+    #[inline]
+    pub fn draining_mut(&mut self) -> &mut ArrayDeque<T, N> {
+        &mut self.buffers[1 - self.filling]
+    }
+
+    /// Returns mutable references to the filling and draining sides, in that
+    /// order.
+    #[inline]
+    pub fn split_mut(&mut self) -> (&mut ArrayDeque<T, N>, &mut ArrayDeque<T, N>) {
+        let [first, second] = &mut self.buffers;
+
+        if self.filling == 0 {
+            (first, second)
+        } else {
+            (second, first)
+        }
+    }
+
+    /// Exchanges the roles of the two sides.
+    ///
+    /// Any elements left on the draining side become part of the filling
+    /// side, ahead of anything pushed after the swap.
+    #[inline]
+    pub fn swap(&mut self) {
+        self.filling = 1 - self.filling;
+    }
+
+    /// Exchanges the roles of the two sides if the draining side is empty,
+    /// returning `true` if they were exchanged.
+    #[inline]
+    pub fn swap_if_drained(&mut self) -> bool {
+        let drained = self.draining().is_empty();
+        if drained {
+            self.swap();
+        }
+
+        drained
+    }
+
+    /// Removes all elements from both sides.
+    pub fn clear(&mut self) {
+        for buffer in &mut self.buffers {
+            buffer.clear();
+        }
+    }
+}
+
+impl<T, const N: usize> Default for DoubleBuffer<T, N>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for DoubleBuffer<T, N>
+where
+    T: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DoubleBuffer")
+            .field("filling", self.filling())
+            .field("draining", self.draining())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_exchanges_roles() {
+        let mut buf: DoubleBuffer<u8, 2> = DoubleBuffer::new();
+
+        buf.filling_mut().push_back(1).unwrap();
+        buf.swap();
+        buf.filling_mut().push_back(2).unwrap();
+        assert!(!buf.swap_if_drained());
+
+        let (filling, draining) = buf.split_mut();
+        assert_eq!(draining.pop_front(), Some(1));
+        filling.push_back(3).unwrap();
+
+        assert!(buf.swap_if_drained());
+        assert!(buf.draining().iter().copied().eq([2, 3]));
+        assert!(buf.filling().is_empty());
+    }
+}
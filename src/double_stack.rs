@@ -0,0 +1,298 @@
+//! Two stacks sharing one slice, growing toward each other.
+
+use core::{fmt, mem};
+
+use crate::CapacityError;
+
+/// Two stacks which grow from opposite ends of a borrowed slice toward the
+/// middle.
+///
+/// The low stack grows up from the start of the slice and the high stack
+/// grows down from its end. Each end is pushed and popped independently, and
+/// any free space between them is available to either, so two stacks whose
+/// combined size is bounded but whose individual sizes vary can share one
+/// buffer. A push fails with a [`CapacityError`] once the stacks meet.
+///
+/// This is the classic layout of a double-ended stack allocator, such as a
+/// scratch arena holding temporary data at one end and long-lived data at
+/// the other.
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::double_stack::DoubleStack;
+/// let mut slice = [0; 4];
+/// let mut stacks = DoubleStack::new_in(&mut slice);
+///
+/// stacks.push_low(1).unwrap();
+/// stacks.push_low(2).unwrap();
+/// stacks.push_high(9).unwrap();
+/// stacks.push_high(8).unwrap();
+///
+/// assert!(stacks.is_full());
+/// assert_eq!(stacks.push_low(3).unwrap_err().into_inner(), 3);
+///
+/// assert_eq!(stacks.pop_high(), Some(8));
+/// stacks.push_low(3).unwrap();
+///
+/// assert_eq!(stacks.low(), &[1, 2, 3]);
+/// assert_eq!(stacks.high(), &[9]);
+/// ```
+pub struct DoubleStack<'a, T>
+where
+    T: Default,
+{
+    items: &'a mut [T],
+    /// The number of elements in the low stack.
+    low: usize,
+    /// The number of elements in the high stack.
+    high: usize,
+}
+
+impl<'a, T> DoubleStack<'a, T>
+where
+    T: Default,
+{
+    /// Creates a pair of empty stacks backed by the provided slice.
+    ///
+    /// The elements in the slice are dropped and replaced with the default
+    /// value of `T`.
+    pub fn new_in(slice: &'a mut [T]) -> DoubleStack<'a, T> {
+        for item in slice.iter_mut() {
+            drop(mem::take(item));
+        }
+
+        DoubleStack {
+            items: slice,
+            low: 0,
+            high: 0,
+        }
+    }
+
+    /// Returns the combined number of elements both stacks may hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns the combined number of elements in both stacks.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.low + self.high
+    }
+
+    /// Returns `true` if both stacks are empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the stacks have met, so neither can grow.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Returns the number of free slots between the two stacks.
+    #[inline]
+    pub fn free(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// Returns the number of elements in the low stack.
+    #[inline]
+    pub fn low_len(&self) -> usize {
+        self.low
+    }
+
+    /// Returns the number of elements in the high stack.
+    #[inline]
+    pub fn high_len(&self) -> usize {
+        self.high
+    }
+
+    /// The index of the top element of the high stack, or of the end of the
+    /// slice if it is empty.
+    #[inline]
+    fn high_start(&self) -> usize {
+        self.capacity() - self.high
+    }
+
+    /// Pushes an element onto the low stack.
+    ///
+    /// If the stacks have met, the element is returned in a
+    /// [`CapacityError`].
+    pub fn push_low(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError { item });
+        }
+
+        self.items[self.low] = item;
+        self.low += 1;
+
+        Ok(())
+    }
+
+    /// Pushes an element onto the high stack.
+    ///
+    /// If the stacks have met, the element is returned in a
+    /// [`CapacityError`].
+    pub fn push_high(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError { item });
+        }
+
+        self.high += 1;
+        let top = self.high_start();
+        self.items[top] = item;
+
+        Ok(())
+    }
+
+    /// Removes and returns the top element of the low stack, or `None` if it
+    /// is empty.
+    pub fn pop_low(&mut self) -> Option<T> {
+        self.low = self.low.checked_sub(1)?;
+
+        Some(mem::take(&mut self.items[self.low]))
+    }
+
+    /// Removes and returns the top element of the high stack, or `None` if
+    /// it is empty.
+    pub fn pop_high(&mut self) -> Option<T> {
+        if self.high == 0 {
+            return None;
+        }
+
+        let top = self.high_start();
+        self.high -= 1;
+
+        Some(mem::take(&mut self.items[top]))
+    }
+
+    /// Returns a reference to the top element of the low stack, or `None` if
+    /// it is empty.
+    #[inline]
+    pub fn peek_low(&self) -> Option<&T> {
+        self.low().last()
+    }
+
+    /// Returns a mutable reference to the top element of the low stack, or
+    /// `None` if it is empty.
+    #[inline]
+    pub fn peek_low_mut(&mut self) -> Option<&mut T> {
+        self.low_mut().last_mut()
+    }
+
+    /// Returns a reference to the top element of the high stack, or `None`
+    /// if it is empty.
+    #[inline]
+    pub fn peek_high(&self) -> Option<&T> {
+        self.high().first()
+    }
+
+    /// Returns a mutable reference to the top element of the high stack, or
+    /// `None` if it is empty.
+    #[inline]
+    pub fn peek_high_mut(&mut self) -> Option<&mut T> {
+        self.high_mut().first_mut()
+    }
+
+    /// Returns the elements of the low stack, from bottom to top.
+    #[inline]
+    pub fn low(&self) -> &[T] {
+        &self.items[..self.low]
+    }
+
+    /// Returns the elements of the low stack mutably, from bottom to top.
+    #[inline]
+    pub fn low_mut(&mut self) -> &mut [T] {
+        &mut self.items[..self.low]
+    }
+
+    /// Returns the elements of the high stack, from top to bottom.
+    ///
+    /// The elements are in the order they are stored in the slice, so the
+    /// most recently pushed element comes first.
+    #[inline]
+    pub fn high(&self) -> &[T] {
+        &self.items[self.high_start()..]
+    }
+
+    /// Returns the elements of the high stack mutably, from top to bottom.
+    #[inline]
+    pub fn high_mut(&mut self) -> &mut [T] {
+        let start = self.high_start();
+
+        &mut self.items[start..]
+    }
+
+    /// Shortens the low stack to `len` elements, dropping the rest.
+    ///
+    /// If `len` is greater than the stack's current length, this has no
+    /// effect.
+    pub fn truncate_low(&mut self, len: usize) {
+        while self.low > len {
+            self.pop_low();
+        }
+    }
+
+    /// Shortens the high stack to `len` elements, dropping the rest.
+    ///
+    /// If `len` is greater than the stack's current length, this has no
+    /// effect.
+    pub fn truncate_high(&mut self, len: usize) {
+        while self.high > len {
+            self.pop_high();
+        }
+    }
+
+    /// Removes all elements from both stacks.
+    pub fn clear(&mut self) {
+        self.truncate_low(0);
+        self.truncate_high(0);
+    }
+}
+
+impl<'a, T> fmt::Debug for DoubleStack<'a, T>
+where
+    T: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DoubleStack")
+            .field("low", &self.low())
+            .field("high", &self.high())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stacks_share_free_space() {
+        let mut slice = [7; 3];
+        let mut stacks = DoubleStack::new_in(&mut slice);
+        assert_eq!(stacks.peek_low(), None);
+        assert_eq!(stacks.pop_high(), None);
+
+        for i in 1..=3 {
+            stacks.push_high(i).unwrap();
+        }
+        assert!(stacks.push_low(4).is_err());
+        assert_eq!(stacks.high(), &[3, 2, 1]);
+        assert_eq!(stacks.peek_high(), Some(&3));
+
+        stacks.truncate_high(1);
+        stacks.push_low(4).unwrap();
+        stacks.push_low(5).unwrap();
+        *stacks.peek_low_mut().unwrap() += 10;
+        assert_eq!((stacks.low(), stacks.high()), (&[4, 15][..], &[1][..]));
+
+        stacks.clear();
+        assert!(stacks.is_empty());
+        assert_eq!(stacks.free(), 3);
+        assert_eq!(slice, [0; 3]);
+    }
+}
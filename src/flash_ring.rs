@@ -0,0 +1,627 @@
+//! A ring of variable-length records stored in NOR flash.
+//!
+//! [`FlashRing`] keeps a log in a region of any flash implementing
+//! [`NorFlash`] from [`embedded-storage`]. Records are appended to the newest
+//! erase page; when it fills, the next page is erased and the oldest records
+//! are lost, as in an overwriting [`ArrayDeque`](crate::ArrayDeque).
+//!
+//! # Layout
+//!
+//! Each erase page begins with a header holding a `u32` sequence number,
+//! which increases by one for each page opened. The header is followed by
+//! records, each a `u16` length and the record bytes, padded to the flash's
+//! write size. All integers are little-endian. The ring is recovered by
+//! [`FlashRing::mount`] from the sequence numbers, so no other state needs
+//! to be kept.
+//!
+//! Records carry no checksum. A record torn by a power loss is detected only
+//! if its length was not fully written; records which must be verified should
+//! include their own checksum.
+//!
+//! [`embedded-storage`]: https://docs.rs/embedded-storage
+
+use core::{fmt, ops::Range};
+
+use embedded_storage::nor_flash::NorFlash;
+
+/// Flash is read and written through a buffer of this size.
+const SCRATCH_LEN: usize = 64;
+
+/// The sequence number of a page which has not been opened since it was
+/// erased.
+const ERASED_SEQ: u32 = u32::MAX;
+
+/// The length field of erased flash, which marks the end of a page's records.
+const ERASED_LEN: u16 = u16::MAX;
+
+const fn align_up(n: usize, align: usize) -> usize {
+    n.div_ceil(align) * align
+}
+
+/// An error that occurs when accessing a [`FlashRing`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum RingError<E> {
+    /// The flash returned an error.
+    Flash(E),
+
+    /// The record is longer than [`FlashRing::max_record_len`].
+    RecordTooLong(usize),
+
+    /// The buffer is shorter than the next record, which has the given
+    /// length.
+    BufferTooSmall(usize),
+}
+
+impl<E> fmt::Display for RingError<E>
+where
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RingError::Flash(e) => write!(f, "flash error: {:?}", e),
+            RingError::RecordTooLong(len) => {
+                write!(f, "record of {} bytes does not fit in an erase page", len)
+            }
+            RingError::BufferTooSmall(len) => {
+                write!(f, "buffer is too small for record of {} bytes", len)
+            }
+        }
+    }
+}
+
+impl<E> core::error::Error for RingError<E> where E: fmt::Debug {}
+
+/// The pages of the ring which are in use.
+#[derive(Clone, Copy, Debug)]
+struct Span {
+    /// The index of the page holding the oldest records.
+    oldest: usize,
+    /// The index of the page records are appended to.
+    newest: usize,
+    /// The sequence number of the newest page.
+    seq: u32,
+    /// The flash offset at which the next record is written.
+    pos: u32,
+}
+
+/// A log of byte records in a region of NOR flash, which erases its oldest
+/// page to make room for new records.
+///
+/// The region must span at least two erase pages, so that erasing the oldest
+/// page never discards every record. Before first use, the region must be
+/// erased with [`format`].
+///
+/// [`format`]: FlashRing::format
+///
+/// # Example
+///
+/// ```
+/// # use embedded_storage::nor_flash::{ErrorType, NorFlash, NorFlashErrorKind, ReadNorFlash};
+/// # struct Flash(Vec<u8>);
+/// # impl ErrorType for Flash {
+/// #     type Error = NorFlashErrorKind;
+/// # }
+/// # impl ReadNorFlash for Flash {
+/// #     const READ_SIZE: usize = 1;
+/// #     fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+/// #         let offset = offset as usize;
+/// #         bytes.copy_from_slice(&self.0[offset..offset + bytes.len()]);
+/// #         Ok(())
+/// #     }
+/// #     fn capacity(&self) -> usize {
+/// #         self.0.len()
+/// #     }
+/// # }
+/// # impl NorFlash for Flash {
+/// #     const WRITE_SIZE: usize = 4;
+/// #     const ERASE_SIZE: usize = 1024;
+/// #     fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+/// #         self.0[from as usize..to as usize].fill(0xff);
+/// #         Ok(())
+/// #     }
+/// #     fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+/// #         self.0[offset as usize..][..bytes.len()].copy_from_slice(bytes);
+/// #         Ok(())
+/// #     }
+/// # }
+/// # fn main() -> Result<(), holodeque::flash_ring::RingError<NorFlashErrorKind>> {
+/// # let flash = Flash(vec![0; 0x4000]);
+/// use holodeque::flash_ring::FlashRing;
+///
+/// // Any `NorFlash` implementation, such as a HAL's internal flash driver.
+/// let mut ring = FlashRing::mount(flash, 0x1000..0x3000)?;
+/// # ring.format()?;
+///
+/// ring.append(b"boot")?;
+/// ring.append(b"sensor online")?;
+///
+/// // After a reset, the records are found again.
+/// let mut ring = FlashRing::mount(ring.into_inner(), 0x1000..0x3000)?;
+/// let mut records = ring.records();
+/// let mut buf = [0; 64];
+/// assert_eq!(records.read_next(&mut buf)?, Some(&b"boot"[..]));
+/// assert_eq!(records.read_next(&mut buf)?, Some(&b"sensor online"[..]));
+/// assert_eq!(records.read_next(&mut buf)?, None);
+/// # Ok(())
+/// # }
+/// ```
+pub struct FlashRing<F>
+where
+    F: NorFlash,
+{
+    flash: F,
+    start: u32,
+    pages: usize,
+    span: Option<Span>,
+}
+
+impl<F> FlashRing<F>
+where
+    F: NorFlash,
+{
+    const PAGE_HEADER_LEN: usize = align_up(4, F::WRITE_SIZE);
+    const LEN_FIELD_LEN: usize = align_up(2, F::WRITE_SIZE);
+
+    /// Opens the ring stored in `region` of `flash`, finding its oldest and
+    /// newest records.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `region` is not aligned to the flash's erase size or spans
+    /// fewer than two erase pages, or if the flash's write size is larger
+    /// than 64 bytes or not a multiple of its read size.
+    pub fn mount(flash: F, region: Range<u32>) -> Result<Self, RingError<F::Error>> {
+        assert!(
+            F::WRITE_SIZE <= SCRATCH_LEN && SCRATCH_LEN % F::WRITE_SIZE == 0,
+            "unsupported flash write size"
+        );
+        assert!(
+            F::WRITE_SIZE % F::READ_SIZE == 0,
+            "flash write size must be a multiple of its read size"
+        );
+        assert!(
+            region.start as usize % F::ERASE_SIZE == 0 && region.end as usize % F::ERASE_SIZE == 0,
+            "flash ring region must be aligned to the erase size"
+        );
+
+        let pages = region.end.saturating_sub(region.start) as usize / F::ERASE_SIZE;
+        assert!(pages >= 2, "flash ring region must span at least two pages");
+
+        let mut ring = FlashRing {
+            flash,
+            start: region.start,
+            pages,
+            span: None,
+        };
+        ring.span = ring.scan().map_err(RingError::Flash)?;
+
+        Ok(ring)
+    }
+
+    /// Returns the length of the longest record which can be appended.
+    pub fn max_record_len(&self) -> usize {
+        (F::ERASE_SIZE.saturating_sub(Self::PAGE_HEADER_LEN + 2)).min(usize::from(ERASED_LEN) - 1)
+    }
+
+    /// Returns `true` if the ring holds no records.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.span.is_none()
+    }
+
+    /// Appends a record to the ring.
+    ///
+    /// If the newest page has no room for the record, the next page is
+    /// erased and the record is written there. If that page held the oldest
+    /// records, they are lost.
+    pub fn append(&mut self, record: &[u8]) -> Result<(), RingError<F::Error>> {
+        if record.len() > self.max_record_len() {
+            return Err(RingError::RecordTooLong(record.len()));
+        }
+
+        let total = Self::record_len(record.len());
+        let span = match self.span {
+            Some(span) if span.pos + total <= self.page_end(span.newest) => span,
+
+            Some(span) => {
+                let newest = (span.newest + 1) % self.pages;
+                let oldest = if newest == span.oldest {
+                    (span.oldest + 1) % self.pages
+                } else {
+                    span.oldest
+                };
+
+                self.open_page(newest, span.seq + 1)?.with_oldest(oldest)
+            }
+
+            None => self.open_page(0, 0)?,
+        };
+
+        self.span = Some(span);
+        self.write_record(span.pos, record)
+            .map_err(RingError::Flash)?;
+        self.span = Some(Span {
+            pos: span.pos + total,
+            ..span
+        });
+
+        Ok(())
+    }
+
+    /// Returns a reader over the records in the ring, from oldest to newest.
+    pub fn records(&mut self) -> Records<'_, F> {
+        let cursor = self
+            .span
+            .map(|span| (span.oldest, self.records_start(span.oldest)));
+
+        Records { ring: self, cursor }
+    }
+
+    /// Erases the whole region, removing every record.
+    pub fn format(&mut self) -> Result<(), RingError<F::Error>> {
+        let end = self.page_start(self.pages);
+
+        self.flash
+            .erase(self.start, end)
+            .map_err(RingError::Flash)?;
+        self.span = None;
+
+        Ok(())
+    }
+
+    /// Consumes the ring, returning the flash.
+    pub fn into_inner(self) -> F {
+        self.flash
+    }
+
+    fn record_len(len: usize) -> u32 {
+        align_up(2 + len, F::WRITE_SIZE) as u32
+    }
+
+    fn page_start(&self, page: usize) -> u32 {
+        self.start + (page * F::ERASE_SIZE) as u32
+    }
+
+    fn page_end(&self, page: usize) -> u32 {
+        self.page_start(page + 1)
+    }
+
+    fn records_start(&self, page: usize) -> u32 {
+        self.page_start(page) + Self::PAGE_HEADER_LEN as u32
+    }
+
+    /// Finds the pages in use from their sequence numbers.
+    fn scan(&mut self) -> Result<Option<Span>, F::Error> {
+        let mut oldest: Option<(u32, usize)> = None;
+        let mut newest: Option<(u32, usize)> = None;
+
+        for page in 0..self.pages {
+            let mut header = [0; SCRATCH_LEN];
+            let header = &mut header[..Self::PAGE_HEADER_LEN];
+            self.flash.read(self.page_start(page), header)?;
+
+            let seq = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+            if seq == ERASED_SEQ {
+                continue;
+            }
+
+            if oldest.map_or(true, |(oldest_seq, _)| seq < oldest_seq) {
+                oldest = Some((seq, page));
+            }
+            if newest.map_or(true, |(newest_seq, _)| seq > newest_seq) {
+                newest = Some((seq, page));
+            }
+        }
+
+        let (oldest, (seq, newest)) = match (oldest, newest) {
+            (Some((_, oldest)), Some(newest)) => (oldest, newest),
+            _ => return Ok(None),
+        };
+
+        let mut pos = self.records_start(newest);
+        while let Some(len) = self.read_len(pos, self.page_end(newest))? {
+            pos += Self::record_len(len);
+        }
+
+        Ok(Some(Span {
+            oldest,
+            newest,
+            seq,
+            pos,
+        }))
+    }
+
+    /// Erases `page` and writes its header, returning a span with `page` as
+    /// its only page.
+    fn open_page(&mut self, page: usize, seq: u32) -> Result<Span, RingError<F::Error>> {
+        let start = self.page_start(page);
+        let mut header = [0xff; SCRATCH_LEN];
+        header[..4].copy_from_slice(&seq.to_le_bytes());
+
+        self.flash
+            .erase(start, self.page_end(page))
+            .and_then(|()| self.flash.write(start, &header[..Self::PAGE_HEADER_LEN]))
+            .map_err(RingError::Flash)?;
+
+        Ok(Span {
+            oldest: page,
+            newest: page,
+            seq,
+            pos: self.records_start(page),
+        })
+    }
+
+    /// Reads the length of the record at `pos`, or `None` if there are no
+    /// more records before `page_end`.
+    fn read_len(&mut self, pos: u32, page_end: u32) -> Result<Option<usize>, F::Error> {
+        if pos + Self::LEN_FIELD_LEN as u32 > page_end {
+            return Ok(None);
+        }
+
+        let mut field = [0; SCRATCH_LEN];
+        let field = &mut field[..Self::LEN_FIELD_LEN];
+        self.flash.read(pos, field)?;
+
+        let len = u16::from_le_bytes([field[0], field[1]]);
+        // A length which runs past the page can only come from a torn write.
+        if len == ERASED_LEN || pos + Self::record_len(usize::from(len)) > page_end {
+            return Ok(None);
+        }
+
+        Ok(Some(usize::from(len)))
+    }
+
+    fn write_record(&mut self, pos: u32, record: &[u8]) -> Result<(), F::Error> {
+        let mut scratch = [0xff; SCRATCH_LEN];
+        scratch[..2].copy_from_slice(&(record.len() as u16).to_le_bytes());
+
+        let mut used = 2;
+        let mut offset = pos;
+        let mut rest = record;
+
+        loop {
+            let n = rest.len().min(SCRATCH_LEN - used);
+            scratch[used..used + n].copy_from_slice(&rest[..n]);
+            used += n;
+            rest = &rest[n..];
+
+            if rest.is_empty() {
+                break;
+            }
+
+            self.flash.write(offset, &scratch)?;
+            offset += SCRATCH_LEN as u32;
+            used = 0;
+        }
+
+        let padded = align_up(used, F::WRITE_SIZE);
+        scratch[used..padded].fill(0xff);
+
+        self.flash.write(offset, &scratch[..padded])
+    }
+
+    fn read_record(&mut self, pos: u32, out: &mut [u8]) -> Result<(), F::Error> {
+        let mut scratch = [0; SCRATCH_LEN];
+        let mut offset = pos;
+        let mut remaining = Self::record_len(out.len()) as usize;
+        // The length field precedes the record bytes.
+        let mut skip = 2;
+        let mut copied = 0;
+
+        while copied < out.len() {
+            let n = remaining.min(SCRATCH_LEN);
+            self.flash.read(offset, &mut scratch[..n])?;
+
+            let available = &scratch[skip..n];
+            let take = available.len().min(out.len() - copied);
+            out[copied..copied + take].copy_from_slice(&available[..take]);
+
+            copied += take;
+            skip = 0;
+            offset += n as u32;
+            remaining -= n;
+        }
+
+        Ok(())
+    }
+}
+
+impl Span {
+    fn with_oldest(self, oldest: usize) -> Span {
+        Span { oldest, ..self }
+    }
+}
+
+impl<F> fmt::Debug for FlashRing<F>
+where
+    F: NorFlash,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlashRing")
+            .field("start", &self.start)
+            .field("pages", &self.pages)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A reader over the records of a [`FlashRing`], from oldest to newest.
+///
+/// This struct is created by the [`records`] method on [`FlashRing`].
+///
+/// [`records`]: FlashRing::records
+pub struct Records<'a, F>
+where
+    F: NorFlash,
+{
+    ring: &'a mut FlashRing<F>,
+    /// The page and flash offset of the next record, or `None` once every
+    /// record has been read.
+    cursor: Option<(usize, u32)>,
+}
+
+impl<'a, F> Records<'a, F>
+where
+    F: NorFlash,
+{
+    /// Reads the next record into `buf`, returning the part of `buf` it
+    /// occupies, or `None` if every record has been read.
+    ///
+    /// If `buf` is too short for the record, an error holding the record's
+    /// length is returned and the reader does not advance.
+    pub fn read_next<'b>(
+        &mut self,
+        buf: &'b mut [u8],
+    ) -> Result<Option<&'b [u8]>, RingError<F::Error>> {
+        while let Some((page, pos)) = self.cursor {
+            let page_end = self.ring.page_end(page);
+
+            if let Some(len) = self
+                .ring
+                .read_len(pos, page_end)
+                .map_err(RingError::Flash)?
+            {
+                let record = buf.get_mut(..len).ok_or(RingError::BufferTooSmall(len))?;
+                self.ring
+                    .read_record(pos, record)
+                    .map_err(RingError::Flash)?;
+                self.cursor = Some((page, pos + FlashRing::<F>::record_len(len)));
+
+                return Ok(Some(record));
+            }
+
+            // A cursor is only set while the ring has records.
+            let newest = self.ring.span.map_or(page, |span| span.newest);
+            self.cursor = if page == newest {
+                None
+            } else {
+                let next = (page + 1) % self.ring.pages;
+                Some((next, self.ring.records_start(next)))
+            };
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    use embedded_storage::nor_flash::{
+        check_erase, check_read, check_write, ErrorType, NorFlashErrorKind, ReadNorFlash,
+    };
+
+    /// Flash in memory which, like real NOR flash, can only clear bits
+    /// between erases.
+    struct RamFlash([u8; 512]);
+
+    impl ErrorType for RamFlash {
+        type Error = NorFlashErrorKind;
+    }
+
+    impl ReadNorFlash for RamFlash {
+        const READ_SIZE: usize = 1;
+
+        fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+            check_read(self, offset, bytes.len())?;
+            let offset = offset as usize;
+            bytes.copy_from_slice(&self.0[offset..offset + bytes.len()]);
+            Ok(())
+        }
+
+        fn capacity(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    impl NorFlash for RamFlash {
+        const WRITE_SIZE: usize = 4;
+        const ERASE_SIZE: usize = 128;
+
+        fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+            check_erase(self, from, to)?;
+            self.0[from as usize..to as usize].fill(0xff);
+            Ok(())
+        }
+
+        fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+            check_write(self, offset, bytes.len())?;
+            for (cell, byte) in self.0[offset as usize..].iter_mut().zip(bytes) {
+                assert_eq!(*cell, 0xff, "write to unerased flash");
+                *cell = *byte;
+            }
+            Ok(())
+        }
+    }
+
+    fn read_all(ring: &mut FlashRing<RamFlash>) -> Vec<Vec<u8>> {
+        let mut records = ring.records();
+        let mut buf = [0; 128];
+        let mut all = Vec::new();
+
+        while let Some(record) = records.read_next(&mut buf).unwrap() {
+            all.push(record.to_vec());
+        }
+
+        all
+    }
+
+    fn formatted(region: Range<u32>) -> FlashRing<RamFlash> {
+        let mut ring = FlashRing::mount(RamFlash([0; 512]), region).unwrap();
+        ring.format().unwrap();
+        ring
+    }
+
+    #[test]
+    fn wrap_erases_oldest_page() {
+        let mut ring = formatted(0..512);
+
+        // Each record takes 12 bytes, so ten fit in a page after its header.
+        for i in 0..60 {
+            ring.append(&[i; 10]).unwrap();
+        }
+
+        let expected: Vec<Vec<u8>> = (20..60).map(|i| [i; 10].to_vec()).collect();
+        assert_eq!(read_all(&mut ring), expected);
+
+        // Remounting finds the same records and the same write position.
+        let mut ring = FlashRing::mount(ring.into_inner(), 0..512).unwrap();
+        assert_eq!(read_all(&mut ring), expected);
+
+        ring.append(&[60; 10]).unwrap();
+        let expected: Vec<Vec<u8>> = (30..61).map(|i| [i; 10].to_vec()).collect();
+        assert_eq!(read_all(&mut ring), expected);
+    }
+
+    #[test]
+    fn records_span_scratch_buffer() {
+        let mut ring = formatted(128..384);
+        let long: Vec<u8> = (0..ring.max_record_len() as u8).collect();
+
+        ring.append(&long).unwrap();
+        ring.append(b"").unwrap();
+        ring.append(b"abc").unwrap();
+        assert_eq!(read_all(&mut ring), [long, Vec::new(), b"abc".to_vec()]);
+    }
+
+    #[test]
+    fn rejects_long_records_and_short_buffers() {
+        let mut ring = formatted(0..256);
+        assert!(ring.is_empty());
+
+        let too_long = [0; 123];
+        assert_eq!(ring.append(&too_long), Err(RingError::RecordTooLong(123)));
+
+        ring.append(b"hello").unwrap();
+        let mut records = ring.records();
+        assert_eq!(
+            records.read_next(&mut [0; 4]),
+            Err(RingError::BufferTooSmall(5))
+        );
+        assert_eq!(records.read_next(&mut [0; 5]), Ok(Some(&b"hello"[..])));
+        assert_eq!(records.read_next(&mut [0; 5]), Ok(None));
+    }
+}
@@ -0,0 +1,167 @@
+//! Formatting adaptors for deques.
+
+use core::fmt::{self, Write};
+
+use crate::meta::MetaLayout;
+
+/// A [`Debug`] adaptor which exposes the physical layout of a deque.
+///
+/// Unlike the `Debug` implementations of the deques themselves, which print
+/// only the elements in logical order, this prints the layout metadata along
+/// with the entire backing storage, including unused slots.
+///
+/// This struct is created by the `debug_internals` method on [`ArrayDeque`]
+/// and [`SliceDeque`].
+///
+/// [`Debug`]: core::fmt::Debug
+/// [`ArrayDeque`]: crate::ArrayDeque
+/// [`SliceDeque`]: crate::SliceDeque
+pub struct DebugInternals<'a, T> {
+    layout: MetaLayout,
+    items: &'a [T],
+}
+
+impl<'a, T> DebugInternals<'a, T> {
+    pub(crate) fn new(layout: MetaLayout, items: &'a [T]) -> DebugInternals<'a, T> {
+        DebugInternals { layout, items }
+    }
+}
+
+impl<'a, T> fmt::Debug for DebugInternals<'a, T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebugInternals")
+            .field("layout", &self.layout)
+            .field("items", &self.items)
+            .finish()
+    }
+}
+
+/// A [`Display`] adaptor which formats the elements of a deque in order,
+/// separated by a string.
+///
+/// Each element is formatted using its own `Display` implementation, so
+/// formatting options such as width and precision are applied to every
+/// element. No allocation is performed.
+///
+/// This struct is created by the `display_separated` method on
+/// [`ArrayDeque`] and [`SliceDeque`].
+///
+/// [`Display`]: core::fmt::Display
+/// [`ArrayDeque`]: crate::ArrayDeque
+/// [`SliceDeque`]: crate::SliceDeque
+pub struct DisplaySeparated<'a, T> {
+    front: &'a [T],
+    back: &'a [T],
+    separator: &'a str,
+}
+
+impl<'a, T> DisplaySeparated<'a, T> {
+    pub(crate) fn new(
+        (front, back): (&'a [T], &'a [T]),
+        separator: &'a str,
+    ) -> DisplaySeparated<'a, T> {
+        DisplaySeparated {
+            front,
+            back,
+            separator,
+        }
+    }
+}
+
+impl<'a, T> fmt::Display for DisplaySeparated<'a, T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, item) in self.front.iter().chain(self.back.iter()).enumerate() {
+            if i > 0 {
+                f.write_str(self.separator)?;
+            }
+
+            fmt::Display::fmt(item, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A [`Display`] adaptor which formats a byte deque as a hex dump.
+///
+/// Each line shows the offset of its first byte from the front of the deque,
+/// up to 16 bytes in hexadecimal, and the same bytes as ASCII, with
+/// non-printable bytes shown as `.`. The layout matches that of `hexdump -C`,
+/// and lines run across the wrap boundary of the deque as if its elements
+/// were contiguous. An empty deque produces no output.
+///
+/// This struct is created by the `hex_dump` method on [`ArrayDeque`] and
+/// [`SliceDeque`].
+///
+/// [`Display`]: core::fmt::Display
+/// [`ArrayDeque`]: crate::ArrayDeque
+/// [`SliceDeque`]: crate::SliceDeque
+pub struct HexDump<'a> {
+    front: &'a [u8],
+    back: &'a [u8],
+}
+
+impl<'a> HexDump<'a> {
+    /// The number of bytes shown on each line.
+    const LINE_LEN: usize = 16;
+
+    pub(crate) fn new((front, back): (&'a [u8], &'a [u8])) -> HexDump<'a> {
+        HexDump { front, back }
+    }
+}
+
+impl<'a> fmt::Display for HexDump<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bytes = self.front.iter().chain(self.back.iter()).copied();
+        let mut offset = 0;
+
+        loop {
+            let mut line = [0; Self::LINE_LEN];
+            let mut len = 0;
+
+            // The line comes first in the zip, so that no byte is taken from
+            // the deque once it is full.
+            for (slot, byte) in line.iter_mut().zip(&mut bytes) {
+                *slot = byte;
+                len += 1;
+            }
+
+            if len == 0 {
+                return Ok(());
+            }
+
+            if offset > 0 {
+                f.write_char('\n')?;
+            }
+
+            write!(f, "{:08x} ", offset)?;
+
+            for (i, byte) in line.iter().enumerate() {
+                if i % 8 == 0 {
+                    f.write_char(' ')?;
+                }
+
+                if i < len {
+                    write!(f, "{:02x} ", byte)?;
+                } else {
+                    f.write_str("   ")?;
+                }
+            }
+
+            f.write_str(" |")?;
+            for &byte in &line[..len] {
+                let printable = byte.is_ascii_graphic() || byte == b' ';
+                f.write_char(if printable { byte as char } else { '.' })?;
+            }
+            f.write_char('|')?;
+
+            offset += len;
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! A queue of length-prefixed byte frames with fixed capacity, backed by an
+//! array.
+
+use core::fmt;
+
+use crate::{ArrayDeque, CapacityError};
+
+/// The maximum length of an encoded frame header.
+const MAX_HEADER_LEN: usize = (usize::BITS as usize).div_ceil(7);
+
+/// Encodes `len` as an unsigned LEB128 varint, returning the buffer and the
+/// number of bytes used.
+fn encode_header(mut len: usize) -> ([u8; MAX_HEADER_LEN], usize) {
+    let mut header = [0; MAX_HEADER_LEN];
+    let mut used = 0;
+
+    loop {
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+
+        if len == 0 {
+            header[used] = byte;
+            return (header, used + 1);
+        }
+
+        header[used] = byte | 0x80;
+        used += 1;
+    }
+}
+
+/// A queue of variable-length byte frames with fixed capacity, backed by an
+/// array.
+///
+/// Frames are stored back to back in a ring of `N` bytes, each preceded by
+/// its length encoded as a varint, so a frame of fewer than 128 bytes costs
+/// one extra byte. Frames are pushed and popped whole; a frame which does not
+/// fit is rejected without modifying the queue.
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::frame_deque::FrameDeque;
+/// let mut packets: FrameDeque<32> = FrameDeque::new();
+///
+/// packets.push_frame(b"hello").unwrap();
+/// packets.push_frame(b"").unwrap();
+/// packets.push_frame(b"world!").unwrap();
+/// assert_eq!(packets.len(), 3);
+///
+/// let mut buf = [0; 16];
+/// assert_eq!(packets.peek_frame_len(), Some(5));
+/// let n = packets.pop_frame_into(&mut buf).unwrap().unwrap();
+/// assert_eq!(&buf[..n], b"hello");
+///
+/// assert_eq!(packets.pop_frame_into(&mut buf), Ok(Some(0)));
+/// assert_eq!(packets.pop_frame_into(&mut buf[..2]).unwrap_err().frame_len(), 6);
+/// ```
+#[derive(Clone, Default)]
+pub struct FrameDeque<const N: usize> {
+    bytes: ArrayDeque<u8, N>,
+    frames: usize,
+}
+
+impl<const N: usize> FrameDeque<N> {
+    /// Constructs a new, empty `FrameDeque<N>`.
+    pub fn new() -> Self {
+        FrameDeque {
+            bytes: ArrayDeque::new_copied(),
+            frames: 0,
+        }
+    }
+
+    /// Returns the maximum number of bytes the queue may hold, including
+    /// frame headers.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of frames in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.frames
+    }
+
+    /// Returns `true` if the queue contains no frames.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frames == 0
+    }
+
+    /// Returns the number of bytes used by the queue, including frame
+    /// headers.
+    #[inline]
+    pub fn bytes_used(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Decodes the header of the first frame, returning the frame's length
+    /// and the header's length.
+    fn front_header(&self) -> Option<(usize, usize)> {
+        let mut len = 0;
+
+        for (i, byte) in self.bytes.iter().copied().enumerate().take(MAX_HEADER_LEN) {
+            len |= usize::from(byte & 0x7f) << (7 * i);
+
+            if byte & 0x80 == 0 {
+                return Some((len, i + 1));
+            }
+        }
+
+        None
+    }
+
+    /// Appends a frame to the back of the queue.
+    ///
+    /// If the queue does not have room for the frame and its header, nothing
+    /// is appended and an `Err` containing `frame` is returned.
+    pub fn push_frame<'f>(&mut self, frame: &'f [u8]) -> Result<(), CapacityError<&'f [u8]>> {
+        let (header, header_len) = encode_header(frame.len());
+
+        if header_len + frame.len() > N - self.bytes.len() {
+            return Err(CapacityError { item: frame });
+        }
+
+        self.bytes
+            .try_extend(header[..header_len].iter().chain(frame).copied())
+            .unwrap();
+        self.frames += 1;
+
+        Ok(())
+    }
+
+    /// Returns the length of the first frame, or `None` if the queue is
+    /// empty.
+    pub fn peek_frame_len(&self) -> Option<usize> {
+        self.front_header().map(|(len, _)| len)
+    }
+
+    /// Removes the first frame from the queue and copies it to the start of
+    /// `buf`, returning its length.
+    ///
+    /// If the queue is empty, `Ok(None)` is returned. If `buf` is too short
+    /// to hold the frame, the frame is left in the queue and an `Err` is
+    /// returned containing its length.
+    pub fn pop_frame_into(&mut self, buf: &mut [u8]) -> Result<Option<usize>, BufferTooSmall> {
+        let (len, header_len) = match self.front_header() {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+
+        if buf.len() < len {
+            return Err(BufferTooSmall { frame_len: len });
+        }
+
+        self.bytes.drain_front(header_len);
+        self.bytes.linearize_into(&mut buf[..len]);
+        self.frames -= 1;
+
+        Ok(Some(len))
+    }
+
+    /// Removes the first frame from the queue without copying it, returning
+    /// its length, or `None` if the queue is empty.
+    pub fn discard_frame(&mut self) -> Option<usize> {
+        let (len, header_len) = self.front_header()?;
+
+        self.bytes.drain_front(header_len + len);
+        self.frames -= 1;
+
+        Some(len)
+    }
+
+    /// Removes all frames from the queue.
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+        self.frames = 0;
+    }
+}
+
+impl<const N: usize> fmt::Debug for FrameDeque<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameDeque")
+            .field("frames", &self.frames)
+            .field("bytes_used", &self.bytes.len())
+            .finish()
+    }
+}
+
+/// An error that occurs when popping a frame into a buffer which is too short
+/// to hold it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferTooSmall {
+    frame_len: usize,
+}
+
+impl BufferTooSmall {
+    /// Returns the length of the frame which did not fit.
+    pub fn frame_len(&self) -> usize {
+        self.frame_len
+    }
+}
+
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "buffer too small for frame of length {}", self.frame_len)
+    }
+}
+
+impl core::error::Error for BufferTooSmall {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_wrap_around() {
+        let mut frames: FrameDeque<12> = FrameDeque::new();
+        let mut buf = [0; 12];
+
+        for round in 0..10u8 {
+            let frame = [round; 5];
+            frames.push_frame(&frame).unwrap();
+            frames.push_frame(&frame[..round as usize % 4]).unwrap();
+
+            assert_eq!(frames.pop_frame_into(&mut buf), Ok(Some(5)));
+            assert_eq!(buf[..5], frame);
+            assert_eq!(frames.discard_frame(), Some(round as usize % 4));
+            assert!(frames.is_empty());
+            assert_eq!(frames.bytes_used(), 0);
+        }
+    }
+
+    #[test]
+    fn rejects_frame_without_room_for_header() {
+        let mut frames: FrameDeque<8> = FrameDeque::new();
+
+        frames.push_frame(&[1; 3]).unwrap();
+        assert_eq!(
+            frames.push_frame(&[2; 4]).unwrap_err().into_inner(),
+            &[2; 4]
+        );
+        frames.push_frame(&[2; 3]).unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames.bytes_used(), 8);
+    }
+
+    #[test]
+    fn long_frames_use_multibyte_headers() {
+        let mut frames: FrameDeque<512> = FrameDeque::new();
+        let frame = [7; 300];
+        let mut buf = [0; 300];
+
+        frames.push_frame(&frame).unwrap();
+        assert_eq!(frames.bytes_used(), 302);
+        assert_eq!(frames.peek_frame_len(), Some(300));
+        assert_eq!(frames.pop_frame_into(&mut buf), Ok(Some(300)));
+        assert_eq!(buf[..], frame[..]);
+    }
+}
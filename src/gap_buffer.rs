@@ -0,0 +1,733 @@
+//! Gap buffers with fixed capacity, backed by an array or a slice.
+//!
+//! A gap buffer stores a sequence of elements along with a cursor position.
+//! The elements before the cursor are kept at the start of the backing
+//! storage and the elements after the cursor are kept at the end, with the
+//! unused space in between. Inserting and deleting at the cursor is O(1), and
+//! moving the cursor is O(n) in the distance moved.
+//!
+//! This is exactly the physical layout of a wrapped deque, so the gap buffers
+//! in this module are thin wrappers around [`ArrayDeque`] and [`SliceDeque`]:
+//! the elements before the cursor are the back of the deque and the elements
+//! after the cursor are the front.
+
+use core::fmt;
+
+use crate::{ArrayDeque, BaseDeque, CapacityError, SliceDeque};
+
+pub(crate) trait BaseGapBuffer<T>
+where
+    T: Default,
+{
+    type Deque: BaseDeque<T>;
+
+    fn deque(&self) -> &Self::Deque;
+
+    fn deque_mut(&mut self) -> &mut Self::Deque;
+
+    fn cursor(&self) -> usize;
+
+    fn cursor_mut(&mut self) -> &mut usize;
+
+    fn len(&self) -> usize {
+        self.deque().len()
+    }
+
+    fn as_slices(&self) -> (&[T], &[T]) {
+        let cursor = self.cursor();
+        let after_start = self.deque().capacity() - (self.len() - cursor);
+        let items = self.deque().items();
+
+        (&items[..cursor], &items[after_start..])
+    }
+
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let cursor = self.cursor();
+        let after_start = self.deque().capacity() - (self.len() - cursor);
+        let (before, rest) = self.deque_mut().items_mut().split_at_mut(cursor);
+
+        (before, &mut rest[after_start - cursor..])
+    }
+
+    fn insert(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.deque_mut().push_back(item)?;
+        *self.cursor_mut() += 1;
+
+        Ok(())
+    }
+
+    fn insert_after(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.deque_mut().push_front(item)
+    }
+
+    fn remove_before(&mut self) -> Option<T> {
+        if self.cursor() == 0 {
+            return None;
+        }
+
+        *self.cursor_mut() -= 1;
+        self.deque_mut().pop_back()
+    }
+
+    fn remove_after(&mut self) -> Option<T> {
+        if self.cursor() == self.len() {
+            return None;
+        }
+
+        self.deque_mut().pop_front()
+    }
+
+    fn move_left(&mut self) -> bool {
+        match self.remove_before() {
+            Some(item) => {
+                // The slot freed by `remove_before` is always available.
+                self.deque_mut().push_front(item).ok().unwrap();
+                true
+            }
+
+            None => false,
+        }
+    }
+
+    fn move_right(&mut self) -> bool {
+        match self.remove_after() {
+            Some(item) => {
+                self.deque_mut().push_back(item).ok().unwrap();
+                *self.cursor_mut() += 1;
+                true
+            }
+
+            None => false,
+        }
+    }
+
+    fn set_cursor(&mut self, position: usize) {
+        assert!(
+            position <= self.len(),
+            "cursor position (is {}) should be <= len (is {})",
+            position,
+            self.len(),
+        );
+
+        while self.cursor() > position {
+            self.move_left();
+        }
+
+        while self.cursor() < position {
+            self.move_right();
+        }
+    }
+
+    fn clear(&mut self) {
+        self.deque_mut().clear();
+        *self.cursor_mut() = 0;
+    }
+}
+
+/// A gap buffer with fixed capacity, backed by an array.
+///
+/// The capacity of the buffer is determined by the generic parameter `N`.
+#[derive(Clone)]
+pub struct GapBuffer<T, const N: usize>
+where
+    T: Default,
+{
+    deque: ArrayDeque<T, N>,
+    cursor: usize,
+}
+
+impl<T, const N: usize> BaseGapBuffer<T> for GapBuffer<T, N>
+where
+    T: Default,
+{
+    type Deque = ArrayDeque<T, N>;
+
+    #[inline(always)]
+    fn deque(&self) -> &Self::Deque {
+        &self.deque
+    }
+
+    #[inline(always)]
+    fn deque_mut(&mut self) -> &mut Self::Deque {
+        &mut self.deque
+    }
+
+    #[inline(always)]
+    fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    #[inline(always)]
+    fn cursor_mut(&mut self) -> &mut usize {
+        &mut self.cursor
+    }
+}
+
+impl<T, const N: usize> Default for GapBuffer<T, N>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        GapBuffer::new()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for GapBuffer<T, N>
+where
+    T: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (before, after) = BaseGapBuffer::as_slices(self);
+
+        f.debug_struct("GapBuffer")
+            .field("before", &before)
+            .field("after", &after)
+            .finish()
+    }
+}
+
+impl<T, const N: usize> GapBuffer<T, N>
+where
+    T: Default,
+{
+    /// Constructs a new, empty `GapBuffer<T, N>` with the cursor at position
+    /// 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::GapBuffer;
+    /// let buf: GapBuffer<char, 8> = GapBuffer::new();
+    ///
+    /// assert!(buf.is_empty());
+    /// assert_eq!(buf.cursor(), 0);
+    /// ```
+    pub fn new() -> Self {
+        GapBuffer {
+            deque: ArrayDeque::new(),
+            cursor: 0,
+        }
+    }
+
+    /// Returns the maximum number of elements the buffer may hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of elements in the buffer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        BaseGapBuffer::len(self)
+    }
+
+    /// Returns `true` if the buffer contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.deque.is_empty()
+    }
+
+    /// Returns `true` if the buffer is at capacity.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.deque.is_full()
+    }
+
+    /// Returns the position of the cursor, i.e. the number of elements before
+    /// it.
+    #[inline]
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns a pair of slices which contain, in order, the elements before
+    /// and after the cursor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, GapBuffer};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut buf: GapBuffer<char, 8> = GapBuffer::new();
+    ///
+    /// buf.insert('a')?;
+    /// buf.insert('c')?;
+    /// buf.move_left();
+    /// buf.insert('b')?;
+    ///
+    /// assert_eq!(buf.as_slices(), (&['a', 'b'][..], &['c'][..]));
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        BaseGapBuffer::as_slices(self)
+    }
+
+    /// Returns a pair of mutable slices which contain, in order, the elements
+    /// before and after the cursor.
+    #[inline]
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        BaseGapBuffer::as_mut_slices(self)
+    }
+
+    /// Inserts an element before the cursor, advancing the cursor past it.
+    ///
+    /// If the buffer is full, an `Err` containing `item` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::GapBuffer;
+    /// let mut buf: GapBuffer<u32, 2> = GapBuffer::new();
+    ///
+    /// buf.insert(1).unwrap();
+    /// buf.insert(2).unwrap();
+    /// assert!(buf.insert(3).is_err());
+    /// assert_eq!(buf.cursor(), 2);
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        BaseGapBuffer::insert(self, item)
+    }
+
+    /// Inserts an element after the cursor, leaving the cursor in place.
+    ///
+    /// If the buffer is full, an `Err` containing `item` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::GapBuffer;
+    /// let mut buf: GapBuffer<u32, 4> = GapBuffer::new();
+    ///
+    /// buf.insert_after(2).unwrap();
+    /// buf.insert_after(1).unwrap();
+    /// assert_eq!(buf.cursor(), 0);
+    /// assert_eq!(buf.as_slices(), (&[][..], &[1, 2][..]));
+    /// ```
+    #[inline]
+    pub fn insert_after(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        BaseGapBuffer::insert_after(self, item)
+    }
+
+    /// Removes and returns the element before the cursor.
+    ///
+    /// If the cursor is at the start of the buffer, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::GapBuffer;
+    /// let mut buf: GapBuffer<u32, 4> = GapBuffer::new();
+    ///
+    /// buf.insert(1).unwrap();
+    /// assert_eq!(buf.remove_before(), Some(1));
+    /// assert_eq!(buf.remove_before(), None);
+    /// ```
+    #[inline]
+    pub fn remove_before(&mut self) -> Option<T> {
+        BaseGapBuffer::remove_before(self)
+    }
+
+    /// Removes and returns the element after the cursor.
+    ///
+    /// If the cursor is at the end of the buffer, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::GapBuffer;
+    /// let mut buf: GapBuffer<u32, 4> = GapBuffer::new();
+    ///
+    /// buf.insert_after(1).unwrap();
+    /// assert_eq!(buf.remove_after(), Some(1));
+    /// assert_eq!(buf.remove_after(), None);
+    /// ```
+    #[inline]
+    pub fn remove_after(&mut self) -> Option<T> {
+        BaseGapBuffer::remove_after(self)
+    }
+
+    /// Moves the cursor one element towards the start of the buffer.
+    ///
+    /// Returns `false` if the cursor was already at the start.
+    #[inline]
+    pub fn move_left(&mut self) -> bool {
+        BaseGapBuffer::move_left(self)
+    }
+
+    /// Moves the cursor one element towards the end of the buffer.
+    ///
+    /// Returns `false` if the cursor was already at the end.
+    #[inline]
+    pub fn move_right(&mut self) -> bool {
+        BaseGapBuffer::move_right(self)
+    }
+
+    /// Moves the cursor to `position`.
+    ///
+    /// This takes time proportional to the distance moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is greater than the length of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::GapBuffer;
+    /// let mut buf: GapBuffer<u32, 4> = GapBuffer::new();
+    ///
+    /// buf.insert(1).unwrap();
+    /// buf.insert(3).unwrap();
+    /// buf.set_cursor(1);
+    /// buf.insert(2).unwrap();
+    ///
+    /// assert_eq!(buf.as_slices(), (&[1, 2][..], &[3][..]));
+    /// ```
+    #[inline]
+    pub fn set_cursor(&mut self, position: usize) {
+        BaseGapBuffer::set_cursor(self, position)
+    }
+
+    /// Removes all elements from the buffer and moves the cursor to position
+    /// 0.
+    #[inline]
+    pub fn clear(&mut self) {
+        BaseGapBuffer::clear(self)
+    }
+}
+
+/// A gap buffer with fixed capacity, backed by a slice.
+///
+/// The capacity of the buffer is determined by the length of the slice.
+pub struct SliceGapBuffer<'a, T>
+where
+    T: Default,
+{
+    deque: SliceDeque<'a, T>,
+    cursor: usize,
+}
+
+impl<'a, T> BaseGapBuffer<T> for SliceGapBuffer<'a, T>
+where
+    T: Default,
+{
+    type Deque = SliceDeque<'a, T>;
+
+    #[inline(always)]
+    fn deque(&self) -> &Self::Deque {
+        &self.deque
+    }
+
+    #[inline(always)]
+    fn deque_mut(&mut self) -> &mut Self::Deque {
+        &mut self.deque
+    }
+
+    #[inline(always)]
+    fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    #[inline(always)]
+    fn cursor_mut(&mut self) -> &mut usize {
+        &mut self.cursor
+    }
+}
+
+impl<'a, T> fmt::Debug for SliceGapBuffer<'a, T>
+where
+    T: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (before, after) = BaseGapBuffer::as_slices(self);
+
+        f.debug_struct("SliceGapBuffer")
+            .field("before", &before)
+            .field("after", &after)
+            .finish()
+    }
+}
+
+impl<'a, T> SliceGapBuffer<'a, T>
+where
+    T: Default,
+{
+    /// Creates an empty `SliceGapBuffer` backed by the provided slice, with
+    /// the cursor at position 0.
+    ///
+    /// The elements in the slice are dropped and replaced with the default
+    /// value of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceGapBuffer;
+    /// let mut slice = ['\0'; 8];
+    /// let buf = SliceGapBuffer::new_in(&mut slice);
+    ///
+    /// assert!(buf.is_empty());
+    /// assert_eq!(buf.capacity(), 8);
+    /// ```
+    pub fn new_in(slice: &'a mut [T]) -> SliceGapBuffer<'a, T> {
+        SliceGapBuffer {
+            deque: SliceDeque::new_in(slice),
+            cursor: 0,
+        }
+    }
+
+    /// Returns the maximum number of elements the buffer may hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.deque.capacity()
+    }
+
+    /// Returns the number of elements in the buffer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        BaseGapBuffer::len(self)
+    }
+
+    /// Returns `true` if the buffer contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.deque.is_empty()
+    }
+
+    /// Returns `true` if the buffer is at capacity.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.deque.is_full()
+    }
+
+    /// Returns the position of the cursor, i.e. the number of elements before
+    /// it.
+    #[inline]
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns a pair of slices which contain, in order, the elements before
+    /// and after the cursor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceGapBuffer};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = ['\0'; 8];
+    /// let mut buf = SliceGapBuffer::new_in(&mut slice);
+    ///
+    /// buf.insert('a')?;
+    /// buf.insert('c')?;
+    /// buf.move_left();
+    /// buf.insert('b')?;
+    ///
+    /// assert_eq!(buf.as_slices(), (&['a', 'b'][..], &['c'][..]));
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        BaseGapBuffer::as_slices(self)
+    }
+
+    /// Returns a pair of mutable slices which contain, in order, the elements
+    /// before and after the cursor.
+    #[inline]
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        BaseGapBuffer::as_mut_slices(self)
+    }
+
+    /// Inserts an element before the cursor, advancing the cursor past it.
+    ///
+    /// If the buffer is full, an `Err` containing `item` is returned.
+    #[inline]
+    pub fn insert(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        BaseGapBuffer::insert(self, item)
+    }
+
+    /// Inserts an element after the cursor, leaving the cursor in place.
+    ///
+    /// If the buffer is full, an `Err` containing `item` is returned.
+    #[inline]
+    pub fn insert_after(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        BaseGapBuffer::insert_after(self, item)
+    }
+
+    /// Removes and returns the element before the cursor.
+    ///
+    /// If the cursor is at the start of the buffer, `None` is returned.
+    #[inline]
+    pub fn remove_before(&mut self) -> Option<T> {
+        BaseGapBuffer::remove_before(self)
+    }
+
+    /// Removes and returns the element after the cursor.
+    ///
+    /// If the cursor is at the end of the buffer, `None` is returned.
+    #[inline]
+    pub fn remove_after(&mut self) -> Option<T> {
+        BaseGapBuffer::remove_after(self)
+    }
+
+    /// Moves the cursor one element towards the start of the buffer.
+    ///
+    /// Returns `false` if the cursor was already at the start.
+    #[inline]
+    pub fn move_left(&mut self) -> bool {
+        BaseGapBuffer::move_left(self)
+    }
+
+    /// Moves the cursor one element towards the end of the buffer.
+    ///
+    /// Returns `false` if the cursor was already at the end.
+    #[inline]
+    pub fn move_right(&mut self) -> bool {
+        BaseGapBuffer::move_right(self)
+    }
+
+    /// Moves the cursor to `position`.
+    ///
+    /// This takes time proportional to the distance moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is greater than the length of the buffer.
+    #[inline]
+    pub fn set_cursor(&mut self, position: usize) {
+        BaseGapBuffer::set_cursor(self, position)
+    }
+
+    /// Removes all elements from the buffer and moves the cursor to position
+    /// 0.
+    #[inline]
+    pub fn clear(&mut self) {
+        BaseGapBuffer::clear(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    fn contents<const N: usize>(buf: &GapBuffer<u32, N>) -> Vec<u32> {
+        let (before, after) = buf.as_slices();
+        before.iter().chain(after).copied().collect()
+    }
+
+    #[test]
+    fn edit_at_cursor() {
+        let mut buf: GapBuffer<u32, 5> = GapBuffer::new();
+
+        buf.insert(1).unwrap();
+        buf.insert(4).unwrap();
+        buf.insert(5).unwrap();
+        buf.set_cursor(1);
+        buf.insert(2).unwrap();
+        buf.insert(3).unwrap();
+        assert!(buf.is_full());
+        assert_eq!(buf.as_slices(), (&[1, 2, 3][..], &[4, 5][..]));
+
+        assert_eq!(buf.remove_after(), Some(4));
+        assert_eq!(buf.remove_before(), Some(3));
+        assert_eq!(contents(&buf), [1, 2, 5]);
+    }
+
+    #[test]
+    fn cursor_sweeps_full_buffer() {
+        let mut buf: GapBuffer<u32, 4> = GapBuffer::new();
+        for i in 0..4 {
+            buf.insert(i).unwrap();
+        }
+
+        for pos in (0..4).rev() {
+            assert!(buf.move_left());
+            assert_eq!(buf.cursor(), pos);
+            assert_eq!(contents(&buf), [0, 1, 2, 3]);
+        }
+        assert!(!buf.move_left());
+
+        for pos in 1..=4 {
+            assert!(buf.move_right());
+            assert_eq!(buf.cursor(), pos);
+            assert_eq!(contents(&buf), [0, 1, 2, 3]);
+        }
+        assert!(!buf.move_right());
+    }
+
+    #[test]
+    fn matches_vec_model() {
+        // A small deterministic pseudo-random sequence of operations.
+        let mut state = 0x2545_f491_u32;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+
+        let mut buf: GapBuffer<u32, 7> = GapBuffer::new();
+        let mut model = Vec::new();
+        let mut cursor = 0;
+
+        for step in 0..2000 {
+            match next() % 6 {
+                0 => {
+                    if buf.insert(step).is_ok() {
+                        model.insert(cursor, step);
+                        cursor += 1;
+                    }
+                }
+                1 => {
+                    if buf.insert_after(step).is_ok() {
+                        model.insert(cursor, step);
+                    }
+                }
+                2 => {
+                    let expected = (cursor > 0).then(|| {
+                        cursor -= 1;
+                        model.remove(cursor)
+                    });
+                    assert_eq!(buf.remove_before(), expected);
+                }
+                3 => {
+                    let expected = (cursor < model.len()).then(|| model.remove(cursor));
+                    assert_eq!(buf.remove_after(), expected);
+                }
+                _ => {
+                    cursor = next() as usize % (model.len() + 1);
+                    buf.set_cursor(cursor);
+                }
+            }
+
+            assert_eq!(buf.cursor(), cursor);
+            assert_eq!(contents(&buf), model);
+            assert_eq!(buf.as_slices().0, &model[..cursor]);
+        }
+    }
+
+    #[test]
+    fn slice_backed() {
+        let mut slice = [0u32; 3];
+        let mut buf = SliceGapBuffer::new_in(&mut slice);
+
+        buf.insert(1).unwrap();
+        buf.insert(3).unwrap();
+        buf.move_left();
+        buf.insert(2).unwrap();
+        assert!(buf.insert(4).is_err());
+
+        buf.as_mut_slices().1[0] = 30;
+        assert_eq!(buf.as_slices(), (&[1, 2][..], &[30][..]));
+    }
+}
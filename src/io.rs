@@ -0,0 +1,107 @@
+//! I/O support for byte deques.
+
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+
+use crate::{BaseDeque, DequeDrain};
+
+#[cfg(any(feature = "embedded-io", feature = "tokio"))]
+/// Copies elements from the front of `deque` into `buf`, removing the copied
+/// elements from the deque.
+///
+/// Returns the number of elements copied.
+pub(crate) fn copy_front_into<D, T>(deque: &mut D, buf: &mut [T]) -> usize
+where
+    D: BaseDeque<T>,
+    T: Copy + Default,
+{
+    let (first, second) = deque.as_slices();
+
+    let first_len = first.len().min(buf.len());
+    let (buf_first, buf_second) = buf.split_at_mut(first_len);
+    buf_first.copy_from_slice(&first[..first_len]);
+
+    let second_len = second.len().min(buf_second.len());
+    buf_second[..second_len].copy_from_slice(&second[..second_len]);
+
+    let copied = first_len + second_len;
+    DequeDrain::front(deque, copied).unwrap();
+
+    copied
+}
+
+#[cfg(any(feature = "embedded-io", feature = "tokio"))]
+/// Copies elements from `buf` into the unused space at the back of `deque`.
+///
+/// Returns the number of elements copied.
+pub(crate) fn copy_back_from<D, T>(deque: &mut D, buf: &[T]) -> usize
+where
+    D: BaseDeque<T>,
+    T: Copy + Default,
+{
+    let (first, second) = deque.spare_slices_mut();
+
+    let first_len = first.len().min(buf.len());
+    let (buf_first, buf_second) = buf.split_at(first_len);
+    first[..first_len].copy_from_slice(buf_first);
+
+    let second_len = second.len().min(buf_second.len());
+    second[..second_len].copy_from_slice(&buf_second[..second_len]);
+
+    let copied = first_len + second_len;
+    deque.commit_back(copied).unwrap();
+
+    copied
+}
+
+#[cfg(feature = "std")]
+/// Reads bytes from `reader` into the unused space at the back of `deque`.
+///
+/// At most two calls to `read()` are made: one for each contiguous region of
+/// unused space.
+pub(crate) fn read_from<D, R>(deque: &mut D, reader: &mut R) -> io::Result<usize>
+where
+    D: BaseDeque<u8>,
+    R: Read + ?Sized,
+{
+    let (first, second) = deque.spare_slices_mut();
+    let first_len = first.len();
+
+    let mut read = reader.read(first)?;
+
+    if read == first_len && !second.is_empty() {
+        // The bytes already read must be committed, so an error from the
+        // second read is deferred to the next call.
+        read += reader.read(second).unwrap_or(0);
+    }
+
+    deque.commit_back(read).unwrap();
+
+    Ok(read)
+}
+
+#[cfg(feature = "std")]
+/// Writes bytes from the front of `deque` to `writer`, removing the bytes that
+/// were written.
+///
+/// At most two calls to `write()` are made: one for each contiguous region of
+/// the deque's contents.
+pub(crate) fn write_front_to<D, W>(deque: &mut D, writer: &mut W) -> io::Result<usize>
+where
+    D: BaseDeque<u8>,
+    W: Write + ?Sized,
+{
+    let (first, second) = deque.as_slices();
+
+    let mut written = writer.write(first)?;
+
+    if written == first.len() && !second.is_empty() {
+        // As in `read_from`, bytes already accepted by the writer must be
+        // removed, so an error from the second write is deferred.
+        written += writer.write(second).unwrap_or(0);
+    }
+
+    DequeDrain::front(deque, written).unwrap();
+
+    Ok(written)
+}
@@ -0,0 +1,209 @@
+//! A deque which can be shared with interrupt handlers.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+
+use crate::{ArrayDeque, CapacityError};
+
+/// A double-ended queue with fixed capacity which can be shared between
+/// interrupt handlers and the main program.
+///
+/// Every operation runs inside a critical section provided by the
+/// [`critical-section`] crate, so an `IsrDeque` can be placed in a `static`
+/// and used from any context. The backing [`ArrayDeque`] is initialized the
+/// first time the deque is accessed, which allows [`new`] to be a `const fn`.
+///
+/// Initializing the backing array takes time proportional to `N`, and by
+/// default happens inside the critical section of the first access, which
+/// may be in an interrupt handler. To keep that cost out of interrupt
+/// latency, call [`init`] during startup.
+///
+/// [`critical-section`]: https://docs.rs/critical-section
+/// [`new`]: IsrDeque::new
+/// [`init`]: IsrDeque::init
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::isr_deque::IsrDeque;
+/// static RX: IsrDeque<u8, 64> = IsrDeque::new();
+///
+/// // During startup, before enabling the interrupt:
+/// RX.init();
+///
+/// // In the UART interrupt handler:
+/// fn on_uart_rx(byte: u8) {
+///     // Drop the byte if the main loop has fallen behind.
+///     let _ = RX.push_from_isr(byte);
+/// }
+///
+/// // In the main loop:
+/// on_uart_rx(b'o');
+/// on_uart_rx(b'k');
+/// assert_eq!(RX.pop(), Some(b'o'));
+/// assert_eq!(RX.pop(), Some(b'k'));
+/// assert_eq!(RX.pop(), None);
+/// ```
+pub struct IsrDeque<T, const N: usize>
+where
+    T: Default,
+{
+    deque: Mutex<RefCell<Option<ArrayDeque<T, N>>>>,
+}
+
+impl<T, const N: usize> IsrDeque<T, N>
+where
+    T: Default,
+{
+    /// Constructs a new, empty `IsrDeque<T, N>`.
+    pub const fn new() -> Self {
+        IsrDeque {
+            deque: Mutex::new(RefCell::new(None)),
+        }
+    }
+
+    /// Returns the maximum number of elements the deque may hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Initializes the backing array, if it has not been already.
+    ///
+    /// The array is built outside of any critical section, so this can be
+    /// called during startup to avoid initializing it inside the first
+    /// operation on the deque. Calling this is never required.
+    pub fn init(&self) {
+        if critical_section::with(|cs| self.deque.borrow_ref(cs).is_some()) {
+            return;
+        }
+
+        let mut fresh = Some(ArrayDeque::new());
+        critical_section::with(|cs| {
+            let mut deque = self.deque.borrow_ref_mut(cs);
+            if deque.is_none() {
+                *deque = fresh.take();
+            }
+        });
+        // If another context initialized the deque first, `fresh` is dropped
+        // here, outside the critical section.
+    }
+
+    /// Calls `f` with exclusive access to the underlying deque inside a
+    /// critical section.
+    ///
+    /// This allows several operations to be performed without an interrupt
+    /// observing the deque in between. `f` should return quickly, as
+    /// interrupts are disabled while it runs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within `f`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::isr_deque::IsrDeque;
+    /// static EVENTS: IsrDeque<u32, 8> = IsrDeque::new();
+    ///
+    /// EVENTS.push_from_isr(1).unwrap();
+    /// EVENTS.push_from_isr(2).unwrap();
+    ///
+    /// let sum: u32 = EVENTS.with(|deque| {
+    ///     let sum = deque.iter().sum();
+    ///     deque.clear();
+    ///     sum
+    /// });
+    ///
+    /// assert_eq!(sum, 3);
+    /// assert!(EVENTS.is_empty());
+    /// ```
+    pub fn with<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut ArrayDeque<T, N>) -> R,
+    {
+        critical_section::with(|cs| {
+            let mut deque = self.deque.borrow_ref_mut(cs);
+            f(deque.get_or_insert_with(ArrayDeque::new))
+        })
+    }
+
+    /// Returns the number of elements in the deque.
+    pub fn len(&self) -> usize {
+        self.with(|deque| deque.len())
+    }
+
+    /// Returns `true` if the deque is empty.
+    pub fn is_empty(&self) -> bool {
+        self.with(|deque| deque.is_empty())
+    }
+
+    /// Appends an element to the back of the deque.
+    ///
+    /// This may be called from an interrupt handler. If the deque is full, the
+    /// element is returned in a [`CapacityError`].
+    pub fn push_from_isr(&self, item: T) -> Result<(), CapacityError<T>> {
+        self.with(|deque| deque.push_back(item))
+    }
+
+    /// Removes the first element and returns it, or `None` if the deque is
+    /// empty.
+    pub fn pop(&self) -> Option<T> {
+        self.with(|deque| deque.pop_front())
+    }
+}
+
+impl<T, const N: usize> Default for IsrDeque<T, N>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        IsrDeque::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_and_pop() {
+        let deque: IsrDeque<u8, 2> = IsrDeque::new();
+
+        assert!(deque.is_empty());
+        assert_eq!(deque.pop(), None);
+
+        deque.push_from_isr(1).unwrap();
+        deque.push_from_isr(2).unwrap();
+        assert_eq!(deque.push_from_isr(3).unwrap_err().into_inner(), 3);
+        assert_eq!(deque.len(), 2);
+
+        assert_eq!(deque.pop(), Some(1));
+        assert_eq!(deque.pop(), Some(2));
+        assert_eq!(deque.pop(), None);
+    }
+
+    #[test]
+    fn with_batches_operations() {
+        let deque: IsrDeque<u8, 4> = IsrDeque::new();
+        deque.push_from_isr(2).unwrap();
+
+        deque.with(|deque| {
+            deque.push_front(1).unwrap();
+            deque.push_back(3).unwrap();
+        });
+
+        assert!(deque.with(|deque| deque.iter().copied().eq(1..=3)));
+    }
+
+    #[test]
+    fn init_keeps_elements() {
+        let deque: IsrDeque<u8, 2> = IsrDeque::new();
+        deque.init();
+        deque.push_from_isr(1).unwrap();
+
+        deque.init();
+        assert_eq!(deque.pop(), Some(1));
+    }
+}
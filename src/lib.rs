@@ -3,14 +3,7 @@
 //! This crate provides [`ArrayDeque`] and [`SliceDeque`], fixed-size ring
 //! buffers with interfaces similar to the standard library's [`VecDeque`].
 //!
-//! `holodeque` makes use of the unstable [`array_map`] feature to provide
-//! `Default` initialization of arbitrarily-sized arrays. As a result, **a
-//! `nightly` compiler is required until this feature is stabilized**. See the
-//! [tracking issue] for its current status.
-//!
 //! [`VecDeque`]: https://doc.rust-lang.org/std/collections/struct.VecDeque.html
-//! [`array_map`]: https://doc.rust-lang.org/unstable-book/library-features/array-map.html
-//! [tracking issue]: https://github.com/rust-lang/rust/issues/75243
 //!
 //! # Example
 //!
@@ -62,15 +55,171 @@
 //! - `std`
 //!   - Optional, enabled by default
 //!   - Disable for `no_std` support
-//!   - Provides [`Error`] implementation for [`CapacityError`]
+//!   - Provides [`SyncDeque`](sync_deque::SyncDeque), a thread-safe deque
+//!     with blocking operations
+//!   - Provides [`StdClock`](timed_deque::StdClock), which measures time for
+//!     a [`TimedDeque`] with the system clock
+//! - `alloc`
+//!   - Optional, enabled by `std`
+//!   - Provides the [`seg_deque`] module, with an unbounded deque made of
+//!     fixed-capacity segments
+//!   - Implements [`Deque`] for [`VecDeque`]
+//!   - Provides `concat()` and `join()` for deques of strings or slices,
+//!     backed by the traits in the [`concat`] module
+//! - `atomics`
+//!   - Optional
+//!   - Provides the [`spsc`] module, a lock-free single-producer,
+//!     single-consumer queue for elements which fit in an atomic
+//!   - Provides the [`seqlock`] module, a ring with a single writer whose
+//!     contents other threads can copy without blocking it
+//!   - Provides [`AtomicBroadcast`](broadcast::AtomicBroadcast), a
+//!     [`Broadcast`](broadcast::Broadcast) which one thread sends to while
+//!     others receive, on targets with 64-bit atomics
 //! - `serde`
 //!   - Optional
 //!   - Provides:
 //!     - [`Serialize`](serde::Serialize) for `ArrayDeque` and `SliceDeque`
 //!     - [`Deserialize`](serde::Deserialize) for `ArrayDeque`
-//!     - [`DeserializeSeed`](serde::de::DeserializeSeed) for `SliceDeque`
+//!     - [`DeserializeSeed`](serde::de::DeserializeSeed) implementations for
+//!       extending an existing `SliceDeque` and for constructing a new one
+//!       with [`SliceDequeSeed`](slice_deque::SliceDequeSeed)
+//!     - `extend_deserialize()` for `ArrayDeque` and `SliceDeque`
+//!     - [`Truncating`](array_deque::Truncating), which deserializes an
+//!       `ArrayDeque` from a longer sequence by discarding the extra elements
+//!     - Zero-copy deserialization of borrowed elements such as `&'de str`
+//!       and `&'de [u8]`
+//!     - The [`serde_layout`] module, which serializes `ArrayDeque` along
+//!       with its physical layout
+//!     - The [`serde_bytes`] module, which serializes `ArrayDeque<u8, N>` as a
+//!       byte string
+//! - `bytes`
+//!   - Optional
+//!   - Provides [`Buf`](bytes::Buf) for `ArrayDeque<u8, N>` and
+//!     `SliceDeque<u8>`, which reads bytes from the front of the deque
+//!   - `BufMut` is an `unsafe` trait and is not implemented, as this crate
+//!     forbids unsafe code. To write into a deque with `BufMut`, use the
+//!     `&mut [u8]` slices returned by `spare_slices_mut()`, then call
+//!     `commit_back()`.
+//! - `embedded-io`
+//!   - Optional
+//!   - Provides [`embedded_io`] traits for `ArrayDeque<u8, N>` and
+//!     `SliceDeque<u8>`:
+//!     - [`Read`](embedded_io::Read) and [`BufRead`](embedded_io::BufRead),
+//!       which remove bytes from the front of the deque
+//!     - [`Write`](embedded_io::Write), which appends bytes to the back of the
+//!       deque
+//!     - [`ReadReady`](embedded_io::ReadReady) and
+//!       [`WriteReady`](embedded_io::WriteReady), which report whether the
+//!       deque is nonempty and nonfull, respectively
+//! - `embedded-storage`
+//!   - Optional
+//!   - Provides the [`flash_ring`] module, with a log of records kept in a
+//!     region of [`NorFlash`](embedded_storage::nor_flash::NorFlash)
+//! - `critical-section`
+//!   - Optional
+//!   - Provides the [`isr_deque`] module, with a deque which can be shared
+//!     with interrupt handlers
+//! - `embedded-hal-nb`
+//!   - Optional, enables `critical-section`
+//!   - Provides the [`serial_fifo`] module, with receive and transmit queues
+//!     filled and drained by UART interrupt handlers and accessed through
+//!     the [`embedded_hal_nb::serial`] traits
+//! - `log`
+//!   - Optional, enables `critical-section`
+//!   - Provides the [`ring_log`] module, with a [`Log`](log::Log)
+//!     implementation which keeps recent records in a fixed-size ring
+//! - `futures`
+//!   - Optional
+//!   - Provides the [`async_deque`] module, which exposes a deque as a
+//!     [`Sink`](futures_sink::Sink) with backpressure and a
+//!     [`Stream`](futures_core::Stream)
+//! - `tokio`
+//!   - Optional, enables `std`
+//!   - Provides the [`tokio_io`] module, with a bounded byte buffer which
+//!     implements [`AsyncRead`](tokio::io::AsyncRead) and
+//!     [`AsyncWrite`](tokio::io::AsyncWrite)
+//! - `heapless`
+//!   - Optional
+//!   - Provides conversions between `ArrayDeque<T, N>` and
+//!     [`heapless::Vec<T, N>`](heapless::Vec) or
+//!     [`heapless::Deque<T, N>`](heapless::Deque)
+//! - `arrayvec`
+//!   - Optional
+//!   - Provides conversions between `ArrayDeque<T, N>` and
+//!     [`arrayvec::ArrayVec<T, N>`](arrayvec::ArrayVec)
+//! - `tinyvec`
+//!   - Optional
+//!   - Provides conversions between `ArrayDeque<T, N>` and
+//!     [`tinyvec::ArrayVec<[T; N]>`](tinyvec::ArrayVec)
+//! - `smallvec`
+//!   - Optional
+//!   - Provides conversions between `ArrayDeque<T, N>` and
+//!     [`smallvec::SmallVec<A>`](smallvec::SmallVec), and
+//!     `extend_from_smallvec()` for `ArrayDeque` and `SliceDeque`
+//! - `minicbor`
+//!   - Optional
+//!   - Provides:
+//!     - [`Encode`](minicbor::Encode) for `ArrayDeque` and `SliceDeque`, as a
+//!       definite-length array
+//!     - [`Decode`](minicbor::Decode) for `ArrayDeque`
+//!     - `SliceDeque::extend_decode()`
+//!   - Decoding an array with more elements than the deque can hold fails
+//!     with an error
+//! - `arbitrary`
+//!   - Optional
+//!   - Provides [`Arbitrary`](arbitrary::Arbitrary) for `ArrayDeque<T, N>`,
+//!     which pushes elements to randomly chosen ends of the deque
+//! - `proptest`
+//!   - Optional
+//!   - Provides [`array_deque_strategy()`](array_deque::array_deque_strategy),
+//!     which generates deques with varied lengths and wrap positions
+//! - `quickcheck`
+//!   - Optional
+//!   - Provides [`Arbitrary`](quickcheck::Arbitrary) for `ArrayDeque<T, N>`
+//!     and `SliceDeque::arbitrary_in()`, which push elements to randomly
+//!     chosen ends of the deque
+//! - `postcard`
+//!   - Optional
+//!   - Provides [`MaxSize`](postcard::experimental::max_size::MaxSize) for
+//!     `ArrayDeque<T, N>`, for computing the worst-case size of its `serde`
+//!     encoding with `postcard`
+//! - `rand`
+//!   - Optional
+//!   - Provides `shuffle()`, `choose()` and `choose_mut()` for `ArrayDeque`
+//!     and `SliceDeque`
+//! - `schemars`
+//!   - Optional
+//!   - Provides [`JsonSchema`](schemars::JsonSchema) for `ArrayDeque<T, N>`,
+//!     describing it as an array of at most `N` items
+//! - `memchr`
+//!   - Optional
+//!   - Accelerates `find_byte()` for `ArrayDeque<u8, N>` and
+//!     `SliceDeque<u8>` with [`memchr`](memchr::memchr), which scans the
+//!     backing storage using SIMD instructions where available
+//! - `test-util`
+//!   - Optional, enables `alloc`
+//!   - Provides the [`test_util`] module, with an enum of deque operations
+//!     and a reference model for differential testing. With `quickcheck` or
+//!     `arbitrary`, the operations can be generated randomly
+//! - `stats`
+//!   - Optional
+//!   - Tracks usage statistics for `ArrayDeque` and `SliceDeque`, available
+//!     through their `stats()` method as a [`DequeStats`]
+//! - `zeroize`
+//!   - Optional
+//!   - Provides [`Zeroize`](zeroize::Zeroize) for `ArrayDeque` and
+//!     `SliceDeque`, along with `pop_front_zeroize()`, `pop_back_zeroize()`
+//!     and `clear_zeroize()`, which zeroize the slots of removed elements
+//!
+//! The `heapless`, `arrayvec` and `tinyvec` conversions require both
+//! containers to have the same capacity. To move elements between containers
+//! of different capacities, pass the source container to `try_extend()`,
+//! which reports how many elements fit. Converting a `SmallVec` into an
+//! `ArrayDeque` fails without moving any elements if the deque is too small.
+//!
+//! [`CapacityError`] implements [`Error`] regardless of enabled features.
 //!
-//! [`Error`]: https://doc.rust-lang.org/std/error/trait.Error.html
+//! [`Error`]: https://doc.rust-lang.org/core/error/trait.Error.html
 //!
 //! # Safe initialization mechanism
 //!
@@ -84,21 +233,509 @@
 //! [`MaybeUninit`]: https://doc.rust-lang.org/core/mem/union.MaybeUninit.html
 //! [`tinyvec`]: https://docs.rs/tinyvec
 
-#![feature(array_map)]
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 #![doc(html_root_url = "https://docs.rs/holodeque/0.2.0")]
 
+#[cfg(any(feature = "alloc", feature = "schemars", feature = "quickcheck", test))]
+extern crate alloc;
+
+pub mod aligned;
 pub mod array_deque;
+#[cfg(feature = "futures")]
+pub mod async_deque;
+pub mod bip_buffer;
+pub mod bit_deque;
+pub mod broadcast;
+#[cfg(feature = "minicbor")]
+mod cbor;
+#[cfg(feature = "alloc")]
+pub mod concat;
+pub mod deque_iter;
+pub mod double_buffer;
+pub mod double_stack;
+#[cfg(feature = "embedded-storage")]
+pub mod flash_ring;
+pub mod format;
+pub mod frame_deque;
+pub mod gap_buffer;
+#[cfg(any(feature = "std", feature = "embedded-io"))]
+mod io;
+#[cfg(feature = "critical-section")]
+pub mod isr_deque;
+pub mod lru;
 mod meta;
+pub mod overflow;
+#[cfg(feature = "log")]
+pub mod ring_log;
+#[cfg(feature = "alloc")]
+pub mod seg_deque;
+#[cfg(feature = "atomics")]
+pub mod seqlock;
+#[cfg(feature = "serde")]
+pub mod serde_bytes;
+#[cfg(feature = "serde")]
+pub mod serde_layout;
+#[cfg(feature = "embedded-hal-nb")]
+pub mod serial_fifo;
 pub mod slice_deque;
+pub mod snapshot;
+pub mod sorted_deque;
+#[cfg(feature = "atomics")]
+pub mod spsc;
+#[cfg(feature = "std")]
+pub mod sync_deque;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+pub mod text_deque;
+pub mod timed_deque;
+pub mod timer_wheel;
+#[cfg(feature = "tokio")]
+pub mod tokio_io;
+
+use core::{cell::Cell, cmp::Ordering, fmt, marker::PhantomData, mem, num::NonZeroUsize};
+
+use crate::{
+    format::{DebugInternals, DisplaySeparated},
+    meta::{Meta, MetaDrain, MetaLayout},
+};
+
+// Collection types are re-exported here. Types for sharing a deque between
+// threads, interrupt handlers or tasks, and adaptors for other crates, are
+// reached through their modules.
+pub use crate::{
+    array_deque::ArrayDeque,
+    bip_buffer::{BipBuffer, SliceBipBuffer},
+    bit_deque::BitDeque,
+    double_buffer::DoubleBuffer,
+    double_stack::DoubleStack,
+    frame_deque::FrameDeque,
+    gap_buffer::{GapBuffer, SliceGapBuffer},
+    lru::ArrayLru,
+    overflow::{OverflowDeque, PolicyDeque},
+    slice_deque::SliceDeque,
+    sorted_deque::SortedDeque,
+    text_deque::TextDeque,
+    timed_deque::TimedDeque,
+    timer_wheel::TimerWheel,
+};
+
+#[cfg(feature = "alloc")]
+pub use crate::seg_deque::SegDeque;
+
+/// A double-ended queue, independent of how its elements are stored.
+///
+/// This trait allows algorithms to be written once for both [`ArrayDeque`]
+/// and [`SliceDeque`], and to move elements between deques with different
+/// backing storage. With the `alloc` feature, it is also implemented for
+/// [`VecDeque`], so the same algorithms can run on unbounded storage.
+///
+/// [`VecDeque`]: alloc::collections::VecDeque
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::{ArrayDeque, Deque, SliceDeque};
+/// fn drain_evens<D: Deque<u32>>(deque: &mut D) -> u32 {
+///     let mut sum = 0;
+///     while let Some(item) = deque.pop_front() {
+///         if item % 2 == 0 {
+///             sum += item;
+///         }
+///     }
+///     sum
+/// }
+///
+/// let mut array: ArrayDeque<u32, 4> = ArrayDeque::new();
+/// array.push_back(1).unwrap();
+/// array.push_back(2).unwrap();
+///
+/// let mut slice = [0; 4];
+/// let mut slice_deque = SliceDeque::new_in(&mut slice);
+/// slice_deque.push_back(4).unwrap();
+///
+/// assert_eq!(drain_evens(&mut array), 2);
+/// assert_eq!(drain_evens(&mut slice_deque), 4);
+/// ```
+pub trait Deque<T> {
+    /// Returns the maximum number of elements the deque may hold.
+    fn capacity(&self) -> usize;
+
+    /// Returns the number of elements in the deque.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the deque is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the deque is full.
+    fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Returns a reference to the first element, or `None` if the deque is
+    /// empty.
+    fn front(&self) -> Option<&T>;
+
+    /// Returns a reference to the last element, or `None` if the deque is
+    /// empty.
+    fn back(&self) -> Option<&T>;
+
+    /// Returns a pair of slices which together contain the elements of the
+    /// deque, in order.
+    fn as_slices(&self) -> (&[T], &[T]);
+
+    /// Returns a pair of mutable slices which together contain the elements
+    /// of the deque, in order.
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]);
+
+    /// Prepends an element to the deque.
+    ///
+    /// If the deque is full, the element is returned in a [`CapacityError`].
+    fn push_front(&mut self, item: T) -> Result<(), CapacityError<T>>;
+
+    /// Appends an element to the deque.
+    ///
+    /// If the deque is full, the element is returned in a [`CapacityError`].
+    fn push_back(&mut self, item: T) -> Result<(), CapacityError<T>>;
+
+    /// Removes and returns the first element, or `None` if the deque is
+    /// empty.
+    fn pop_front(&mut self) -> Option<T>;
+
+    /// Removes and returns the last element, or `None` if the deque is empty.
+    fn pop_back(&mut self) -> Option<T>;
+
+    /// Removes all elements from the deque.
+    fn clear(&mut self);
+
+    /// Shortens the deque, keeping the last `len` elements and dropping the
+    /// rest.
+    ///
+    /// If `len` is greater than the deque's current length, this has no
+    /// effect.
+    fn truncate_front(&mut self, len: usize);
+
+    /// Returns a pair of mutable slices which together contain the unused
+    /// slots after the back of the deque, in order.
+    fn spare_slices_mut(&mut self) -> (&mut [T], &mut [T]);
+
+    /// Appends the first `n` unused slots to the back of the deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` exceeds the number of unused slots.
+    fn commit_back(&mut self, n: usize);
+
+    /// Returns a front-to-back iterator over the elements of the deque.
+    ///
+    /// Deque types may also have an inherent `iter` method returning their
+    /// own iterator type, which takes precedence when the type is known.
+    #[inline]
+    fn iter(&self) -> deque_iter::Iter<'_, T> {
+        deque_iter::Iter::new(self.as_slices())
+    }
+
+    /// Returns a front-to-back iterator which allows modifying each element
+    /// of the deque.
+    #[inline]
+    fn iter_mut(&mut self) -> deque_iter::IterMut<'_, T> {
+        deque_iter::IterMut::new(self.as_mut_slices())
+    }
+
+    /// Returns an iterator which removes the elements of the deque from the
+    /// front.
+    ///
+    /// When the iterator is dropped, any elements it did not yield are
+    /// dropped, leaving the deque empty.
+    #[inline]
+    fn drain(&mut self) -> deque_iter::Drain<'_, Self, T>
+    where
+        Self: Sized,
+    {
+        deque_iter::Drain::new(self)
+    }
+
+    /// Exchanges the contents of this deque with those of `other`,
+    /// preserving the order of both.
+    ///
+    /// If either deque's contents would not fit in the other, neither deque
+    /// is modified and `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, Deque, SliceDeque};
+    /// let mut read: ArrayDeque<u8, 4> = ArrayDeque::new();
+    /// read.push_back(1).unwrap();
+    ///
+    /// let mut slice = [0; 3];
+    /// let mut write = SliceDeque::new_in(&mut slice);
+    /// write.push_back(2).unwrap();
+    /// write.push_back(3).unwrap();
+    ///
+    /// read.swap_contents(&mut write).unwrap();
+    ///
+    /// assert_eq!(read.as_slices(), (&[2, 3][..], &[][..]));
+    /// assert_eq!(write.as_slices(), (&[1][..], &[][..]));
+    /// ```
+    fn swap_contents<D>(&mut self, other: &mut D) -> Option<()>
+    where
+        Self: Sized,
+        D: Deque<T> + ?Sized,
+    {
+        if self.len() > other.capacity() || other.len() > self.capacity() {
+            return None;
+        }
+
+        if self.len() >= other.len() {
+            swap_longer_first(self, other);
+        } else {
+            swap_longer_first(other, self);
+        }
+
+        Some(())
+    }
+
+    /// Moves up to `n` elements from the front of this deque to the back of
+    /// `dst`, preserving their order, and returns the number of elements
+    /// moved.
+    ///
+    /// As many elements are moved as are available and fit in `dst`. The
+    /// elements are moved as at most three contiguous runs, rather than one
+    /// at a time, except into a deque without spare slots, such as a
+    /// [`VecDeque`], where they are pushed individually. If there is at least
+    /// one element to move but `dst` is full, nothing is moved and a
+    /// [`CapacityError`] is returned.
+    ///
+    /// [`VecDeque`]: alloc::collections::VecDeque
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, Deque, SliceDeque};
+    /// let mut stage: ArrayDeque<u8, 8> = ArrayDeque::new();
+    /// stage.extend(1..=5);
+    ///
+    /// let mut slice = [0; 3];
+    /// let mut next = SliceDeque::new_in(&mut slice);
+    ///
+    /// assert_eq!(stage.transfer_to(&mut next, 4), Ok(3));
+    /// assert_eq!(next.as_slices(), (&[1, 2, 3][..], &[][..]));
+    /// assert_eq!(stage.as_slices(), (&[4, 5][..], &[][..]));
+    ///
+    /// assert!(stage.transfer_to(&mut next, 4).is_err());
+    /// ```
+    fn transfer_to<D>(&mut self, dst: &mut D, n: usize) -> Result<usize, CapacityError<()>>
+    where
+        Self: Sized,
+        D: Deque<T> + ?Sized,
+    {
+        let wanted = n.min(self.len());
+        if wanted == 0 {
+            return Ok(0);
+        }
+
+        let n = wanted.min(dst.capacity() - dst.len());
+        if n == 0 {
+            return Err(CapacityError { item: () });
+        }
+
+        let (src_front, src_wrap) = self.as_mut_slices();
+        let (dst_first, dst_second) = dst.spare_slices_mut();
+        let bulk = n.min(dst_first.len() + dst_second.len());
+        let (mut src, mut src_next) = (src_front, src_wrap);
+        let (mut spare, mut spare_next) = (dst_first, dst_second);
+        let mut remaining = bulk;
+
+        // Each run ends where a source or destination slice does, so there are
+        // at most three runs.
+        while remaining > 0 {
+            if src.is_empty() {
+                src = mem::take(&mut src_next);
+            }
+            if spare.is_empty() {
+                spare = mem::take(&mut spare_next);
+            }
+
+            let run = remaining.min(src.len()).min(spare.len());
+            let (src_run, src_rest) = mem::take(&mut src).split_at_mut(run);
+            let (spare_run, spare_rest) = mem::take(&mut spare).split_at_mut(run);
+            spare_run.swap_with_slice(src_run);
+
+            src = src_rest;
+            spare = spare_rest;
+            remaining -= run;
+        }
+
+        // The source slots now hold the unused values from `dst`, and are
+        // removed from this deque.
+        dst.commit_back(bulk);
+        let len = self.len();
+        self.truncate_front(len - bulk);
+
+        // Deques which grow on demand have fewer spare slots than their
+        // capacity allows, so the rest is pushed one element at a time.
+        for _ in bulk..n {
+            if let Some(item) = self.pop_front() {
+                if dst.push_back(item).is_err() {
+                    unreachable!();
+                }
+            }
+        }
+
+        Ok(n)
+    }
+}
+
+/// An unbounded deque.
+///
+/// The capacity is reported as `usize::MAX`, so pushing never fails. A
+/// `VecDeque` has no initialized spare slots, so [`spare_slices_mut`] always
+/// returns empty slices and [`commit_back`] only accepts zero.
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::{ArrayDeque, Deque};
+/// # use std::collections::VecDeque;
+/// fn ingest<D: Deque<u8>>(deque: &mut D, bytes: &[u8]) -> usize {
+///     bytes
+///         .iter()
+///         .take_while(|&&b| deque.push_back(b).is_ok())
+///         .count()
+/// }
+///
+/// let mut bounded: ArrayDeque<u8, 2> = ArrayDeque::new();
+/// let mut unbounded = VecDeque::new();
+///
+/// assert_eq!(ingest(&mut bounded, b"abc"), 2);
+/// assert_eq!(ingest(&mut unbounded, b"abc"), 3);
+///
+/// assert_eq!(bounded.transfer_to(&mut unbounded, 2), Ok(2));
+/// assert_eq!(unbounded, b"abcab");
+/// ```
+///
+/// [`spare_slices_mut`]: Deque::spare_slices_mut
+/// [`commit_back`]: Deque::commit_back
+#[cfg(feature = "alloc")]
+impl<T> Deque<T> for alloc::collections::VecDeque<T> {
+    #[inline]
+    fn capacity(&self) -> usize {
+        usize::MAX
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn front(&self) -> Option<&T> {
+        self.front()
+    }
+
+    #[inline]
+    fn back(&self) -> Option<&T> {
+        self.back()
+    }
+
+    #[inline]
+    fn as_slices(&self) -> (&[T], &[T]) {
+        self.as_slices()
+    }
+
+    #[inline]
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        self.as_mut_slices()
+    }
+
+    #[inline]
+    fn push_front(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.push_front(item);
+        Ok(())
+    }
+
+    #[inline]
+    fn push_back(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.push_back(item);
+        Ok(())
+    }
+
+    #[inline]
+    fn pop_front(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    #[inline]
+    fn pop_back(&mut self) -> Option<T> {
+        self.pop_back()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    #[inline]
+    fn truncate_front(&mut self, len: usize) {
+        let excess = self.len().saturating_sub(len);
+        self.drain(..excess);
+    }
+
+    #[inline]
+    fn spare_slices_mut(&mut self) -> (&mut [T], &mut [T]) {
+        (&mut [], &mut [])
+    }
+
+    #[inline]
+    #[track_caller]
+    fn commit_back(&mut self, n: usize) {
+        assert!(n == 0, "cannot commit {} slots; only 0 are unused", n);
+    }
+}
 
-use core::{fmt, mem};
+/// Exchanges the contents of two deques, where `long` holds at least as many
+/// elements as `short` and each fits in the other.
+fn swap_longer_first<L, S, T>(long: &mut L, short: &mut S)
+where
+    L: Deque<T> + ?Sized,
+    S: Deque<T> + ?Sized,
+{
+    let long_len = long.len();
+    let short_len = short.len();
 
-use crate::meta::{Meta, MetaDrain, MetaLayout};
+    // Exchange the first `short_len` elements in place.
+    let (long_front, long_wrap) = long.as_mut_slices();
+    let (short_front, short_wrap) = short.as_mut_slices();
+    long_front
+        .iter_mut()
+        .chain(long_wrap)
+        .zip(short_front.iter_mut().chain(short_wrap))
+        .for_each(|(a, b)| mem::swap(a, b));
+
+    // Rotate the elements which came from `short` to the back of `long`,
+    // leaving the excess at the front, then move the excess to `short`.
+    // Neither push can fail, as each follows a pop or was checked against the
+    // capacity of `short`.
+    for _ in 0..short_len {
+        if let Some(item) = long.pop_front() {
+            if long.push_back(item).is_err() {
+                unreachable!();
+            }
+        }
+    }
 
-pub use crate::{array_deque::ArrayDeque, slice_deque::SliceDeque};
+    for _ in short_len..long_len {
+        if let Some(item) = long.pop_front() {
+            if short.push_back(item).is_err() {
+                unreachable!();
+            }
+        }
+    }
+}
 
 /// Provides default implementations for common deque operations.
 ///
@@ -111,14 +748,40 @@ where
 
     fn meta(&self) -> &Self::Meta;
 
-    fn meta_mut(&mut self) -> &mut Self::Meta;
+    /// Returns the metadata mutably, without invalidating savepoints.
+    fn raw_meta_mut(&mut self) -> &mut Self::Meta;
 
     fn items(&self) -> &[T];
 
-    fn items_mut(&mut self) -> &mut [T];
+    /// Returns the backing store mutably, without invalidating savepoints.
+    fn raw_items_mut(&mut self) -> &mut [T];
+
+    /// Returns the metadata and backing store mutably, without invalidating
+    /// savepoints.
+    fn raw_meta_and_items_mut(&mut self) -> (&mut Self::Meta, &mut [T]);
 
     fn capacity(&self) -> usize;
 
+    #[inline]
+    fn meta_mut(&mut self) -> &mut Self::Meta {
+        let meta = self.raw_meta_mut();
+        meta.invalidate_savepoints();
+        meta
+    }
+
+    #[inline]
+    fn items_mut(&mut self) -> &mut [T] {
+        self.raw_meta_mut().invalidate_savepoints();
+        self.raw_items_mut()
+    }
+
+    #[inline]
+    fn meta_and_items_mut(&mut self) -> (&mut Self::Meta, &mut [T]) {
+        let (meta, items) = self.raw_meta_and_items_mut();
+        meta.invalidate_savepoints();
+        (meta, items)
+    }
+
     #[inline]
     fn len(&self) -> usize {
         self.meta().len()
@@ -144,6 +807,106 @@ where
         (&mut front[front_range], &mut wrap[wrap_range])
     }
 
+    fn make_contiguous(&mut self) -> &mut [T] {
+        let (front_range, wrap_range) = self.meta().as_ranges();
+
+        if wrap_range.is_empty() {
+            return &mut self.items_mut()[front_range];
+        }
+
+        // Rotating the entire backing store moves the front element to index
+        // 0 and places the wrapped portion directly after it.
+        let len = self.len();
+        self.items_mut().rotate_left(front_range.start);
+        self.meta_mut().set_layout(MetaLayout::Linear {
+            first: 0,
+            len: NonZeroUsize::new(len).unwrap(),
+        });
+
+        &mut self.items_mut()[..len]
+    }
+
+    fn rotate_to_front(&mut self, index: usize) {
+        assert!(index <= self.len(), "rotation index out of bounds");
+
+        self.make_contiguous().rotate_left(index);
+    }
+
+    fn round_robin(&mut self) -> Option<&mut T> {
+        // Freeing a slot at the front guarantees one can be reserved at the
+        // back. If the deque is full, these are the same slot and nothing
+        // moves.
+        let freed = self.meta_mut().free_front()?;
+        let reserved = self.meta_mut().reserve_back().unwrap();
+        self.items_mut().swap(freed, reserved);
+
+        let front = self.meta().front().unwrap();
+        Some(&mut self.items_mut()[front])
+    }
+
+    fn select_nth_unstable_by<F>(
+        &mut self,
+        index: usize,
+        compare: F,
+    ) -> (&mut [T], &mut T, &mut [T])
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // The partition is done in place on the contiguous elements, so any
+        // wrapped portion has to be moved first.
+        self.make_contiguous()
+            .select_nth_unstable_by(index, compare)
+    }
+
+    fn spare_slices_mut(&mut self) -> (&mut [T], &mut [T]) {
+        let (first_range, second_range) = self.meta().spare_ranges();
+
+        // Writing to the unused slots leaves the elements untouched, so
+        // savepoints stay valid.
+        if second_range.is_empty() {
+            return (&mut self.raw_items_mut()[first_range], &mut []);
+        }
+
+        let (second, first) = self.raw_items_mut().split_at_mut(first_range.start);
+
+        (&mut first[..first_range.len()], &mut second[second_range])
+    }
+
+    fn commit_back(&mut self, n: usize) -> Option<()> {
+        self.raw_meta_mut().commit_back(n)
+    }
+
+    fn as_slices_of_cells(&mut self) -> (&[Cell<T>], &[Cell<T>]) {
+        let (first, second) = self.as_mut_slices();
+
+        (
+            Cell::from_mut(first).as_slice_of_cells(),
+            Cell::from_mut(second).as_slice_of_cells(),
+        )
+    }
+
+    fn debug_internals(&self) -> DebugInternals<'_, T> {
+        DebugInternals::new(self.meta().layout(), self.items())
+    }
+
+    fn layout_info(&self) -> LayoutInfo {
+        let (wrap_len, gap_len) = match self.meta().layout() {
+            MetaLayout::Wrapped { wrap_len, gap_len } => (wrap_len.get(), gap_len),
+            _ => (0, self.capacity() - self.len()),
+        };
+
+        LayoutInfo {
+            head: self.meta().front().unwrap_or(0),
+            len: self.len(),
+            wrap_len,
+            gap_len,
+        }
+    }
+
+    fn display_separated<'a>(&'a self, separator: &'a str) -> DisplaySeparated<'a, T> {
+        DisplaySeparated::new(self.as_slices(), separator)
+    }
+
     #[inline]
     fn is_empty(&self) -> bool {
         matches!(self.meta().layout(), MetaLayout::Empty)
@@ -178,171 +941,1579 @@ where
         Some(&mut self.items_mut()[back])
     }
 
+    fn ends_mut(&mut self) -> Option<(&mut T, &mut T)> {
+        if self.len() < 2 {
+            return None;
+        }
+
+        let front = self.meta().front()?;
+        let back = self.meta().back()?;
+        let items = self.items_mut();
+
+        if front < back {
+            let (low, high) = items.split_at_mut(back);
+            Some((&mut low[front], &mut high[0]))
+        } else {
+            let (low, high) = items.split_at_mut(front);
+            Some((&mut high[0], &mut low[back]))
+        }
+    }
+
     fn push_front(&mut self, item: T) -> Result<(), CapacityError<T>> {
-        match self.meta_mut().reserve_front() {
+        self.push_front_mut(item).map(|_| ())
+    }
+
+    fn push_front_mut(&mut self, item: T) -> Result<&mut T, CapacityError<T>> {
+        match self.raw_meta_mut().reserve_front() {
             Some(front) => {
-                self.items_mut()[front] = item;
-                Ok(())
+                let slot = &mut self.raw_items_mut()[front];
+                *slot = item;
+                Ok(slot)
             }
 
             None => Err(CapacityError { item }),
         }
     }
 
+    #[track_caller]
+    fn push_front_expect(&mut self, item: T) {
+        if self.push_front(item).is_err() {
+            panic!(
+                "push_front on a full deque (capacity is {})",
+                self.capacity()
+            );
+        }
+    }
+
+    fn push_front_with<F>(&mut self, f: F) -> Option<&mut T>
+    where
+        F: FnOnce() -> T,
+    {
+        // Check for space before calling `f` so that a panic in `f` leaves the
+        // deque untouched.
+        if self.is_full() {
+            #[cfg(feature = "stats")]
+            self.raw_meta_mut().stats_mut().record_rejected();
+
+            return None;
+        }
+
+        self.push_front_mut(f()).ok()
+    }
+
     fn push_back(&mut self, item: T) -> Result<(), CapacityError<T>> {
-        match self.meta_mut().reserve_back() {
+        self.push_back_mut(item).map(|_| ())
+    }
+
+    fn push_back_mut(&mut self, item: T) -> Result<&mut T, CapacityError<T>> {
+        match self.raw_meta_mut().reserve_back() {
             Some(back) => {
-                self.items_mut()[back] = item;
-                Ok(())
+                let slot = &mut self.raw_items_mut()[back];
+                *slot = item;
+                Ok(slot)
             }
 
             None => Err(CapacityError { item }),
         }
     }
 
-    fn pop_front(&mut self) -> Option<T> {
-        let freed = self.meta_mut().free_front()?;
-
-        Some(mem::take(&mut self.items_mut()[freed]))
+    #[track_caller]
+    fn push_back_expect(&mut self, item: T) {
+        if self.push_back(item).is_err() {
+            panic!(
+                "push_back on a full deque (capacity is {})",
+                self.capacity()
+            );
+        }
     }
 
-    fn pop_back(&mut self) -> Option<T> {
-        let freed = self.meta_mut().free_back()?;
+    fn push_back_with<F>(&mut self, f: F) -> Option<&mut T>
+    where
+        F: FnOnce() -> T,
+    {
+        if self.is_full() {
+            #[cfg(feature = "stats")]
+            self.raw_meta_mut().stats_mut().record_rejected();
 
-        Some(mem::take(&mut self.items_mut()[freed]))
+            return None;
+        }
+
+        self.push_back_mut(f()).ok()
     }
 
-    fn clear(&mut self) {
-        for freed in self.meta_mut().clear() {
-            drop(mem::take(&mut self.items_mut()[freed]));
+    /// Moves elements from `iter` directly into the unused slots at the back
+    /// of the deque, if the iterator reports an exact length which fits.
+    ///
+    /// Returns the number of elements appended. Elements beyond the reported
+    /// length are left in the iterator, as are all elements if the length is
+    /// inexact or too large.
+    fn extend_exact<I>(&mut self, iter: &mut I) -> usize
+    where
+        I: Iterator<Item = T>,
+    {
+        let (lower, upper) = iter.size_hint();
+        if upper != Some(lower) || lower > self.capacity() - self.len() {
+            return 0;
+        }
+
+        let (first, second) = self.spare_slices_mut();
+        let mut appended = 0;
+
+        // The slots come first in the zip, so that no element is taken from
+        // the iterator once they run out.
+        for (slot, item) in first.iter_mut().chain(second).take(lower).zip(iter) {
+            *slot = item;
+            appended += 1;
         }
+
+        self.commit_back(appended).unwrap();
+
+        appended
     }
 
-    fn truncate(&mut self, len: usize) {
-        let n = self.len().saturating_sub(len);
+    #[track_caller]
+    fn extend_expect<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+        self.extend_exact(&mut iter);
 
-        if let Some(drain) = self.meta_mut().drain_back(n) {
-            for freed in drain {
-                drop(mem::take(&mut self.items_mut()[freed]));
-            }
+        for item in iter {
+            self.push_back_expect(item);
         }
     }
-}
 
-/// An immutable iterator over a deque.
-pub(crate) struct DequeIter<'a, D, T>
+    fn try_extend<I>(&mut self, iter: I) -> Result<(), TryExtendError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+        let appended = self.extend_exact(&mut iter);
+
+        for (accepted, item) in (appended..).zip(iter) {
+            self.push_back(item).map_err(|e| TryExtendError {
+                accepted,
+                first_rejected: e.into_inner(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn count_overflow<I>(&mut self, iter: I) -> usize
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+
+        match self.try_extend(&mut iter) {
+            Ok(()) => 0,
+            Err(_) => 1 + iter.count(),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn collect_overflow<I>(&mut self, iter: I) -> alloc::vec::Vec<T>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+
+        match self.try_extend(&mut iter) {
+            Ok(()) => alloc::vec::Vec::new(),
+            Err(e) => core::iter::once(e.into_first_rejected())
+                .chain(iter)
+                .collect(),
+        }
+    }
+
+    fn try_extend_front<I>(&mut self, iter: I) -> Result<(), TryExtendError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        // Pushing each item to the front reverses them, so the block of new
+        // elements is reversed once all of them are in place.
+        let mut accepted = 0;
+        let mut result = Ok(());
+
+        for item in iter {
+            if let Err(e) = self.push_front(item) {
+                result = Err(TryExtendError {
+                    accepted,
+                    first_rejected: e.into_inner(),
+                });
+                break;
+            }
+
+            accepted += 1;
+        }
+
+        // Only the new elements are reordered, so savepoints taken before the
+        // extension stay valid.
+        let generation = self.meta().generation();
+        self.reverse_range(0, accepted);
+        self.raw_meta_mut().set_generation(generation);
+
+        result
+    }
+
+    #[track_caller]
+    fn extend_front_expect<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        if self.try_extend_front(iter).is_err() {
+            panic!(
+                "extend_front on a full deque (capacity is {})",
+                self.capacity()
+            );
+        }
+    }
+
+    fn push_back_dedup(&mut self, item: T) -> Result<bool, CapacityError<T>>
+    where
+        T: PartialEq,
+    {
+        if self.back() == Some(&item) {
+            return Ok(false);
+        }
+
+        self.push_back(item).map(|()| true)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        let freed = self.meta_mut().free_front()?;
+
+        Some(mem::take(&mut self.items_mut()[freed]))
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        let freed = self.meta_mut().free_back()?;
+
+        Some(mem::take(&mut self.items_mut()[freed]))
+    }
+
+    fn pop_front_copied(&mut self) -> Option<T>
+    where
+        T: Copy,
+    {
+        let freed = self.meta_mut().free_front()?;
+
+        Some(self.items()[freed])
+    }
+
+    fn pop_back_copied(&mut self) -> Option<T>
+    where
+        T: Copy,
+    {
+        let freed = self.meta_mut().free_back()?;
+
+        Some(self.items()[freed])
+    }
+
+    fn clear(&mut self) {
+        let drain = self.meta_mut().clear();
+
+        // Removed elements without drop glue can be left in place, as unused
+        // slots are never read before being overwritten.
+        if mem::needs_drop::<T>() {
+            for freed in drain {
+                drop(mem::take(&mut self.items_mut()[freed]));
+            }
+        }
+    }
+
+    fn truncate(&mut self, len: usize) {
+        let n = self.len().saturating_sub(len);
+
+        if let Some(drain) = self.meta_mut().drain_back(n) {
+            if mem::needs_drop::<T>() {
+                for freed in drain {
+                    drop(mem::take(&mut self.items_mut()[freed]));
+                }
+            }
+        }
+    }
+
+    fn truncate_front(&mut self, len: usize) {
+        let n = self.len().saturating_sub(len);
+
+        if let Some(drain) = self.meta_mut().drain_front(n) {
+            if mem::needs_drop::<T>() {
+                for freed in drain {
+                    drop(mem::take(&mut self.items_mut()[freed]));
+                }
+            }
+        }
+    }
+
+    fn linearize_into(&mut self, dst: &mut [T]) -> usize {
+        let n = self.len().min(dst.len());
+
+        if let Some(drain) = self.meta_mut().drain_front(n) {
+            for (slot, freed) in dst.iter_mut().zip(drain) {
+                *slot = mem::take(&mut self.items_mut()[freed]);
+            }
+        }
+
+        n
+    }
+
+    fn clone_into_slice(&self, dst: &mut [T]) -> usize
+    where
+        T: Clone,
+    {
+        let (front, wrap) = self.as_slices();
+        let n = self.len().min(dst.len());
+        let split = front.len().min(n);
+
+        dst[..split].clone_from_slice(&front[..split]);
+        dst[split..n].clone_from_slice(&wrap[..n - split]);
+
+        n
+    }
+
+    #[cfg(feature = "stats")]
+    fn reset_stats(&mut self) {
+        let len = self.len();
+        let stats = self.raw_meta_mut().stats_mut();
+
+        *stats = DequeStats::new();
+        stats.high_water_mark = len;
+    }
+
+    fn savepoint(&self) -> Savepoint {
+        Savepoint {
+            front: self.meta().front().unwrap_or(0),
+            len: self.len(),
+            generation: self.meta().generation(),
+        }
+    }
+
+    fn rollback_to(&mut self, savepoint: Savepoint) {
+        // Clearing restores an empty deque no matter what happened since.
+        if savepoint.len == 0 {
+            self.clear();
+            return;
+        }
+
+        assert!(
+            savepoint.generation == self.meta().generation(),
+            "savepoint is not valid: the deque was modified other than by pushing"
+        );
+
+        let capacity = self.capacity();
+        let front = self.meta().front().unwrap_or(savepoint.front);
+
+        // The elements present at the savepoint have not moved, so the
+        // distance between the old and new front is the number of elements
+        // pushed to the front since then.
+        let pushed_front = (savepoint.front + capacity - front) % capacity.max(1);
+
+        assert!(
+            savepoint.front < capacity && pushed_front + savepoint.len <= self.len(),
+            "savepoint is not valid for this deque"
+        );
+
+        if let Some(drain) = self.meta_mut().drain_front(pushed_front) {
+            for freed in drain {
+                drop(mem::take(&mut self.items_mut()[freed]));
+            }
+        }
+
+        // Whatever remains beyond the savepoint was pushed to the back.
+        self.truncate(savepoint.len);
+    }
+
+    #[cfg(feature = "zeroize")]
+    fn pop_front_zeroize(&mut self) -> Option<T>
+    where
+        T: zeroize::Zeroize,
+    {
+        let freed = self.meta_mut().free_front()?;
+        let slot = &mut self.items_mut()[freed];
+        let item = mem::take(slot);
+        slot.zeroize();
+
+        Some(item)
+    }
+
+    #[cfg(feature = "zeroize")]
+    fn pop_back_zeroize(&mut self) -> Option<T>
+    where
+        T: zeroize::Zeroize,
+    {
+        let freed = self.meta_mut().free_back()?;
+        let slot = &mut self.items_mut()[freed];
+        let item = mem::take(slot);
+        slot.zeroize();
+
+        Some(item)
+    }
+
+    #[cfg(feature = "zeroize")]
+    fn clear_zeroize(&mut self)
+    where
+        T: zeroize::Zeroize,
+    {
+        for freed in self.meta_mut().clear() {
+            self.items_mut()[freed].zeroize();
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    fn zeroize_all(&mut self)
+    where
+        T: zeroize::Zeroize,
+    {
+        drop(self.meta_mut().clear());
+        self.items_mut().iter_mut().for_each(|item| item.zeroize());
+    }
+
+    #[cfg(feature = "rand")]
+    fn shuffle<R>(&mut self, rng: &mut R)
+    where
+        R: rand::Rng + ?Sized,
+    {
+        // Fisher-Yates, operating on logical indices so that the shuffle is
+        // uniform regardless of whether the deque is wrapped.
+        for i in (1..self.len()).rev() {
+            let j = rng.random_range(0..=i);
+            let a = self.physical_index(i).unwrap();
+            let b = self.physical_index(j).unwrap();
+
+            self.items_mut().swap(a, b);
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    fn choose<R>(&self, rng: &mut R) -> Option<&T>
+    where
+        R: rand::Rng + ?Sized,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        let physical = self.physical_index(rng.random_range(0..self.len()))?;
+
+        Some(&self.items()[physical])
+    }
+
+    #[cfg(feature = "rand")]
+    fn choose_mut<R>(&mut self, rng: &mut R) -> Option<&mut T>
+    where
+        R: rand::Rng + ?Sized,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        let index = rng.random_range(0..self.len());
+
+        self.get_mut(index)
+    }
+
+    /// Maps a logical index to an index into the backing storage.
+    fn physical_index(&self, index: usize) -> Option<usize> {
+        let (front, wrap) = self.meta().as_ranges();
+
+        if index < front.len() {
+            Some(front.start + index)
+        } else if index - front.len() < wrap.len() {
+            Some(wrap.start + (index - front.len()))
+        } else {
+            None
+        }
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let physical = self.physical_index(index)?;
+
+        Some(&mut self.items_mut()[physical])
+    }
+
+    /// Maps an index counted from the back to a logical index.
+    fn index_from_back(&self, index: usize) -> Option<usize> {
+        self.len().checked_sub(index.checked_add(1)?)
+    }
+
+    fn get_from_back(&self, index: usize) -> Option<&T> {
+        let physical = self.physical_index(self.index_from_back(index)?)?;
+
+        Some(&self.items()[physical])
+    }
+
+    fn get_from_back_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.get_mut(self.index_from_back(index)?)
+    }
+
+    /// Returns a pair of mutable slices of the backing storage which together
+    /// contain the elements at logical indices `start..end`, in order.
+    fn range_slices_mut(&mut self, start: usize, end: usize) -> (&mut [T], &mut [T]) {
+        debug_assert!(start <= end && end <= self.len());
+
+        let (front, wrap) = self.meta().as_ranges();
+        let split = front.len();
+
+        let front_part = start.min(split)..end.min(split);
+        let wrap_part =
+            wrap.start + start.saturating_sub(split)..wrap.start + end.saturating_sub(split);
+
+        // Any wrapped elements are stored before the front elements.
+        let (low, high) = self.items_mut().split_at_mut(front.start);
+
+        (&mut high[front_part], &mut low[wrap_part])
+    }
+
+    /// Rotates the elements at logical indices `start..end` left by one
+    /// place, moving the first of them to the end.
+    ///
+    /// Each contiguous part of the range is moved in bulk with
+    /// [`slice::rotate_left`], rather than one element at a time.
+    fn rotate_range_left(&mut self, start: usize, end: usize) {
+        if end - start < 2 {
+            return;
+        }
+
+        match self.range_slices_mut(start, end) {
+            ([], part) | (part, []) => part.rotate_left(1),
+
+            (first, second) => {
+                first.rotate_left(1);
+                mem::swap(first.last_mut().unwrap(), &mut second[0]);
+                second.rotate_left(1);
+            }
+        }
+    }
+
+    /// Rotates the elements at logical indices `start..end` right by one
+    /// place, moving the last of them to the start.
+    fn rotate_range_right(&mut self, start: usize, end: usize) {
+        if end - start < 2 {
+            return;
+        }
+
+        match self.range_slices_mut(start, end) {
+            ([], part) | (part, []) => part.rotate_right(1),
+
+            (first, second) => {
+                second.rotate_right(1);
+                mem::swap(first.last_mut().unwrap(), &mut second[0]);
+                first.rotate_right(1);
+            }
+        }
+    }
+
+    /// Reverses the order of the elements at logical indices `start..end`.
+    fn reverse_range(&mut self, start: usize, end: usize) {
+        match self.range_slices_mut(start, end) {
+            ([], part) | (part, []) => part.reverse(),
+
+            _ => {
+                for i in 0..(end - start) / 2 {
+                    let a = self.physical_index(start + i).unwrap();
+                    let b = self.physical_index(end - 1 - i).unwrap();
+
+                    self.items_mut().swap(a, b);
+                }
+            }
+        }
+    }
+
+    /// Inserts `item` at logical index `index`, shifting whichever side of the
+    /// deque is shorter.
+    fn insert(&mut self, index: usize, item: T) -> Result<(), CapacityError<T>> {
+        let len = self.len();
+        debug_assert!(index <= len);
+
+        if index <= len / 2 {
+            self.push_front(item)?;
+            self.rotate_range_left(0, index + 1);
+        } else {
+            self.push_back(item)?;
+            self.rotate_range_right(index, len + 1);
+        }
+
+        Ok(())
+    }
+
+    /// Removes the element at logical index `index`, shifting whichever side
+    /// of the deque is shorter.
+    fn remove(&mut self, index: usize) -> Option<T> {
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+
+        if index < len / 2 {
+            self.rotate_range_right(0, index + 1);
+            self.pop_front()
+        } else {
+            self.rotate_range_left(index, len);
+            self.pop_back()
+        }
+    }
+
+    /// Removes the first element equal to `item`, shifting whichever side of
+    /// the deque is shorter.
+    fn remove_item(&mut self, item: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        let (front, back) = self.as_slices();
+        let index = front.iter().chain(back).position(|other| other == item)?;
+
+        self.remove(index)
+    }
+
+    fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut shift = Backshift::new(self);
+
+        while shift.processed < shift.len {
+            let index = shift.processed;
+            let keep = f(shift.deque.get_mut(index).unwrap());
+            shift.processed += 1;
+
+            if keep {
+                shift.keep(index);
+            } else {
+                shift.deleted += 1;
+            }
+        }
+    }
+
+    fn starts_with(&self, prefix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        if prefix.len() > self.len() {
+            return false;
+        }
+
+        let (front, back) = self.as_slices();
+        let (in_front, in_back) = prefix.split_at(front.len().min(prefix.len()));
+
+        front[..in_front.len()] == *in_front && back[..in_back.len()] == *in_back
+    }
+
+    fn ends_with(&self, suffix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        if suffix.len() > self.len() {
+            return false;
+        }
+
+        let (front, back) = self.as_slices();
+        let (in_front, in_back) = suffix.split_at(suffix.len().saturating_sub(back.len()));
+
+        front[front.len() - in_front.len()..] == *in_front
+            && back[back.len() - in_back.len()..] == *in_back
+    }
+
+    fn strip_prefix(&mut self, prefix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        let matched = self.starts_with(prefix);
+        if matched {
+            self.truncate_front(self.len() - prefix.len());
+        }
+
+        matched
+    }
+
+    fn strip_suffix(&mut self, suffix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        let matched = self.ends_with(suffix);
+        if matched {
+            self.truncate(self.len() - suffix.len());
+        }
+
+        matched
+    }
+
+    fn cyclic_eq<D>(&self, other: &D) -> bool
+    where
+        D: Deque<T> + ?Sized,
+        T: PartialEq,
+    {
+        let len = self.len();
+        if other.len() != len {
+            return false;
+        }
+
+        let (self_front, self_back) = self.as_slices();
+        let (other_front, other_back) = other.as_slices();
+        let self_at = |i: usize| {
+            self_front
+                .get(i)
+                .unwrap_or_else(|| &self_back[i - self_front.len()])
+        };
+        let other_at = |i: usize| {
+            other_front
+                .get(i)
+                .unwrap_or_else(|| &other_back[i - other_front.len()])
+        };
+
+        // `other` is a rotation of `self` if it occurs in `self` followed by
+        // all but the last element of `self` again.
+        len == 0 || occurs_in(other_at, len, |i| self_at(i % len), 2 * len - 1)
+    }
+
+    fn contains(&self, item: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let (front, back) = self.as_slices();
+
+        front.contains(item) || back.contains(item)
+    }
+
+    fn position_of(&self, item: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        let (front, back) = self.as_slices();
+
+        front.iter().chain(back).position(|other| other == item)
+    }
+
+    /// Returns `true` if the deque holds the same elements as `other`, in the
+    /// same order.
+    ///
+    /// The backing slices are compared piecewise, so that slice comparisons
+    /// specialized by `core`, such as `memcmp` for bytes, can be used.
+    fn eq_elements<D>(&self, other: &D) -> bool
+    where
+        D: BaseDeque<T>,
+        T: PartialEq,
+    {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        let (a_front, a_back) = self.as_slices();
+        let (b_front, b_back) = other.as_slices();
+
+        if a_front.len() <= b_front.len() {
+            let (b_mid, b_front_rest) = b_front.split_at(a_front.len());
+            let (a_mid, a_back_rest) = a_back.split_at(b_front_rest.len());
+
+            a_front == b_mid && a_mid == b_front_rest && a_back_rest == b_back
+        } else {
+            let (a_mid, a_front_rest) = a_front.split_at(b_front.len());
+            let (b_mid, b_back_rest) = b_back.split_at(a_front_rest.len());
+
+            a_mid == b_front && a_front_rest == b_mid && a_back == b_back_rest
+        }
+    }
+}
+
+/// Returns the logical index of the first occurrence of `byte` in a byte
+/// deque.
+///
+/// With the `memchr` feature, each backing slice is scanned with
+/// [`memchr::memchr`], which uses SIMD instructions where available.
+fn find_byte<D>(deque: &D, byte: u8) -> Option<usize>
+where
+    D: BaseDeque<u8>,
+{
+    #[cfg(feature = "memchr")]
+    use memchr::memchr;
+
+    #[cfg(not(feature = "memchr"))]
+    fn memchr(byte: u8, haystack: &[u8]) -> Option<usize> {
+        haystack.iter().position(|&b| b == byte)
+    }
+
+    let (front, back) = deque.as_slices();
+
+    memchr(byte, front).or_else(|| memchr(byte, back).map(|index| front.len() + index))
+}
+
+/// The smallest periods of the prefixes of a pattern, where those periods
+/// are at most a third of the prefix length.
+///
+/// A prefix with such a period repeats at least three times, and the next
+/// such period must be more than twice as long, so at most `usize::BITS`
+/// distinct periods can occur.
+struct PrefixPeriods {
+    /// Each run holds a period and the range of prefix lengths with that
+    /// period, in increasing order.
+    runs: [(usize, usize, usize); usize::BITS as usize],
+    len: usize,
+}
+
+impl PrefixPeriods {
+    /// The fraction of a prefix's length which its period may not exceed.
+    const REPEATS: usize = 3;
+
+    fn new() -> PrefixPeriods {
+        PrefixPeriods {
+            runs: [(0, 0, 0); usize::BITS as usize],
+            len: 0,
+        }
+    }
+
+    /// Returns the smallest period of the prefix of length `len`, if it is
+    /// at most a third of `len`.
+    fn get(&self, len: usize) -> Option<usize> {
+        let runs = &self.runs[..self.len];
+        let run = runs.partition_point(|&(_, start, _)| start <= len);
+
+        run.checked_sub(1)
+            .map(|run| runs[run])
+            .filter(|&(_, _, end)| len <= end)
+            .map(|(period, _, _)| period)
+    }
+
+    /// Records that `period` is the smallest period of the prefix of length
+    /// `len`, which must be longer than every prefix recorded so far.
+    fn record(&mut self, period: usize, len: usize) {
+        if period > len / Self::REPEATS {
+            return;
+        }
+
+        match self.runs[..self.len].last_mut() {
+            Some((last, _, end)) if *last == period => *end = len,
+            _ => {
+                self.runs[self.len] = (period, len, len);
+                self.len += 1;
+            }
+        }
+    }
+}
+
+/// Returns `true` if the `pattern_len` elements of `pattern` occur
+/// contiguously in the `text_len` elements of `text`.
+///
+/// This takes linear time and constant space, comparing elements only for
+/// equality. After a mismatch, the pattern is shifted by the period of the
+/// matched prefix if that prefix is highly periodic, and by a third of its
+/// length otherwise, which cannot skip an occurrence. The periods are
+/// learned by first matching the pattern against itself.
+fn occurs_in<'a, T, P, X>(pattern: P, pattern_len: usize, text: X, text_len: usize) -> bool
+where
+    T: PartialEq + 'a,
+    P: Fn(usize) -> &'a T,
+    X: Fn(usize) -> &'a T,
+{
+    let mut periods = PrefixPeriods::new();
+
+    // Matching the pattern against itself from shift 1, the shift upon
+    // first reaching a position is the smallest period of the prefix ending
+    // there. Only shorter prefixes need to be known to compute the next.
+    let (mut shift, mut matched, mut reached) = (1, 0, 1);
+    while shift + matched < pattern_len {
+        if pattern(matched) == pattern(shift + matched) {
+            matched += 1;
+            if shift + matched > reached {
+                reached = shift + matched;
+                periods.record(shift, reached);
+            }
+        } else {
+            (shift, matched) = next_shift(&periods, shift, matched);
+        }
+    }
+
+    let (mut shift, mut matched) = (0, 0);
+    while shift + pattern_len <= text_len {
+        if matched == pattern_len {
+            return true;
+        }
+
+        if pattern(matched) == text(shift + matched) {
+            matched += 1;
+        } else {
+            (shift, matched) = next_shift(&periods, shift, matched);
+        }
+    }
+
+    false
+}
+
+/// Returns the shift and matched length to resume from after a mismatch
+/// following `matched` matching elements at `shift`.
+fn next_shift(periods: &PrefixPeriods, shift: usize, matched: usize) -> (usize, usize) {
+    match periods.get(matched) {
+        Some(period) => (shift + period, matched - period),
+        None => (shift + matched / PrefixPeriods::REPEATS + 1, 0),
+    }
+}
+
+/// An immutable iterator over a deque.
+pub(crate) struct DequeIter<'a, D, T>
+where
+    D: BaseDeque<T>,
+    T: Default,
+{
+    meta: D::Meta,
+    deque: &'a D,
+}
+
+impl<'a, D, T> DequeIter<'a, D, T>
+where
+    D: BaseDeque<T>,
+    T: Default,
+{
+    pub fn new(deque: &'a D) -> DequeIter<'a, D, T> {
+        DequeIter {
+            meta: deque.meta().clone(),
+            deque,
+        }
+    }
+}
+
+impl<'a, D, T> Iterator for DequeIter<'a, D, T>
+where
+    D: BaseDeque<T>,
+    T: Default + 'a,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.meta.free_front()?;
+
+        Some(&self.deque.items()[next])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.meta.len(), Some(self.meta.len()))
+    }
+}
+
+impl<'a, D, T> DoubleEndedIterator for DequeIter<'a, D, T>
+where
+    D: BaseDeque<T>,
+    T: Default + 'a,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let next_back = self.meta.free_back()?;
+
+        Some(&self.deque.items()[next_back])
+    }
+}
+
+/// A draining iterator over a deque.
+pub(crate) struct DequeDrain<'a, D, T>
+where
+    D: BaseDeque<T>,
+    T: Default,
+{
+    meta: MetaDrain<D::Meta>,
+    deque: &'a mut D,
+}
+
+impl<'a, D, T> DequeDrain<'a, D, T>
+where
+    D: BaseDeque<T>,
+    T: Default,
+{
+    fn front(deque: &'a mut D, n: usize) -> Option<DequeDrain<'a, D, T>> {
+        let meta = deque.meta_mut().drain_front(n)?;
+
+        Some(DequeDrain { meta, deque })
+    }
+
+    fn back(deque: &'a mut D, n: usize) -> Option<DequeDrain<'a, D, T>> {
+        let meta = deque.meta_mut().drain_back(n)?;
+
+        Some(DequeDrain { meta, deque })
+    }
+
+    fn as_slices(&self) -> (&[T], &[T]) {
+        let (first, second) = self.meta.as_ranges();
+        let items = self.deque.items();
+
+        (&items[first], &items[second])
+    }
+}
+
+impl<'a, D, T> Iterator for DequeDrain<'a, D, T>
+where
+    D: BaseDeque<T>,
+    T: Default,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.meta.next()?;
+
+        Some(mem::take(&mut self.deque.items_mut()[next]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.meta.size_hint()
+    }
+}
+
+impl<'a, D, T> Drop for DequeDrain<'a, D, T>
+where
+    D: BaseDeque<T>,
+    T: Default,
+{
+    fn drop(&mut self) {
+        for index in &mut self.meta {
+            drop(mem::take(&mut self.deque.items_mut()[index]))
+        }
+    }
+}
+
+/// An iterator which removes elements from the front of a deque in arrays of
+/// `C` elements, stopping when fewer than `C` remain.
+pub(crate) struct DequeDrainChunks<'a, D, T, const C: usize>
+where
+    D: BaseDeque<T>,
+    T: Default,
+{
+    deque: &'a mut D,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, D, T, const C: usize> DequeDrainChunks<'a, D, T, C>
+where
+    D: BaseDeque<T>,
+    T: Default,
+{
+    #[track_caller]
+    fn new(deque: &'a mut D) -> DequeDrainChunks<'a, D, T, C> {
+        assert!(C > 0, "chunk size must be nonzero");
+
+        DequeDrainChunks {
+            deque,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, D, T, const C: usize> Iterator for DequeDrainChunks<'a, D, T, C>
+where
+    D: BaseDeque<T>,
+    T: Default,
+{
+    type Item = [T; C];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut indices = self.deque.meta_mut().drain_front(C)?;
+        let items = self.deque.items_mut();
+
+        Some(core::array::from_fn(|_| {
+            mem::take(&mut items[indices.next().unwrap()])
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let chunks = self.deque.len() / C;
+
+        (chunks, Some(chunks))
+    }
+}
+
+/// Compacts a deque whose elements are being removed in place, such as by
+/// `retain_mut`, and truncates it when dropped.
+///
+/// The deque's length is not changed until the guard is dropped. Until then,
+/// its elements form three runs: those which were processed and kept, those
+/// which were processed and removed, and those not yet processed. When the
+/// guard is dropped, the unprocessed elements are moved back past the
+/// removed ones, which are then dropped. This happens even if processing
+/// panics, so the deque never exposes a removed element, or a default value
+/// left in its place.
+struct Backshift<'a, D, T>
+where
+    D: BaseDeque<T> + ?Sized,
+    T: Default,
+{
+    deque: &'a mut D,
+    /// The length of the deque before any element was removed.
+    len: usize,
+    /// The number of elements processed, which precede any unprocessed ones.
+    processed: usize,
+    /// The number of elements removed, which directly precede any unprocessed
+    /// ones.
+    deleted: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, D, T> Backshift<'a, D, T>
+where
+    D: BaseDeque<T> + ?Sized,
+    T: Default,
+{
+    fn new(deque: &'a mut D) -> Backshift<'a, D, T> {
+        Backshift {
+            len: deque.len(),
+            deque,
+            processed: 0,
+            deleted: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Moves the element at logical index `index` in front of the removed
+    /// elements.
+    fn keep(&mut self, index: usize) {
+        if self.deleted > 0 {
+            let hole = self.deque.physical_index(index - self.deleted).unwrap();
+            let kept = self.deque.physical_index(index).unwrap();
+
+            self.deque.items_mut().swap(hole, kept);
+        }
+    }
+}
+
+impl<'a, D, T> Drop for Backshift<'a, D, T>
+where
+    D: BaseDeque<T> + ?Sized,
+    T: Default,
+{
+    fn drop(&mut self) {
+        for index in self.processed..self.len {
+            self.keep(index);
+        }
+
+        self.deque.truncate(self.len - self.deleted);
+    }
+}
+
+/// An iterator which removes and yields the elements of a deque for which a
+/// predicate returns `true`.
+pub(crate) struct DequeExtractIf<'a, D, T, F>
+where
+    D: BaseDeque<T>,
+    T: Default,
+{
+    shift: Backshift<'a, D, T>,
+    pred: F,
+}
+
+impl<'a, D, T, F> DequeExtractIf<'a, D, T, F>
+where
+    D: BaseDeque<T>,
+    T: Default,
+    F: FnMut(&mut T) -> bool,
+{
+    fn new(deque: &'a mut D, pred: F) -> DequeExtractIf<'a, D, T, F> {
+        DequeExtractIf {
+            shift: Backshift::new(deque),
+            pred,
+        }
+    }
+}
+
+impl<'a, D, T, F> Iterator for DequeExtractIf<'a, D, T, F>
 where
     D: BaseDeque<T>,
     T: Default,
+    F: FnMut(&mut T) -> bool,
 {
-    meta: D::Meta,
-    deque: &'a D,
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let shift = &mut self.shift;
+
+        while shift.processed < shift.len {
+            let index = shift.processed;
+            let extract = (self.pred)(shift.deque.get_mut(index).unwrap());
+            shift.processed += 1;
+
+            if extract {
+                shift.deleted += 1;
+
+                // The default value left behind is dropped with the other
+                // removed slots when the iterator is dropped.
+                return Some(mem::take(shift.deque.get_mut(index).unwrap()));
+            }
+
+            shift.keep(index);
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.shift.len - self.shift.processed))
+    }
 }
 
-impl<'a, D, T> DequeIter<'a, D, T>
+/// A cursor over a deque with editing operations.
+///
+/// The cursor points either at an element of the deque or at a "ghost"
+/// position one past the back, which is where the cursor wraps around between
+/// the back and the front.
+pub(crate) struct DequeCursor<'a, D, T>
 where
     D: BaseDeque<T>,
     T: Default,
 {
-    pub fn new(deque: &'a D) -> DequeIter<'a, D, T> {
-        DequeIter {
-            meta: deque.meta().clone(),
+    deque: &'a mut D,
+    // Equal to the deque's length at the ghost position.
+    index: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, D, T> DequeCursor<'a, D, T>
+where
+    D: BaseDeque<T>,
+    T: Default,
+{
+    pub fn front(deque: &'a mut D) -> DequeCursor<'a, D, T> {
+        DequeCursor {
+            deque,
+            index: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn back(deque: &'a mut D) -> DequeCursor<'a, D, T> {
+        let index = deque.len().saturating_sub(1);
+
+        DequeCursor {
             deque,
+            index,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn index(&self) -> Option<usize> {
+        (self.index < self.deque.len()).then_some(self.index)
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.deque.get_mut(self.index)
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let len = self.deque.len();
+        let next = if self.index == len { 0 } else { self.index + 1 };
+
+        self.deque.get_mut(next)
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = self.index.checked_sub(1).unwrap_or(self.deque.len());
+
+        self.deque.get_mut(prev)
+    }
+
+    pub fn move_next(&mut self) {
+        if self.index == self.deque.len() {
+            self.index = 0;
+        } else {
+            self.index += 1;
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.index.checked_sub(1) {
+            Some(prev) => self.index = prev,
+            None => self.index = self.deque.len(),
+        }
+    }
+
+    pub fn insert_before(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.deque.insert(self.index, item)?;
+        self.index += 1;
+
+        Ok(())
+    }
+
+    pub fn insert_after(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        if self.index == self.deque.len() {
+            // After the ghost position is the front of the deque.
+            self.deque.insert(0, item)?;
+            self.index += 1;
+        } else {
+            self.deque.insert(self.index + 1, item)?;
         }
+
+        Ok(())
+    }
+
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.deque.remove(self.index)
     }
 }
 
-impl<'a, D, T> Iterator for DequeIter<'a, D, T>
+/// A reserved slot at the back of a deque, which becomes an element of the
+/// deque only when committed.
+pub(crate) struct DequeSlot<'a, D, T>
 where
     D: BaseDeque<T>,
-    T: Default + 'a,
+    T: Default,
 {
-    type Item = &'a T;
+    deque: &'a mut D,
+    // The index of the slot in the backing storage.
+    index: usize,
+    committed: bool,
+    _marker: PhantomData<T>,
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let next = self.meta.free_front()?;
+impl<'a, D, T> DequeSlot<'a, D, T>
+where
+    D: BaseDeque<T>,
+    T: Default,
+{
+    pub fn back(deque: &'a mut D) -> Option<DequeSlot<'a, D, T>> {
+        // The slot is the one `commit_back` would commit first.
+        let (first, second) = deque.meta().spare_ranges();
+        let index = first.chain(second).next()?;
 
-        Some(&self.deque.items()[next])
+        // Unused slots may hold stale elements; start from a clean slot.
+        drop(mem::take(&mut deque.items_mut()[index]));
+
+        Some(DequeSlot {
+            deque,
+            index,
+            committed: false,
+            _marker: PhantomData,
+        })
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.meta.len(), Some(self.meta.len()))
+    pub fn get(&self) -> &T {
+        &self.deque.items()[self.index]
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.deque.items_mut()[self.index]
+    }
+
+    pub fn commit(mut self) {
+        // The slot is unused, so there is room for one more element.
+        self.deque.commit_back(1).unwrap();
+        self.committed = true;
     }
 }
 
-impl<'a, D, T> DoubleEndedIterator for DequeIter<'a, D, T>
+impl<'a, D, T> Drop for DequeSlot<'a, D, T>
 where
     D: BaseDeque<T>,
-    T: Default + 'a,
+    T: Default,
 {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        let next_back = self.meta.free_back()?;
-
-        Some(&self.deque.items()[next_back])
+    fn drop(&mut self) {
+        if !self.committed {
+            drop(mem::take(self.get_mut()));
+        }
     }
 }
 
-/// A draining iterator over a deque.
-pub(crate) struct DequeDrain<'a, D, T>
+/// The front element of a deque, which is removed only when committed.
+pub(crate) struct DequePopGuard<'a, D, T>
 where
     D: BaseDeque<T>,
     T: Default,
 {
-    meta: MetaDrain<D::Meta>,
     deque: &'a mut D,
+    // The index of the front element in the backing storage.
+    index: usize,
+    _marker: PhantomData<T>,
 }
 
-impl<'a, D, T> DequeDrain<'a, D, T>
+impl<'a, D, T> DequePopGuard<'a, D, T>
 where
     D: BaseDeque<T>,
     T: Default,
 {
-    fn front(deque: &'a mut D, n: usize) -> Option<DequeDrain<'a, D, T>> {
-        let meta = deque.meta_mut().drain_front(n)?;
+    pub fn front(deque: &'a mut D) -> Option<DequePopGuard<'a, D, T>> {
+        let index = deque.meta().front()?;
 
-        Some(DequeDrain { meta, deque })
+        Some(DequePopGuard {
+            deque,
+            index,
+            _marker: PhantomData,
+        })
     }
 
-    fn back(deque: &'a mut D, n: usize) -> Option<DequeDrain<'a, D, T>> {
-        let meta = deque.meta_mut().drain_back(n)?;
+    pub fn get(&self) -> &T {
+        &self.deque.items()[self.index]
+    }
 
-        Some(DequeDrain { meta, deque })
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.deque.items_mut()[self.index]
+    }
+
+    pub fn into_inner(self) -> T {
+        // The guarded element is the front element, so the deque is nonempty.
+        self.deque.pop_front().unwrap()
     }
 }
 
-impl<'a, D, T> Iterator for DequeDrain<'a, D, T>
+/// A deque which is truncated to its original length when dropped, unless
+/// kept.
+pub(crate) struct DequeTruncateGuard<'a, D, T>
 where
     D: BaseDeque<T>,
     T: Default,
 {
-    type Item = T;
+    deque: &'a mut D,
+    len: usize,
+    kept: bool,
+    _marker: PhantomData<T>,
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let next = self.meta.next()?;
+impl<'a, D, T> DequeTruncateGuard<'a, D, T>
+where
+    D: BaseDeque<T>,
+    T: Default,
+{
+    pub fn new(deque: &'a mut D) -> DequeTruncateGuard<'a, D, T> {
+        let len = deque.len();
 
-        Some(mem::take(&mut self.deque.items_mut()[next]))
+        DequeTruncateGuard {
+            deque,
+            len,
+            kept: false,
+            _marker: PhantomData,
+        }
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.meta.size_hint()
+    pub fn get(&self) -> &D {
+        self.deque
+    }
+
+    pub fn get_mut(&mut self) -> &mut D {
+        self.deque
+    }
+
+    pub fn original_len(&self) -> usize {
+        self.len
+    }
+
+    pub fn keep(mut self) {
+        self.kept = true;
     }
 }
 
-impl<'a, D, T> Drop for DequeDrain<'a, D, T>
+impl<'a, D, T> Drop for DequeTruncateGuard<'a, D, T>
 where
     D: BaseDeque<T>,
     T: Default,
 {
     fn drop(&mut self) {
-        for index in &mut self.meta {
-            drop(mem::take(&mut self.deque.items_mut()[index]))
+        if !self.kept {
+            self.deque.truncate(self.len);
+        }
+    }
+}
+
+/// One half of a deque which has been split into front and back handles.
+///
+/// Both halves share the deque's metadata and storage through `Cell`s, so each
+/// can modify the deque while the other is alive. The public handle types only
+/// expose operations on their own end.
+pub(crate) struct DequeHalf<'a, M, T> {
+    meta: &'a Cell<M>,
+    items: &'a [Cell<T>],
+}
+
+impl<'a, M, T> DequeHalf<'a, M, T>
+where
+    M: Meta + Copy,
+    T: Default,
+{
+    pub fn split<D>(deque: &'a mut D) -> (DequeHalf<'a, M, T>, DequeHalf<'a, M, T>)
+    where
+        D: BaseDeque<T, Meta = M>,
+    {
+        let (meta, items) = deque.meta_and_items_mut();
+        let meta = Cell::from_mut(meta);
+        let items = Cell::from_mut(items).as_slice_of_cells();
+
+        (DequeHalf { meta, items }, DequeHalf { meta, items })
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.meta.get().capacity()
+    }
+
+    pub fn len(&self) -> usize {
+        self.meta.get().len()
+    }
+
+    /// Clones the element at physical index `index`.
+    fn clone_at(&self, index: usize) -> T
+    where
+        T: Clone,
+    {
+        // The element is put back even if cloning it panics.
+        let taken = Untaken {
+            cell: &self.items[index],
+            item: self.items[index].take(),
+        };
+
+        taken.item.clone()
+    }
+
+    pub fn front(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        let front = self.meta.get().front()?;
+
+        Some(self.clone_at(front))
+    }
+
+    pub fn back(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        let back = self.meta.get().back()?;
+
+        Some(self.clone_at(back))
+    }
+
+    pub fn pop_front(&self) -> Option<T> {
+        let mut meta = self.meta.get();
+        let freed = meta.free_front()?;
+        self.meta.set(meta);
+
+        Some(self.items[freed].take())
+    }
+
+    pub fn push_back(&self, item: T) -> Result<(), CapacityError<T>> {
+        let mut meta = self.meta.get();
+
+        match meta.reserve_back() {
+            Some(back) => {
+                self.meta.set(meta);
+                self.items[back].set(item);
+                Ok(())
+            }
+
+            None => Err(CapacityError { item }),
         }
     }
 }
 
+/// A value taken out of a cell, which is put back when dropped.
+struct Untaken<'a, T>
+where
+    T: Default,
+{
+    cell: &'a Cell<T>,
+    item: T,
+}
+
+impl<'a, T> Drop for Untaken<'a, T>
+where
+    T: Default,
+{
+    fn drop(&mut self) {
+        self.cell.set(mem::take(&mut self.item));
+    }
+}
+
 /// An error that occurs when attempting to add an item to a deque which is
 /// already full.
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct CapacityError<T> {
     item: T,
 }
@@ -357,6 +2528,24 @@ impl<T> CapacityError<T> {
     pub fn into_inner(self) -> T {
         self.item
     }
+
+    /// Maps the contained value to a new value by applying a function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::ArrayDeque;
+    /// let mut deque: ArrayDeque<u32, 0> = ArrayDeque::new();
+    ///
+    /// let err = deque.push_back(42).unwrap_err().map(|item| item * 2);
+    /// assert_eq!(err.into_inner(), 84);
+    /// ```
+    pub fn map<U, F>(self, f: F) -> CapacityError<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        CapacityError { item: f(self.item) }
+    }
 }
 
 impl<T> fmt::Display for CapacityError<T> {
@@ -365,8 +2554,204 @@ impl<T> fmt::Display for CapacityError<T> {
     }
 }
 
-#[cfg(feature = "std")]
-impl<T> std::error::Error for CapacityError<T> where T: fmt::Debug {}
+impl<T> core::error::Error for CapacityError<T> where T: fmt::Debug {}
+
+#[cfg(feature = "embedded-io")]
+impl embedded_io::Error for CapacityError<()> {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        embedded_io::ErrorKind::WriteZero
+    }
+}
+
+/// Usage statistics of a deque.
+///
+/// Every change to the number of elements in a deque is counted, whichever
+/// method caused it: for example, [`ArrayDeque::clear`] counts one pop for each
+/// element removed. Rejected pushes are counted when an element could not be
+/// added because the deque was full.
+///
+/// This struct is returned by the `stats` method on [`ArrayDeque`] and
+/// [`SliceDeque`].
+#[cfg(feature = "stats")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DequeStats {
+    high_water_mark: usize,
+    pushes: u64,
+    pops: u64,
+    rejected_pushes: u64,
+}
+
+#[cfg(feature = "stats")]
+impl DequeStats {
+    const fn new() -> DequeStats {
+        DequeStats {
+            high_water_mark: 0,
+            pushes: 0,
+            pops: 0,
+            rejected_pushes: 0,
+        }
+    }
+
+    fn record_len_change(&mut self, old_len: usize, new_len: usize) {
+        if new_len > old_len {
+            self.pushes = self.pushes.saturating_add((new_len - old_len) as u64);
+            self.high_water_mark = self.high_water_mark.max(new_len);
+        } else {
+            self.pops = self.pops.saturating_add((old_len - new_len) as u64);
+        }
+    }
+
+    fn record_rejected(&mut self) {
+        self.rejected_pushes = self.rejected_pushes.saturating_add(1);
+    }
+
+    /// Returns the largest number of elements the deque has held.
+    #[inline]
+    pub fn high_water_mark(&self) -> usize {
+        self.high_water_mark
+    }
+
+    /// Returns the total number of elements added to the deque.
+    #[inline]
+    pub fn pushes(&self) -> u64 {
+        self.pushes
+    }
+
+    /// Returns the total number of elements removed from the deque.
+    #[inline]
+    pub fn pops(&self) -> u64 {
+        self.pops
+    }
+
+    /// Returns the number of elements which could not be added because the
+    /// deque was full.
+    #[inline]
+    pub fn rejected_pushes(&self) -> u64 {
+        self.rejected_pushes
+    }
+}
+
+/// A record of the contents of a deque, used to undo pushes.
+///
+/// This struct is created by the `savepoint` method on [`ArrayDeque`] and
+/// [`SliceDeque`], and consumed by their `rollback_to` method.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Savepoint {
+    // Physical index of the front element.
+    front: usize,
+    len: usize,
+    // Modification generation of the deque when the savepoint was taken.
+    generation: usize,
+}
+
+/// A snapshot of the physical layout of a deque's backing storage.
+///
+/// This struct is returned by the `layout_info` method on [`ArrayDeque`] and
+/// [`SliceDeque`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LayoutInfo {
+    head: usize,
+    len: usize,
+    wrap_len: usize,
+    gap_len: usize,
+}
+
+impl LayoutInfo {
+    /// Returns the index of the first element in the backing storage.
+    ///
+    /// If the deque is empty, this is 0.
+    #[inline]
+    pub fn head(&self) -> usize {
+        self.head
+    }
+
+    /// Returns the number of elements in the deque.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the deque is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements which have wrapped around to the start
+    /// of the backing storage.
+    #[inline]
+    pub fn wrap_len(&self) -> usize {
+        self.wrap_len
+    }
+
+    /// Returns the number of unused slots in the backing storage.
+    ///
+    /// The unused slots follow the last element, wrapping around to the
+    /// start of the backing storage if necessary.
+    #[inline]
+    pub fn gap_len(&self) -> usize {
+        self.gap_len
+    }
+
+    /// Returns `true` if the elements occupy a single contiguous region of
+    /// the backing storage.
+    #[inline]
+    pub fn is_contiguous(&self) -> bool {
+        self.wrap_len == 0
+    }
+}
+
+/// An error that occurs when a deque fills up while being extended with
+/// multiple items.
+///
+/// The error records how many items were added to the deque before it became
+/// full, along with the first item that did not fit.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TryExtendError<T> {
+    accepted: usize,
+    first_rejected: T,
+}
+
+impl<T> TryExtendError<T> {
+    /// Returns the number of items that were added to the deque.
+    pub fn accepted(&self) -> usize {
+        self.accepted
+    }
+
+    /// Returns a reference to the first item that was not added to the deque.
+    pub fn first_rejected(&self) -> &T {
+        &self.first_rejected
+    }
+
+    /// Consumes the error, returning the first item that was not added to the
+    /// deque.
+    pub fn into_first_rejected(self) -> T {
+        self.first_rejected
+    }
+
+    /// Maps the rejected item to a new value by applying a function.
+    pub fn map<U, F>(self, f: F) -> TryExtendError<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        TryExtendError {
+            accepted: self.accepted,
+            first_rejected: f(self.first_rejected),
+        }
+    }
+}
+
+impl<T> fmt::Display for TryExtendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "deque capacity exceeded after adding {} items",
+            self.accepted
+        )
+    }
+}
+
+impl<T> core::error::Error for TryExtendError<T> where T: fmt::Debug {}
 
 pub(crate) enum DequeEnd {
     Front,
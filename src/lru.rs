@@ -0,0 +1,251 @@
+//! A fixed-capacity least-recently-used cache.
+
+use core::fmt;
+
+use crate::{array_deque, ArrayDeque, BaseDeque};
+
+/// A least-recently-used cache holding up to `N` entries, backed by an array.
+///
+/// Entries are kept in an [`ArrayDeque`] in order of use, from least to most
+/// recently used. Looking up an entry with [`get`] moves it to the back, and
+/// inserting into a full cache evicts the entry at the front. Keys are found
+/// by linear search, which is fast for the small capacities this type is
+/// intended for.
+///
+/// [`get`]: ArrayLru::get
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::lru::ArrayLru;
+/// let mut cache: ArrayLru<u32, &str, 2> = ArrayLru::new();
+///
+/// cache.insert(1, "one");
+/// cache.insert(2, "two");
+/// assert_eq!(cache.get(&1), Some(&"one"));
+///
+/// // Key 2 is now the least recently used, so it is evicted.
+/// assert_eq!(cache.insert(3, "three"), Some((2, "two")));
+/// assert!(cache.iter().eq([(&1, &"one"), (&3, &"three")]));
+/// ```
+#[derive(Clone, Default)]
+pub struct ArrayLru<K, V, const N: usize>
+where
+    K: Default + PartialEq,
+    V: Default,
+{
+    entries: ArrayDeque<(K, V), N>,
+}
+
+impl<K, V, const N: usize> ArrayLru<K, V, N>
+where
+    K: Default + PartialEq,
+    V: Default,
+{
+    /// Constructs a new, empty `ArrayLru<K, V, N>`.
+    pub fn new() -> Self {
+        ArrayLru {
+            entries: ArrayDeque::new(),
+        }
+    }
+
+    /// Returns the maximum number of entries the cache may hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of entries in the cache.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the cache is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn position(&self, key: &K) -> Option<usize> {
+        self.entries.iter().position(|(k, _)| k == key)
+    }
+
+    /// Moves the entry at logical index `index` to the back of the deque.
+    fn touch(&mut self, index: usize) {
+        let len = self.len();
+        BaseDeque::rotate_range_left(&mut self.entries, index, len);
+    }
+
+    /// Returns `true` if the cache contains an entry for `key`.
+    ///
+    /// This does not mark the entry as used.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.position(key).is_some()
+    }
+
+    /// Returns a reference to the value for `key` and marks it as the most
+    /// recently used, or returns `None` if there is no such entry.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_mut(key).map(|v| &*v)
+    }
+
+    /// Returns a mutable reference to the value for `key` and marks it as the
+    /// most recently used, or returns `None` if there is no such entry.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.position(key)?;
+        self.touch(index);
+
+        self.entries.back_mut().map(|(_, v)| v)
+    }
+
+    /// Returns a reference to the value for `key` without marking it as used,
+    /// or `None` if there is no such entry.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.entries.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Inserts an entry as the most recently used, returning the entry it
+    /// displaced.
+    ///
+    /// If the cache already contained an entry for `key`, that entry is
+    /// replaced and returned. Otherwise, if the cache was full, the least
+    /// recently used entry is evicted and returned. If the cache has a
+    /// capacity of zero, the new entry itself is returned.
+    pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        let displaced = match self.position(&key) {
+            Some(index) => BaseDeque::remove(&mut self.entries, index),
+            None if self.entries.is_full() => self.entries.pop_front(),
+            None => None,
+        };
+
+        match self.entries.push_back((key, value)) {
+            Ok(()) => displaced,
+            Err(e) => Some(e.into_inner()),
+        }
+    }
+
+    /// Removes the entry for `key`, returning its value, or `None` if there
+    /// is no such entry.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self.position(key)?;
+
+        BaseDeque::remove(&mut self.entries, index).map(|(_, v)| v)
+    }
+
+    /// Removes and returns the least recently used entry, or `None` if the
+    /// cache is empty.
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        self.entries.pop_front()
+    }
+
+    /// Removes all entries from the cache.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns an iterator over the entries of the cache, from least to most
+    /// recently used.
+    pub fn iter(&self) -> Iter<'_, K, V, N> {
+        Iter {
+            inner: self.entries.iter(),
+        }
+    }
+}
+
+impl<K, V, const N: usize> fmt::Debug for ArrayLru<K, V, N>
+where
+    K: Default + PartialEq + fmt::Debug,
+    V: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// An iterator over the entries of an [`ArrayLru`], from least to most
+/// recently used.
+///
+/// This struct is created by the [`iter`] method on [`ArrayLru`].
+///
+/// [`iter`]: ArrayLru::iter
+pub struct Iter<'a, K, V, const N: usize>
+where
+    K: Default,
+    V: Default,
+{
+    inner: array_deque::Iter<'a, (K, V), N>,
+}
+
+impl<'a, K, V, const N: usize> Iterator for Iter<'a, K, V, N>
+where
+    K: Default,
+    V: Default,
+{
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, v)| (k, v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V, const N: usize> DoubleEndedIterator for Iter<'a, K, V, N>
+where
+    K: Default,
+    V: Default,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, v)| (k, v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    fn keys<const N: usize>(cache: &ArrayLru<u32, u32, N>) -> Vec<u32> {
+        cache.iter().map(|(k, _)| *k).collect()
+    }
+
+    #[test]
+    fn recency_order() {
+        let mut cache: ArrayLru<u32, u32, 3> = ArrayLru::new();
+
+        assert_eq!(cache.insert(1, 10), None);
+        assert_eq!(cache.insert(2, 20), None);
+        assert_eq!(cache.insert(3, 30), None);
+        assert_eq!(cache.get(&1), Some(&10));
+        assert_eq!(cache.peek(&2), Some(&20));
+        assert_eq!(keys(&cache), [2, 3, 1]);
+
+        assert_eq!(cache.insert(3, 31), Some((3, 30)));
+        assert_eq!(keys(&cache), [2, 1, 3]);
+
+        assert_eq!(cache.insert(4, 40), Some((2, 20)));
+        assert_eq!(keys(&cache), [1, 3, 4]);
+
+        *cache.get_mut(&1).unwrap() += 1;
+        assert_eq!(cache.remove(&3), Some(31));
+        assert_eq!(cache.pop_lru(), Some((4, 40)));
+        assert_eq!(cache.pop_lru(), Some((1, 11)));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut cache: ArrayLru<u32, u32, 0> = ArrayLru::new();
+
+        assert_eq!(cache.insert(1, 10), Some((1, 10)));
+        assert_eq!(cache.get(&1), None);
+    }
+}
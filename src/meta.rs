@@ -1,6 +1,8 @@
 use core::{num::NonZeroUsize, ops::Range};
 
 use crate::DequeEnd;
+#[cfg(feature = "stats")]
+use crate::DequeStats;
 
 /// Metadata tracking the layout of the deque's backing array.
 #[derive(Copy, Clone, Debug)]
@@ -42,6 +44,29 @@ pub trait Meta: Clone + Sized {
     /// Sets the layout of the deque's backing store.
     fn set_layout(&mut self, layout: MetaLayout);
 
+    /// Returns the usage statistics of the deque.
+    #[cfg(feature = "stats")]
+    fn stats(&self) -> &DequeStats;
+
+    /// Returns the usage statistics of the deque mutably.
+    #[cfg(feature = "stats")]
+    fn stats_mut(&mut self) -> &mut DequeStats;
+
+    /// Returns the modification generation of the deque.
+    ///
+    /// The generation changes whenever the deque is modified other than by
+    /// pushing to either end, which is how savepoints are invalidated.
+    fn generation(&self) -> usize;
+
+    /// Sets the modification generation of the deque.
+    fn set_generation(&mut self, generation: usize);
+
+    /// Invalidates any savepoints taken of the deque.
+    #[inline]
+    fn invalidate_savepoints(&mut self) {
+        self.set_generation(self.generation().wrapping_add(1));
+    }
+
     /// Returns the number of elements in the deque.
     fn len(&self) -> usize {
         match self.layout() {
@@ -64,7 +89,7 @@ pub trait Meta: Clone + Sized {
             MetaLayout::Linear { first, len } => (first..first + len.get(), 0..0),
             MetaLayout::Wrapped { wrap_len, gap_len } => {
                 let start = wrap_len.get() + gap_len;
-                (start..self.len(), 0..wrap_len.get())
+                (start..self.capacity(), 0..wrap_len.get())
             }
         }
     }
@@ -122,7 +147,10 @@ pub trait Meta: Clone + Sized {
 
     /// Reserves an index at the front of the deque.
     fn reserve_front(&mut self) -> Option<usize> {
-        if self.capacity() == 0 {
+        if self.len() == self.capacity() {
+            #[cfg(feature = "stats")]
+            self.stats_mut().record_rejected();
+
             return None;
         }
 
@@ -140,7 +168,7 @@ pub trait Meta: Clone + Sized {
 
             MetaLayout::Linear { first: 0, len } => {
                 self.set_layout(MetaLayout::Wrapped {
-                    wrap_len: NonZeroUsize::new(1).unwrap(),
+                    wrap_len: len,
                     gap_len: self.capacity() - (len.get() + 1),
                 });
 
@@ -176,7 +204,10 @@ pub trait Meta: Clone + Sized {
 
     /// Reserves an index at the back of the deque.
     fn reserve_back(&mut self) -> Option<usize> {
-        if self.capacity() == 0 {
+        if self.len() == self.capacity() {
+            #[cfg(feature = "stats")]
+            self.stats_mut().record_rejected();
+
             return None;
         }
 
@@ -418,6 +449,60 @@ pub trait Meta: Clone + Sized {
 
         Some(drain)
     }
+
+    /// Returns the ranges of unused indices, in the order in which
+    /// [`reserve_back`](Meta::reserve_back) would reserve them.
+    fn spare_ranges(&self) -> (Range<usize>, Range<usize>) {
+        match self.layout() {
+            MetaLayout::Empty => (0..self.capacity(), 0..0),
+            MetaLayout::Linear { first, len } => (first + len.get()..self.capacity(), 0..first),
+            MetaLayout::Wrapped { wrap_len, gap_len } => {
+                (wrap_len.get()..wrap_len.get() + gap_len, 0..0)
+            }
+        }
+    }
+
+    /// Reserves `n` indices at the back of the deque.
+    ///
+    /// The reserved indices are the first `n` indices of the ranges returned by
+    /// [`spare_ranges`](Meta::spare_ranges). If fewer than `n` indices are
+    /// unused, `None` is returned and the layout is unchanged.
+    fn commit_back(&mut self, n: usize) -> Option<()> {
+        if n > self.capacity() - self.len() {
+            return None;
+        }
+
+        let new_layout = match (self.layout(), NonZeroUsize::new(n)) {
+            (_, None) => return Some(()),
+
+            (MetaLayout::Empty, Some(len)) => MetaLayout::Linear { first: 0, len },
+
+            (MetaLayout::Linear { first, len }, Some(_)) => {
+                let new_len = len.get() + n;
+
+                match NonZeroUsize::new((first + new_len).saturating_sub(self.capacity())) {
+                    Some(wrap_len) => MetaLayout::Wrapped {
+                        wrap_len,
+                        gap_len: first - wrap_len.get(),
+                    },
+
+                    None => MetaLayout::Linear {
+                        first,
+                        len: NonZeroUsize::new(new_len).unwrap(),
+                    },
+                }
+            }
+
+            (MetaLayout::Wrapped { wrap_len, gap_len }, Some(_)) => MetaLayout::Wrapped {
+                wrap_len: NonZeroUsize::new(wrap_len.get() + n).unwrap(),
+                gap_len: gap_len - n,
+            },
+        };
+
+        self.set_layout(new_layout);
+
+        Some(())
+    }
 }
 
 pub struct MetaDrain<M>
@@ -464,6 +549,33 @@ where
     }
 }
 
+impl<M> MetaDrain<M>
+where
+    M: Meta,
+{
+    /// Returns the ranges of indices which have not yet been drained, in
+    /// front-to-back order.
+    pub fn as_ranges(&self) -> (Range<usize>, Range<usize>) {
+        let (mut first, mut second) = self.meta.as_ranges();
+
+        match self.end {
+            DequeEnd::Front => {
+                let first_len = self.remaining.min(first.len());
+                first.end = first.start + first_len;
+                second.end = second.start + (self.remaining - first_len);
+            }
+
+            DequeEnd::Back => {
+                let second_len = self.remaining.min(second.len());
+                second.start = second.end - second_len;
+                first.start = first.end - (self.remaining - second_len);
+            }
+        }
+
+        (first, second)
+    }
+}
+
 impl<M> Iterator for MetaDrain<M>
 where
     M: Meta,
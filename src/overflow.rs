@@ -0,0 +1,370 @@
+//! Deques which handle overflow on behalf of the caller.
+
+use core::fmt;
+
+use crate::ArrayDeque;
+
+/// An element which was lost because a deque was full.
+///
+/// This is passed to the hook of an [`OverflowDeque`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overflow<T> {
+    /// An element was removed from one end of the deque to make room for an
+    /// element pushed to the other end.
+    Evicted(T),
+
+    /// An element could not be pushed because the deque was full.
+    Rejected(T),
+}
+
+impl<T> Overflow<T> {
+    /// Consumes the `Overflow`, returning the lost element.
+    pub fn into_inner(self) -> T {
+        match self {
+            Overflow::Evicted(item) | Overflow::Rejected(item) => item,
+        }
+    }
+}
+
+/// An [`ArrayDeque`] which passes elements lost to overflow to a hook.
+///
+/// Elements are lost either when a push is rejected because the deque is
+/// full, or when an element is evicted to make room by one of the
+/// `_overwrite` methods. In both cases, the lost element is passed to the
+/// hook as an [`Overflow`], so that drops can be counted or logged in one
+/// place rather than at every push site.
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::overflow::{Overflow, OverflowDeque};
+/// let mut dropped = Vec::new();
+/// let mut log = OverflowDeque::<_, 2, _>::new(|lost: Overflow<&str>| dropped.push(lost));
+///
+/// log.push_back_overwrite("boot");
+/// log.push_back_overwrite("link up");
+/// log.push_back_overwrite("link down");
+/// log.push_front("urgent");
+///
+/// assert_eq!(
+///     dropped,
+///     [Overflow::Evicted("boot"), Overflow::Rejected("urgent")]
+/// );
+/// ```
+pub struct OverflowDeque<T, const N: usize, F>
+where
+    T: Default,
+    F: FnMut(Overflow<T>),
+{
+    deque: ArrayDeque<T, N>,
+    hook: F,
+}
+
+impl<T, const N: usize, F> OverflowDeque<T, N, F>
+where
+    T: Default,
+    F: FnMut(Overflow<T>),
+{
+    /// Constructs a new, empty `OverflowDeque` which passes lost elements to
+    /// `hook`.
+    pub fn new(hook: F) -> Self {
+        OverflowDeque::from_parts(ArrayDeque::new(), hook)
+    }
+
+    /// Constructs an `OverflowDeque` from an existing deque and a hook.
+    pub fn from_parts(deque: ArrayDeque<T, N>, hook: F) -> Self {
+        OverflowDeque { deque, hook }
+    }
+
+    /// Consumes the `OverflowDeque`, returning the deque and the hook.
+    pub fn into_parts(self) -> (ArrayDeque<T, N>, F) {
+        (self.deque, self.hook)
+    }
+
+    /// Returns a reference to the underlying deque.
+    #[inline]
+    pub fn deque(&self) -> &ArrayDeque<T, N> {
+        &self.deque
+    }
+
+    /// Returns a mutable reference to the underlying deque.
+    ///
+    /// Elements pushed directly to the underlying deque bypass the hook.
+    #[inline]
+    pub fn deque_mut(&mut self) -> &mut ArrayDeque<T, N> {
+        &mut self.deque
+    }
+
+    /// Appends an element to the back of the deque.
+    ///
+    /// If the deque is full, the element is passed to the hook as
+    /// [`Overflow::Rejected`] and `false` is returned.
+    pub fn push_back(&mut self, item: T) -> bool {
+        match self.deque.push_back(item) {
+            Ok(()) => true,
+            Err(e) => {
+                (self.hook)(Overflow::Rejected(e.into_inner()));
+                false
+            }
+        }
+    }
+
+    /// Prepends an element to the front of the deque.
+    ///
+    /// If the deque is full, the element is passed to the hook as
+    /// [`Overflow::Rejected`] and `false` is returned.
+    pub fn push_front(&mut self, item: T) -> bool {
+        match self.deque.push_front(item) {
+            Ok(()) => true,
+            Err(e) => {
+                (self.hook)(Overflow::Rejected(e.into_inner()));
+                false
+            }
+        }
+    }
+
+    /// Appends an element to the back of the deque, evicting the front
+    /// element if the deque is full.
+    ///
+    /// The evicted element is passed to the hook as [`Overflow::Evicted`]. If
+    /// the deque has a capacity of zero, the pushed element is passed to the
+    /// hook as [`Overflow::Rejected`] instead.
+    pub fn push_back_overwrite(&mut self, item: T) {
+        if self.deque.is_full() {
+            if let Some(evicted) = self.deque.pop_front() {
+                (self.hook)(Overflow::Evicted(evicted));
+            }
+        }
+
+        self.push_back(item);
+    }
+
+    /// Prepends an element to the front of the deque, evicting the back
+    /// element if the deque is full.
+    ///
+    /// The evicted element is passed to the hook as [`Overflow::Evicted`]. If
+    /// the deque has a capacity of zero, the pushed element is passed to the
+    /// hook as [`Overflow::Rejected`] instead.
+    pub fn push_front_overwrite(&mut self, item: T) {
+        if self.deque.is_full() {
+            if let Some(evicted) = self.deque.pop_back() {
+                (self.hook)(Overflow::Evicted(evicted));
+            }
+        }
+
+        self.push_front(item);
+    }
+
+    /// Removes the first element and returns it, or `None` if the deque is
+    /// empty.
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    /// Removes the last element and returns it, or `None` if the deque is
+    /// empty.
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T, const N: usize, F> fmt::Debug for OverflowDeque<T, N, F>
+where
+    T: Default + fmt::Debug,
+    F: FnMut(Overflow<T>),
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OverflowDeque")
+            .field("deque", &self.deque)
+            .finish_non_exhaustive()
+    }
+}
+
+/// What a [`PolicyDeque`] does when an element is pushed while it is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OverflowPolicy {
+    /// The pushed element is rejected, leaving the deque unchanged.
+    RejectNew,
+
+    /// The oldest element, at the front, is removed to make room.
+    DropOldest,
+
+    /// The newest element, at the back, is removed to make room.
+    DropNewest,
+}
+
+/// An [`ArrayDeque`] used as a queue, with a fixed policy for handling pushes
+/// while it is full.
+///
+/// Elements are pushed to the back and popped from the front. When a push
+/// finds the deque full, the [`OverflowPolicy`] chosen at construction
+/// decides which element is displaced, so the decision need not be repeated
+/// at every call site.
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::overflow::{OverflowPolicy, PolicyDeque};
+/// let mut latest = PolicyDeque::<u32, 2>::new(OverflowPolicy::DropOldest);
+///
+/// assert_eq!(latest.push_back(1), None);
+/// assert_eq!(latest.push_back(2), None);
+/// assert_eq!(latest.push_back(3), Some(1));
+///
+/// assert_eq!(latest.pop_front(), Some(2));
+/// assert_eq!(latest.pop_front(), Some(3));
+/// ```
+#[derive(Clone)]
+pub struct PolicyDeque<T, const N: usize>
+where
+    T: Default,
+{
+    deque: ArrayDeque<T, N>,
+    policy: OverflowPolicy,
+}
+
+impl<T, const N: usize> PolicyDeque<T, N>
+where
+    T: Default,
+{
+    /// Constructs a new, empty `PolicyDeque` with the given overflow policy.
+    pub fn new(policy: OverflowPolicy) -> Self {
+        PolicyDeque::from_parts(ArrayDeque::new(), policy)
+    }
+
+    /// Constructs a `PolicyDeque` from an existing deque and a policy.
+    pub fn from_parts(deque: ArrayDeque<T, N>, policy: OverflowPolicy) -> Self {
+        PolicyDeque { deque, policy }
+    }
+
+    /// Consumes the `PolicyDeque`, returning the underlying deque.
+    pub fn into_inner(self) -> ArrayDeque<T, N> {
+        self.deque
+    }
+
+    /// Returns the overflow policy of the deque.
+    #[inline]
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Returns a reference to the underlying deque.
+    #[inline]
+    pub fn deque(&self) -> &ArrayDeque<T, N> {
+        &self.deque
+    }
+
+    /// Returns a mutable reference to the underlying deque.
+    ///
+    /// Elements pushed directly to the underlying deque bypass the policy.
+    #[inline]
+    pub fn deque_mut(&mut self) -> &mut ArrayDeque<T, N> {
+        &mut self.deque
+    }
+
+    /// Appends an element to the back of the deque, applying the overflow
+    /// policy if the deque is full.
+    ///
+    /// Returns the element displaced by the policy, if any: the rejected
+    /// `item` under [`OverflowPolicy::RejectNew`], or the removed element
+    /// under the drop policies. If the deque has a capacity of zero, `item`
+    /// is always returned.
+    pub fn push_back(&mut self, item: T) -> Option<T> {
+        let displaced = if self.deque.is_full() {
+            match self.policy {
+                OverflowPolicy::RejectNew => return Some(item),
+                OverflowPolicy::DropOldest => self.deque.pop_front(),
+                OverflowPolicy::DropNewest => self.deque.pop_back(),
+            }
+        } else {
+            None
+        };
+
+        match self.deque.push_back(item) {
+            Ok(()) => displaced,
+            Err(e) => Some(e.into_inner()),
+        }
+    }
+
+    /// Removes the first element and returns it, or `None` if the deque is
+    /// empty.
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    /// Removes the last element and returns it, or `None` if the deque is
+    /// empty.
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for PolicyDeque<T, N>
+where
+    T: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PolicyDeque")
+            .field("deque", &self.deque)
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_lost_elements() {
+        let mut evicted = 0;
+        let mut rejected = 0;
+
+        let mut deque = OverflowDeque::<u8, 2, _>::new(|lost| match lost {
+            Overflow::Evicted(_) => evicted += 1,
+            Overflow::Rejected(_) => rejected += 1,
+        });
+
+        assert!(deque.push_back(1));
+        assert!(deque.push_back(2));
+        assert!(!deque.push_back(3));
+        deque.push_front_overwrite(0);
+        deque.push_back_overwrite(3);
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_back(), Some(3));
+
+        assert_eq!((evicted, rejected), (2, 1));
+    }
+
+    #[test]
+    fn zero_capacity_rejects() {
+        let mut lost = None;
+        let mut deque = OverflowDeque::<u8, 0, _>::new(|o| lost = Some(o));
+
+        deque.push_back_overwrite(7);
+        assert_eq!(lost, Some(Overflow::Rejected(7)));
+    }
+
+    #[test]
+    fn policies_displace_expected_element() {
+        let cases = [
+            (OverflowPolicy::RejectNew, 3, [1, 2]),
+            (OverflowPolicy::DropOldest, 1, [2, 3]),
+            (OverflowPolicy::DropNewest, 2, [1, 3]),
+        ];
+
+        for (policy, displaced, remaining) in cases.iter().copied() {
+            let mut deque = PolicyDeque::<u8, 2>::new(policy);
+
+            assert_eq!(deque.push_back(1), None);
+            assert_eq!(deque.push_back(2), None);
+            assert_eq!(deque.push_back(3), Some(displaced));
+            assert_eq!(deque.pop_front(), Some(remaining[0]));
+            assert_eq!(deque.pop_front(), Some(remaining[1]));
+        }
+    }
+}
@@ -0,0 +1,275 @@
+//! A [`log`] sink which keeps the most recent records in a fixed-size ring.
+//!
+//! [`log`]: https://docs.rs/log
+
+use core::{cell::RefCell, fmt};
+
+use critical_section::Mutex;
+use log::{LevelFilter, Log, Metadata, Record};
+
+use crate::TextDeque;
+
+/// A logger which formats records into a ring of `N` bytes of text,
+/// discarding the oldest lines to make room for new ones.
+///
+/// Each record is written as a single line of the form
+/// `LEVEL target: message`. Whole lines are discarded from the front of the
+/// ring, so the history always begins at the start of a record. A record
+/// longer than `LINE` bytes or than the ring is cut from the front, keeping
+/// its end.
+///
+/// Each record is formatted into a buffer of `LINE` bytes on the stack before
+/// the critical section is entered, so formatting does not delay interrupts.
+/// Logging a record therefore uses `LINE` bytes of stack, and takes time
+/// proportional to `LINE` to initialize the buffer, however large the ring.
+///
+/// Like [`IsrDeque`](crate::isr_deque::IsrDeque), the ring is accessed inside
+/// a critical section, so a `RingLogger` can be placed in a `static`,
+/// installed with [`log::set_logger`], and drained after a fault to dump the
+/// recent log history.
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::ring_log::RingLogger;
+/// use log::{LevelFilter, Log, Record};
+///
+/// static LOGGER: RingLogger<56> = RingLogger::new(LevelFilter::Info);
+///
+/// let args = ["boot", "sensor online", "sensor offline"];
+/// for message in args.iter() {
+///     LOGGER.log(&Record::builder().args(format_args!("{}", message)).target("app").build());
+/// }
+///
+/// // The oldest line was discarded to make room.
+/// LOGGER.drain_with(|first, second| {
+///     assert_eq!([first, second].concat(), "INFO app: sensor online\nINFO app: sensor offline\n");
+/// });
+/// assert!(LOGGER.is_empty());
+/// ```
+pub struct RingLogger<const N: usize, const LINE: usize = 128> {
+    text: Mutex<RefCell<Option<TextDeque<N>>>>,
+    level: LevelFilter,
+}
+
+impl<const N: usize, const LINE: usize> RingLogger<N, LINE> {
+    /// Constructs a new, empty `RingLogger` which records messages at `level`
+    /// and above.
+    pub const fn new(level: LevelFilter) -> Self {
+        RingLogger {
+            text: Mutex::new(RefCell::new(None)),
+            level,
+        }
+    }
+
+    /// Returns the maximum number of bytes of text the ring may hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the most verbose level which is recorded.
+    #[inline]
+    pub fn level(&self) -> LevelFilter {
+        self.level
+    }
+
+    /// Calls `f` with exclusive access to the ring inside a critical section.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within `f`, or if a record is logged from within
+    /// `f`.
+    pub fn with<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut TextDeque<N>) -> R,
+    {
+        critical_section::with(|cs| {
+            let mut text = self.text.borrow_ref_mut(cs);
+            f(text.get_or_insert_with(TextDeque::new))
+        })
+    }
+
+    /// Returns the number of bytes of text in the ring.
+    pub fn len(&self) -> usize {
+        self.with(|text| text.len())
+    }
+
+    /// Returns `true` if the ring is empty.
+    pub fn is_empty(&self) -> bool {
+        self.with(|text| text.is_empty())
+    }
+
+    /// Calls `f` with the text in the ring, oldest first, as two string
+    /// slices, then clears the ring.
+    ///
+    /// `f` runs inside a critical section.
+    pub fn drain_with<F>(&self, f: F)
+    where
+        F: FnOnce(&str, &str),
+    {
+        self.with(|text| {
+            let (first, second) = text.as_strs();
+            f(first, second);
+            text.clear();
+        })
+    }
+}
+
+impl<const N: usize, const LINE: usize> Log for RingLogger<N, LINE> {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        // Format outside the critical section, so interrupts are only held
+        // off while the text is copied in.
+        let mut record_text = TailWriter {
+            text: TextDeque::<LINE>::new(),
+            limit: LINE.min(N),
+        };
+        // Writing to a `TailWriter` cannot fail.
+        let _ = write_record(&mut record_text, record);
+        let (first, second) = record_text.text.as_strs();
+        let len = first.len() + second.len();
+
+        self.with(|text| {
+            // The record is at most `N` bytes long, so at worst this empties
+            // the ring.
+            while N - text.len() < len {
+                discard_line(text);
+            }
+
+            // Room was made for the whole record above.
+            let _ = text.push_str(first);
+            let _ = text.push_str(second);
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+fn write_record<W>(w: &mut W, record: &Record<'_>) -> fmt::Result
+where
+    W: fmt::Write,
+{
+    writeln!(
+        w,
+        "{} {}: {}",
+        record.level(),
+        record.target(),
+        record.args()
+    )
+}
+
+/// Removes the first line, including its terminating newline, from `text`.
+fn discard_line<const N: usize>(text: &mut TextDeque<N>) {
+    let line_len: usize = text
+        .chars()
+        .position(|ch| ch == '\n')
+        .map_or(text.len(), |pos| {
+            text.chars().take(pos + 1).map(char::len_utf8).sum()
+        });
+
+    text.truncate_front(text.len() - line_len);
+}
+
+/// A writer which appends to `text`, discarding characters from the front to
+/// make room, so that it keeps the last `limit` bytes written to it.
+struct TailWriter<const N: usize> {
+    text: TextDeque<N>,
+    /// The number of bytes to keep, at most `N`.
+    limit: usize,
+}
+
+impl<const N: usize> fmt::Write for TailWriter<N> {
+    fn write_str(&mut self, mut s: &str) -> fmt::Result {
+        if s.len() > self.limit {
+            let mut start = s.len() - self.limit;
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+
+            s = &s[start..];
+            self.text.clear();
+        }
+
+        while self.limit - self.text.len() < s.len() {
+            self.text.pop_char_front();
+        }
+
+        self.text.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate alloc;
+    use alloc::string::String;
+
+    use log::Level;
+
+    fn log<const N: usize, const LINE: usize>(
+        logger: &RingLogger<N, LINE>,
+        level: Level,
+        message: &str,
+    ) {
+        logger.log(
+            &Record::builder()
+                .args(format_args!("{}", message))
+                .level(level)
+                .target("t")
+                .build(),
+        );
+    }
+
+    fn contents<const N: usize, const LINE: usize>(logger: &RingLogger<N, LINE>) -> String {
+        logger.with(|text| text.chars().collect())
+    }
+
+    #[test]
+    fn filters_by_level() {
+        let logger: RingLogger<64> = RingLogger::new(LevelFilter::Warn);
+
+        log(&logger, Level::Info, "ignored");
+        log(&logger, Level::Error, "kept");
+        assert_eq!(contents(&logger), "ERROR t: kept\n");
+    }
+
+    #[test]
+    fn discards_whole_lines() {
+        let logger: RingLogger<21> = RingLogger::new(LevelFilter::Trace);
+
+        log(&logger, Level::Warn, "a");
+        log(&logger, Level::Warn, "bb");
+        assert_eq!(logger.len(), 21);
+
+        // Needs 12 bytes, so both earlier lines go.
+        log(&logger, Level::Warn, "ccc");
+        assert_eq!(contents(&logger), "WARN t: ccc\n");
+    }
+
+    #[test]
+    fn oversized_record_keeps_its_end() {
+        let logger: RingLogger<8> = RingLogger::new(LevelFilter::Trace);
+
+        log(&logger, Level::Info, "first");
+        log(&logger, Level::Info, "much too long ñ");
+        assert_eq!(contents(&logger), "long ñ\n");
+    }
+
+    #[test]
+    fn record_longer_than_line_keeps_its_end() {
+        let logger: RingLogger<32, 12> = RingLogger::new(LevelFilter::Trace);
+
+        log(&logger, Level::Info, "a");
+        log(&logger, Level::Info, "much too long ñ");
+        assert_eq!(contents(&logger), "INFO t: a\ntoo long ñ\n");
+    }
+}
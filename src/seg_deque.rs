@@ -0,0 +1,401 @@
+//! An unbounded deque made of fixed-capacity segments.
+
+use alloc::{
+    boxed::Box,
+    collections::{vec_deque, VecDeque},
+};
+use core::{
+    fmt,
+    iter::{self, FlatMap, FromIterator},
+};
+
+use crate::{slice_deque::SliceMeta, BaseDeque, DequeIter};
+
+/// A fixed-capacity deque whose backing slice is allocated directly on the
+/// heap.
+///
+/// Boxing an `ArrayDeque<T, CHUNK>` would build the whole segment on the
+/// stack first, which overflows the stack for large segments.
+struct Segment<T> {
+    meta: SliceMeta,
+    items: Box<[T]>,
+}
+
+impl<T> Segment<T>
+where
+    T: Default,
+{
+    fn new(capacity: usize) -> Self {
+        Segment {
+            meta: SliceMeta::empty(capacity),
+            items: iter::repeat_with(T::default).take(capacity).collect(),
+        }
+    }
+}
+
+impl<T> BaseDeque<T> for Segment<T>
+where
+    T: Default,
+{
+    type Meta = SliceMeta;
+
+    #[inline(always)]
+    fn meta(&self) -> &Self::Meta {
+        &self.meta
+    }
+
+    #[inline(always)]
+    fn raw_meta_mut(&mut self) -> &mut Self::Meta {
+        &mut self.meta
+    }
+
+    #[inline(always)]
+    fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    #[inline(always)]
+    fn raw_items_mut(&mut self) -> &mut [T] {
+        &mut self.items
+    }
+
+    #[inline(always)]
+    fn raw_meta_and_items_mut(&mut self) -> (&mut Self::Meta, &mut [T]) {
+        (&mut self.meta, &mut self.items)
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        self.items.len()
+    }
+}
+
+/// An unbounded double-ended queue made of heap-allocated segments, each
+/// holding up to `CHUNK` elements.
+///
+/// When the segment at one end fills up, a new segment is allocated rather
+/// than growing a single buffer, so elements are never moved once pushed and
+/// growth never copies the contents of the deque. This makes `SegDeque` a
+/// drop-in escape hatch for a workload which outgrows a fixed-capacity deque.
+///
+/// The most recently emptied segment is kept for reuse, so a deque whose
+/// length oscillates around a segment boundary does not repeatedly allocate.
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::seg_deque::SegDeque;
+/// let mut deque: SegDeque<u32, 4> = SegDeque::new();
+///
+/// for i in 0..10 {
+///     deque.push_back(i);
+/// }
+/// deque.push_front(100);
+///
+/// assert_eq!(deque.len(), 11);
+/// assert_eq!(deque.pop_front(), Some(100));
+/// assert_eq!(deque.pop_back(), Some(9));
+/// assert!(deque.iter().copied().eq(0..9));
+/// ```
+pub struct SegDeque<T, const CHUNK: usize>
+where
+    T: Default,
+{
+    segments: VecDeque<Segment<T>>,
+    spare: Option<Segment<T>>,
+    len: usize,
+}
+
+impl<T, const CHUNK: usize> SegDeque<T, CHUNK>
+where
+    T: Default,
+{
+    /// Constructs a new, empty `SegDeque<T, CHUNK>`.
+    ///
+    /// No segments are allocated until an element is pushed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `CHUNK` is zero.
+    pub fn new() -> Self {
+        assert!(CHUNK > 0, "segments must have a nonzero capacity");
+
+        SegDeque {
+            segments: VecDeque::new(),
+            spare: None,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of elements in the deque.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the deque is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of segments currently allocated to hold elements.
+    #[inline]
+    pub fn segments(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Returns a reference to the first element, or `None` if the deque is
+    /// empty.
+    pub fn front(&self) -> Option<&T> {
+        self.segments.front()?.front()
+    }
+
+    /// Returns a reference to the last element, or `None` if the deque is
+    /// empty.
+    pub fn back(&self) -> Option<&T> {
+        self.segments.back()?.back()
+    }
+
+    fn new_segment(&mut self) -> Segment<T> {
+        self.spare.take().unwrap_or_else(|| Segment::new(CHUNK))
+    }
+
+    fn retire_segment(&mut self, segment: Segment<T>) {
+        debug_assert!(segment.is_empty());
+        self.spare = Some(segment);
+    }
+
+    /// Prepends an element to the deque, allocating a new segment if the
+    /// first segment is full.
+    pub fn push_front(&mut self, item: T) {
+        if self.segments.front().map_or(true, |seg| seg.is_full()) {
+            let segment = self.new_segment();
+            self.segments.push_front(segment);
+        }
+
+        // The front segment was just checked to have room.
+        if let Some(segment) = self.segments.front_mut() {
+            segment.push_front_expect(item);
+        }
+        self.len += 1;
+    }
+
+    /// Appends an element to the deque, allocating a new segment if the last
+    /// segment is full.
+    pub fn push_back(&mut self, item: T) {
+        if self.segments.back().map_or(true, |seg| seg.is_full()) {
+            let segment = self.new_segment();
+            self.segments.push_back(segment);
+        }
+
+        if let Some(segment) = self.segments.back_mut() {
+            segment.push_back_expect(item);
+        }
+        self.len += 1;
+    }
+
+    /// Removes and returns the first element, or `None` if the deque is
+    /// empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let segment = self.segments.front_mut()?;
+        let item = segment.pop_front();
+
+        if segment.is_empty() {
+            if let Some(segment) = self.segments.pop_front() {
+                self.retire_segment(segment);
+            }
+        }
+
+        self.len -= 1;
+        item
+    }
+
+    /// Removes and returns the last element, or `None` if the deque is
+    /// empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        let segment = self.segments.back_mut()?;
+        let item = segment.pop_back();
+
+        if segment.is_empty() {
+            if let Some(segment) = self.segments.pop_back() {
+                self.retire_segment(segment);
+            }
+        }
+
+        self.len -= 1;
+        item
+    }
+
+    /// Removes all elements from the deque, freeing all but one segment.
+    pub fn clear(&mut self) {
+        if let Some(mut segment) = self.segments.pop_front() {
+            segment.clear();
+            self.retire_segment(segment);
+        }
+
+        self.segments.clear();
+        self.len = 0;
+    }
+
+    /// Returns a front-to-back iterator over the elements of the deque.
+    pub fn iter(&self) -> Iter<'_, T, CHUNK> {
+        let segment_iter: fn(&Segment<T>) -> DequeIter<'_, Segment<T>, T> =
+            |segment| DequeIter::new(segment);
+
+        Iter {
+            inner: self.segments.iter().flat_map(segment_iter),
+        }
+    }
+}
+
+impl<T, const CHUNK: usize> Default for SegDeque<T, CHUNK>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        SegDeque::new()
+    }
+}
+
+impl<T, const CHUNK: usize> fmt::Debug for SegDeque<T, CHUNK>
+where
+    T: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, const CHUNK: usize> Extend<T> for SegDeque<T, CHUNK>
+where
+    T: Default,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<T, const CHUNK: usize> FromIterator<T> for SegDeque<T, CHUNK>
+where
+    T: Default,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut deque = SegDeque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+type SegmentIter<'a, T, const CHUNK: usize> = FlatMap<
+    vec_deque::Iter<'a, Segment<T>>,
+    DequeIter<'a, Segment<T>, T>,
+    fn(&'a Segment<T>) -> DequeIter<'a, Segment<T>, T>,
+>;
+
+/// An immutable iterator over the elements of a [`SegDeque`].
+///
+/// This struct is created by the [`iter`] method on [`SegDeque`].
+///
+/// [`iter`]: SegDeque::iter
+pub struct Iter<'a, T, const CHUNK: usize>
+where
+    T: Default,
+{
+    inner: SegmentIter<'a, T, CHUNK>,
+}
+
+impl<'a, T, const CHUNK: usize> Iterator for Iter<'a, T, CHUNK>
+where
+    T: Default,
+{
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T, const CHUNK: usize> DoubleEndedIterator for Iter<'a, T, CHUNK>
+where
+    T: Default,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.inner.next_back()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    #[test]
+    fn grows_at_both_ends() {
+        let mut deque: SegDeque<u32, 3> = SegDeque::new();
+
+        for i in 0..5 {
+            deque.push_back(i + 5);
+            deque.push_front(4 - i);
+        }
+
+        assert_eq!(deque.len(), 10);
+        assert_eq!(deque.segments(), 4);
+        assert_eq!(
+            deque.iter().copied().collect::<Vec<_>>(),
+            (0..10).collect::<Vec<_>>()
+        );
+        assert!(deque.iter().rev().copied().eq((0..10).rev()));
+
+        for i in 0..5 {
+            assert_eq!(deque.pop_front(), Some(i));
+            assert_eq!(deque.pop_back(), Some(9 - i));
+        }
+
+        assert!(deque.is_empty());
+        assert_eq!(deque.segments(), 0);
+        assert_eq!(deque.pop_front(), None);
+        assert_eq!(deque.pop_back(), None);
+    }
+
+    #[test]
+    fn reuses_spare_segment() {
+        let mut deque: SegDeque<u32, 2> = (0..4).collect();
+        assert_eq!(deque.segments(), 2);
+
+        deque.clear();
+        assert!(deque.is_empty());
+        assert!(deque.spare.is_some());
+
+        deque.push_back(1);
+        assert!(deque.spare.is_none());
+        assert_eq!(deque.front(), Some(&1));
+        assert_eq!(deque.back(), Some(&1));
+    }
+
+    #[test]
+    fn allocates_large_segments_on_heap() {
+        // Larger than the stack of a test thread.
+        let mut deque: SegDeque<u64, { 1 << 20 }> = SegDeque::new();
+
+        deque.push_back(1);
+        deque.push_front(0);
+        assert!(deque.iter().copied().eq(0..2));
+    }
+}
@@ -0,0 +1,279 @@
+//! A ring with a single writer whose contents can be read consistently from
+//! other threads without blocking the writer.
+//!
+//! [`SeqRing`] uses a sequence lock: the writer increments a counter before
+//! and after each modification, and readers copy the contents, then check
+//! that the counter is even and has not changed. A reader which overlaps a
+//! modification discards its copy and tries again, so the writer never waits
+//! for readers.
+//!
+//! As this crate forbids `unsafe` code, each slot of the ring is itself an
+//! atomic, so elements are limited to types implementing
+//! [`AtomicItem`](crate::spsc::AtomicItem).
+//!
+//! # Example
+//!
+//! ```
+//! use holodeque::seqlock::SeqRing;
+//!
+//! static TELEMETRY: SeqRing<u16, 4> = SeqRing::new();
+//!
+//! // In the producer:
+//! let mut writer = TELEMETRY.writer().unwrap();
+//! for sample in [310, 312, 309, 315, 320] {
+//!     writer.push(sample);
+//! }
+//!
+//! // In a dashboard thread:
+//! let latest = TELEMETRY.snapshot();
+//! assert!(latest.iter().copied().eq([312, 309, 315, 320]));
+//! ```
+
+use core::sync::atomic::{fence, AtomicBool, AtomicUsize, Ordering};
+
+use crate::{spsc::AtomicItem, ArrayDeque};
+
+/// A fixed-capacity ring with one writer and any number of readers, which
+/// take consistent snapshots of its contents.
+///
+/// Pushing to a full ring overwrites the oldest element, so the ring always
+/// holds the `N` most recent elements. The single [`Writer`] is obtained with
+/// [`writer`]; readers only need a shared reference to the ring.
+///
+/// [`writer`]: SeqRing::writer
+pub struct SeqRing<T, const N: usize>
+where
+    T: AtomicItem,
+{
+    /// Odd while the writer is modifying the ring.
+    seq: AtomicUsize,
+    head: AtomicUsize,
+    len: AtomicUsize,
+    has_writer: AtomicBool,
+    slots: [T::Atomic; N],
+}
+
+impl<T, const N: usize> SeqRing<T, N>
+where
+    T: AtomicItem,
+{
+    /// Creates an empty ring.
+    ///
+    /// This is a `const fn`, so the ring can be placed in a `static`.
+    pub const fn new() -> SeqRing<T, N> {
+        SeqRing {
+            seq: AtomicUsize::new(0),
+            head: AtomicUsize::new(0),
+            len: AtomicUsize::new(0),
+            has_writer: AtomicBool::new(false),
+            slots: [T::INIT; N],
+        }
+    }
+
+    /// Returns the maximum number of elements the ring can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the writer of the ring.
+    ///
+    /// A ring has only one writer; subsequent calls return `None`.
+    #[inline]
+    pub fn writer(&self) -> Option<Writer<'_, T, N>> {
+        if self.has_writer.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+
+        Some(Writer { ring: self })
+    }
+
+    /// Copies the contents of the ring, or returns `None` if the writer
+    /// modified the ring while they were being copied.
+    pub fn try_snapshot(&self) -> Option<ArrayDeque<T, N>>
+    where
+        T: Default,
+    {
+        let before = self.seq.load(Ordering::Acquire);
+        if before % 2 == 1 {
+            return None;
+        }
+
+        // A concurrent modification may produce an inconsistent head and
+        // length, which are clamped to keep the copy in bounds. The copy is
+        // discarded in that case, as the sequence number will have changed.
+        let head = self.head.load(Ordering::Relaxed);
+        let len = self.len.load(Ordering::Relaxed).min(N);
+
+        let mut snapshot = ArrayDeque::new_copied();
+        for i in 0..len {
+            snapshot.push_back_expect(T::load(&self.slots[(head + i) % N]));
+        }
+
+        fence(Ordering::Acquire);
+        if self.seq.load(Ordering::Relaxed) != before {
+            return None;
+        }
+
+        Some(snapshot)
+    }
+
+    /// Copies the contents of the ring, retrying until the copy is not
+    /// disturbed by the writer.
+    ///
+    /// The writer is never blocked, so this may spin for as long as the
+    /// writer keeps modifying the ring.
+    pub fn snapshot(&self) -> ArrayDeque<T, N>
+    where
+        T: Default,
+    {
+        loop {
+            if let Some(snapshot) = self.try_snapshot() {
+                return snapshot;
+            }
+
+            core::hint::spin_loop();
+        }
+    }
+}
+
+impl<T, const N: usize> Default for SeqRing<T, N>
+where
+    T: AtomicItem,
+{
+    fn default() -> Self {
+        SeqRing::new()
+    }
+}
+
+/// The writer of a [`SeqRing`].
+///
+/// This struct is created by the [`writer`] method on [`SeqRing`].
+///
+/// [`writer`]: SeqRing::writer
+pub struct Writer<'r, T, const N: usize>
+where
+    T: AtomicItem,
+{
+    ring: &'r SeqRing<T, N>,
+}
+
+impl<'r, T, const N: usize> Writer<'r, T, N>
+where
+    T: AtomicItem,
+{
+    /// Returns the number of elements in the ring.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ring.len.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if the ring is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Runs `modify` between the two increments of the sequence number.
+    fn write<R, F>(&mut self, modify: F) -> R
+    where
+        F: FnOnce(&SeqRing<T, N>) -> R,
+    {
+        let seq = self.ring.seq.load(Ordering::Relaxed);
+        self.ring.seq.store(seq.wrapping_add(1), Ordering::Relaxed);
+        fence(Ordering::Release);
+
+        let result = modify(self.ring);
+
+        self.ring.seq.store(seq.wrapping_add(2), Ordering::Release);
+
+        result
+    }
+
+    /// Appends an element to the ring, overwriting the oldest element if the
+    /// ring is full.
+    ///
+    /// The overwritten element is returned. If the capacity is zero, `item`
+    /// itself is returned.
+    pub fn push(&mut self, item: T) -> Option<T> {
+        if N == 0 {
+            return Some(item);
+        }
+
+        self.write(|ring| {
+            let head = ring.head.load(Ordering::Relaxed);
+            let len = ring.len.load(Ordering::Relaxed);
+            let slot = &ring.slots[(head + len) % N];
+
+            if len < N {
+                T::store(slot, item);
+                ring.len.store(len + 1, Ordering::Relaxed);
+
+                None
+            } else {
+                let oldest = T::load(slot);
+                T::store(slot, item);
+                ring.head.store((head + 1) % N, Ordering::Relaxed);
+
+                Some(oldest)
+            }
+        })
+    }
+
+    /// Removes all elements from the ring.
+    pub fn clear(&mut self) {
+        self.write(|ring| ring.len.store(0, Ordering::Relaxed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_overwrites_oldest() {
+        let ring: SeqRing<u8, 3> = SeqRing::new();
+        let mut writer = ring.writer().unwrap();
+        assert!(ring.writer().is_none());
+
+        for i in 0..5 {
+            assert_eq!(writer.push(i), i.checked_sub(3));
+        }
+
+        assert_eq!(writer.len(), 3);
+        assert!(ring.snapshot().iter().copied().eq([2, 3, 4]));
+
+        writer.clear();
+        assert!(ring.try_snapshot().unwrap().is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn concurrent_snapshots_are_consistent() {
+        const COUNT: u32 = 10_000;
+
+        let ring: SeqRing<u32, 8> = SeqRing::new();
+        let mut writer = ring.writer().unwrap();
+
+        std::thread::scope(|s| {
+            s.spawn(move || {
+                for i in 1..=COUNT {
+                    writer.push(i);
+                }
+            });
+
+            let mut last = 0;
+            while last < COUNT {
+                let snapshot = ring.snapshot();
+
+                // The writer pushes consecutive values, so every consistent
+                // snapshot is a run of consecutive values.
+                let mut values = snapshot.iter().copied();
+                if let Some(first) = values.next() {
+                    assert!(values.eq(first + 1..first + snapshot.len() as u32));
+                    last = *snapshot.back().unwrap();
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,206 @@
+//! Compact serialization of byte deques as byte strings.
+//!
+//! By default, `ArrayDeque<u8, N>` is serialized as a sequence of individual
+//! `u8` elements. Binary formats can usually encode a byte string much more
+//! compactly, so this module provides functions for use with serde's `with`
+//! attribute which serialize the contents of the deque as a single byte
+//! string.
+//!
+//! # Example
+//!
+//! ```
+//! # use holodeque::ArrayDeque;
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Serialize, Deserialize)]
+//! struct Packet {
+//!     #[serde(with = "holodeque::serde_bytes")]
+//!     payload: ArrayDeque<u8, 64>,
+//! }
+//! ```
+//!
+//! The deque is always serialized as a byte string, even if its contents wrap
+//! around the end of its backing array. Deserialization accepts either a byte
+//! string or a sequence of bytes, and fails if the input contains more than
+//! `N` bytes.
+
+use core::fmt;
+
+use serde::{
+    de::{Deserializer, Error, SeqAccess, Visitor},
+    ser::Serializer,
+};
+
+use crate::{array_deque::ExceededCapacity, ArrayDeque, BaseDeque};
+
+/// Serializes the contents of a byte deque as a byte string.
+///
+/// A byte string must be contiguous, so if the contents of the deque wrap
+/// around the end of the backing array, they are first copied into an
+/// `N`-byte buffer on the stack. Call [`rotate_to_front(0)`] beforehand to
+/// avoid the copy.
+///
+/// [`rotate_to_front(0)`]: ArrayDeque::rotate_to_front
+pub fn serialize<S, const N: usize>(
+    deque: &ArrayDeque<u8, N>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match BaseDeque::as_slices(deque) {
+        (front, []) => serializer.serialize_bytes(front),
+        (front, back) => {
+            let mut buf = [0; N];
+            buf[..front.len()].copy_from_slice(front);
+            buf[front.len()..deque.len()].copy_from_slice(back);
+
+            serializer.serialize_bytes(&buf[..deque.len()])
+        }
+    }
+}
+
+/// Deserializes a byte deque from a byte string or a sequence of bytes.
+///
+/// Returns an error if the input contains more than `N` bytes.
+pub fn deserialize<'de, D, const N: usize>(deserializer: D) -> Result<ArrayDeque<u8, N>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct BytesVisitor<const N: usize>;
+
+    impl<'de, const N: usize> Visitor<'de> for BytesVisitor<N> {
+        type Value = ArrayDeque<u8, N>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a byte string of at most {} bytes", N)
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            if v.len() > N {
+                return Err(E::invalid_length(
+                    v.len(),
+                    &ExceededCapacity { capacity: N },
+                ));
+            }
+
+            let mut deque = ArrayDeque::new();
+            deque.extend(v.iter().copied());
+
+            Ok(deque)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut deque = ArrayDeque::new();
+
+            while let Some(byte) = seq.next_element()? {
+                deque.push_back(byte).map_err(|_| {
+                    A::Error::invalid_length(deque.len() + 1, &ExceededCapacity { capacity: N })
+                })?;
+            }
+
+            Ok(deque)
+        }
+    }
+
+    deserializer.deserialize_bytes(BytesVisitor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::{Deserialize, Serialize};
+    use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Token};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Packet {
+        #[serde(with = "crate::serde_bytes")]
+        payload: ArrayDeque<u8, 4>,
+    }
+
+    fn packet(bytes: &[u8]) -> Packet {
+        let mut payload = ArrayDeque::new();
+        payload.extend(bytes.iter().copied());
+
+        Packet { payload }
+    }
+
+    #[test]
+    fn serializes_contiguous_deque_as_bytes() {
+        let mut packet = packet(&[0, 1, 2, 3]);
+        packet.payload.pop_front();
+
+        assert_tokens(
+            &packet,
+            &[
+                Token::Struct {
+                    name: "Packet",
+                    len: 1,
+                },
+                Token::Str("payload"),
+                Token::Bytes(&[1, 2, 3]),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn serializes_wrapped_deque_as_bytes() {
+        let mut packet = packet(&[0, 1, 2, 3]);
+        packet.payload.pop_front();
+        packet.payload.push_back(4).unwrap();
+
+        assert_tokens(
+            &packet,
+            &[
+                Token::Struct {
+                    name: "Packet",
+                    len: 1,
+                },
+                Token::Str("payload"),
+                Token::Bytes(&[1, 2, 3, 4]),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn deserializes_from_seq() {
+        assert_de_tokens(
+            &packet(&[7, 8]),
+            &[
+                Token::Struct {
+                    name: "Packet",
+                    len: 1,
+                },
+                Token::Str("payload"),
+                Token::Seq { len: Some(2) },
+                Token::U8(7),
+                Token::U8(8),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn rejects_too_many_bytes() {
+        assert_de_tokens_error::<Packet>(
+            &[
+                Token::Struct {
+                    name: "Packet",
+                    len: 1,
+                },
+                Token::Str("payload"),
+                Token::Bytes(&[1, 2, 3, 4, 5]),
+            ],
+            "invalid length 5, expected a sequence of at most 4 elements",
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//! Layout-preserving serialization of deques.
+//!
+//! The default `Serialize` implementation of `ArrayDeque` produces a plain
+//! sequence of elements, and deserializing it always places the front element
+//! at the start of the backing array. This module provides functions for use
+//! with serde's `with` attribute which also record the physical position of
+//! the front element, so that a deserialized deque has exactly the same layout
+//! as the one that was serialized.
+//!
+//! The serialized form is a 2-tuple containing the index of the front element
+//! in the backing array, followed by the elements in logical order.
+//!
+//! # Example
+//!
+//! ```
+//! # use holodeque::ArrayDeque;
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Serialize, Deserialize)]
+//! struct Snapshot {
+//!     #[serde(with = "holodeque::serde_layout")]
+//!     queue: ArrayDeque<u32, 16>,
+//! }
+//! ```
+
+use core::{fmt, marker::PhantomData, num::NonZeroUsize};
+
+use serde::{
+    de::{Deserialize, DeserializeSeed, Deserializer, Error, SeqAccess, Unexpected, Visitor},
+    ser::{Serialize, SerializeTuple, Serializer},
+};
+
+use crate::{
+    array_deque::ExceededCapacity,
+    meta::{Meta, MetaLayout},
+    ArrayDeque, BaseDeque,
+};
+
+/// Serializes a deque along with the physical position of its front element.
+pub fn serialize<S, T, const N: usize>(
+    deque: &ArrayDeque<T, N>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize + Default,
+{
+    let head = BaseDeque::meta(deque).front().unwrap_or(0);
+
+    let mut tuple = serializer.serialize_tuple(2)?;
+    tuple.serialize_element(&head)?;
+    tuple.serialize_element(deque)?;
+    tuple.end()
+}
+
+/// Deserializes a deque, restoring the physical position of its front
+/// element.
+///
+/// Returns an error if the front position is outside the backing array or if
+/// there are more than `N` elements.
+pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<ArrayDeque<T, N>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    struct LayoutVisitor<T, const N: usize> {
+        phantom: PhantomData<T>,
+    }
+
+    impl<'de, T, const N: usize> Visitor<'de> for LayoutVisitor<T, N>
+    where
+        T: Deserialize<'de> + Default,
+    {
+        type Value = ArrayDeque<T, N>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a front position followed by a sequence of elements")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let head: usize = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+
+            if head >= N.max(1) {
+                return Err(A::Error::invalid_value(
+                    Unexpected::Unsigned(head as u64),
+                    &"an index into the backing array",
+                ));
+            }
+
+            seq.next_element_seed(ElementsSeed {
+                head,
+                phantom: PhantomData,
+            })?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))
+        }
+    }
+
+    deserializer.deserialize_tuple(
+        2,
+        LayoutVisitor {
+            phantom: PhantomData,
+        },
+    )
+}
+
+/// Deserializes a sequence of elements into a deque whose front element is
+/// placed at `head`.
+struct ElementsSeed<T, const N: usize> {
+    head: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<'de, T, const N: usize> DeserializeSeed<'de> for ElementsSeed<T, N>
+where
+    T: Deserialize<'de> + Default,
+{
+    type Value = ArrayDeque<T, N>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T, const N: usize> Visitor<'de> for ElementsSeed<T, N>
+where
+    T: Deserialize<'de> + Default,
+{
+    type Value = ArrayDeque<T, N>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a sequence of at most {} elements", N)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut deque = ArrayDeque::new();
+
+        if let Some(first) = seq.next_element()? {
+            // A zero-capacity deque accepts a head of zero, but no elements.
+            if self.head >= N {
+                return Err(A::Error::invalid_length(
+                    1,
+                    &ExceededCapacity { capacity: N },
+                ));
+            }
+
+            deque.meta_mut().set_layout(MetaLayout::Linear {
+                first: self.head,
+                len: NonZeroUsize::new(1).unwrap(),
+            });
+            deque.items_mut()[self.head] = first;
+        }
+
+        while let Some(elem) = seq.next_element()? {
+            deque.push_back(elem).map_err(|_| {
+                A::Error::invalid_length(deque.len() + 1, &ExceededCapacity { capacity: N })
+            })?;
+        }
+
+        Ok(deque)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate alloc;
+    use alloc::{format, vec, vec::Vec};
+
+    use serde::{
+        de::value::{Error, SeqDeserializer},
+        Deserialize, Serialize,
+    };
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Snapshot {
+        #[serde(with = "crate::serde_layout")]
+        queue: ArrayDeque<u32, 4>,
+    }
+
+    fn tokens(head: u64, elements: &[u32]) -> Vec<Token> {
+        let mut tokens = vec![
+            Token::Struct {
+                name: "Snapshot",
+                len: 1,
+            },
+            Token::Str("queue"),
+            Token::Tuple { len: 2 },
+            Token::U64(head),
+            Token::Seq {
+                len: Some(elements.len()),
+            },
+        ];
+        tokens.extend(elements.iter().map(|&e| Token::U32(e)));
+        tokens.extend([Token::SeqEnd, Token::TupleEnd, Token::StructEnd]);
+        tokens
+    }
+
+    #[test]
+    fn serializes_head_position() {
+        let mut queue = ArrayDeque::new();
+        queue.push_back(1).unwrap();
+        queue.push_back(2).unwrap();
+        queue.push_front(0).unwrap();
+
+        assert_tokens(&Snapshot { queue }, &tokens(3, &[0, 1, 2]));
+        assert_tokens(
+            &Snapshot {
+                queue: ArrayDeque::new(),
+            },
+            &tokens(0, &[]),
+        );
+    }
+
+    #[test]
+    fn elements_are_placed_at_head() {
+        let mut original: ArrayDeque<u32, 4> = ArrayDeque::new();
+        original.push_back(1).unwrap();
+        original.push_back(2).unwrap();
+        original.push_front(0).unwrap();
+
+        let seed = ElementsSeed::<u32, 4> {
+            head: 3,
+            phantom: PhantomData,
+        };
+        let restored = seed
+            .deserialize(SeqDeserializer::<_, Error>::new(
+                [0u32, 1, 2].iter().copied(),
+            ))
+            .unwrap();
+
+        assert_eq!(
+            format!("{:?}", restored.debug_internals()),
+            format!("{:?}", original.debug_internals()),
+        );
+    }
+
+    #[test]
+    fn rejects_out_of_range_head() {
+        assert_de_tokens_error::<Snapshot>(
+            &tokens(4, &[])[..4],
+            "invalid value: integer `4`, expected an index into the backing array",
+        );
+    }
+
+    #[test]
+    fn rejects_elements_for_zero_capacity() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Empty {
+            #[serde(with = "crate::serde_layout")]
+            queue: ArrayDeque<u32, 0>,
+        }
+
+        let mut tokens = tokens(0, &[7]);
+        tokens[0] = Token::Struct {
+            name: "Empty",
+            len: 1,
+        };
+        assert_de_tokens_error::<Empty>(
+            &tokens[..6],
+            "invalid length 1, expected a sequence of at most 0 elements",
+        );
+
+        tokens.remove(5);
+        tokens[4] = Token::Seq { len: Some(0) };
+        assert_tokens(
+            &Empty {
+                queue: ArrayDeque::new(),
+            },
+            &tokens,
+        );
+    }
+
+    #[test]
+    fn rejects_too_many_elements() {
+        assert_de_tokens_error::<Snapshot>(
+            &tokens(2, &[1, 2, 3, 4, 5])[..10],
+            "invalid length 5, expected a sequence of at most 4 elements",
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Interrupt-driven serial buffering with the [`embedded-hal-nb`] serial
+//! traits.
+//!
+//! [`embedded-hal-nb`]: https://docs.rs/embedded-hal-nb
+
+use core::cell::Cell;
+
+use critical_section::Mutex;
+use embedded_hal_nb::{
+    nb,
+    serial::{self, ErrorKind, ErrorType},
+};
+
+use crate::{isr_deque::IsrDeque, CapacityError};
+
+/// A pair of byte queues for buffering a serial port, one for received bytes
+/// and one for bytes waiting to be transmitted.
+///
+/// The UART driver runs in interrupt handlers: it stores each received byte
+/// with [`push_received`], and takes the next byte to send with
+/// [`pop_transmit`]. Application code reads and writes through a [`Port`],
+/// which implements the [`embedded-hal-nb`] serial [`Read`] and [`Write`]
+/// traits, so it can be passed to any driver written against them.
+///
+/// Both queues are [`IsrDeque`]s, so a `SerialFifo` can be placed in a
+/// `static`. If a byte is received while the receive queue is full, it is
+/// discarded, and the next read through the [`Port`] reports
+/// [`ErrorKind::Overrun`].
+///
+/// [`push_received`]: SerialFifo::push_received
+/// [`pop_transmit`]: SerialFifo::pop_transmit
+/// [`embedded-hal-nb`]: https://docs.rs/embedded-hal-nb
+/// [`Read`]: embedded_hal_nb::serial::Read
+/// [`Write`]: embedded_hal_nb::serial::Write
+///
+/// # Example
+///
+/// ```
+/// use embedded_hal_nb::{
+///     nb,
+///     serial::{Read, Write},
+/// };
+/// use holodeque::serial_fifo::SerialFifo;
+///
+/// static UART: SerialFifo<64, 64> = SerialFifo::new();
+///
+/// // In the receive interrupt handler:
+/// fn on_rx(byte: u8) {
+///     let _ = UART.push_received(byte);
+/// }
+///
+/// // In the transmit interrupt handler, writing to the data register:
+/// fn on_tx_ready() -> Option<u8> {
+///     UART.pop_transmit()
+/// }
+///
+/// let mut port = UART.port();
+///
+/// on_rx(b'?');
+/// assert_eq!(port.read(), Ok(b'?'));
+/// assert_eq!(port.read(), Err(nb::Error::WouldBlock));
+///
+/// port.write(b'!').unwrap();
+/// assert_eq!(port.flush(), Err(nb::Error::WouldBlock));
+/// assert_eq!(on_tx_ready(), Some(b'!'));
+/// assert_eq!(port.flush(), Ok(()));
+/// ```
+pub struct SerialFifo<const RX: usize, const TX: usize> {
+    rx: IsrDeque<u8, RX>,
+    tx: IsrDeque<u8, TX>,
+    overrun: Mutex<Cell<bool>>,
+}
+
+impl<const RX: usize, const TX: usize> SerialFifo<RX, TX> {
+    /// Constructs a new `SerialFifo` with both queues empty.
+    pub const fn new() -> Self {
+        SerialFifo {
+            rx: IsrDeque::new(),
+            tx: IsrDeque::new(),
+            overrun: Mutex::new(Cell::new(false)),
+        }
+    }
+
+    /// Appends a received byte to the receive queue.
+    ///
+    /// This is meant to be called from the receive interrupt handler. If the
+    /// queue is full, the byte is returned in a [`CapacityError`] and the
+    /// next read through a [`Port`] reports an overrun.
+    pub fn push_received(&self, byte: u8) -> Result<(), CapacityError<u8>> {
+        self.rx.push_from_isr(byte).inspect_err(|_| {
+            critical_section::with(|cs| self.overrun.borrow(cs).set(true));
+        })
+    }
+
+    /// Removes and returns the next byte to transmit, or `None` if there is
+    /// nothing to send.
+    ///
+    /// This is meant to be called from the transmit interrupt handler.
+    #[inline]
+    pub fn pop_transmit(&self) -> Option<u8> {
+        self.tx.pop()
+    }
+
+    /// Returns `true` if bytes are waiting to be transmitted.
+    ///
+    /// A driver which disables the transmit interrupt when it runs out of
+    /// bytes can check this to decide whether to enable it again.
+    #[inline]
+    pub fn has_pending_transmit(&self) -> bool {
+        !self.tx.is_empty()
+    }
+
+    /// Returns the receive queue.
+    #[inline]
+    pub fn rx(&self) -> &IsrDeque<u8, RX> {
+        &self.rx
+    }
+
+    /// Returns the transmit queue.
+    #[inline]
+    pub fn tx(&self) -> &IsrDeque<u8, TX> {
+        &self.tx
+    }
+
+    /// Returns a handle for reading and writing through the
+    /// [`embedded-hal-nb`] serial traits.
+    ///
+    /// [`embedded-hal-nb`]: https://docs.rs/embedded-hal-nb
+    #[inline]
+    pub fn port(&self) -> Port<'_, RX, TX> {
+        Port { fifo: self }
+    }
+}
+
+impl<const RX: usize, const TX: usize> Default for SerialFifo<RX, TX> {
+    fn default() -> Self {
+        SerialFifo::new()
+    }
+}
+
+/// The application side of a [`SerialFifo`].
+///
+/// Reading removes bytes from the receive queue, and writing appends bytes to
+/// the transmit queue. Both return [`nb::Error::WouldBlock`] rather than
+/// waiting: reads when no byte has been received, and writes when the
+/// transmit queue is full. Flushing completes once the interrupt handler has
+/// taken every queued byte.
+///
+/// This struct is created by the [`port`] method on [`SerialFifo`].
+///
+/// [`port`]: SerialFifo::port
+#[derive(Clone, Copy)]
+pub struct Port<'a, const RX: usize, const TX: usize> {
+    fifo: &'a SerialFifo<RX, TX>,
+}
+
+/// An error reported by a [`Port`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Bytes were received while the receive queue was full, and were lost.
+    Overrun,
+}
+
+impl serial::Error for Error {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Overrun => ErrorKind::Overrun,
+        }
+    }
+}
+
+impl<'a, const RX: usize, const TX: usize> ErrorType for Port<'a, RX, TX> {
+    type Error = Error;
+}
+
+impl<'a, const RX: usize, const TX: usize> serial::Read<u8> for Port<'a, RX, TX> {
+    fn read(&mut self) -> nb::Result<u8, Error> {
+        let overrun = critical_section::with(|cs| self.fifo.overrun.borrow(cs).replace(false));
+        if overrun {
+            return Err(nb::Error::Other(Error::Overrun));
+        }
+
+        self.fifo.rx.pop().ok_or(nb::Error::WouldBlock)
+    }
+}
+
+impl<'a, const RX: usize, const TX: usize> serial::Write<u8> for Port<'a, RX, TX> {
+    fn write(&mut self, word: u8) -> nb::Result<(), Error> {
+        self.fifo
+            .tx
+            .push_from_isr(word)
+            .map_err(|_| nb::Error::WouldBlock)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Error> {
+        if self.fifo.has_pending_transmit() {
+            Err(nb::Error::WouldBlock)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use embedded_hal_nb::serial::{Read, Write};
+
+    #[test]
+    fn overrun_is_reported_once() {
+        let fifo: SerialFifo<2, 1> = SerialFifo::new();
+        let mut port = fifo.port();
+
+        fifo.push_received(1).unwrap();
+        fifo.push_received(2).unwrap();
+        assert_eq!(fifo.push_received(3).unwrap_err().into_inner(), 3);
+
+        assert_eq!(port.read(), Err(nb::Error::Other(Error::Overrun)));
+        assert_eq!(port.read(), Ok(1));
+        assert_eq!(port.read(), Ok(2));
+        assert_eq!(port.read(), Err(nb::Error::WouldBlock));
+
+        port.write(4).unwrap();
+        assert_eq!(port.write(5), Err(nb::Error::WouldBlock));
+        assert_eq!(fifo.pop_transmit(), Some(4));
+        assert_eq!(port.flush(), Ok(()));
+    }
+}
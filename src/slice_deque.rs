@@ -1,10 +1,27 @@
 //! A double-ended queue with fixed capacity, backed by a slice.
 
-use core::mem;
+use core::{
+    cell::Cell,
+    cmp::Ordering,
+    fmt, mem,
+    num::NonZeroUsize,
+    ops::{Deref, DerefMut},
+};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
+#[cfg(feature = "alloc")]
+use crate::concat::{Concat, Join};
+#[cfg(feature = "stats")]
+use crate::DequeStats;
 use crate::{
+    format::{DebugInternals, DisplaySeparated, HexDump},
     meta::{Meta, MetaLayout},
-    BaseDeque, CapacityError, DequeDrain, DequeIter,
+    snapshot::{self, LoadError, Persist},
+    BaseDeque, CapacityError, Deque, DequeCursor, DequeDrain, DequeDrainChunks, DequeExtractIf,
+    DequeHalf, DequeIter, DequePopGuard, DequeSlot, DequeTruncateGuard, LayoutInfo, Savepoint,
+    TryExtendError,
 };
 
 #[cfg(feature = "serde")]
@@ -13,10 +30,13 @@ use serde::{
     ser::{Serialize, SerializeSeq, Serializer},
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub(crate) struct SliceMeta {
     capacity: usize,
     layout: MetaLayout,
+    generation: usize,
+    #[cfg(feature = "stats")]
+    stats: DequeStats,
 }
 
 impl SliceMeta {
@@ -24,6 +44,9 @@ impl SliceMeta {
         SliceMeta {
             capacity,
             layout: MetaLayout::Empty,
+            generation: 0,
+            #[cfg(feature = "stats")]
+            stats: DequeStats::new(),
         }
     }
 }
@@ -41,14 +64,44 @@ impl Meta for SliceMeta {
 
     #[inline(always)]
     fn set_layout(&mut self, layout: MetaLayout) {
+        #[cfg(feature = "stats")]
+        let old_len = self.len();
+
         self.layout = layout;
+
+        #[cfg(feature = "stats")]
+        {
+            let new_len = self.len();
+            self.stats.record_len_change(old_len, new_len);
+        }
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline(always)]
+    fn stats(&self) -> &DequeStats {
+        &self.stats
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline(always)]
+    fn stats_mut(&mut self) -> &mut DequeStats {
+        &mut self.stats
+    }
+
+    #[inline(always)]
+    fn generation(&self) -> usize {
+        self.generation
+    }
+
+    #[inline(always)]
+    fn set_generation(&mut self, generation: usize) {
+        self.generation = generation;
     }
 }
 
 /// A double-ended queue with fixed capacity, backed by a slice.
 ///
 /// The capacity of the deque is determined by the length of the slice.
-#[derive(Debug)]
 pub struct SliceDeque<'a, T>
 where
     T: Default,
@@ -69,7 +122,7 @@ where
     }
 
     #[inline(always)]
-    fn meta_mut(&mut self) -> &mut Self::Meta {
+    fn raw_meta_mut(&mut self) -> &mut Self::Meta {
         &mut self.meta
     }
 
@@ -79,16 +132,124 @@ where
     }
 
     #[inline(always)]
-    fn items_mut(&mut self) -> &mut [T] {
+    fn raw_items_mut(&mut self) -> &mut [T] {
         self.items
     }
 
+    #[inline(always)]
+    fn raw_meta_and_items_mut(&mut self) -> (&mut Self::Meta, &mut [T]) {
+        (&mut self.meta, self.items)
+    }
+
     #[inline(always)]
     fn capacity(&self) -> usize {
         self.items.len()
     }
 }
 
+impl<'a, T> Deque<T> for SliceDeque<'a, T>
+where
+    T: Default,
+{
+    #[inline]
+    fn capacity(&self) -> usize {
+        BaseDeque::capacity(self)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        BaseDeque::len(self)
+    }
+
+    #[inline]
+    fn front(&self) -> Option<&T> {
+        BaseDeque::front(self)
+    }
+
+    #[inline]
+    fn back(&self) -> Option<&T> {
+        BaseDeque::back(self)
+    }
+
+    #[inline]
+    fn as_slices(&self) -> (&[T], &[T]) {
+        BaseDeque::as_slices(self)
+    }
+
+    #[inline]
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        BaseDeque::as_mut_slices(self)
+    }
+
+    #[inline]
+    fn push_front(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        BaseDeque::push_front(self, item)
+    }
+
+    #[inline]
+    fn push_back(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        BaseDeque::push_back(self, item)
+    }
+
+    #[inline]
+    fn pop_front(&mut self) -> Option<T> {
+        BaseDeque::pop_front(self)
+    }
+
+    #[inline]
+    fn pop_back(&mut self) -> Option<T> {
+        BaseDeque::pop_back(self)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        BaseDeque::clear(self)
+    }
+
+    #[inline]
+    fn truncate_front(&mut self, len: usize) {
+        BaseDeque::truncate_front(self, len)
+    }
+
+    #[inline]
+    fn spare_slices_mut(&mut self) -> (&mut [T], &mut [T]) {
+        BaseDeque::spare_slices_mut(self)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn commit_back(&mut self, n: usize) {
+        SliceDeque::commit_back(self, n)
+    }
+}
+
+impl<'a, T> fmt::Debug for SliceDeque<'a, T>
+where
+    T: fmt::Debug + Default,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Appends the elements of an iterator to the back of the deque.
+///
+/// # Panics
+///
+/// Panics if the deque becomes full before the iterator is exhausted. Use
+/// `try_extend()` to handle this case without panicking.
+impl<'a, T> Extend<T> for SliceDeque<'a, T>
+where
+    T: Default,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        BaseDeque::extend_expect(self, iter);
+    }
+}
+
 impl<'a, T> SliceDeque<'a, T>
 where
     T: Default,
@@ -120,6 +281,92 @@ where
         SliceDeque { meta, items: slice }
     }
 
+    /// Appends an element to the back of the deque and returns the deque, for
+    /// building a deque in a single expression.
+    ///
+    /// If the deque is full, the element is returned in a `CapacityError` and
+    /// the deque is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0; 4];
+    /// let deque = SliceDeque::new_in(&mut slice).with_back(2)?.with_back(3)?;
+    ///
+    /// assert_eq!(deque.as_slices(), (&[2, 3][..], &[][..]));
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    pub fn with_back(mut self, item: T) -> Result<Self, CapacityError<T>> {
+        BaseDeque::push_back(&mut self, item)?;
+        Ok(self)
+    }
+
+    /// Prepends an element to the front of the deque and returns the deque,
+    /// for building a deque in a single expression.
+    ///
+    /// If the deque is full, the element is returned in a `CapacityError` and
+    /// the deque is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0; 4];
+    /// let deque = SliceDeque::new_in(&mut slice).with_back(2)?.with_front(1)?;
+    ///
+    /// assert_eq!(deque.front(), Some(&1));
+    /// assert_eq!(deque.back(), Some(&2));
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    pub fn with_front(mut self, item: T) -> Result<Self, CapacityError<T>> {
+        BaseDeque::push_front(&mut self, item)?;
+        Ok(self)
+    }
+
+    /// Wraps the deque so that its backing slice is put in a known state when
+    /// the wrapper is dropped.
+    ///
+    /// By default, dropping a `SliceDeque` leaves its elements in the slice
+    /// at whatever positions they occupied, among stale values. The returned
+    /// [`ResetOnDrop`] dereferences to the deque and, when dropped, either
+    /// clears the slice or compacts the elements to its start, as selected by
+    /// `behavior`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{slice_deque::DropBehavior, CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice).reset_on_drop(DropBehavior::Compact);
+    ///
+    /// deque.push_back(2)?;
+    /// deque.push_front(1)?;
+    /// drop(deque);
+    ///
+    /// assert_eq!(slice, [1, 2, 0, 0]);
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn reset_on_drop(self, behavior: DropBehavior) -> ResetOnDrop<'a, T> {
+        ResetOnDrop {
+            deque: self,
+            behavior,
+        }
+    }
+
     /// Returns the maximum number of elements the deque may hold.
     ///
     /// This is the length of the backing slice.
@@ -327,6 +574,86 @@ where
         BaseDeque::back_mut(self)
     }
 
+    /// Returns mutable references to the first and last elements in the
+    /// deque at the same time.
+    ///
+    /// If the deque contains fewer than two elements, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.try_extend([1, 2, 3].iter().copied()).unwrap();
+    ///
+    /// if let Some((head, tail)) = deque.ends_mut() {
+    ///     *head += 10;
+    ///     *tail += 20;
+    /// }
+    ///
+    /// assert_eq!(deque.as_slices(), (&[11, 2, 23][..], &[][..]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn ends_mut(&mut self) -> Option<(&mut T, &mut T)> {
+        BaseDeque::ends_mut(self)
+    }
+
+    /// Returns a reference to the element `index` positions from the back of
+    /// the deque, where index 0 is the last element.
+    ///
+    /// If `index` is out of bounds, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.try_extend([10, 20, 30].iter().copied()).unwrap();
+    ///
+    /// assert_eq!(deque.get_from_back(0), Some(&30));
+    /// assert_eq!(deque.get_from_back(2), Some(&10));
+    /// assert_eq!(deque.get_from_back(3), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn get_from_back(&self, index: usize) -> Option<&T> {
+        BaseDeque::get_from_back(self, index)
+    }
+
+    /// Returns a mutable reference to the element `index` positions from the
+    /// back of the deque, where index 0 is the last element.
+    ///
+    /// If `index` is out of bounds, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.try_extend([10, 20, 30].iter().copied()).unwrap();
+    ///
+    /// if let Some(reading) = deque.get_from_back_mut(1) {
+    ///     *reading += 5;
+    /// }
+    ///
+    /// assert_eq!(deque.as_slices(), (&[10, 25, 30][..], &[][..]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn get_from_back_mut(&mut self, index: usize) -> Option<&mut T> {
+        BaseDeque::get_from_back_mut(self, index)
+    }
+
     /// Returns a pair of slices which contain, in order, the elements of the
     /// `SliceDeque`.
     ///
@@ -397,43 +724,69 @@ where
         BaseDeque::as_mut_slices(self)
     }
 
-    /// Prepends an element to the deque.
+    /// Returns a pair of slices of [`Cell`]s which contain, in order, the
+    /// elements of the `SliceDeque`.
     ///
-    /// If the deque is at capacity, an `Err` containing the pushed value is
-    /// returned.
+    /// Because the returned slices are shared, several closures may hold them
+    /// at once and mutate the elements through [`Cell::set`] or
+    /// [`Cell::replace`].
+    ///
+    /// [`Cell`]: core::cell::Cell
+    /// [`Cell::set`]: core::cell::Cell::set
+    /// [`Cell::replace`]: core::cell::Cell::replace
     ///
     /// # Example
     ///
     /// ```
-    /// # use holodeque::{SliceDeque, CapacityError};
+    /// # use holodeque::{CapacityError, SliceDeque};
     /// # fn main() {
     /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut slice = [0, 0, 0];
+    /// let mut slice = [0; 4];
     /// let mut deque = SliceDeque::new_in(&mut slice);
     ///
-    /// deque.push_front(1)?;
-    /// deque.push_front(2)?;
-    /// deque.push_front(3)?;
+    /// deque.push_back(1)?;
+    /// deque.push_back(2)?;
+    /// deque.push_front(0)?;
     ///
-    /// assert_eq!(deque.front(), Some(&3));
-    /// assert_eq!(deque.back(), Some(&1));
+    /// let (first, second) = deque.as_slices_of_cells();
+    /// let double = |i: usize| {
+    ///     let cell = first.iter().chain(second).nth(i).unwrap();
+    ///     cell.set(cell.get() * 2);
+    /// };
+    /// let bump_all = || first.iter().chain(second).for_each(|c| c.set(c.get() + 1));
     ///
-    /// // Another element would exceed capacity, so this fails.
-    /// let err = deque.push_front(4).unwrap_err();
-    /// assert_eq!(err.into_inner(), 4);
+    /// double(2);
+    /// bump_all();
+    ///
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2, 5]);
     /// # Ok(())
     /// # })().unwrap();
     /// # }
     /// ```
     #[inline]
-    pub fn push_front(&mut self, item: T) -> Result<(), CapacityError<T>> {
-        BaseDeque::push_front(self, item)
+    pub fn as_slices_of_cells(&mut self) -> (&[Cell<T>], &[Cell<T>]) {
+        BaseDeque::as_slices_of_cells(self)
     }
 
-    /// Appends an element to the deque.
+    /// Returns a pair of mutable slices which contain, in order, the unused
+    /// slots of the `SliceDeque`.
     ///
-    /// If the deque is at capacity, an `Err` containing the pushed value is
-    /// returned.
+    /// The slots are ordered as they would be filled by repeated calls to
+    /// [`push_back`]. After writing to some prefix of the slots, call
+    /// [`commit_back`] to append them to the deque.
+    ///
+    /// Unused slots normally contain the default value of `T`, but may contain
+    /// stale elements if a draining iterator was leaked, if elements of a type
+    /// which does not need to be dropped were removed by [`clear`] or
+    /// [`truncate`], or if elements were removed by [`pop_front_copied`] or
+    /// [`pop_back_copied`].
+    ///
+    /// [`push_back`]: SliceDeque::push_back
+    /// [`commit_back`]: SliceDeque::commit_back
+    /// [`clear`]: SliceDeque::clear
+    /// [`truncate`]: SliceDeque::truncate
+    /// [`pop_front_copied`]: SliceDeque::pop_front_copied
+    /// [`pop_back_copied`]: SliceDeque::pop_back_copied
     ///
     /// # Example
     ///
@@ -441,155 +794,173 @@ where
     /// # use holodeque::{CapacityError, SliceDeque};
     /// # fn main() {
     /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut slice = [0, 0, 0];
+    /// let mut slice = [0; 5];
     /// let mut deque = SliceDeque::new_in(&mut slice);
     ///
     /// deque.push_back(1)?;
     /// deque.push_back(2)?;
     /// deque.push_back(3)?;
+    /// deque.pop_front();
     ///
-    /// assert_eq!(deque.front(), Some(&1));
-    /// assert_eq!(deque.back(), Some(&3));
+    /// let (first, second) = deque.spare_slices_mut();
+    /// assert_eq!((first.len(), second.len()), (2, 1));
     ///
-    /// // Another element would exceed capacity, so this fails.
-    /// let err = deque.push_back(4).unwrap_err();
-    /// assert_eq!(err.into_inner(), 4);
+    /// first[0] = 4;
+    /// first[1] = 5;
+    /// second[0] = 6;
+    /// deque.commit_back(3);
     ///
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [2, 3, 4, 5, 6]);
     /// # Ok(())
     /// # })().unwrap();
     /// # }
     /// ```
     #[inline]
-    pub fn push_back(&mut self, item: T) -> Result<(), CapacityError<T>> {
-        BaseDeque::push_back(self, item)
+    pub fn spare_slices_mut(&mut self) -> (&mut [T], &mut [T]) {
+        BaseDeque::spare_slices_mut(self)
     }
 
-    /// Removes and returns the first element of the deque.
+    /// Appends the first `n` unused slots to the back of the deque.
     ///
-    /// If the deque is empty, `None` is returned.
+    /// This is used together with [`spare_slices_mut`] to fill the deque
+    /// in place. The appended elements are whatever values the slots contain.
+    ///
+    /// [`spare_slices_mut`]: SliceDeque::spare_slices_mut
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` exceeds the number of unused slots.
     ///
     /// # Example
     ///
     /// ```
-    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # use holodeque::SliceDeque;
     /// # fn main() {
-    /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut slice = [0, 0, 0];
+    /// let mut slice = [0; 4];
     /// let mut deque = SliceDeque::new_in(&mut slice);
     ///
-    /// deque.push_back(1)?;
-    /// deque.push_back(2)?;
-    /// deque.push_back(3)?;
-    ///
-    /// assert_eq!(deque.pop_front(), Some(1));
-    /// assert_eq!(deque.pop_front(), Some(2));
-    /// assert_eq!(deque.pop_front(), Some(3));
-    /// assert_eq!(deque.pop_front(), None);
+    /// let (first, _) = deque.spare_slices_mut();
+    /// first[..2].copy_from_slice(b"hi");
+    /// deque.commit_back(2);
     ///
-    /// # Ok(())
-    /// # })().unwrap();
+    /// assert_eq!(deque.as_slices().0, b"hi");
     /// # }
     /// ```
     #[inline]
-    pub fn pop_front(&mut self) -> Option<T> {
-        BaseDeque::pop_front(self)
+    #[track_caller]
+    pub fn commit_back(&mut self, n: usize) {
+        if BaseDeque::commit_back(self, n).is_none() {
+            panic!(
+                "cannot commit {} slots; only {} are unused",
+                n,
+                self.capacity() - self.len()
+            );
+        }
     }
 
-    /// Removes and returns the last element of the deque.
+    /// Reserves a slot at the back of the deque, returning a guard which
+    /// provides mutable access to it.
     ///
-    /// If the deque is empty, `None` is returned.
+    /// The value in the slot becomes the back element of the deque only when
+    /// [`SlotGuard::commit`] is called. If the guard is dropped instead, the
+    /// deque is left unchanged. This allows an element to be built in place,
+    /// possibly fallibly, without exposing a partially built element.
+    ///
+    /// If the deque is full, `None` is returned.
     ///
     /// # Example
     ///
     /// ```
-    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # use holodeque::SliceDeque;
     /// # fn main() {
-    /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut slice = [0, 0, 0];
+    /// let mut slice = [[0; 4]; 2];
     /// let mut deque = SliceDeque::new_in(&mut slice);
     ///
-    /// deque.push_back(1)?;
-    /// deque.push_back(2)?;
-    /// deque.push_back(3)?;
-    ///
-    /// assert_eq!(deque.pop_back(), Some(3));
-    /// assert_eq!(deque.pop_back(), Some(2));
-    /// assert_eq!(deque.pop_back(), Some(1));
-    /// assert_eq!(deque.pop_back(), None);
+    /// let mut slot = deque.reserve_back_slot().unwrap();
+    /// slot[0] = 1;
+    /// drop(slot);
+    /// assert!(deque.is_empty());
     ///
-    /// # Ok(())
-    /// # })().unwrap();
+    /// let mut slot = deque.reserve_back_slot().unwrap();
+    /// slot.copy_from_slice(&[1, 2, 3, 4]);
+    /// slot.commit();
+    /// assert_eq!(deque.back(), Some(&[1, 2, 3, 4]));
     /// # }
     /// ```
     #[inline]
-    pub fn pop_back(&mut self) -> Option<T> {
-        BaseDeque::pop_back(self)
+    pub fn reserve_back_slot(&mut self) -> Option<SlotGuard<'_, 'a, T>> {
+        DequeSlot::back(self).map(|inner| SlotGuard { inner })
     }
 
-    /// Clears the `SliceDeque`, removing all values.
+    /// Prepends an element to the deque.
+    ///
+    /// If the deque is at capacity, an `Err` containing the pushed value is
+    /// returned.
     ///
     /// # Example
     ///
     /// ```
-    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # use holodeque::{SliceDeque, CapacityError};
     /// # fn main() {
     /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut slice = [0, 0, 0, 0, 0, 0, 0, 0];
+    /// let mut slice = [0, 0, 0];
     /// let mut deque = SliceDeque::new_in(&mut slice);
     ///
-    /// for i in 0..deque.capacity() / 2 {
-    ///     deque.push_front(i)?;
-    ///     deque.push_back(i)?;
-    /// }
+    /// deque.push_front(1)?;
+    /// deque.push_front(2)?;
+    /// deque.push_front(3)?;
     ///
-    /// assert_eq!(deque.len(), 8);
-    /// deque.clear();
-    /// assert!(deque.is_empty());
+    /// assert_eq!(deque.front(), Some(&3));
+    /// assert_eq!(deque.back(), Some(&1));
     ///
+    /// // Another element would exceed capacity, so this fails.
+    /// let err = deque.push_front(4).unwrap_err();
+    /// assert_eq!(err.into_inner(), 4);
     /// # Ok(())
     /// # })().unwrap();
     /// # }
     /// ```
     #[inline]
-    pub fn clear(&mut self) {
-        BaseDeque::clear(self)
+    pub fn push_front(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        BaseDeque::push_front(self, item)
     }
 
-    /// Shortens the `SliceDeque`, keeping the first `len` elements and dropping
-    /// the rest.
+    /// Prepends an element to the deque, panicking if the deque is full.
     ///
-    /// If `len` is greater than the `SliceDeque`'s current length, this has no
-    /// effect.
+    /// This is a convenience for code in which overflowing the deque is a
+    /// logic error. Prefer [`push_front`] where a full deque is an expected
+    /// condition.
+    ///
+    /// [`push_front`]: SliceDeque::push_front
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deque is at capacity.
     ///
     /// # Example
     ///
     /// ```
-    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # use holodeque::SliceDeque;
     /// # fn main() {
-    /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut slice = [0, 0, 0, 0, 0, 0, 0, 0];
+    /// let mut slice = [0; 3];
     /// let mut deque = SliceDeque::new_in(&mut slice);
     ///
-    /// deque.push_back(5)?;
-    /// deque.push_back(10)?;
-    /// deque.push_back(15)?;
-    /// deque.push_back(20)?;
-    /// deque.push_back(25)?;
-    ///
-    /// assert_eq!(deque.len(), 5);
-    /// deque.truncate(2);
-    /// assert_eq!(deque.len(), 2);
+    /// deque.push_front_expect(1);
+    /// deque.push_front_expect(2);
     ///
-    /// # Ok(())
-    /// # })().unwrap();
+    /// assert_eq!(deque.front(), Some(&2));
     /// # }
     /// ```
     #[inline]
-    pub fn truncate(&mut self, len: usize) {
-        BaseDeque::truncate(self, len)
+    #[track_caller]
+    pub fn push_front_expect(&mut self, item: T) {
+        BaseDeque::push_front_expect(self, item)
     }
 
-    /// Returns an iterator over the elements of the deque.
+    /// Prepends an element to the deque, returning a mutable reference to it.
+    ///
+    /// If the deque is at capacity, an `Err` containing the pushed value is
+    /// returned.
     ///
     /// # Example
     ///
@@ -597,44 +968,56 @@ where
     /// # use holodeque::{CapacityError, SliceDeque};
     /// # fn main() {
     /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut slice = ["", "", "", "", ""];
+    /// let mut slice = [(0, ""); 3];
     /// let mut deque = SliceDeque::new_in(&mut slice);
     ///
-    /// deque.push_back("ideas")?;
-    /// deque.push_front("green")?;
-    /// deque.push_back("sleep")?;
-    /// deque.push_front("colorless")?;
-    /// deque.push_back("furiously")?;
-    ///
-    /// let sentence = deque.iter().cloned().collect::<Vec<_>>();
+    /// let (id, _) = deque.push_front_mut((1, "pending"))?;
+    /// *id += 100;
     ///
-    /// assert_eq!(
-    ///     sentence,
-    ///     &["colorless", "green", "ideas", "sleep", "furiously"],
-    /// );
+    /// assert_eq!(deque.front(), Some(&(101, "pending")));
     /// # Ok(())
     /// # })().unwrap();
     /// # }
     /// ```
     #[inline]
-    pub fn iter(&self) -> Iter<'_, 'a, T> {
-        Iter::new(self)
+    pub fn push_front_mut(&mut self, item: T) -> Result<&mut T, CapacityError<T>> {
+        BaseDeque::push_front_mut(self, item)
     }
 
-    /// Drains `n` elements from the front of the deque.
+    /// Prepends an element produced by `f` to the deque, returning a mutable
+    /// reference to it.
     ///
-    /// If `n` exceeds `self.len()`, `None` is returned.
+    /// `f` is only called if the deque has room for another element. If the
+    /// deque is at capacity, `None` is returned instead.
     ///
-    /// When this method is called, `n` elements are immediately removed from
-    /// the front of the deque. If the returned iterator is dropped before
-    /// yielding all its items, they are dropped along with it.
+    /// # Example
     ///
-    /// If the returned iterator is leaked (e.g. with [`mem::forget`]), the
-    /// drained elements will not be dropped immediately. They may be dropped as
-    /// a result of subsequent operations on the deque; otherwise, they will be
-    /// dropped when the deque itself is dropped.
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [String::new()];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
     ///
-    /// [`mem::forget`]: https://doc.rust-lang.org/stable/core/mem/fn.forget.html
+    /// let s = deque.push_front_with(|| String::from("hello")).unwrap();
+    /// s.push_str(", world");
+    /// assert_eq!(deque.front().map(String::as_str), Some("hello, world"));
+    ///
+    /// // The deque is full, so the closure is never called.
+    /// assert!(deque.push_front_with(|| unreachable!()).is_none());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn push_front_with<F>(&mut self, f: F) -> Option<&mut T>
+    where
+        F: FnOnce() -> T,
+    {
+        BaseDeque::push_front_with(self, f)
+    }
+
+    /// Appends an element to the deque.
+    ///
+    /// If the deque is at capacity, an `Err` containing the pushed value is
+    /// returned.
     ///
     /// # Example
     ///
@@ -642,47 +1025,65 @@ where
     /// # use holodeque::{CapacityError, SliceDeque};
     /// # fn main() {
     /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut slice = [0, 0, 0, 0, 0];
+    /// let mut slice = [0, 0, 0];
     /// let mut deque = SliceDeque::new_in(&mut slice);
     ///
-    /// deque.push_back(0)?;
     /// deque.push_back(1)?;
     /// deque.push_back(2)?;
     /// deque.push_back(3)?;
-    /// deque.push_back(4)?;
     ///
-    /// let mut drain = deque.drain_front(3).unwrap();
+    /// assert_eq!(deque.front(), Some(&1));
+    /// assert_eq!(deque.back(), Some(&3));
     ///
-    /// assert_eq!(drain.next(), Some(0));
-    /// assert_eq!(drain.next(), Some(1));
-    /// assert_eq!(drain.next(), Some(2));
-    /// assert_eq!(drain.next(), None);
-    /// drop(drain);
+    /// // Another element would exceed capacity, so this fails.
+    /// let err = deque.push_back(4).unwrap_err();
+    /// assert_eq!(err.into_inner(), 4);
     ///
-    /// assert_eq!(deque.len(), 2);
     /// # Ok(())
     /// # })().unwrap();
     /// # }
     /// ```
     #[inline]
-    pub fn drain_front(&mut self, n: usize) -> Option<DrainFront<'_, 'a, T>> {
-        DrainFront::new(self, n)
+    pub fn push_back(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        BaseDeque::push_back(self, item)
     }
 
-    /// Drains `n` elements from the back of the deque.
+    /// Appends an element to the deque, panicking if the deque is full.
     ///
-    /// If `n` exceeds `self.len()`, `None` is returned.
+    /// This is a convenience for code in which overflowing the deque is a
+    /// logic error. Prefer [`push_back`] where a full deque is an expected
+    /// condition.
     ///
-    /// When this method is called, `n` elements are immediately removed from
-    /// the back of the deque. If the returned iterator is dropped before
-    /// yielding all its items, they are dropped along with it.
+    /// [`push_back`]: SliceDeque::push_back
     ///
-    /// If the returned iterator is leaked (e.g. with [`mem::forget`]), the
-    /// drained elements will not be dropped immediately. They may be dropped as
-    /// a result of subsequent operations on the deque; otherwise, they will be
-    /// dropped when the deque itself is dropped.
+    /// # Panics
     ///
-    /// [`mem::forget`]: https://doc.rust-lang.org/stable/core/mem/fn.forget.html
+    /// Panics if the deque is at capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 3];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.push_back_expect(1);
+    /// deque.push_back_expect(2);
+    ///
+    /// assert_eq!(deque.back(), Some(&2));
+    /// # }
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn push_back_expect(&mut self, item: T) {
+        BaseDeque::push_back_expect(self, item)
+    }
+
+    /// Appends an element to the deque, returning a mutable reference to it.
+    ///
+    /// If the deque is at capacity, an `Err` containing the pushed value is
+    /// returned.
     ///
     /// # Example
     ///
@@ -690,172 +1091,3004 @@ where
     /// # use holodeque::{CapacityError, SliceDeque};
     /// # fn main() {
     /// # (|| -> Result<(), CapacityError<_>> {
-    /// let mut slice = [0, 0, 0, 0, 0];
+    /// let mut slice = [(0, ""); 3];
     /// let mut deque = SliceDeque::new_in(&mut slice);
     ///
-    /// deque.push_back(0)?;
-    /// deque.push_back(1)?;
-    /// deque.push_back(2)?;
-    /// deque.push_back(3)?;
-    /// deque.push_back(4)?;
+    /// let entry = deque.push_back_mut((1, "pending"))?;
+    /// entry.1 = "done";
     ///
-    /// let mut drain = deque.drain_back(3).unwrap();
+    /// assert_eq!(deque.back(), Some(&(1, "done")));
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn push_back_mut(&mut self, item: T) -> Result<&mut T, CapacityError<T>> {
+        BaseDeque::push_back_mut(self, item)
+    }
+
+    /// Appends an element produced by `f` to the deque, returning a mutable
+    /// reference to it.
     ///
-    /// assert_eq!(drain.next(), Some(4));
-    /// assert_eq!(drain.next(), Some(3));
-    /// assert_eq!(drain.next(), Some(2));
-    /// assert_eq!(drain.next(), None);
-    /// drop(drain);
+    /// `f` is only called if the deque has room for another element. If the
+    /// deque is at capacity, `None` is returned instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [String::new()];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// let s = deque.push_back_with(|| String::from("hello")).unwrap();
+    /// s.push_str(", world");
+    /// assert_eq!(deque.back().map(String::as_str), Some("hello, world"));
+    ///
+    /// // The deque is full, so the closure is never called.
+    /// assert!(deque.push_back_with(|| unreachable!()).is_none());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn push_back_with<F>(&mut self, f: F) -> Option<&mut T>
+    where
+        F: FnOnce() -> T,
+    {
+        BaseDeque::push_back_with(self, f)
+    }
+
+    /// Appends an element to the back of the deque, unless it is equal to the
+    /// current back element.
     ///
+    /// Returns `Ok(true)` if the element was appended and `Ok(false)` if it
+    /// was skipped. This coalesces runs of repeated values, such as unchanged
+    /// sensor readings.
+    ///
+    /// If the element is not skipped and the deque is full, an `Err`
+    /// containing `item` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// for reading in [20, 20, 21, 21, 21, 20].iter().copied() {
+    ///     deque.push_back_dedup(reading).unwrap();
+    /// }
+    ///
+    /// assert_eq!(deque.as_slices(), (&[20, 21, 20][..], &[][..]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn push_back_dedup(&mut self, item: T) -> Result<bool, CapacityError<T>>
+    where
+        T: PartialEq,
+    {
+        BaseDeque::push_back_dedup(self, item)
+    }
+
+    /// Appends the items of an iterator to the deque until it is full.
+    ///
+    /// If the deque becomes full before the iterator is exhausted, an `Err` is
+    /// returned containing the number of items that were appended and the
+    /// first item that did not fit. The remaining items are left in the
+    /// iterator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{SliceDeque, TryExtendError};
+    /// # fn main() {
+    /// # (|| -> Result<(), TryExtendError<_>> {
+    /// let mut slice = [0; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.try_extend(vec![1, 2])?;
     /// assert_eq!(deque.len(), 2);
+    ///
+    /// let err = deque.try_extend(3..10).unwrap_err();
+    /// assert_eq!(err.accepted(), 2);
+    /// assert_eq!(err.into_first_rejected(), 5);
+    /// assert!(deque.is_full());
     /// # Ok(())
     /// # })().unwrap();
     /// # }
     /// ```
-    #[inline]
-    pub fn drain_back(&mut self, n: usize) -> Option<DrainBack<'_, 'a, T>> {
-        DrainBack::new(self, n)
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), TryExtendError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        BaseDeque::try_extend(self, iter)
     }
-}
 
-#[cfg(feature = "serde")]
-impl<'a, 'de, T> SliceDeque<'a, T>
-where
-    T: Deserialize<'de> + Default,
-{
-    /// Extends the deque with the contents of a deserializer.
-    pub fn extend_deserialize<D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    /// Prepends the items of an iterator to the deque, keeping their order.
+    ///
+    /// After this call, the first item of the iterator is the front element
+    /// of the deque, followed by the other items and then by the elements
+    /// previously in the deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deque becomes full before the iterator is exhausted. The
+    /// items prepended before then are kept, in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back(4).unwrap();
+    ///
+    /// deque.extend_front(1..=3);
+    /// assert!(deque.iter().copied().eq(1..=4));
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn extend_front<I>(&mut self, iter: I)
     where
-        D: Deserializer<'de>,
+        I: IntoIterator<Item = T>,
     {
-        let extend = ExtendSliceDeque { deque: self };
-        extend.deserialize(deserializer)?;
-        Ok(())
+        BaseDeque::extend_front_expect(self, iter)
+    }
+
+    /// Prepends the items of an iterator to the deque, keeping their order,
+    /// until it is full.
+    ///
+    /// If the deque becomes full before the iterator is exhausted, an `Err` is
+    /// returned containing the number of items that were prepended and the
+    /// first item that did not fit. The prepended items are in the same order
+    /// as in the iterator, ahead of the elements previously in the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back(9).unwrap();
+    ///
+    /// let err = deque.try_extend_front(1..).unwrap_err();
+    /// assert_eq!(err.accepted(), 3);
+    /// assert_eq!(err.into_first_rejected(), 4);
+    /// assert!(deque.iter().copied().eq([1, 2, 3, 9]));
+    /// # }
+    /// ```
+    pub fn try_extend_front<I>(&mut self, iter: I) -> Result<(), TryExtendError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        BaseDeque::try_extend_front(self, iter)
+    }
+
+    /// Appends the items of an iterator to the deque until it is full, and
+    /// returns the number of items that did not fit.
+    ///
+    /// Unlike [`try_extend`], this consumes the whole iterator: the items
+    /// which did not fit are dropped and counted. With the `alloc` feature,
+    /// [`collect_overflow`] returns them instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// assert_eq!(deque.count_overflow(1..=3), 0);
+    /// assert_eq!(deque.count_overflow(4..=10), 6);
+    /// assert!(deque.iter().copied().eq(1..=4));
+    /// # }
+    /// ```
+    ///
+    /// [`try_extend`]: SliceDeque::try_extend
+    /// [`collect_overflow`]: SliceDeque::collect_overflow
+    pub fn count_overflow<I>(&mut self, iter: I) -> usize
+    where
+        I: IntoIterator<Item = T>,
+    {
+        BaseDeque::count_overflow(self, iter)
+    }
+
+    /// Removes and returns the first element of the deque.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0, 0, 0];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.push_back(1)?;
+    /// deque.push_back(2)?;
+    /// deque.push_back(3)?;
+    ///
+    /// assert_eq!(deque.pop_front(), Some(1));
+    /// assert_eq!(deque.pop_front(), Some(2));
+    /// assert_eq!(deque.pop_front(), Some(3));
+    /// assert_eq!(deque.pop_front(), None);
+    ///
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        BaseDeque::pop_front(self)
+    }
+
+    /// Returns a guard which provides mutable access to the first element of
+    /// the deque, and removes it only when committed.
+    ///
+    /// Calling [`PopGuard::commit`] or [`PopGuard::into_inner`] removes the
+    /// element. If the guard is dropped instead, the element stays at the
+    /// front of the deque. Unlike popping an element and pushing it back on
+    /// failure, this cannot fail for lack of capacity, and the element keeps
+    /// its place ahead of the others.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [""; 4];
+    /// let mut inbox = SliceDeque::new_in(&mut slice);
+    /// inbox.try_extend(["retry me", "ok"].iter().copied()).unwrap();
+    ///
+    /// fn handle(msg: &str) -> Result<(), ()> {
+    ///     if msg == "ok" { Ok(()) } else { Err(()) }
+    /// }
+    ///
+    /// let msg = inbox.pop_front_guard().unwrap();
+    /// if handle(*msg).is_ok() {
+    ///     msg.commit();
+    /// }
+    /// assert_eq!(inbox.len(), 2);
+    ///
+    /// inbox.rotate_to_front(1);
+    /// let msg = inbox.pop_front_guard().unwrap();
+    /// if handle(*msg).is_ok() {
+    ///     msg.commit();
+    /// }
+    /// assert!(inbox.iter().eq(&["retry me"]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn pop_front_guard(&mut self) -> Option<PopGuard<'_, 'a, T>> {
+        DequePopGuard::front(self).map(|inner| PopGuard { inner })
+    }
+
+    /// Removes and returns the last element of the deque.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0, 0, 0];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.push_back(1)?;
+    /// deque.push_back(2)?;
+    /// deque.push_back(3)?;
+    ///
+    /// assert_eq!(deque.pop_back(), Some(3));
+    /// assert_eq!(deque.pop_back(), Some(2));
+    /// assert_eq!(deque.pop_back(), Some(1));
+    /// assert_eq!(deque.pop_back(), None);
+    ///
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        BaseDeque::pop_back(self)
+    }
+
+    /// Removes the first element and returns a copy of it.
+    ///
+    /// Unlike [`pop_front`], this does not write the default value of `T`
+    /// into the vacated slot, which avoids a redundant store for large `Copy`
+    /// types. The stale value is left in place until it is overwritten.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// [`pop_front`]: SliceDeque::pop_front
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [[0; 32]; 2];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back([7; 32]).unwrap();
+    ///
+    /// assert_eq!(deque.pop_front_copied(), Some([7; 32]));
+    /// assert_eq!(deque.pop_front_copied(), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn pop_front_copied(&mut self) -> Option<T>
+    where
+        T: Copy,
+    {
+        BaseDeque::pop_front_copied(self)
+    }
+
+    /// Removes the last element and returns a copy of it.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// See [`pop_front_copied`] for details.
+    ///
+    /// [`pop_front_copied`]: SliceDeque::pop_front_copied
+    #[inline]
+    pub fn pop_back_copied(&mut self) -> Option<T>
+    where
+        T: Copy,
+    {
+        BaseDeque::pop_back_copied(self)
+    }
+
+    /// Removes and returns the first element equal to `item`.
+    ///
+    /// The elements between the removed element and the nearer end of the
+    /// deque are shifted to fill the gap, so this takes time proportional to
+    /// the position of the element plus the distance to the nearer end.
+    ///
+    /// If no element is equal to `item`, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0; 4];
+    /// let mut tasks = SliceDeque::new_in(&mut slice);
+    /// tasks.push_back(10)?;
+    /// tasks.push_back(11)?;
+    /// tasks.push_back(12)?;
+    ///
+    /// assert_eq!(tasks.remove_item(&11), Some(11));
+    /// assert_eq!(tasks.remove_item(&11), None);
+    /// assert_eq!(tasks.as_slices(), (&[10, 12][..], &[][..]));
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn remove_item(&mut self, item: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        BaseDeque::remove_item(self, item)
+    }
+
+    /// Returns `true` if the deque contains an element equal to `item`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = ['\0'; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back('b')?;
+    /// deque.push_front('a')?;
+    ///
+    /// assert!(deque.contains(&'a'));
+    /// assert!(!deque.contains(&'c'));
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn contains(&self, item: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        BaseDeque::contains(self, item)
+    }
+
+    /// Returns the index of the first element equal to `item`, counting from
+    /// the front of the deque.
+    ///
+    /// If no element is equal to `item`, `None` is returned. To search a
+    /// byte deque, [`find_byte`] may be faster.
+    ///
+    /// [`find_byte`]: SliceDeque::find_byte
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = ['\0'; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back('b')?;
+    /// deque.push_back('c')?;
+    /// deque.push_front('a')?;
+    ///
+    /// assert_eq!(deque.position_of(&'c'), Some(2));
+    /// assert_eq!(deque.position_of(&'d'), None);
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn position_of(&self, item: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        BaseDeque::position_of(self, item)
+    }
+
+    /// Returns `true` if `other` holds the same elements as this deque, in
+    /// the same cyclic order.
+    ///
+    /// The two deques are equal up to rotation: one of them can be turned
+    /// into the other by repeatedly moving its front element to the back.
+    /// This takes time linear in the length of the deques and does not
+    /// allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{ArrayDeque, SliceDeque};
+    /// # fn main() {
+    /// let mut slice = [' '; 4];
+    /// let mut before = SliceDeque::new_in(&mut slice);
+    /// before.try_extend("abcd".chars()).unwrap();
+    ///
+    /// let mut after: ArrayDeque<char, 4> = ArrayDeque::new();
+    /// after.try_extend("cdab".chars()).unwrap();
+    /// assert!(before.cyclic_eq(&after));
+    ///
+    /// after.clear();
+    /// after.try_extend("dcba".chars()).unwrap();
+    /// assert!(!before.cyclic_eq(&after));
+    /// # }
+    /// ```
+    pub fn cyclic_eq<D>(&self, other: &D) -> bool
+    where
+        D: Deque<T> + ?Sized,
+        T: PartialEq,
+    {
+        BaseDeque::cyclic_eq(self, other)
+    }
+
+    /// Clears the `SliceDeque`, removing all values.
+    ///
+    /// If `T` does not need to be dropped, this takes constant time, as the
+    /// removed elements are left in the backing storage to be overwritten.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0, 0, 0, 0, 0, 0, 0, 0];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// for i in 0..deque.capacity() / 2 {
+    ///     deque.push_front(i)?;
+    ///     deque.push_back(i)?;
+    /// }
+    ///
+    /// assert_eq!(deque.len(), 8);
+    /// deque.clear();
+    /// assert!(deque.is_empty());
+    ///
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        BaseDeque::clear(self)
+    }
+
+    /// Shortens the `SliceDeque`, keeping the first `len` elements and dropping
+    /// the rest.
+    ///
+    /// If `len` is greater than the `SliceDeque`'s current length, this has no
+    /// effect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0, 0, 0, 0, 0, 0, 0, 0];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.push_back(5)?;
+    /// deque.push_back(10)?;
+    /// deque.push_back(15)?;
+    /// deque.push_back(20)?;
+    /// deque.push_back(25)?;
+    ///
+    /// assert_eq!(deque.len(), 5);
+    /// deque.truncate(2);
+    /// assert_eq!(deque.len(), 2);
+    ///
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        BaseDeque::truncate(self, len)
+    }
+
+    /// Returns a guard which truncates the deque back to its current length
+    /// when dropped, unless [`keep`] is called.
+    ///
+    /// This undoes speculative pushes to the back of the deque on every path
+    /// out of a scope, including early returns and panics.
+    ///
+    /// [`keep`]: TruncateGuard::keep
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 8];
+    /// let mut path = SliceDeque::new_in(&mut slice);
+    /// path.push_back(0).unwrap();
+    ///
+    /// {
+    ///     let mut attempt = path.truncate_guard();
+    ///     attempt.push_back(1).unwrap();
+    ///     attempt.push_back(2).unwrap();
+    ///     // Dead end: dropping the guard removes 1 and 2.
+    /// }
+    ///
+    /// let mut attempt = path.truncate_guard();
+    /// attempt.push_back(3).unwrap();
+    /// attempt.keep();
+    ///
+    /// assert!(path.iter().copied().eq([0, 3]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn truncate_guard(&mut self) -> TruncateGuard<'_, 'a, T> {
+        TruncateGuard {
+            inner: DequeTruncateGuard::new(self),
+        }
+    }
+
+    /// Shortens the `SliceDeque`, keeping the last `len` elements and dropping
+    /// the rest.
+    ///
+    /// If `len` is greater than the `SliceDeque`'s current length, this has no
+    /// effect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0; 8];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.push_back(5)?;
+    /// deque.push_back(10)?;
+    /// deque.push_back(15)?;
+    ///
+    /// deque.truncate_front(2);
+    /// assert_eq!(deque.front(), Some(&10));
+    /// assert_eq!(deque.len(), 2);
+    ///
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn truncate_front(&mut self, len: usize) {
+        BaseDeque::truncate_front(self, len)
+    }
+
+    /// Returns `true` if the first elements of the deque are equal to
+    /// `prefix`.
+    ///
+    /// An empty `prefix` matches any deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 16];
+    /// let mut rx = SliceDeque::new_in(&mut slice);
+    /// rx.try_extend(b"GET /".iter().copied()).unwrap();
+    ///
+    /// assert!(rx.starts_with(b"GET"));
+    /// assert!(!rx.starts_with(b"PUT"));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn starts_with(&self, prefix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        BaseDeque::starts_with(self, prefix)
+    }
+
+    /// Returns `true` if the last elements of the deque are equal to
+    /// `suffix`.
+    ///
+    /// An empty `suffix` matches any deque.
+    #[inline]
+    pub fn ends_with(&self, suffix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        BaseDeque::ends_with(self, suffix)
+    }
+
+    /// Removes the first elements of the deque if they are equal to `prefix`,
+    /// returning `true` if they were removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 16];
+    /// let mut rx = SliceDeque::new_in(&mut slice);
+    /// rx.try_extend(b"OK\r\n".iter().copied()).unwrap();
+    ///
+    /// assert!(!rx.strip_prefix(b"ERR"));
+    /// assert!(rx.strip_prefix(b"OK"));
+    /// assert!(rx.strip_suffix(b"\r\n"));
+    /// assert!(rx.is_empty());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn strip_prefix(&mut self, prefix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        BaseDeque::strip_prefix(self, prefix)
+    }
+
+    /// Removes the last elements of the deque if they are equal to `suffix`,
+    /// returning `true` if they were removed.
+    #[inline]
+    pub fn strip_suffix(&mut self, suffix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        BaseDeque::strip_suffix(self, suffix)
+    }
+
+    /// Moves elements from the front of the deque into `dst`, in order,
+    /// returning the number of elements moved.
+    ///
+    /// As many elements are moved as fit in `dst`; any remaining elements stay
+    /// in the deque. If `dst` is at least as long as the deque, the deque is
+    /// left empty. This makes the contents contiguous in a single pass without
+    /// rotating the backing storage, at the cost of a scratch buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back(2).unwrap();
+    /// deque.push_back(3).unwrap();
+    /// deque.push_front(1).unwrap();
+    ///
+    /// let mut scratch = [0; 8];
+    /// let n = deque.linearize_into(&mut scratch);
+    ///
+    /// assert_eq!(&scratch[..n], &[1, 2, 3]);
+    /// assert!(deque.is_empty());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn linearize_into(&mut self, dst: &mut [T]) -> usize {
+        BaseDeque::linearize_into(self, dst)
+    }
+
+    /// Clones elements from the front of the deque into `dst`, in order,
+    /// returning the number of elements cloned.
+    ///
+    /// Unlike [`linearize_into`], this leaves the deque unchanged.
+    ///
+    /// [`linearize_into`]: SliceDeque::linearize_into
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back(2).unwrap();
+    /// deque.push_back(3).unwrap();
+    /// deque.push_front(1).unwrap();
+    ///
+    /// let mut scratch = [0; 2];
+    /// assert_eq!(deque.clone_into_slice(&mut scratch), 2);
+    ///
+    /// assert_eq!(scratch, [1, 2]);
+    /// assert_eq!(deque.len(), 3);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn clone_into_slice(&self, dst: &mut [T]) -> usize
+    where
+        T: Clone,
+    {
+        BaseDeque::clone_into_slice(self, dst)
+    }
+
+    /// Moves the contents of the deque into `slice`, which becomes the new
+    /// backing storage, and returns the old backing slice.
+    ///
+    /// The elements keep their order and are placed at the start of `slice`;
+    /// the capacity of the deque becomes the length of `slice`. As with
+    /// [`new_in`], the elements previously in `slice` are dropped. The
+    /// returned slice is left filled with the default value of `T`.
+    ///
+    /// If `slice` is too short to hold every element of the deque, the deque
+    /// is left unchanged and `slice` is returned in a [`CapacityError`].
+    ///
+    /// [`new_in`]: SliceDeque::new_in
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut small = [0; 2];
+    /// let mut deque = SliceDeque::new_in(&mut small);
+    /// deque.push_back(2).unwrap();
+    /// deque.push_front(1).unwrap();
+    /// assert!(deque.push_back(3).is_err());
+    ///
+    /// let mut large = [0; 4];
+    /// let old = deque.replace_storage(&mut large).unwrap();
+    /// assert_eq!(old.len(), 2);
+    ///
+    /// deque.push_back(3).unwrap();
+    /// assert_eq!(deque.capacity(), 4);
+    /// assert!(deque.iter().eq(&[1, 2, 3]));
+    /// # }
+    /// ```
+    pub fn replace_storage(
+        &mut self,
+        slice: &'a mut [T],
+    ) -> Result<&'a mut [T], CapacityError<&'a mut [T]>> {
+        let len = self.len();
+        if slice.len() < len {
+            return Err(CapacityError { item: slice });
+        }
+
+        let (front, back) = self.as_mut_slices();
+        for (dst, src) in slice.iter_mut().zip(front.iter_mut().chain(back)) {
+            *dst = mem::take(src);
+        }
+        for item in slice[len..].iter_mut() {
+            drop(mem::take(item));
+        }
+
+        // The length is unchanged, so the layout is replaced directly rather
+        // than through `set_layout`, which would count it in the statistics.
+        self.meta.capacity = slice.len();
+        self.meta.layout = match NonZeroUsize::new(len) {
+            Some(len) => MetaLayout::Linear { first: 0, len },
+            None => MetaLayout::Empty,
+        };
+
+        Ok(mem::replace(&mut self.items, slice))
+    }
+
+    /// Shrinks the capacity of the deque to `n` and returns the rest of the
+    /// backing slice.
+    ///
+    /// The elements keep their order. If any of them lie at or beyond index
+    /// `n` of the backing slice, they are moved to its start. The returned
+    /// slice holds no elements of the deque, but may contain stale copies of
+    /// removed elements whose type does not need to be dropped.
+    ///
+    /// If `n` is less than `self.len()` or greater than `self.capacity()`,
+    /// the deque is left unchanged and `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 8];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.try_extend(1..=3).unwrap();
+    ///
+    /// let scratch = deque.truncate_capacity(4).unwrap();
+    /// assert_eq!(scratch.len(), 4);
+    /// scratch.fill(9);
+    ///
+    /// assert_eq!(deque.capacity(), 4);
+    /// assert!(deque.iter().eq(&[1, 2, 3]));
+    /// # }
+    /// ```
+    pub fn truncate_capacity(&mut self, n: usize) -> Option<&'a mut [T]> {
+        let len = self.len();
+        if n < len || n > self.capacity() {
+            return None;
+        }
+
+        // Savepoints record physical positions, which this may change.
+        self.meta.invalidate_savepoints();
+
+        let (front, wrap) = self.meta.as_ranges();
+        if front.end > n || !wrap.is_empty() {
+            // Rotating the slice up to the end of the front portion moves it
+            // to the start, followed by the wrapped portion if there is one.
+            let end = if wrap.is_empty() {
+                front.end
+            } else {
+                self.items.len()
+            };
+            self.items[..end].rotate_left(front.start);
+
+            // The length is unchanged, so the layout is replaced directly
+            // rather than through `set_layout`, as in `replace_storage`.
+            self.meta.layout = MetaLayout::Linear {
+                first: 0,
+                len: NonZeroUsize::new(len).unwrap(),
+            };
+        }
+        self.meta.capacity = n;
+
+        let (kept, rest) = mem::take(&mut self.items).split_at_mut(n);
+        self.items = kept;
+
+        Some(rest)
+    }
+
+    /// Rotates the deque so that the element at logical index `index` becomes
+    /// the first element.
+    ///
+    /// The elements before `index` are moved, in order, to the back of the
+    /// deque. This is equivalent to rotating the deque left by `index`
+    /// positions. The deque is made contiguous first, so this may also change
+    /// its layout in the backing storage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > self.len()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 8];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.try_extend([0, 1, 2, 3, 4].iter().copied()).unwrap();
+    ///
+    /// let next = deque.iter().position(|&task| task == 3).unwrap();
+    /// deque.rotate_to_front(next);
+    ///
+    /// assert_eq!(deque.as_slices(), (&[3, 4, 0, 1, 2][..], &[][..]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn rotate_to_front(&mut self, index: usize) {
+        BaseDeque::rotate_to_front(self, index)
+    }
+
+    /// Moves the front element to the back of the deque, returning a mutable
+    /// reference to the new front element, or `None` if the deque is empty.
+    ///
+    /// This takes constant time, and unlike a [`pop_front`] followed by a
+    /// [`push_back`], it cannot fail for lack of capacity. If the deque holds
+    /// a single element, that element is returned.
+    ///
+    /// [`pop_front`]: SliceDeque::pop_front
+    /// [`push_back`]: SliceDeque::push_back
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [""; 4];
+    /// let mut tasks = SliceDeque::new_in(&mut slice);
+    /// tasks.try_extend(["net", "ui", "disk"].iter().copied()).unwrap();
+    ///
+    /// assert_eq!(tasks.round_robin(), Some(&mut "ui"));
+    /// assert_eq!(tasks.round_robin(), Some(&mut "disk"));
+    /// assert!(tasks.iter().eq(&["disk", "net", "ui"]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn round_robin(&mut self) -> Option<&mut T> {
+        BaseDeque::round_robin(self)
+    }
+
+    /// Reorders the deque such that the element at logical index `index` is
+    /// at its final sorted position, returning the elements before it, the
+    /// element itself and the elements after it.
+    ///
+    /// This behaves like [`slice::select_nth_unstable`]: every element
+    /// before `index` is less than or equal to it and every element after is
+    /// greater than or equal to it. The deque is made contiguous first, so
+    /// this may also change its layout in the backing storage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= self.len()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 8];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.try_extend([7, 1, 9, 4, 3].iter().copied()).unwrap();
+    ///
+    /// let (_, median, _) = deque.select_nth_unstable(2);
+    /// assert_eq!(*median, 4);
+    /// assert_eq!(deque.len(), 5);
+    /// # }
+    /// ```
+    ///
+    /// [`slice::select_nth_unstable`]: https://doc.rust-lang.org/stable/std/primitive.slice.html#method.select_nth_unstable
+    #[inline]
+    pub fn select_nth_unstable(&mut self, index: usize) -> (&mut [T], &mut T, &mut [T])
+    where
+        T: Ord,
+    {
+        BaseDeque::select_nth_unstable_by(self, index, T::cmp)
+    }
+
+    /// Reorders the deque with a comparator function such that the element at
+    /// logical index `index` is at its final sorted position.
+    ///
+    /// See [`select_nth_unstable`](Self::select_nth_unstable) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= self.len()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0.0f32; 8];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.try_extend([0.5, 2.0, -1.0, 1.5].iter().copied()).unwrap();
+    ///
+    /// let (_, max, _) = deque.select_nth_unstable_by(3, |a, b| a.total_cmp(b));
+    /// assert_eq!(*max, 2.0);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn select_nth_unstable_by<F>(
+        &mut self,
+        index: usize,
+        compare: F,
+    ) -> (&mut [T], &mut T, &mut [T])
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        BaseDeque::select_nth_unstable_by(self, index, compare)
+    }
+
+    /// Reorders the deque with a key extraction function such that the
+    /// element at logical index `index` is at its final sorted position.
+    ///
+    /// See [`select_nth_unstable`](Self::select_nth_unstable) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= self.len()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0i32; 8];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.try_extend([-8, 3, -1, 5].iter().copied()).unwrap();
+    ///
+    /// let (_, nearest, _) = deque.select_nth_unstable_by_key(0, |x| x.abs());
+    /// assert_eq!(*nearest, -1);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn select_nth_unstable_by_key<K, F>(
+        &mut self,
+        index: usize,
+        mut f: F,
+    ) -> (&mut [T], &mut T, &mut [T])
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        BaseDeque::select_nth_unstable_by(self, index, |a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Returns a [`Savepoint`] recording the current contents of the deque.
+    ///
+    /// Passing the savepoint to [`rollback_to`] later removes every element
+    /// pushed to either end of the deque since the savepoint was created.
+    ///
+    /// Pushing to either end, including by extending the deque, keeps the
+    /// savepoint valid. Any other modification invalidates it, including
+    /// popping, removing or inserting elements, mutable access to elements,
+    /// rotating, sorting and editing through a cursor. The one exception is
+    /// a savepoint of an empty deque, which can always be rolled back to.
+    ///
+    /// [`rollback_to`]: SliceDeque::rollback_to
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = ['\0'; 8];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back('a')?;
+    ///
+    /// let savepoint = deque.savepoint();
+    /// deque.push_back('b')?;
+    /// deque.push_front('c')?;
+    ///
+    /// deque.rollback_to(savepoint);
+    /// assert_eq!(deque.iter().collect::<String>(), "a");
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn savepoint(&self) -> Savepoint {
+        BaseDeque::savepoint(self)
+    }
+
+    /// Removes every element pushed since `savepoint` was created, restoring
+    /// the deque to its contents at that time.
+    ///
+    /// The removed elements are dropped in place, without moving the
+    /// remaining elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deque has been modified other than by pushing since the
+    /// savepoint was created, as described in [`savepoint`]. If the savepoint
+    /// was created by a different deque, this may not be detected, and the
+    /// contents of the deque are unspecified.
+    ///
+    /// [`savepoint`]: SliceDeque::savepoint
+    #[inline]
+    pub fn rollback_to(&mut self, savepoint: Savepoint) {
+        BaseDeque::rollback_to(self, savepoint)
+    }
+
+    /// Returns the usage statistics of the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// let mut slice = [0; 2];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back(1).unwrap();
+    /// deque.push_back(2).unwrap();
+    /// assert!(deque.push_back(3).is_err());
+    /// deque.pop_front();
+    ///
+    /// let stats = deque.stats();
+    /// assert_eq!(stats.high_water_mark(), 2);
+    /// assert_eq!(stats.pushes(), 2);
+    /// assert_eq!(stats.pops(), 1);
+    /// assert_eq!(stats.rejected_pushes(), 1);
+    /// ```
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn stats(&self) -> &DequeStats {
+        self.meta.stats()
+    }
+
+    /// Resets the usage statistics of the deque.
+    ///
+    /// The counters are set to zero and the high-water mark is set to the
+    /// current number of elements.
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn reset_stats(&mut self) {
+        BaseDeque::reset_stats(self)
+    }
+
+    /// Returns an iterator over the elements of the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = ["", "", "", "", ""];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.push_back("ideas")?;
+    /// deque.push_front("green")?;
+    /// deque.push_back("sleep")?;
+    /// deque.push_front("colorless")?;
+    /// deque.push_back("furiously")?;
+    ///
+    /// let sentence = deque.iter().cloned().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     sentence,
+    ///     &["colorless", "green", "ideas", "sleep", "furiously"],
+    /// );
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, 'a, T> {
+        Iter::new(self)
+    }
+
+    /// Drains `n` elements from the front of the deque.
+    ///
+    /// If `n` exceeds `self.len()`, `None` is returned.
+    ///
+    /// When this method is called, `n` elements are immediately removed from
+    /// the front of the deque. If the returned iterator is dropped before
+    /// yielding all its items, they are dropped along with it.
+    ///
+    /// If the returned iterator is leaked (e.g. with [`mem::forget`]), the
+    /// drained elements will not be dropped immediately. They may be dropped as
+    /// a result of subsequent operations on the deque; otherwise, they will be
+    /// dropped when the deque itself is dropped.
+    ///
+    /// [`mem::forget`]: https://doc.rust-lang.org/stable/core/mem/fn.forget.html
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0, 0, 0, 0, 0];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.push_back(0)?;
+    /// deque.push_back(1)?;
+    /// deque.push_back(2)?;
+    /// deque.push_back(3)?;
+    /// deque.push_back(4)?;
+    ///
+    /// let mut drain = deque.drain_front(3).unwrap();
+    ///
+    /// assert_eq!(drain.next(), Some(0));
+    /// assert_eq!(drain.next(), Some(1));
+    /// assert_eq!(drain.next(), Some(2));
+    /// assert_eq!(drain.next(), None);
+    /// drop(drain);
+    ///
+    /// assert_eq!(deque.len(), 2);
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn drain_front(&mut self, n: usize) -> Option<DrainFront<'_, 'a, T>> {
+        DrainFront::new(self, n)
+    }
+
+    /// Drains `n` elements from the back of the deque.
+    ///
+    /// If `n` exceeds `self.len()`, `None` is returned.
+    ///
+    /// When this method is called, `n` elements are immediately removed from
+    /// the back of the deque. If the returned iterator is dropped before
+    /// yielding all its items, they are dropped along with it.
+    ///
+    /// If the returned iterator is leaked (e.g. with [`mem::forget`]), the
+    /// drained elements will not be dropped immediately. They may be dropped as
+    /// a result of subsequent operations on the deque; otherwise, they will be
+    /// dropped when the deque itself is dropped.
+    ///
+    /// [`mem::forget`]: https://doc.rust-lang.org/stable/core/mem/fn.forget.html
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0, 0, 0, 0, 0];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.push_back(0)?;
+    /// deque.push_back(1)?;
+    /// deque.push_back(2)?;
+    /// deque.push_back(3)?;
+    /// deque.push_back(4)?;
+    ///
+    /// let mut drain = deque.drain_back(3).unwrap();
+    ///
+    /// assert_eq!(drain.next(), Some(4));
+    /// assert_eq!(drain.next(), Some(3));
+    /// assert_eq!(drain.next(), Some(2));
+    /// assert_eq!(drain.next(), None);
+    /// drop(drain);
+    ///
+    /// assert_eq!(deque.len(), 2);
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn drain_back(&mut self, n: usize) -> Option<DrainBack<'_, 'a, T>> {
+        DrainBack::new(self, n)
+    }
+
+    /// Returns an iterator which removes elements from the front of the deque
+    /// in arrays of `C` elements.
+    ///
+    /// Each call to `next` removes one array's worth of elements. Iteration
+    /// stops when fewer than `C` elements remain; those are left in the deque,
+    /// so that they can be completed by elements pushed later.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `C` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 8];
+    /// let mut samples = SliceDeque::new_in(&mut slice);
+    /// samples.try_extend(0..7).unwrap();
+    ///
+    /// let mut chunks = samples.drain_chunks::<3>();
+    /// assert_eq!(chunks.next(), Some([0, 1, 2]));
+    /// assert_eq!(chunks.next(), Some([3, 4, 5]));
+    /// assert_eq!(chunks.next(), None);
+    ///
+    /// assert_eq!(samples.len(), 1);
+    /// # }
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn drain_chunks<const C: usize>(&mut self) -> DrainChunks<'_, 'a, T, C> {
+        DrainChunks {
+            inner: DequeDrainChunks::new(self),
+        }
+    }
+
+    /// Retains only the elements for which `f` returns `true`, removing the
+    /// rest.
+    ///
+    /// The retained elements keep their order. See [`retain_mut`] for the
+    /// behavior if `f` panics.
+    ///
+    /// [`retain_mut`]: SliceDeque::retain_mut
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 8];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.try_extend(1..=6).unwrap();
+    ///
+    /// deque.retain(|&x| x % 3 != 0);
+    /// assert!(deque.iter().copied().eq([1, 2, 4, 5]));
+    /// # }
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        BaseDeque::retain_mut(self, |item| f(item))
+    }
+
+    /// Retains only the elements for which `f` returns `true`, removing the
+    /// rest. `f` may modify the elements it is passed.
+    ///
+    /// The retained elements keep their order.
+    ///
+    /// If `f` panics, the deque keeps the elements `f` has already retained,
+    /// followed by the element `f` panicked on and all elements not yet
+    /// passed to `f`. The elements `f` has already rejected are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 8];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.try_extend([5, 1, 8, 2].iter().copied()).unwrap();
+    ///
+    /// deque.retain_mut(|x| {
+    ///     *x *= 10;
+    ///     *x > 20
+    /// });
+    /// assert!(deque.iter().copied().eq([50, 80]));
+    /// # }
+    /// ```
+    pub fn retain_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        BaseDeque::retain_mut(self, f)
+    }
+
+    /// Returns an iterator which removes and yields the elements for which
+    /// `pred` returns `true`, in order.
+    ///
+    /// `pred` may modify the elements it is passed. If the iterator is
+    /// dropped before it is exhausted, the elements not yet passed to `pred`
+    /// are kept. The remaining elements keep their order.
+    ///
+    /// If `pred` panics, the deque keeps every element which has not been
+    /// extracted, including the element `pred` panicked on. If the iterator
+    /// is leaked, for example with [`mem::forget`], default values may be
+    /// left in the deque in place of the extracted elements.
+    ///
+    /// [`mem::forget`]: core::mem::forget
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 8];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.try_extend(1..=6).unwrap();
+    ///
+    /// let evens: Vec<u32> = deque.extract_if(|x| *x % 2 == 0).collect();
+    /// assert_eq!(evens, [2, 4, 6]);
+    /// assert!(deque.iter().copied().eq([1, 3, 5]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, 'a, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf {
+            inner: DequeExtractIf::new(self, pred),
+        }
+    }
+
+    /// Returns a cursor pointing at the front element of the deque.
+    ///
+    /// If the deque is empty, the cursor points at the "ghost" position
+    /// between the back and the front of the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0; 8];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back(1)?;
+    /// deque.push_back(3)?;
+    /// deque.push_back(4)?;
+    ///
+    /// let mut cursor = deque.cursor_front_mut();
+    /// cursor.move_next();
+    /// cursor.insert_before(2)?;
+    /// cursor.move_next();
+    /// assert_eq!(cursor.remove_current(), Some(4));
+    ///
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, 'a, T> {
+        CursorMut {
+            inner: DequeCursor::front(self),
+        }
+    }
+
+    /// Returns a cursor pointing at the back element of the deque.
+    ///
+    /// If the deque is empty, the cursor points at the "ghost" position
+    /// between the back and the front of the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0; 8];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back(1)?;
+    /// deque.push_back(2)?;
+    ///
+    /// let mut cursor = deque.cursor_back_mut();
+    /// *cursor.current().unwrap() *= 10;
+    /// cursor.insert_after(3)?;
+    ///
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 20, 3]);
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, 'a, T> {
+        CursorMut {
+            inner: DequeCursor::back(self),
+        }
+    }
+
+    /// Splits the deque into a handle to its front and a handle to its back.
+    ///
+    /// The [`FrontHandle`] can only inspect and remove elements at the front
+    /// of the deque, and the [`BackHandle`] can only inspect and add elements
+    /// at the back. Both handles may be used at the same time, which allows a
+    /// producer and a consumer within one thread to share the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0u32; 2];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// let (front, back) = deque.split();
+    ///
+    /// let produce = |item| back.push_back(item).is_ok();
+    /// let consume = || front.pop_front();
+    ///
+    /// assert!(produce(1));
+    /// assert!(produce(2));
+    /// assert_eq!(consume(), Some(1));
+    /// assert!(produce(3));
+    /// assert_eq!(consume(), Some(2));
+    /// assert_eq!(consume(), Some(3));
+    /// assert_eq!(consume(), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn split(&mut self) -> (FrontHandle<'_, T>, BackHandle<'_, T>) {
+        let (front, back) = DequeHalf::split(self);
+
+        (FrontHandle { inner: front }, BackHandle { inner: back })
+    }
+
+    /// Returns an adaptor which formats the physical layout of the deque.
+    ///
+    /// The `Debug` implementation of `SliceDeque` prints only the elements of
+    /// the deque in logical order. The returned adaptor instead prints the
+    /// layout metadata and the entire backing slice, including unused slots.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0, 0, 0, 0];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.push_back(1)?;
+    /// deque.push_front(2)?;
+    ///
+    /// assert_eq!(format!("{:?}", deque), "[2, 1]");
+    /// assert_eq!(
+    ///     format!("{:?}", deque.debug_internals()),
+    ///     "DebugInternals { \
+    ///         layout: Wrapped { wrap_len: 1, gap_len: 2 }, \
+    ///         items: [1, 0, 0, 2] \
+    ///     }",
+    /// );
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn debug_internals(&self) -> DebugInternals<'_, T> {
+        BaseDeque::debug_internals(self)
+    }
+
+    /// Returns a [`LayoutInfo`] describing where the elements of the deque
+    /// are placed in the backing storage.
+    ///
+    /// Unlike [`debug_internals`](Self::debug_internals), the returned value
+    /// can be inspected programmatically, for example to check whether the
+    /// elements can be handed to a DMA transfer as a single buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0, 0, 0, 0];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.push_back(1)?;
+    /// assert!(deque.layout_info().is_contiguous());
+    ///
+    /// deque.push_front(2)?;
+    /// let layout = deque.layout_info();
+    /// assert!(!layout.is_contiguous());
+    /// assert_eq!(layout.head(), 3);
+    /// assert_eq!(layout.wrap_len(), 1);
+    /// assert_eq!(layout.gap_len(), 2);
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn layout_info(&self) -> LayoutInfo {
+        BaseDeque::layout_info(self)
+    }
+
+    /// Returns an adaptor which displays the elements of the deque in order,
+    /// separated by `separator`.
+    ///
+    /// Formatting options are applied to each element individually.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, SliceDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut slice = [0.0; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.push_back(0.5)?;
+    /// deque.push_back(1.0)?;
+    /// deque.push_front(0.25)?;
+    ///
+    /// assert_eq!(
+    ///     format!("{:.2}", deque.display_separated(", ")),
+    ///     "0.25, 0.50, 1.00",
+    /// );
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn display_separated<'s>(&'s self, separator: &'s str) -> DisplaySeparated<'s, T> {
+        BaseDeque::display_separated(self, separator)
+    }
+}
+
+#[cfg(feature = "minicbor")]
+impl<'a, T> SliceDeque<'a, T>
+where
+    T: Default,
+{
+    /// Extends the deque with the elements of a CBOR array.
+    ///
+    /// Both definite- and indefinite-length arrays are accepted. If the array
+    /// has more elements than the deque has room for, an error is returned
+    /// and the elements decoded before the error remain in the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() -> Result<(), minicbor::decode::Error> {
+    /// let mut slice = [0u32; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// // [1, 2, 3]
+    /// let mut decoder = minicbor::Decoder::new(&[0x83, 0x01, 0x02, 0x03]);
+    /// deque.extend_decode(&mut decoder, &mut ())?;
+    ///
+    /// assert_eq!(deque.len(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn extend_decode<'b, C>(
+        &mut self,
+        d: &mut minicbor::Decoder<'b>,
+        ctx: &mut C,
+    ) -> Result<(), minicbor::decode::Error>
+    where
+        T: minicbor::Decode<'b, C>,
+    {
+        crate::cbor::extend_decode(self, d, ctx)
+    }
+}
+
+#[cfg(feature = "minicbor")]
+impl<'a, C, T> minicbor::Encode<C> for SliceDeque<'a, T>
+where
+    T: minicbor::Encode<C> + Default,
+{
+    fn encode<W>(
+        &self,
+        e: &mut minicbor::Encoder<W>,
+        ctx: &mut C,
+    ) -> Result<(), minicbor::encode::Error<W::Error>>
+    where
+        W: minicbor::encode::Write,
+    {
+        crate::cbor::encode(self, e, ctx)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<'a, T> SliceDeque<'a, T>
+where
+    T: Default + zeroize::Zeroize,
+{
+    /// Removes the first element and returns it, zeroizing the slot it
+    /// occupied.
+    ///
+    /// Unlike [`pop_front`], which leaves the default value of `T` in the
+    /// vacated slot, this overwrites the slot using [`Zeroize`], which is
+    /// guaranteed not to be optimized away.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// [`pop_front`]: SliceDeque::pop_front
+    /// [`Zeroize`]: zeroize::Zeroize
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [[0; 32]; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back([0x55; 32]).unwrap();
+    ///
+    /// assert_eq!(deque.pop_front_zeroize(), Some([0x55; 32]));
+    /// assert_eq!(deque.pop_front_zeroize(), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn pop_front_zeroize(&mut self) -> Option<T> {
+        BaseDeque::pop_front_zeroize(self)
+    }
+
+    /// Removes the last element and returns it, zeroizing the slot it
+    /// occupied.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// See [`pop_front_zeroize`] for details.
+    ///
+    /// [`pop_front_zeroize`]: SliceDeque::pop_front_zeroize
+    #[inline]
+    pub fn pop_back_zeroize(&mut self) -> Option<T> {
+        BaseDeque::pop_back_zeroize(self)
+    }
+
+    /// Clears the deque, zeroizing each element in place before removing it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [[0; 32]; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back([0x55; 32]).unwrap();
+    /// deque.push_front([0xaa; 32]).unwrap();
+    ///
+    /// deque.clear_zeroize();
+    /// assert!(deque.is_empty());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn clear_zeroize(&mut self) {
+        BaseDeque::clear_zeroize(self)
+    }
+}
+
+/// Zeroizes every slot of the backing storage, including unused ones, and
+/// leaves the deque empty.
+#[cfg(feature = "zeroize")]
+impl<'a, T> zeroize::Zeroize for SliceDeque<'a, T>
+where
+    T: Default + zeroize::Zeroize,
+{
+    fn zeroize(&mut self) {
+        BaseDeque::zeroize_all(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> SliceDeque<'a, T>
+where
+    T: Default,
+{
+    /// Appends the items of an iterator to the deque until it is full, and
+    /// returns the items that did not fit, in order.
+    ///
+    /// Unlike [`try_extend`], this consumes the whole iterator, so nothing is
+    /// silently lost and the returned items can be retried later. If every
+    /// item fits, the returned `Vec` is empty and does not allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back(1).unwrap();
+    ///
+    /// let leftover = deque.collect_overflow(2..=6);
+    /// assert_eq!(leftover, [5, 6]);
+    /// assert!(deque.iter().copied().eq(1..=4));
+    /// # }
+    /// ```
+    ///
+    /// [`try_extend`]: SliceDeque::try_extend
+    pub fn collect_overflow<I>(&mut self, iter: I) -> Vec<T>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        BaseDeque::collect_overflow(self, iter)
+    }
+
+    /// Concatenates the elements of the deque into a single value.
+    ///
+    /// This mirrors [`slice::concat`]: a deque of strings produces a `String`
+    /// and a deque of slices produces a `Vec`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [""; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.push_back("ERROR").unwrap();
+    /// deque.push_back(": ").unwrap();
+    /// deque.push_back("disk full").unwrap();
+    ///
+    /// assert_eq!(deque.concat(), "ERROR: disk full");
+    /// # }
+    /// ```
+    ///
+    /// [`slice::concat`]: https://doc.rust-lang.org/stable/std/primitive.slice.html#method.concat
+    pub fn concat<Item>(&self) -> <T as Concat<Item>>::Output
+    where
+        T: Concat<Item>,
+        Item: ?Sized,
+    {
+        let (front, back) = self.as_slices();
+
+        Concat::concat(front.iter().chain(back))
+    }
+
+    /// Joins the elements of the deque into a single value, placing `sep`
+    /// between each pair of elements.
+    ///
+    /// This mirrors [`slice::join`]: a deque of strings joined by a `&str`
+    /// produces a `String` and a deque of slices joined by a slice produces a
+    /// `Vec`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [""; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// deque.push_back("ts=12").unwrap();
+    /// deque.push_back("level=warn").unwrap();
+    /// deque.push_back("temp=81").unwrap();
+    ///
+    /// assert_eq!(deque.join(" "), "ts=12 level=warn temp=81");
+    /// # }
+    /// ```
+    ///
+    /// [`slice::join`]: https://doc.rust-lang.org/stable/std/primitive.slice.html#method.join
+    pub fn join<Separator>(&self, sep: Separator) -> <T as Join<Separator>>::Output
+    where
+        T: Join<Separator>,
+    {
+        let (front, back) = self.as_slices();
+
+        Join::join(front.iter().chain(back), sep)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<'a, T> SliceDeque<'a, T>
+where
+    T: Default,
+{
+    /// Moves all elements of `vec` to the back of the deque, in order.
+    ///
+    /// If the deque does not have room for every element of `vec`, nothing is
+    /// appended and an `Err` containing `vec` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// use smallvec::{smallvec, SmallVec};
+    ///
+    /// # fn main() {
+    /// let mut slice = [0; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    ///
+    /// let tokens: SmallVec<[u32; 2]> = smallvec![1, 2, 3];
+    /// deque.extend_from_smallvec(tokens).unwrap();
+    ///
+    /// let more: SmallVec<[u32; 2]> = smallvec![4, 5];
+    /// let rejected = deque.extend_from_smallvec(more).unwrap_err();
+    /// assert_eq!(rejected.into_inner().as_slice(), [4, 5]);
+    /// assert_eq!(deque.len(), 3);
+    /// # }
+    /// ```
+    pub fn extend_from_smallvec<A>(
+        &mut self,
+        vec: smallvec::SmallVec<A>,
+    ) -> Result<(), CapacityError<smallvec::SmallVec<A>>>
+    where
+        A: smallvec::Array<Item = T>,
+    {
+        if vec.len() > self.capacity() - self.len() {
+            return Err(CapacityError { item: vec });
+        }
+
+        for item in vec {
+            self.push_back_expect(item);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<'a, T> SliceDeque<'a, T>
+where
+    T: Default,
+{
+    /// Shuffles the elements of the deque in place.
+    ///
+    /// The shuffle is uniform over all permutations of the elements, and the
+    /// deque's layout in the backing storage is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut slice = [0u32; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back(3).unwrap();
+    /// deque.push_back(4).unwrap();
+    /// deque.push_front(2).unwrap();
+    /// deque.push_front(1).unwrap();
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    /// deque.shuffle(&mut rng);
+    ///
+    /// assert_eq!(deque.len(), 4);
+    /// assert!((1..=4).all(|i| deque.iter().any(|&x| x == i)));
+    /// ```
+    #[inline]
+    pub fn shuffle<R>(&mut self, rng: &mut R)
+    where
+        R: rand::Rng + ?Sized,
+    {
+        BaseDeque::shuffle(self, rng)
+    }
+
+    /// Returns a reference to a randomly chosen element, or `None` if the
+    /// deque is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut slice = [0u32; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.push_back(3).unwrap();
+    /// deque.push_back(4).unwrap();
+    /// deque.push_front(2).unwrap();
+    /// deque.push_front(1).unwrap();
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    ///
+    /// let chosen = deque.choose(&mut rng).unwrap();
+    /// assert!((1..=4).contains(chosen));
+    /// ```
+    #[inline]
+    pub fn choose<R>(&self, rng: &mut R) -> Option<&T>
+    where
+        R: rand::Rng + ?Sized,
+    {
+        BaseDeque::choose(self, rng)
+    }
+
+    /// Returns a mutable reference to a randomly chosen element, or `None` if
+    /// the deque is empty.
+    ///
+    /// See [`choose`] for details.
+    ///
+    /// [`choose`]: SliceDeque::choose
+    #[inline]
+    pub fn choose_mut<R>(&mut self, rng: &mut R) -> Option<&mut T>
+    where
+        R: rand::Rng + ?Sized,
+    {
+        BaseDeque::choose_mut(self, rng)
+    }
+}
+
+impl<'a, T> SliceDeque<'a, T>
+where
+    T: Persist + Default,
+{
+    /// Writes a binary snapshot of the deque, passing the bytes to `write`.
+    ///
+    /// The elements are written from front to back, after a short header.
+    /// `write` is called with a few bytes at a time and should write all of
+    /// them; the first error it returns is passed on. See the [`snapshot`]
+    /// module for the format.
+    ///
+    /// [`snapshot`]: crate::snapshot
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deque holds more than `u32::MAX` elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() -> std::io::Result<()> {
+    /// use std::io::Write;
+    ///
+    /// let mut slice = [0u32; 8];
+    /// let mut readings = SliceDeque::new_in(&mut slice);
+    /// readings.push_back(1013).unwrap();
+    /// readings.push_back(1009).unwrap();
+    ///
+    /// let mut file = Vec::new();
+    /// readings.persist_to(|bytes| file.write_all(bytes))?;
+    ///
+    /// let mut reader = &file[..];
+    /// let mut restored_slice = [0u32; 4];
+    /// let restored = SliceDeque::load_from_in(&mut restored_slice, |buf| {
+    ///     std::io::Read::read_exact(&mut reader, buf)
+    /// })
+    /// .unwrap();
+    /// assert!(restored.iter().eq(readings.iter()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn persist_to<W, E>(&self, write: W) -> Result<(), E>
+    where
+        W: FnMut(&[u8]) -> Result<(), E>,
+    {
+        snapshot::persist_to(self, write)
+    }
+
+    /// Constructs a deque backed by the provided slice from a binary snapshot
+    /// written by [`persist_to`], filling buffers with `read`.
+    ///
+    /// `read` should fill the whole buffer it is given. If the snapshot was
+    /// written with a different version of the format or element size, or
+    /// holds more elements than fit in `slice`, an error is returned. As with
+    /// [`new_in`], the elements in the slice are dropped.
+    ///
+    /// [`persist_to`]: SliceDeque::persist_to
+    /// [`new_in`]: SliceDeque::new_in
+    pub fn load_from_in<R, E>(slice: &'a mut [T], read: R) -> Result<Self, LoadError<E>>
+    where
+        R: FnMut(&mut [u8]) -> Result<(), E>,
+    {
+        let mut deque = SliceDeque::new_in(slice);
+        snapshot::load_from(&mut deque, read)?;
+
+        Ok(deque)
+    }
+}
+
+impl<'a> SliceDeque<'a, u8> {
+    /// Returns the index of the first occurrence of `byte`, counting from the
+    /// front of the deque.
+    ///
+    /// Each of the two backing slices is scanned in bulk; with the `memchr`
+    /// feature, this uses SIMD instructions where available.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 16];
+    /// let mut rx = SliceDeque::new_in(&mut slice);
+    /// rx.try_extend(b"ping\npo".iter().copied()).unwrap();
+    ///
+    /// let end = rx.find_byte(b'\n').unwrap();
+    /// let line: Vec<u8> = rx.drain_front(end + 1).unwrap().collect();
+    /// assert_eq!(line, b"ping\n");
+    /// assert_eq!(rx.find_byte(b'\n'), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn find_byte(&self, byte: u8) -> Option<usize> {
+        crate::find_byte(self, byte)
+    }
+
+    /// Returns an adaptor which displays the contents of the deque as a hex
+    /// dump, in the style of `hexdump -C`.
+    ///
+    /// See [`HexDump`] for the format.
+    ///
+    /// [`HexDump`]: crate::format::HexDump
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 32];
+    /// let mut rx = SliceDeque::new_in(&mut slice);
+    /// rx.try_extend(b"\x02HELLO, WORLD!\r\n\x03".iter().copied()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     rx.hex_dump().to_string(),
+    ///     "00000000  02 48 45 4c 4c 4f 2c 20  57 4f 52 4c 44 21 0d 0a  |.HELLO, WORLD!..|\n\
+    ///      00000010  03                                                |.|",
+    /// );
+    /// # }
+    /// ```
+    #[inline]
+    pub fn hex_dump(&self) -> HexDump<'_> {
+        HexDump::new(self.as_slices())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> SliceDeque<'a, u8> {
+    /// Reads bytes from `reader` into the back of the deque, returning the
+    /// number of bytes read.
+    ///
+    /// The unused space in the deque consists of at most two contiguous
+    /// regions, so at most two calls to [`Read::read`] are made. The second
+    /// call is only made if the first one fills its region completely. If the
+    /// second call fails, the bytes from the first call are kept and the
+    /// error is discarded.
+    ///
+    /// A return value of `Ok(0)` indicates either that the deque is full or
+    /// that `reader` has reached end-of-file.
+    ///
+    /// [`Read::read`]: std::io::Read::read
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() -> std::io::Result<()> {
+    /// let mut slice = [0; 4];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// let mut reader: &[u8] = b"abcdef";
+    ///
+    /// assert_eq!(deque.read_from(&mut reader)?, 4);
+    /// assert!(deque.is_full());
+    /// assert_eq!(deque.read_from(&mut reader)?, 0);
+    ///
+    /// deque.pop_front();
+    /// deque.pop_front();
+    /// assert_eq!(deque.read_from(&mut reader)?, 2);
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), b"cdef");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_from<R>(&mut self, reader: &mut R) -> std::io::Result<usize>
+    where
+        R: std::io::Read + ?Sized,
+    {
+        crate::io::read_from(self, reader)
+    }
+
+    /// Writes bytes from the front of the deque to `writer`, removing the
+    /// bytes that were written and returning their count.
+    ///
+    /// The contents of the deque consist of at most two contiguous regions, so
+    /// at most two calls to [`Write::write`] are made. The second call is only
+    /// made if the first one writes its region completely. If the second call
+    /// fails, the bytes from the first call are still removed and the error is
+    /// discarded.
+    ///
+    /// Bytes not accepted by `writer` remain at the front of the deque.
+    ///
+    /// [`Write::write`]: std::io::Write::write
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() -> std::io::Result<()> {
+    /// let mut slice = [0; 8];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.try_extend(b"hello".iter().copied()).unwrap();
+    ///
+    /// // A fixed-size writer which only accepts three bytes.
+    /// let mut buf = [0; 3];
+    /// let mut writer = &mut buf[..];
+    ///
+    /// assert_eq!(deque.write_front_to(&mut writer)?, 3);
+    /// assert_eq!(&buf, b"hel");
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), b"lo");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_front_to<W>(&mut self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: std::io::Write + ?Sized,
+    {
+        crate::io::write_front_to(self, writer)
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl<'a, T> SliceDeque<'a, T>
+where
+    T: quickcheck::Arbitrary + Default,
+{
+    /// Creates a `SliceDeque` backed by the provided slice and fills it with
+    /// random elements.
+    ///
+    /// Each element is pushed to a randomly chosen end of the deque, so both
+    /// linear and wrapped layouts are produced. The elements in the slice are
+    /// dropped and replaced before generation begins.
+    ///
+    /// # Example
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// use quickcheck::Gen;
+    ///
+    /// let mut slice = [0u32; 8];
+    /// let deque = SliceDeque::arbitrary_in(&mut slice, &mut Gen::new(8));
+    ///
+    /// assert!(deque.len() <= 8);
+    /// ```
+    pub fn arbitrary_in(slice: &'a mut [T], g: &mut quickcheck::Gen) -> SliceDeque<'a, T> {
+        use crate::DequeEnd;
+        use quickcheck::Arbitrary;
+
+        let mut deque = SliceDeque::new_in(slice);
+        let len = usize::arbitrary(g) % (deque.capacity() + 1);
+
+        for _ in 0..len {
+            let val = T::arbitrary(g);
+            match g.choose(&[DequeEnd::Front, DequeEnd::Back]).unwrap() {
+                DequeEnd::Front => deque.push_front(val).ok().unwrap(),
+                DequeEnd::Back => deque.push_back(val).ok().unwrap(),
+            }
+        }
+
+        deque
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, 'de, T> SliceDeque<'a, T>
+where
+    T: Deserialize<'de> + Default,
+{
+    /// Extends the deque with the contents of a deserializer.
+    pub fn extend_deserialize<D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let extend = ExtendSliceDeque { deque: self };
+        extend.deserialize(deserializer)?;
+        Ok(())
+    }
+}
+
+/// Runs `f` with an empty `SliceDeque` backed by an `N`-element array on the
+/// stack, returning the result of `f`.
+///
+/// This replaces declaring a backing array and passing it to
+/// [`SliceDeque::new_in`]. The array is not accessible outside of the deque,
+/// so it cannot be touched by accident while the deque is in use, and it is
+/// dropped when `f` returns.
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::slice_deque::with_slice_deque;
+/// let sum = with_slice_deque::<u32, 64, _>(|dq| {
+///     dq.try_extend(1..=10).unwrap();
+///     dq.rotate_to_front(5);
+///
+///     dq.iter().take(3).sum::<u32>()
+/// });
+///
+/// assert_eq!(sum, 6 + 7 + 8);
+/// ```
+pub fn with_slice_deque<T, const N: usize, R>(f: impl FnOnce(&mut SliceDeque<'_, T>) -> R) -> R
+where
+    T: Default,
+{
+    let mut items: [T; N] = core::array::from_fn(|_| T::default());
+
+    f(&mut SliceDeque {
+        meta: SliceMeta::empty(N),
+        items: &mut items,
+    })
+}
+
+/// An immutable iterator over a `SliceDeque<'a, T>`.
+///
+/// This struct is created by the [`iter`] method on [`SliceDeque`].
+///
+/// [`iter`]: SliceDeque::iter
+pub struct Iter<'it, 'a, T>
+where
+    T: Default,
+{
+    inner: DequeIter<'it, SliceDeque<'a, T>, T>,
+}
+
+impl<'it, 'a, T> Iter<'it, 'a, T>
+where
+    T: Default,
+{
+    #[inline]
+    fn new(deque: &'it SliceDeque<'a, T>) -> Iter<'it, 'a, T> {
+        Iter {
+            inner: DequeIter::new(deque),
+        }
+    }
+}
+
+impl<'it, 'a, T> Iterator for Iter<'it, 'a, T>
+where
+    T: Default,
+{
+    type Item = &'it T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<'it, 'a, T> DoubleEndedIterator for Iter<'it, 'a, T>
+where
+    T: Default,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+/// A draining iterator which removes elements from the front of an
+/// `SliceDeque<'a, T>`.
+///
+/// This struct is created by the [`drain_front`] method on [`SliceDeque`].
+///
+/// [`drain_front`]: SliceDeque::drain_front
+pub struct DrainFront<'it, 'a, T>
+where
+    T: Default,
+{
+    inner: DequeDrain<'it, SliceDeque<'a, T>, T>,
+}
+
+impl<'it, 'a, T> DrainFront<'it, 'a, T>
+where
+    T: Default,
+{
+    #[inline]
+    fn new(deque: &'it mut SliceDeque<'a, T>, n: usize) -> Option<DrainFront<'it, 'a, T>> {
+        Some(DrainFront {
+            inner: DequeDrain::front(deque, n)?,
+        })
+    }
+
+    /// Returns the elements which have not yet been yielded as a pair of
+    /// slices, in front-to-back order.
+    ///
+    /// This allows the remaining elements to be processed in bulk; they are
+    /// still dropped along with the iterator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 6];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.try_extend(0..6).unwrap();
+    ///
+    /// let mut drain = deque.drain_front(3).unwrap();
+    /// assert_eq!(drain.as_slices(), (&[0, 1, 2][..], &[][..]));
+    ///
+    /// drain.next();
+    /// assert_eq!(drain.as_slices(), (&[1, 2][..], &[][..]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.inner.as_slices()
+    }
+}
+
+impl<'it, 'a, T> Iterator for DrainFront<'it, 'a, T>
+where
+    T: Default,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// A draining iterator which removes elements from the back of an
+/// `SliceDeque<'a, T>`.
+///
+/// This struct is created by the [`drain_back`] method on [`SliceDeque`].
+///
+/// [`drain_back`]: SliceDeque::drain_back
+pub struct DrainBack<'it, 'a, T>
+where
+    T: Default,
+{
+    inner: DequeDrain<'it, SliceDeque<'a, T>, T>,
+}
+
+impl<'it, 'a, T> DrainBack<'it, 'a, T>
+where
+    T: Default,
+{
+    #[inline]
+    fn new(deque: &'it mut SliceDeque<'a, T>, n: usize) -> Option<DrainBack<'it, 'a, T>> {
+        Some(DrainBack {
+            inner: DequeDrain::back(deque, n)?,
+        })
+    }
+
+    /// Returns the elements which have not yet been yielded as a pair of
+    /// slices, in front-to-back order. This is the reverse of the order in
+    /// which they are yielded.
+    ///
+    /// This allows the remaining elements to be processed in bulk; they are
+    /// still dropped along with the iterator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::SliceDeque;
+    /// # fn main() {
+    /// let mut slice = [0; 6];
+    /// let mut deque = SliceDeque::new_in(&mut slice);
+    /// deque.try_extend(0..6).unwrap();
+    ///
+    /// let mut drain = deque.drain_back(3).unwrap();
+    /// assert_eq!(drain.as_slices(), (&[3, 4, 5][..], &[][..]));
+    ///
+    /// drain.next();
+    /// assert_eq!(drain.as_slices(), (&[3, 4][..], &[][..]));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.inner.as_slices()
+    }
+}
+
+impl<'it, 'a, T> Iterator for DrainBack<'it, 'a, T>
+where
+    T: Default,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// An iterator which removes elements from the front of a `SliceDeque<'a, T>`
+/// in arrays of `C` elements.
+///
+/// This struct is created by the [`drain_chunks`] method on [`SliceDeque`].
+///
+/// [`drain_chunks`]: SliceDeque::drain_chunks
+pub struct DrainChunks<'it, 'a, T, const C: usize>
+where
+    T: Default,
+{
+    inner: DequeDrainChunks<'it, SliceDeque<'a, T>, T, C>,
+}
+
+impl<'it, 'a, T, const C: usize> Iterator for DrainChunks<'it, 'a, T, C>
+where
+    T: Default,
+{
+    type Item = [T; C];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'it, 'a, T, const C: usize> ExactSizeIterator for DrainChunks<'it, 'a, T, C> where T: Default {}
+
+/// An iterator which removes the elements of a `SliceDeque<'a, T>` for which a
+/// predicate returns `true`.
+///
+/// This struct is created by the [`extract_if`] method on [`SliceDeque`].
+///
+/// [`extract_if`]: SliceDeque::extract_if
+pub struct ExtractIf<'it, 'a, T, F>
+where
+    T: Default,
+{
+    inner: DequeExtractIf<'it, SliceDeque<'a, T>, T, F>,
+}
+
+impl<'it, 'a, T, F> Iterator for ExtractIf<'it, 'a, T, F>
+where
+    T: Default,
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// A cursor over a `SliceDeque<'a, T>` with editing operations.
+///
+/// A cursor points either at an element of the deque or at a "ghost"
+/// position between the back and the front. Moving past either end of the
+/// deque lands on the ghost position, and moving again wraps around to the
+/// other end.
+///
+/// Inserting or removing at the cursor shifts whichever side of the deque is
+/// shorter, so these operations take time proportional to the distance from
+/// the cursor to the nearer end of the deque.
+///
+/// This struct is created by the [`cursor_front_mut`] and [`cursor_back_mut`]
+/// methods on [`SliceDeque`].
+///
+/// [`cursor_front_mut`]: SliceDeque::cursor_front_mut
+/// [`cursor_back_mut`]: SliceDeque::cursor_back_mut
+pub struct CursorMut<'c, 'a, T>
+where
+    T: Default,
+{
+    inner: DequeCursor<'c, SliceDeque<'a, T>, T>,
+}
+
+impl<'c, 'a, T> CursorMut<'c, 'a, T>
+where
+    T: Default,
+{
+    /// Returns the logical index of the element the cursor points at.
+    ///
+    /// If the cursor points at the ghost position, `None` is returned.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.inner.index()
+    }
+
+    /// Returns a mutable reference to the element the cursor points at.
+    ///
+    /// If the cursor points at the ghost position, `None` is returned.
+    #[inline]
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.inner.current()
+    }
+
+    /// Returns a mutable reference to the element after the cursor.
+    ///
+    /// If the cursor points at the ghost position, this is the front element.
+    /// If the cursor points at the back element, `None` is returned.
+    #[inline]
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        self.inner.peek_next()
+    }
+
+    /// Returns a mutable reference to the element before the cursor.
+    ///
+    /// If the cursor points at the ghost position, this is the back element.
+    /// If the cursor points at the front element, `None` is returned.
+    #[inline]
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        self.inner.peek_prev()
+    }
+
+    /// Moves the cursor to the next element.
+    ///
+    /// If the cursor points at the back element, it moves to the ghost
+    /// position. If it points at the ghost position, it moves to the front
+    /// element.
+    #[inline]
+    pub fn move_next(&mut self) {
+        self.inner.move_next()
+    }
+
+    /// Moves the cursor to the previous element.
+    ///
+    /// If the cursor points at the front element, it moves to the ghost
+    /// position. If it points at the ghost position, it moves to the back
+    /// element.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        self.inner.move_prev()
+    }
+
+    /// Inserts an element before the cursor.
+    ///
+    /// If the cursor points at the ghost position, the element is inserted at
+    /// the back of the deque. The cursor continues to point at the same
+    /// element.
+    ///
+    /// If the deque is full, an `Err` containing `item` is returned.
+    #[inline]
+    pub fn insert_before(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.inner.insert_before(item)
+    }
+
+    /// Inserts an element after the cursor.
+    ///
+    /// If the cursor points at the ghost position, the element is inserted at
+    /// the front of the deque. The cursor continues to point at the same
+    /// element.
+    ///
+    /// If the deque is full, an `Err` containing `item` is returned.
+    #[inline]
+    pub fn insert_after(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.inner.insert_after(item)
+    }
+
+    /// Removes and returns the element the cursor points at.
+    ///
+    /// The cursor moves to the element that followed the removed one, or to
+    /// the ghost position if the back element was removed. If the cursor
+    /// points at the ghost position, nothing is removed and `None` is
+    /// returned.
+    #[inline]
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.inner.remove_current()
+    }
+}
+
+/// The front element of a [`SliceDeque`], which is removed from the deque only
+/// when committed.
+///
+/// The guard dereferences to the element. If the guard is dropped without
+/// calling [`commit`] or [`into_inner`], the element is left at the front of
+/// the deque, including any changes made through the guard.
+///
+/// This struct is created by the [`pop_front_guard`] method on
+/// [`SliceDeque`].
+///
+/// [`commit`]: PopGuard::commit
+/// [`into_inner`]: PopGuard::into_inner
+/// [`pop_front_guard`]: SliceDeque::pop_front_guard
+pub struct PopGuard<'s, 'a, T>
+where
+    T: Default,
+{
+    inner: DequePopGuard<'s, SliceDeque<'a, T>, T>,
+}
+
+impl<'s, 'a, T> PopGuard<'s, 'a, T>
+where
+    T: Default,
+{
+    /// Removes the element from the front of the deque and drops it.
+    #[inline]
+    pub fn commit(self) {
+        drop(self.inner.into_inner());
+    }
+
+    /// Removes the element from the front of the deque and returns it.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.inner.into_inner()
+    }
+}
+
+impl<'s, 'a, T> Deref for PopGuard<'s, 'a, T>
+where
+    T: Default,
+{
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.inner.get()
+    }
+}
+
+impl<'s, 'a, T> DerefMut for PopGuard<'s, 'a, T>
+where
+    T: Default,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        self.inner.get_mut()
+    }
+}
+
+/// A [`SliceDeque`] which is truncated back to its original length when the guard
+/// is dropped, unless kept.
+///
+/// The guard dereferences to the deque, so elements can be pushed through it
+/// as usual. When the guard is dropped without calling [`keep`], every
+/// element beyond the length the deque had when the guard was created is
+/// removed from the back and dropped.
+///
+/// Only elements pushed to the back are undone. To also undo elements pushed
+/// to the front, use [`savepoint`] instead.
+///
+/// This struct is created by the [`truncate_guard`] method on [`SliceDeque`].
+///
+/// [`keep`]: TruncateGuard::keep
+/// [`savepoint`]: SliceDeque::savepoint
+/// [`truncate_guard`]: SliceDeque::truncate_guard
+pub struct TruncateGuard<'s, 'a, T>
+where
+    T: Default,
+{
+    inner: DequeTruncateGuard<'s, SliceDeque<'a, T>, T>,
+}
+
+impl<'s, 'a, T> TruncateGuard<'s, 'a, T>
+where
+    T: Default,
+{
+    /// Returns the length the deque is truncated to when the guard is
+    /// dropped.
+    #[inline]
+    pub fn original_len(&self) -> usize {
+        self.inner.original_len()
+    }
+
+    /// Keeps the elements pushed while the guard was alive.
+    #[inline]
+    pub fn keep(self) {
+        self.inner.keep()
+    }
+}
+
+impl<'s, 'a, T> Deref for TruncateGuard<'s, 'a, T>
+where
+    T: Default,
+{
+    type Target = SliceDeque<'a, T>;
+
+    #[inline]
+    fn deref(&self) -> &SliceDeque<'a, T> {
+        self.inner.get()
+    }
+}
+
+impl<'s, 'a, T> DerefMut for TruncateGuard<'s, 'a, T>
+where
+    T: Default,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut SliceDeque<'a, T> {
+        self.inner.get_mut()
+    }
+}
+
+/// A reserved slot at the back of a [`SliceDeque`], which becomes the back
+/// element of the deque only when committed.
+///
+/// The guard dereferences to the value in the slot, which starts out as the
+/// default value of `T`. If the guard is dropped without calling [`commit`],
+/// the slot is reset to the default value and the deque is left unchanged.
+///
+/// This struct is created by the [`reserve_back_slot`] method on
+/// [`SliceDeque`].
+///
+/// [`commit`]: SlotGuard::commit
+/// [`reserve_back_slot`]: SliceDeque::reserve_back_slot
+pub struct SlotGuard<'s, 'a, T>
+where
+    T: Default,
+{
+    inner: DequeSlot<'s, SliceDeque<'a, T>, T>,
+}
+
+impl<'s, 'a, T> SlotGuard<'s, 'a, T>
+where
+    T: Default,
+{
+    /// Appends the value in the slot to the back of the deque.
+    #[inline]
+    pub fn commit(self) {
+        self.inner.commit()
+    }
+}
+
+impl<'s, 'a, T> Deref for SlotGuard<'s, 'a, T>
+where
+    T: Default,
+{
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.inner.get()
     }
 }
 
-/// An immutable iterator over a `SliceDeque<'a, T>`.
+impl<'s, 'a, T> DerefMut for SlotGuard<'s, 'a, T>
+where
+    T: Default,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        self.inner.get_mut()
+    }
+}
+
+/// What a [`ResetOnDrop`] does with the backing slice of its deque when it is
+/// dropped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DropBehavior {
+    /// Every slot in the slice is reset to `T::default()`, dropping the
+    /// elements of the deque.
+    Clear,
+
+    /// The elements of the deque are moved to the start of the slice, in
+    /// order, and the remaining slots are reset to `T::default()`.
+    Compact,
+}
+
+/// A [`SliceDeque`] which resets its backing slice when dropped.
 ///
-/// This struct is created by the [`iter`] method on [`SliceDeque`].
+/// The wrapper dereferences to the deque. Dropping it clears or compacts the
+/// slice according to its [`DropBehavior`]; [`into_inner`] recovers the
+/// deque without doing either.
 ///
-/// [`iter`]: SliceDeque::iter
-pub struct Iter<'it, 'a, T>
+/// This struct is created by the [`reset_on_drop`] method on [`SliceDeque`].
+///
+/// [`into_inner`]: ResetOnDrop::into_inner
+/// [`reset_on_drop`]: SliceDeque::reset_on_drop
+pub struct ResetOnDrop<'a, T>
 where
     T: Default,
 {
-    inner: DequeIter<'it, SliceDeque<'a, T>, T>,
+    deque: SliceDeque<'a, T>,
+    behavior: DropBehavior,
 }
 
-impl<'it, 'a, T> Iter<'it, 'a, T>
+impl<'a, T> ResetOnDrop<'a, T>
 where
     T: Default,
 {
+    /// Returns what the wrapper does with the slice when it is dropped.
     #[inline]
-    fn new(deque: &'it SliceDeque<'a, T>) -> Iter<'it, 'a, T> {
-        Iter {
-            inner: DequeIter::new(deque),
-        }
+    pub fn behavior(&self) -> DropBehavior {
+        self.behavior
+    }
+
+    /// Unwraps the deque, leaving its backing slice as it is.
+    pub fn into_inner(mut self) -> SliceDeque<'a, T> {
+        // The wrapper's deque is left with an empty slice, which has no
+        // slots to reset when the wrapper is dropped.
+        let meta = mem::replace(&mut self.deque.meta, SliceMeta::empty(0));
+        let items = mem::take(&mut self.deque.items);
+
+        SliceDeque { meta, items }
     }
 }
 
-impl<'it, 'a, T> Iterator for Iter<'it, 'a, T>
+impl<'a, T> Deref for ResetOnDrop<'a, T>
 where
     T: Default,
 {
-    type Item = &'it T;
+    type Target = SliceDeque<'a, T>;
 
     #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next()
+    fn deref(&self) -> &SliceDeque<'a, T> {
+        &self.deque
     }
 }
 
-impl<'it, 'a, T> DoubleEndedIterator for Iter<'it, 'a, T>
+impl<'a, T> DerefMut for ResetOnDrop<'a, T>
 where
     T: Default,
 {
     #[inline]
-    fn next_back(&mut self) -> Option<Self::Item> {
-        self.inner.next_back()
+    fn deref_mut(&mut self) -> &mut SliceDeque<'a, T> {
+        &mut self.deque
     }
 }
 
-/// A draining iterator which removes elements from the front of an
-/// `SliceDeque<'a, T>`.
-///
-/// This struct is created by the [`drain_front`] method on [`SliceDeque`].
-///
-/// [`drain_front`]: SliceDeque::drain_front
-pub struct DrainFront<'it, 'a, T>
+impl<'a, T> Drop for ResetOnDrop<'a, T>
 where
     T: Default,
 {
-    inner: DequeDrain<'it, SliceDeque<'a, T>, T>,
+    fn drop(&mut self) {
+        let deque = &mut self.deque;
+
+        let keep = match self.behavior {
+            DropBehavior::Clear => 0,
+            DropBehavior::Compact => {
+                BaseDeque::make_contiguous(deque);
+                deque.items.rotate_left(deque.meta.front().unwrap_or(0));
+                deque.len()
+            }
+        };
+
+        for slot in &mut deque.items[keep..] {
+            *slot = T::default();
+        }
+    }
 }
 
-impl<'it, 'a, T> DrainFront<'it, 'a, T>
+/// A handle to the front of a split `SliceDeque`.
+///
+/// This struct is created by the [`split`] method on [`SliceDeque`].
+///
+/// [`split`]: SliceDeque::split
+pub struct FrontHandle<'h, T>
 where
     T: Default,
 {
-    #[inline]
-    fn new(deque: &'it mut SliceDeque<'a, T>, n: usize) -> Option<DrainFront<'it, 'a, T>> {
-        Some(DrainFront {
-            inner: DequeDrain::front(deque, n)?,
-        })
-    }
+    inner: DequeHalf<'h, SliceMeta, T>,
 }
 
-impl<'it, 'a, T> Iterator for DrainFront<'it, 'a, T>
+impl<'h, T> FrontHandle<'h, T>
 where
     T: Default,
 {
-    type Item = T;
+    /// Returns the number of elements in the deque.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
 
+    /// Returns `true` if the deque is empty.
     #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next()
+    pub fn is_empty(&self) -> bool {
+        self.inner.len() == 0
+    }
+
+    /// Returns a clone of the front element, or `None` if the deque is empty.
+    #[inline]
+    pub fn front(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.inner.front()
+    }
+
+    /// Removes the front element and returns it, or `None` if the deque is
+    /// empty.
+    #[inline]
+    pub fn pop_front(&self) -> Option<T> {
+        self.inner.pop_front()
     }
 }
 
-/// A draining iterator which removes elements from the back of an
-/// `SliceDeque<'a, T>`.
+/// A handle to the back of a split `SliceDeque`.
 ///
-/// This struct is created by the [`drain_back`] method on [`SliceDeque`].
+/// This struct is created by the [`split`] method on [`SliceDeque`].
 ///
-/// [`drain_back`]: SliceDeque::drain_back
-pub struct DrainBack<'it, 'a, T>
+/// [`split`]: SliceDeque::split
+pub struct BackHandle<'h, T>
 where
     T: Default,
 {
-    inner: DequeDrain<'it, SliceDeque<'a, T>, T>,
+    inner: DequeHalf<'h, SliceMeta, T>,
 }
 
-impl<'it, 'a, T> DrainBack<'it, 'a, T>
+impl<'h, T> BackHandle<'h, T>
 where
     T: Default,
 {
+    /// Returns the number of elements in the deque.
     #[inline]
-    fn new(deque: &'it mut SliceDeque<'a, T>, n: usize) -> Option<DrainBack<'it, 'a, T>> {
-        Some(DrainBack {
-            inner: DequeDrain::back(deque, n)?,
-        })
+    pub fn len(&self) -> usize {
+        self.inner.len()
     }
-}
 
-impl<'it, 'a, T> Iterator for DrainBack<'it, 'a, T>
-where
-    T: Default,
-{
-    type Item = T;
+    /// Returns `true` if the deque is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.len() == 0
+    }
 
+    /// Returns `true` if the deque is full.
     #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next()
+    pub fn is_full(&self) -> bool {
+        self.inner.len() == self.inner.capacity()
     }
-}
 
-#[cfg(feature = "serde")]
-use core::fmt;
+    /// Returns a clone of the back element, or `None` if the deque is empty.
+    #[inline]
+    pub fn back(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.inner.back()
+    }
+
+    /// Appends an element to the back of the deque.
+    ///
+    /// If the deque is full, the element is returned in a [`CapacityError`].
+    #[inline]
+    pub fn push_back(&self, item: T) -> Result<(), CapacityError<T>> {
+        self.inner.push_back(item)
+    }
+}
 
 #[cfg(feature = "serde")]
 impl<'a, T> serde::Serialize for SliceDeque<'a, T>
@@ -958,12 +4191,154 @@ where
     }
 }
 
+/// A [`DeserializeSeed`] which deserializes a sequence into a new
+/// `SliceDeque` backed by the provided slice.
+///
+/// Unlike [`extend_deserialize`], this does not require constructing the
+/// deque beforehand, so it can be passed directly to methods such as
+/// [`SeqAccess::next_element_seed`] when deserializing a parent structure.
+///
+/// The elements in the slice are dropped and replaced with the default value
+/// of `T` before deserialization begins. If the sequence is longer than the
+/// slice, an error is returned.
+///
+/// As with `ArrayDeque`, elements may borrow from the input: a seed for a
+/// `SliceDeque<'a, &'de str>` accepts any `Deserializer<'de>`.
+///
+/// [`extend_deserialize`]: SliceDeque::extend_deserialize
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::slice_deque::SliceDequeSeed;
+/// use serde::de::{value::{Error, SeqDeserializer}, DeserializeSeed};
+///
+/// let mut slice = [0u32; 4];
+/// let deserializer = SeqDeserializer::<_, Error>::new([1u32, 2, 3].iter().copied());
+///
+/// let deque = SliceDequeSeed::new(&mut slice).deserialize(deserializer)?;
+/// assert_eq!(deque.len(), 3);
+/// # Ok::<(), Error>(())
+/// ```
+#[cfg(feature = "serde")]
+pub struct SliceDequeSeed<'a, T>
+where
+    T: Default,
+{
+    slice: &'a mut [T],
+}
+
+#[cfg(feature = "serde")]
+impl<'a, T> SliceDequeSeed<'a, T>
+where
+    T: Default,
+{
+    /// Creates a seed which will deserialize into a deque backed by `slice`.
+    pub fn new(slice: &'a mut [T]) -> SliceDequeSeed<'a, T> {
+        SliceDequeSeed { slice }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, 'de, T> DeserializeSeed<'de> for SliceDequeSeed<'a, T>
+where
+    T: Deserialize<'de> + Default,
+{
+    type Value = SliceDeque<'a, T>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut deque = SliceDeque::new_in(self.slice);
+        ExtendSliceDeque { deque: &mut deque }.deserialize(deserializer)?;
+
+        Ok(deque)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<'a> bytes::Buf for SliceDeque<'a, u8> {
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.as_slices().0
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        if DequeDrain::front(self, cnt).is_none() {
+            panic!("cannot advance past end of deque: {} > {}", cnt, self.len());
+        }
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<'a> embedded_io::ErrorType for SliceDeque<'a, u8> {
+    type Error = CapacityError<()>;
+}
+
+#[cfg(feature = "embedded-io")]
+impl<'a> embedded_io::Read for SliceDeque<'a, u8> {
+    /// Removes bytes from the front of the deque and copies them into `buf`.
+    ///
+    /// This never blocks. If the deque is empty, `Ok(0)` is returned.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(crate::io::copy_front_into(self, buf))
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<'a> embedded_io::BufRead for SliceDeque<'a, u8> {
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        Ok(self.as_slices().0)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        DequeDrain::front(self, amt).unwrap();
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<'a> embedded_io::Write for SliceDeque<'a, u8> {
+    /// Appends as many bytes from `buf` as will fit to the back of the deque.
+    ///
+    /// This never blocks. If `buf` is nonempty and the deque is full, an error
+    /// is returned.
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if !buf.is_empty() && self.is_full() {
+            return Err(CapacityError { item: () });
+        }
+
+        Ok(crate::io::copy_back_from(self, buf))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<'a> embedded_io::ReadReady for SliceDeque<'a, u8> {
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_empty())
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<'a> embedded_io::WriteReady for SliceDeque<'a, u8> {
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_full())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     extern crate alloc;
-    use alloc::{rc::Rc, vec::Vec};
+    use alloc::{format, rc::Rc, vec::Vec};
 
     #[test]
     fn empty_deque_has_zero_len() {
@@ -1125,6 +4500,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn push_front_then_back() {
         let mut slice_ff = ["", "", ""];
         let mut slice_fb = slice_ff.clone();
@@ -1172,6 +4548,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn push_back_then_front() {
         let mut slice_ff = ["", "", ""];
         let mut slice_fb = slice_ff.clone();
@@ -1483,11 +4860,112 @@ mod tests {
         }
     }
 
+    #[test]
+    fn debug_prints_logical_order() {
+        let mut slice = [0; 5];
+        let mut deque = SliceDeque::new_in(&mut slice);
+        assert_eq!(format!("{:?}", deque), "[]");
+
+        deque.push_front(1).unwrap();
+        deque.push_front(2).unwrap();
+        deque.push_back(3).unwrap();
+        assert_eq!(format!("{:?}", deque), "[2, 1, 3]");
+    }
+
+    #[test]
+    fn replace_storage_linearizes_wrapped_deque() {
+        let mut small = [0; 3];
+        let mut deque = SliceDeque::new_in(&mut small);
+        deque.push_back(2).unwrap();
+        deque.push_back(3).unwrap();
+        deque.push_front(1).unwrap();
+
+        let mut too_small = [0; 2];
+        assert_eq!(
+            deque
+                .replace_storage(&mut too_small)
+                .unwrap_err()
+                .get()
+                .len(),
+            2
+        );
+        assert_eq!(deque.capacity(), 3);
+
+        let mut large = [9; 5];
+        assert_eq!(deque.replace_storage(&mut large).unwrap(), [0, 0, 0]);
+        assert!(deque.layout_info().is_contiguous());
+        assert_eq!(deque.as_slices(), (&[1, 2, 3][..], &[][..]));
+
+        deque.push_back(4).unwrap();
+        deque.push_back(5).unwrap();
+        assert!(deque.is_full());
+    }
+
+    #[test]
+    fn with_slice_deque_drops_buffer() {
+        let rc = Rc::new(());
+
+        let len = with_slice_deque::<Option<Rc<()>>, 3, _>(|dq| {
+            assert_eq!(dq.capacity(), 3);
+            dq.push_back(Some(Rc::clone(&rc))).unwrap();
+            dq.push_front(Some(Rc::clone(&rc))).unwrap();
+            dq.len()
+        });
+
+        assert_eq!(len, 2);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn reset_on_drop_normalizes_slice() {
+        fn fill(slice: &mut [u32]) -> SliceDeque<'_, u32> {
+            let mut deque = SliceDeque::new_in(slice);
+            deque.push_back(3).unwrap();
+            deque.push_back(4).unwrap();
+            deque.push_front(2).unwrap();
+            deque.push_front(1).unwrap();
+            deque.pop_back();
+            // Leaves a stale value behind, as `u32` has no drop glue.
+            deque.truncate(2);
+            deque
+        }
+
+        let mut slice = [0; 5];
+        drop(fill(&mut slice).reset_on_drop(DropBehavior::Compact));
+        assert_eq!(slice, [1, 2, 0, 0, 0]);
+
+        drop(fill(&mut slice).reset_on_drop(DropBehavior::Clear));
+        assert_eq!(slice, [0; 5]);
+
+        let deque = fill(&mut slice).reset_on_drop(DropBehavior::Clear);
+        assert_eq!(deque.into_inner().as_slices(), (&[1, 2][..], &[][..]));
+        assert_eq!(slice, [3, 0, 0, 1, 2]);
+    }
+
+    #[test]
+    fn truncate_capacity_moves_elements_below_n() {
+        let mut slice = [0; 6];
+        let mut deque = SliceDeque::new_in(&mut slice);
+        deque.try_extend(0..5).unwrap();
+        deque.drain_front(3).unwrap();
+        assert_eq!(deque.truncate_capacity(1), None);
+
+        // The elements at indices 3 and 4 are moved to the start.
+        assert_eq!(deque.truncate_capacity(3).unwrap().len(), 3);
+        assert_eq!(deque.as_slices(), (&[3, 4][..], &[][..]));
+
+        deque.push_front(2).unwrap();
+        assert!(deque.is_full());
+        assert_eq!(deque.truncate_capacity(4), None);
+        assert!(deque.iter().eq(&[2, 3, 4]));
+    }
+
     #[cfg(feature = "serde")]
     use serde_test::{assert_ser_tokens, Token};
 
     #[cfg(feature = "serde")]
     #[test]
+    #[allow(clippy::useless_conversion)]
     fn serialize() {
         let mut slice = ["", "", "", "", "", "", "", "", "", ""];
         let mut deque = SliceDeque::new_in(&mut slice);
@@ -1519,4 +4997,116 @@ mod tests {
             ],
         );
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn slice_deque_seed() {
+        use serde::de::value::{Error, SeqDeserializer};
+
+        let mut slice = [9u32; 3];
+        let deserializer = SeqDeserializer::<_, Error>::new([1u32, 2].iter().copied());
+        let deque = SliceDequeSeed::new(&mut slice)
+            .deserialize(deserializer)
+            .unwrap();
+        assert_eq!(deque.as_slices(), (&[1, 2][..], &[][..]));
+
+        let mut slice = [0u32; 1];
+        let deserializer = SeqDeserializer::<_, Error>::new([1u32, 2].iter().copied());
+        let err = SliceDequeSeed::new(&mut slice)
+            .deserialize(deserializer)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid length 2, expected a sequence of at most 1 elements"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn slice_deque_seed_borrowed() {
+        use serde::de::value::{BorrowedStrDeserializer, Error, SeqDeserializer};
+
+        let input = String::from("zero copy");
+        let (zero, copy) = input.split_at(4);
+        let elements = [zero, copy].map(BorrowedStrDeserializer::<Error>::new);
+
+        let mut slice = [""; 2];
+        let deserializer = SeqDeserializer::<_, Error>::new(elements.iter().cloned());
+        let deque = SliceDequeSeed::new(&mut slice)
+            .deserialize(deserializer)
+            .unwrap();
+
+        assert_eq!(deque.as_slices(), (&["zero", " copy"][..], &[][..]));
+        assert!(core::ptr::eq(
+            deque.front().unwrap().as_ptr(),
+            input.as_ptr()
+        ));
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn arbitrary_in_respects_capacity() {
+        let mut g = quickcheck::Gen::new(64);
+
+        for _ in 0..32 {
+            let mut slice = [0u8; 4];
+            let deque = SliceDeque::arbitrary_in(&mut slice, &mut g);
+            assert!(deque.len() <= 4);
+        }
+
+        let deque = SliceDeque::<u8>::arbitrary_in(&mut [], &mut g);
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn split_handles_share_deque() {
+        let mut slice = [0u32; 2];
+        let mut deque = SliceDeque::new_in(&mut slice);
+
+        {
+            let (front, back) = deque.split();
+            assert!(front.is_empty());
+            assert_eq!(front.pop_front(), None);
+
+            back.push_back(1).unwrap();
+            back.push_back(2).unwrap();
+            assert_eq!(front.pop_front(), Some(1));
+            back.push_back(3).unwrap();
+            assert_eq!(front.front(), Some(2));
+            assert_eq!(back.back(), Some(3));
+        }
+
+        assert_eq!(deque.as_slices(), (&[2][..], &[3][..]));
+    }
+
+    #[test]
+    fn rollback_to_savepoint() {
+        let mut slice = [0u8; 3];
+        let mut deque = SliceDeque::new_in(&mut slice);
+        deque.push_back(1).unwrap();
+
+        let savepoint = deque.savepoint();
+        let layout = deque.layout_info();
+        deque.push_back(2).unwrap();
+        deque.push_front(0).unwrap();
+        deque.rollback_to(savepoint);
+
+        assert_eq!(deque.as_slices(), (&[1][..], &[][..]));
+        assert_eq!(deque.layout_info(), layout);
+    }
+
+    #[test]
+    #[should_panic(expected = "savepoint is not valid")]
+    fn rollback_after_truncate_capacity_panics() {
+        let mut slice = [0u8; 4];
+        let mut deque = SliceDeque::new_in(&mut slice);
+        deque.push_back(1).unwrap();
+        deque.push_back(2).unwrap();
+        deque.pop_front();
+
+        let savepoint = deque.savepoint();
+        deque.truncate_capacity(2).unwrap();
+        deque.push_back(3).unwrap();
+        deque.rollback_to(savepoint);
+    }
 }
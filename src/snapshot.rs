@@ -0,0 +1,308 @@
+//! A compact binary snapshot format for deques of plain numbers.
+//!
+//! A snapshot is written by `persist_to` and read back by `load_from`, which
+//! are provided by [`ArrayDeque`] and [`SliceDeque`] for element types
+//! implementing [`Persist`]. Neither requires `serde` or `std`: bytes are
+//! passed to and from a closure, which can forward them to a file, a flash
+//! driver or a buffer in memory.
+//!
+//! # Format
+//!
+//! All integers are little-endian.
+//!
+//! | Offset | Size            | Contents                            |
+//! |--------|-----------------|-------------------------------------|
+//! | 0      | 1               | Format version, currently 1         |
+//! | 1      | 1               | Size of each element in bytes       |
+//! | 2      | 4               | Number of elements, as a `u32`      |
+//! | 6      | size × elements | Elements, from front to back        |
+//!
+//! [`ArrayDeque`]: crate::ArrayDeque
+//! [`SliceDeque`]: crate::SliceDeque
+
+use core::{convert::TryFrom, fmt};
+
+use crate::BaseDeque;
+
+/// The version of the snapshot format written by `persist_to`.
+pub const VERSION: u8 = 1;
+
+const HEADER_LEN: usize = 6;
+
+/// Elements are encoded into a buffer of this size, so that the writer is not
+/// called once per element.
+const CHUNK_LEN: usize = 64;
+
+/// An element type which can be stored in a snapshot.
+///
+/// This is implemented for the fixed-size integer types, `f32` and `f64`.
+/// `usize` and `isize` are not supported, as their size depends on the
+/// platform which wrote the snapshot.
+pub trait Persist: Sized {
+    /// The number of bytes in the encoding of each element.
+    ///
+    /// This must be at least 1 and at most 64. Persisting or loading a deque
+    /// of a type whose size is outside this range fails to compile:
+    ///
+    /// ```compile_fail
+    /// # use holodeque::{snapshot::Persist, ArrayDeque};
+    /// #[derive(Default)]
+    /// struct Padded(u8);
+    ///
+    /// impl Persist for Padded {
+    ///     const SIZE: usize = 65;
+    ///
+    ///     fn write_bytes(&self, bytes: &mut [u8]) {
+    ///         bytes[0] = self.0;
+    ///     }
+    ///
+    ///     fn read_bytes(bytes: &[u8]) -> Self {
+    ///         Padded(bytes[0])
+    ///     }
+    /// }
+    ///
+    /// let deque: ArrayDeque<Padded, 1> = ArrayDeque::new();
+    /// deque.persist_to(|_| Ok::<_, ()>(())).unwrap();
+    /// ```
+    const SIZE: usize;
+
+    /// Writes the encoding of `self` to `bytes`, which is `SIZE` bytes long.
+    fn write_bytes(&self, bytes: &mut [u8]);
+
+    /// Decodes an element from `bytes`, which is `SIZE` bytes long.
+    fn read_bytes(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_persist {
+    ($($ty:ty),*) => {
+        $(
+            impl Persist for $ty {
+                const SIZE: usize = core::mem::size_of::<$ty>();
+
+                #[inline]
+                fn write_bytes(&self, bytes: &mut [u8]) {
+                    bytes.copy_from_slice(&self.to_le_bytes());
+                }
+
+                #[inline]
+                fn read_bytes(bytes: &[u8]) -> Self {
+                    let mut buf = [0; core::mem::size_of::<$ty>()];
+                    buf.copy_from_slice(bytes);
+                    <$ty>::from_le_bytes(buf)
+                }
+            }
+        )*
+    };
+}
+
+impl_persist!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+/// An error that occurs when loading a snapshot.
+#[derive(Debug, PartialEq, Eq)]
+pub enum LoadError<E> {
+    /// The reader returned an error.
+    Read(E),
+
+    /// The snapshot was written with an unsupported version of the format.
+    Version(u8),
+
+    /// The size of the elements in the snapshot does not match the element
+    /// type of the deque.
+    ElementSize(u8),
+
+    /// The snapshot holds more elements than the deque has room for.
+    Capacity(u32),
+}
+
+impl<E> fmt::Display for LoadError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Read(e) => write!(f, "failed to read snapshot: {}", e),
+            LoadError::Version(v) => write!(f, "unsupported snapshot version {}", v),
+            LoadError::ElementSize(size) => {
+                write!(f, "snapshot elements are {} bytes long", size)
+            }
+            LoadError::Capacity(len) => {
+                write!(f, "snapshot of {} elements exceeds deque capacity", len)
+            }
+        }
+    }
+}
+
+impl<E> core::error::Error for LoadError<E> where E: fmt::Debug + fmt::Display {}
+
+/// Fails to compile if `T::SIZE` is outside the range supported by the
+/// format.
+///
+/// Elements must fit in a chunk, and their size must fit in the `u8` of the
+/// header.
+fn assert_element_size<T>()
+where
+    T: Persist,
+{
+    const {
+        assert!(
+            1 <= T::SIZE && T::SIZE <= CHUNK_LEN,
+            "Persist::SIZE must be between 1 and 64"
+        )
+    }
+}
+
+/// Writes a snapshot of `deque` by passing its bytes to `write`.
+///
+/// # Panics
+///
+/// Panics if the deque holds more than `u32::MAX` elements.
+pub(crate) fn persist_to<D, T, W, E>(deque: &D, mut write: W) -> Result<(), E>
+where
+    D: BaseDeque<T>,
+    T: Persist + Default,
+    W: FnMut(&[u8]) -> Result<(), E>,
+{
+    assert_element_size::<T>();
+    let len = u32::try_from(deque.len()).expect("deque is too long to persist");
+
+    let mut header = [0; HEADER_LEN];
+    header[0] = VERSION;
+    header[1] = T::SIZE as u8;
+    header[2..].copy_from_slice(&len.to_le_bytes());
+    write(&header)?;
+
+    let (front, back) = deque.as_slices();
+    let mut chunk = [0; CHUNK_LEN];
+    let mut used = 0;
+
+    for item in front.iter().chain(back) {
+        if used + T::SIZE > CHUNK_LEN {
+            write(&chunk[..used])?;
+            used = 0;
+        }
+
+        item.write_bytes(&mut chunk[used..used + T::SIZE]);
+        used += T::SIZE;
+    }
+
+    if used > 0 {
+        write(&chunk[..used])?;
+    }
+
+    Ok(())
+}
+
+/// Reads a snapshot by filling buffers with `read`, appending its elements to
+/// the back of `deque`.
+///
+/// The header is checked before any elements are read; if it is rejected,
+/// `deque` is unchanged.
+pub(crate) fn load_from<D, T, R, E>(deque: &mut D, mut read: R) -> Result<(), LoadError<E>>
+where
+    D: BaseDeque<T>,
+    T: Persist + Default,
+    R: FnMut(&mut [u8]) -> Result<(), E>,
+{
+    assert_element_size::<T>();
+    let mut header = [0; HEADER_LEN];
+    read(&mut header).map_err(LoadError::Read)?;
+
+    if header[0] != VERSION {
+        return Err(LoadError::Version(header[0]));
+    }
+    if usize::from(header[1]) != T::SIZE {
+        return Err(LoadError::ElementSize(header[1]));
+    }
+
+    let mut len_bytes = [0; 4];
+    len_bytes.copy_from_slice(&header[2..]);
+    let len = u32::from_le_bytes(len_bytes);
+    let mut remaining = len as usize;
+    if remaining > deque.capacity() - deque.len() {
+        return Err(LoadError::Capacity(len));
+    }
+
+    let per_chunk = CHUNK_LEN / T::SIZE;
+    let mut chunk = [0; CHUNK_LEN];
+
+    while remaining > 0 {
+        let n = remaining.min(per_chunk);
+        let bytes = &mut chunk[..n * T::SIZE];
+        read(bytes).map_err(LoadError::Read)?;
+
+        for encoded in bytes.chunks_exact(T::SIZE) {
+            deque.push_back_expect(T::read_bytes(encoded));
+        }
+
+        remaining -= n;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    use crate::{ArrayDeque, SliceDeque};
+
+    #[test]
+    fn round_trip_spans_chunks() {
+        let mut deque: ArrayDeque<u32, 40> = ArrayDeque::new();
+        for i in 0..20 {
+            deque.push_back(i).unwrap();
+            deque.push_front(1000 + i).unwrap();
+        }
+
+        let mut bytes = Vec::new();
+        deque
+            .persist_to(|b| {
+                bytes.extend_from_slice(b);
+                Ok::<_, ()>(())
+            })
+            .unwrap();
+        assert_eq!(bytes.len(), HEADER_LEN + 40 * 4);
+        assert_eq!(&bytes[..HEADER_LEN], [1, 4, 40, 0, 0, 0]);
+
+        let mut reader = &bytes[..];
+        let loaded: ArrayDeque<u32, 40> = ArrayDeque::load_from(|buf: &mut [u8]| {
+            let (head, rest) = reader.split_at(buf.len());
+            buf.copy_from_slice(head);
+            reader = rest;
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+
+        assert_eq!(loaded, deque);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn rejects_bad_header() {
+        let mut slice = [0u16; 2];
+        let load = |header: [u8; HEADER_LEN], slice: &mut [u16]| {
+            SliceDeque::load_from_in(slice, |buf: &mut [u8]| {
+                buf.copy_from_slice(&header[..buf.len()]);
+                Ok::<_, ()>(())
+            })
+            .map(|deque| deque.len())
+        };
+
+        assert_eq!(
+            load([2, 2, 0, 0, 0, 0], &mut slice),
+            Err(LoadError::Version(2))
+        );
+        assert_eq!(
+            load([1, 4, 0, 0, 0, 0], &mut slice),
+            Err(LoadError::ElementSize(4))
+        );
+        assert_eq!(
+            load([1, 2, 3, 0, 0, 0], &mut slice),
+            Err(LoadError::Capacity(3))
+        );
+        assert_eq!(load([1, 2, 0, 0, 0, 0], &mut slice), Ok(0));
+    }
+}
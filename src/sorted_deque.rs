@@ -0,0 +1,260 @@
+//! A fixed-capacity deque which keeps its elements in sorted order.
+
+use core::{
+    fmt,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::{array_deque, ArrayDeque, BaseDeque, CapacityError};
+
+/// A multiset holding up to `N` elements in ascending order, backed by an
+/// array.
+///
+/// Elements can only be added with [`insert`], which finds the element's
+/// position by binary search and shifts whichever side of the deque is
+/// shorter to make room. Equal elements are kept in insertion order. Both the
+/// smallest and the largest element can be removed in constant time.
+///
+/// [`insert`]: SortedDeque::insert
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::sorted_deque::SortedDeque;
+/// let mut latencies: SortedDeque<u32, 8> = SortedDeque::new();
+///
+/// for ms in [12, 3, 48, 7, 12, 95].iter().copied() {
+///     latencies.insert(ms).unwrap();
+/// }
+///
+/// assert_eq!(latencies.pop_max(), Some(95));
+/// assert_eq!(latencies.min(), Some(&3));
+///
+/// let (first, second) = latencies.range_of(5..=12);
+/// assert_eq!([first, second].concat(), [7, 12, 12]);
+/// ```
+#[derive(Clone, Default)]
+pub struct SortedDeque<T, const N: usize>
+where
+    T: Ord + Default,
+{
+    items: ArrayDeque<T, N>,
+}
+
+impl<T, const N: usize> SortedDeque<T, N>
+where
+    T: Ord + Default,
+{
+    /// Constructs a new, empty `SortedDeque<T, N>`.
+    pub fn new() -> Self {
+        SortedDeque {
+            items: ArrayDeque::new(),
+        }
+    }
+
+    /// Returns the maximum number of elements the deque may hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of elements in the deque.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if the deque is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns `true` if the deque is full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.items.is_full()
+    }
+
+    /// Returns the number of elements for which `pred` returns `true`, given
+    /// that it returns `true` for a prefix of the deque and `false` for the
+    /// rest.
+    fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let (front, back) = self.items.as_slices();
+
+        match back.first() {
+            Some(item) if pred(item) => front.len() + back.partition_point(pred),
+            _ => front.partition_point(pred),
+        }
+    }
+
+    /// Inserts an element at its sorted position, after any equal elements.
+    ///
+    /// If the deque is full, the element is returned in a [`CapacityError`].
+    pub fn insert(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        if self.items.is_full() {
+            return Err(CapacityError { item });
+        }
+
+        let index = self.partition_point(|other| *other <= item);
+
+        BaseDeque::insert(&mut self.items, index, item)
+    }
+
+    /// Returns `true` if the deque contains an element equal to `item`.
+    pub fn contains(&self, item: &T) -> bool {
+        let index = self.partition_point(|other| other < item);
+
+        BaseDeque::physical_index(&self.items, index)
+            .is_some_and(|index| BaseDeque::items(&self.items)[index] == *item)
+    }
+
+    /// Returns a reference to the smallest element, or `None` if the deque
+    /// is empty.
+    #[inline]
+    pub fn min(&self) -> Option<&T> {
+        self.items.front()
+    }
+
+    /// Returns a reference to the largest element, or `None` if the deque is
+    /// empty.
+    #[inline]
+    pub fn max(&self) -> Option<&T> {
+        self.items.back()
+    }
+
+    /// Removes and returns the smallest element, or `None` if the deque is
+    /// empty.
+    ///
+    /// Of several equal smallest elements, the first inserted is removed.
+    #[inline]
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.items.pop_front()
+    }
+
+    /// Removes and returns the largest element, or `None` if the deque is
+    /// empty.
+    ///
+    /// Of several equal largest elements, the last inserted is removed.
+    #[inline]
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.items.pop_back()
+    }
+
+    /// Returns a pair of slices which together contain the elements within
+    /// `range`, in ascending order.
+    ///
+    /// If `range` is empty or contains no elements, both slices are empty.
+    pub fn range_of<R>(&self, range: R) -> (&[T], &[T])
+    where
+        R: RangeBounds<T>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(low) => self.partition_point(|item| item < low),
+            Bound::Excluded(low) => self.partition_point(|item| item <= low),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(high) => self.partition_point(|item| item <= high),
+            Bound::Excluded(high) => self.partition_point(|item| item < high),
+            Bound::Unbounded => self.len(),
+        }
+        .max(start);
+
+        let (front, back) = self.items.as_slices();
+        let split = front.len();
+
+        (
+            &front[start.min(split)..end.min(split)],
+            &back[start.saturating_sub(split)..end.saturating_sub(split)],
+        )
+    }
+
+    /// Returns a pair of slices which together contain the elements of the
+    /// deque, in ascending order.
+    #[inline]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.items.as_slices()
+    }
+
+    /// Returns an iterator over the elements of the deque, in ascending
+    /// order.
+    #[inline]
+    pub fn iter(&self) -> array_deque::Iter<'_, T, N> {
+        self.items.iter()
+    }
+
+    /// Removes all elements from the deque.
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for SortedDeque<T, N>
+where
+    T: Ord + Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.items, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    fn concat<'a>((first, second): (&'a [u32], &'a [u32])) -> Vec<u32> {
+        [first, second].concat()
+    }
+
+    /// Builds a deque whose elements wrap around the end of the backing
+    /// array.
+    fn wrapped() -> SortedDeque<u32, 6> {
+        let mut deque = SortedDeque::new();
+        for item in [50, 60, 10, 20, 30, 40].iter().copied() {
+            deque.insert(item).unwrap();
+        }
+        assert!(!deque.items.layout_info().is_contiguous());
+
+        deque
+    }
+
+    #[test]
+    fn insert_keeps_order() {
+        let deque = wrapped();
+
+        assert!(deque.iter().copied().eq([10, 20, 30, 40, 50, 60]));
+        assert!(deque.contains(&40));
+        assert!(!deque.contains(&45));
+    }
+
+    #[test]
+    fn range_of_spans_wrap() {
+        let deque = wrapped();
+
+        assert_eq!(concat(deque.range_of(15..55)), [20, 30, 40, 50]);
+        assert_eq!(concat(deque.range_of(20..=20)), [20]);
+        assert_eq!(concat(deque.range_of(..)), [10, 20, 30, 40, 50, 60]);
+        assert!(concat(deque.range_of(45..=48)).is_empty());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = deque.range_of(50..20);
+        assert!(concat(reversed).is_empty());
+    }
+
+    #[test]
+    fn full_deque_rejects() {
+        let mut deque = wrapped();
+
+        assert_eq!(deque.insert(35).unwrap_err().into_inner(), 35);
+        assert_eq!(deque.pop_min(), Some(10));
+        assert_eq!(deque.pop_max(), Some(60));
+        deque.insert(35).unwrap();
+        assert!(deque.iter().copied().eq([20, 30, 35, 40, 50]));
+    }
+}
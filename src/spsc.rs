@@ -0,0 +1,502 @@
+//! A lock-free single-producer, single-consumer queue.
+//!
+//! [`Queue`] and [`SliceQueue`] are bounded ring buffers which can be split
+//! into a [`Producer`] and a [`Consumer`]. The two halves may be used
+//! concurrently, for example from an interrupt handler and the main loop or
+//! from two threads, without locking.
+//!
+//! As this crate forbids `unsafe` code, each slot of the queue is itself an
+//! atomic, so elements are limited to types implementing [`AtomicItem`]: the
+//! primitive integer types and `bool`, subject to the atomic operations
+//! available on the target.
+//!
+//! # Example
+//!
+//! ```
+//! use holodeque::spsc::Queue;
+//!
+//! static QUEUE: Queue<u8, 16> = Queue::new();
+//!
+//! let (mut producer, mut consumer) = QUEUE.split().unwrap();
+//!
+//! // In an interrupt handler:
+//! producer.push(b'h').unwrap();
+//! producer.push(b'i').unwrap();
+//!
+//! // In the main loop:
+//! assert_eq!(consumer.pop(), Some(b'h'));
+//! assert_eq!(consumer.pop(), Some(b'i'));
+//! assert_eq!(consumer.pop(), None);
+//! ```
+
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::CapacityError;
+
+/// A type which can be stored in a lock-free queue.
+///
+/// Each element is stored in an atomic of type `Atomic`, which is read and
+/// written with relaxed ordering; the queue's head and tail indices provide
+/// the necessary synchronization.
+pub trait AtomicItem: Copy {
+    /// The atomic type used to store elements.
+    type Atomic: Sync;
+
+    /// The initial value of an empty slot.
+    const INIT: Self::Atomic;
+
+    /// Loads the value stored in `slot`.
+    fn load(slot: &Self::Atomic) -> Self;
+
+    /// Stores `value` in `slot`.
+    fn store(slot: &Self::Atomic, value: Self);
+}
+
+macro_rules! atomic_item {
+    ($($width:literal => $($ty:ty: $atomic:ident = $init:expr),+;)+) => {
+        $($(
+            #[cfg(target_has_atomic = $width)]
+            impl AtomicItem for $ty {
+                type Atomic = core::sync::atomic::$atomic;
+
+                // A `const` is needed so that it can be used in array repeat
+                // expressions.
+                #[allow(clippy::declare_interior_mutable_const)]
+                const INIT: Self::Atomic = core::sync::atomic::$atomic::new($init);
+
+                #[inline]
+                fn load(slot: &Self::Atomic) -> Self {
+                    slot.load(Ordering::Relaxed)
+                }
+
+                #[inline]
+                fn store(slot: &Self::Atomic, value: Self) {
+                    slot.store(value, Ordering::Relaxed)
+                }
+            }
+        )+)+
+    };
+}
+
+atomic_item! {
+    "8" => bool: AtomicBool = false, u8: AtomicU8 = 0, i8: AtomicI8 = 0;
+    "16" => u16: AtomicU16 = 0, i16: AtomicI16 = 0;
+    "32" => u32: AtomicU32 = 0, i32: AtomicI32 = 0;
+    "64" => u64: AtomicU64 = 0, i64: AtomicI64 = 0;
+    "ptr" => usize: AtomicUsize = 0, isize: AtomicIsize = 0;
+}
+
+/// The head and tail indices shared by a producer and a consumer.
+///
+/// Both indices count modulo twice the capacity, so that a full queue can be
+/// distinguished from an empty one without sacrificing a slot.
+struct Indices {
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    split: AtomicBool,
+}
+
+impl Indices {
+    const fn new() -> Indices {
+        Indices {
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            split: AtomicBool::new(false),
+        }
+    }
+
+    fn split<'q, T>(&'q self, slots: &'q [T::Atomic]) -> Option<(Producer<'q, T>, Consumer<'q, T>)>
+    where
+        T: AtomicItem,
+    {
+        if self.split.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+
+        Some((
+            Producer {
+                slots,
+                indices: self,
+            },
+            Consumer {
+                slots,
+                indices: self,
+            },
+        ))
+    }
+}
+
+/// Returns the number of elements between `head` and `tail`.
+fn distance(head: usize, tail: usize, capacity: usize) -> usize {
+    if tail >= head {
+        tail - head
+    } else {
+        tail + 2 * capacity - head
+    }
+}
+
+/// Advances an index by one, wrapping at twice the capacity.
+fn advance(index: usize, capacity: usize) -> usize {
+    if index + 1 == 2 * capacity {
+        0
+    } else {
+        index + 1
+    }
+}
+
+/// Maps an index to a slot.
+fn slot(index: usize, capacity: usize) -> usize {
+    if index >= capacity {
+        index - capacity
+    } else {
+        index
+    }
+}
+
+/// A lock-free single-producer, single-consumer queue backed by an array.
+///
+/// The capacity of the queue is determined by the generic parameter `N`.
+pub struct Queue<T, const N: usize>
+where
+    T: AtomicItem,
+{
+    indices: Indices,
+    slots: [T::Atomic; N],
+}
+
+impl<T, const N: usize> Queue<T, N>
+where
+    T: AtomicItem,
+{
+    /// Creates an empty queue.
+    ///
+    /// This is a `const fn`, so the queue can be placed in a `static`.
+    pub const fn new() -> Queue<T, N> {
+        Queue {
+            indices: Indices::new(),
+            slots: [T::INIT; N],
+        }
+    }
+
+    /// Returns the maximum number of elements the queue can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Splits the queue into a producer and a consumer.
+    ///
+    /// A queue can only be split once; subsequent calls return `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::spsc::Queue;
+    /// let queue: Queue<u32, 4> = Queue::new();
+    ///
+    /// let (mut producer, mut consumer) = queue.split().unwrap();
+    /// assert!(queue.split().is_none());
+    ///
+    /// producer.push(1).unwrap();
+    /// assert_eq!(consumer.pop(), Some(1));
+    /// ```
+    #[inline]
+    pub fn split(&self) -> Option<(Producer<'_, T>, Consumer<'_, T>)> {
+        self.indices.split(&self.slots)
+    }
+}
+
+impl<T, const N: usize> Default for Queue<T, N>
+where
+    T: AtomicItem,
+{
+    fn default() -> Self {
+        Queue::new()
+    }
+}
+
+/// A lock-free single-producer, single-consumer queue backed by a slice.
+///
+/// The capacity of the queue is determined by the length of the slice.
+pub struct SliceQueue<'a, T>
+where
+    T: AtomicItem,
+{
+    indices: Indices,
+    slots: &'a [T::Atomic],
+}
+
+impl<'a, T> SliceQueue<'a, T>
+where
+    T: AtomicItem,
+{
+    /// Creates an empty queue backed by the provided slice.
+    ///
+    /// Any values already stored in the slice are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::spsc::SliceQueue;
+    /// use core::sync::atomic::AtomicU8;
+    ///
+    /// static SLOTS: [AtomicU8; 4] = [const { AtomicU8::new(0) }; 4];
+    /// static QUEUE: SliceQueue<'static, u8> = SliceQueue::new_in(&SLOTS);
+    ///
+    /// let (mut producer, mut consumer) = QUEUE.split().unwrap();
+    /// producer.push(7).unwrap();
+    /// assert_eq!(consumer.pop(), Some(7));
+    /// ```
+    pub const fn new_in(slots: &'a [T::Atomic]) -> SliceQueue<'a, T> {
+        SliceQueue {
+            indices: Indices::new(),
+            slots,
+        }
+    }
+
+    /// Returns the maximum number of elements the queue can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Splits the queue into a producer and a consumer.
+    ///
+    /// A queue can only be split once; subsequent calls return `None`.
+    #[inline]
+    pub fn split(&self) -> Option<(Producer<'_, T>, Consumer<'_, T>)> {
+        self.indices.split(self.slots)
+    }
+}
+
+/// The producing half of a split queue.
+///
+/// Pushing requires a mutable reference, as the producer updates the tail
+/// index without synchronizing with other producers. A producer can be sent
+/// to another thread, but not pushed to through a shared reference:
+///
+/// ```compile_fail
+/// # use holodeque::spsc::Queue;
+/// let queue: Queue<u32, 4> = Queue::new();
+/// let (producer, _consumer) = queue.split().unwrap();
+///
+/// let shared = &producer;
+/// shared.push(1).unwrap();
+/// ```
+///
+/// This struct is created by the `split` method on [`Queue`] and
+/// [`SliceQueue`].
+pub struct Producer<'q, T>
+where
+    T: AtomicItem,
+{
+    slots: &'q [T::Atomic],
+    indices: &'q Indices,
+}
+
+impl<'q, T> Producer<'q, T>
+where
+    T: AtomicItem,
+{
+    /// Returns the number of elements in the queue.
+    ///
+    /// As the consumer may remove elements concurrently, the actual number of
+    /// elements may be lower by the time this method returns.
+    #[inline]
+    pub fn len(&self) -> usize {
+        let head = self.indices.head.load(Ordering::Acquire);
+        let tail = self.indices.tail.load(Ordering::Relaxed);
+
+        distance(head, tail, self.slots.len())
+    }
+
+    /// Returns `true` if the queue is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.slots.len()
+    }
+
+    /// Appends an element to the back of the queue.
+    ///
+    /// If the queue is full, the element is returned in a [`CapacityError`].
+    pub fn push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        let capacity = self.slots.len();
+        let head = self.indices.head.load(Ordering::Acquire);
+        let tail = self.indices.tail.load(Ordering::Relaxed);
+
+        if distance(head, tail, capacity) == capacity {
+            return Err(CapacityError { item });
+        }
+
+        T::store(&self.slots[slot(tail, capacity)], item);
+        self.indices
+            .tail
+            .store(advance(tail, capacity), Ordering::Release);
+
+        Ok(())
+    }
+}
+
+/// The consuming half of a split queue.
+///
+/// Popping requires a mutable reference, as the consumer updates the head
+/// index without synchronizing with other consumers. A consumer can be sent
+/// to another thread, but not popped from through a shared reference:
+///
+/// ```compile_fail
+/// # use holodeque::spsc::Queue;
+/// let queue: Queue<u32, 4> = Queue::new();
+/// let (_producer, consumer) = queue.split().unwrap();
+///
+/// let shared = &consumer;
+/// shared.pop();
+/// ```
+///
+/// This struct is created by the `split` method on [`Queue`] and
+/// [`SliceQueue`].
+pub struct Consumer<'q, T>
+where
+    T: AtomicItem,
+{
+    slots: &'q [T::Atomic],
+    indices: &'q Indices,
+}
+
+impl<'q, T> Consumer<'q, T>
+where
+    T: AtomicItem,
+{
+    /// Returns the number of elements in the queue.
+    ///
+    /// As the producer may add elements concurrently, the actual number of
+    /// elements may be higher by the time this method returns.
+    #[inline]
+    pub fn len(&self) -> usize {
+        let head = self.indices.head.load(Ordering::Relaxed);
+        let tail = self.indices.tail.load(Ordering::Acquire);
+
+        distance(head, tail, self.slots.len())
+    }
+
+    /// Returns `true` if the queue is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the front element without removing it, or `None` if the queue
+    /// is empty.
+    pub fn peek(&self) -> Option<T> {
+        let head = self.indices.head.load(Ordering::Relaxed);
+        let tail = self.indices.tail.load(Ordering::Acquire);
+
+        if head == tail {
+            return None;
+        }
+
+        Some(T::load(&self.slots[slot(head, self.slots.len())]))
+    }
+
+    /// Removes the front element and returns it, or `None` if the queue is
+    /// empty.
+    pub fn pop(&mut self) -> Option<T> {
+        let capacity = self.slots.len();
+        let head = self.indices.head.load(Ordering::Relaxed);
+        let tail = self.indices.tail.load(Ordering::Acquire);
+
+        if head == tail {
+            return None;
+        }
+
+        let item = T::load(&self.slots[slot(head, capacity)]);
+        self.indices
+            .head
+            .store(advance(head, capacity), Ordering::Release);
+
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_and_drain_across_wrap() {
+        let queue: Queue<u32, 3> = Queue::new();
+        let (mut producer, mut consumer) = queue.split().unwrap();
+
+        for round in 0..10 {
+            for i in 0..3 {
+                producer.push(round * 3 + i).unwrap();
+            }
+
+            assert!(producer.is_full());
+            assert_eq!(producer.push(99).unwrap_err().into_inner(), 99);
+            assert_eq!(consumer.peek(), Some(round * 3));
+
+            for i in 0..3 {
+                assert_eq!(consumer.pop(), Some(round * 3 + i));
+            }
+
+            assert!(consumer.is_empty());
+            assert_eq!(consumer.pop(), None);
+        }
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let queue: Queue<u8, 0> = Queue::new();
+        let (mut producer, mut consumer) = queue.split().unwrap();
+
+        assert!(producer.is_full());
+        assert!(producer.push(1).is_err());
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn split_once() {
+        let slots = [u8::INIT, u8::INIT];
+        let queue = SliceQueue::<u8>::new_in(&slots);
+
+        assert_eq!(queue.capacity(), 2);
+        assert!(queue.split().is_some());
+        assert!(queue.split().is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn concurrent_transfer() {
+        const COUNT: u32 = 10_000;
+
+        let queue: Queue<u32, 8> = Queue::new();
+        let (mut producer, mut consumer) = queue.split().unwrap();
+
+        std::thread::scope(|s| {
+            s.spawn(move || {
+                for i in 0..COUNT {
+                    while producer.push(i).is_err() {
+                        std::thread::yield_now();
+                    }
+                }
+            });
+
+            let mut expected = 0;
+            while expected < COUNT {
+                match consumer.pop() {
+                    Some(item) => {
+                        assert_eq!(item, expected);
+                        expected += 1;
+                    }
+                    None => std::thread::yield_now(),
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,337 @@
+//! A thread-safe double-ended queue with fixed capacity and blocking
+//! operations.
+
+use std::{
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::{ArrayDeque, CapacityError};
+
+/// A thread-safe double-ended queue with fixed capacity, backed by an array.
+///
+/// `SyncDeque` wraps an [`ArrayDeque`] in a mutex and provides blocking
+/// operations, making it usable as a bounded channel which never allocates.
+/// Elements are normally added with [`push_back`] and removed with
+/// [`pop_front`], but urgent elements may be added with [`push_front`] to
+/// skip ahead of those already queued.
+///
+/// Each blocking operation has a non-blocking `try_` variant and a variant
+/// with a timeout.
+///
+/// [`push_back`]: SyncDeque::push_back
+/// [`push_front`]: SyncDeque::push_front
+/// [`pop_front`]: SyncDeque::pop_front
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::sync_deque::SyncDeque;
+/// use std::thread;
+///
+/// let deque: SyncDeque<u32, 4> = SyncDeque::new();
+///
+/// thread::scope(|s| {
+///     s.spawn(|| {
+///         for i in 0..16 {
+///             deque.push_back(i);
+///         }
+///     });
+///
+///     for i in 0..16 {
+///         assert_eq!(deque.pop_front(), i);
+///     }
+/// });
+/// ```
+pub struct SyncDeque<T, const N: usize>
+where
+    T: Default,
+{
+    deque: Mutex<ArrayDeque<T, N>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+}
+
+impl<T, const N: usize> SyncDeque<T, N>
+where
+    T: Default,
+{
+    /// Constructs a new, empty `SyncDeque<T, N>`.
+    pub fn new() -> Self {
+        SyncDeque {
+            deque: Mutex::new(ArrayDeque::new()),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+        }
+    }
+
+    /// Returns the maximum number of elements the deque may hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of elements in the deque.
+    ///
+    /// As other threads may modify the deque concurrently, the result may be
+    /// out of date by the time this method returns.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if the deque is empty.
+    ///
+    /// As other threads may modify the deque concurrently, the result may be
+    /// out of date by the time this method returns.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Returns `true` if the deque is full.
+    ///
+    /// As other threads may modify the deque concurrently, the result may be
+    /// out of date by the time this method returns.
+    pub fn is_full(&self) -> bool {
+        self.lock().is_full()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ArrayDeque<T, N>> {
+        // Every deque operation leaves the deque in a consistent state, even
+        // if an element's destructor panics, so poisoning can be ignored.
+        self.deque.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Waits on `condvar` until `ready` returns true or `deadline` passes.
+    ///
+    /// Returns `None` if the deadline passed first.
+    fn wait_until<'g, F>(
+        &self,
+        mut guard: MutexGuard<'g, ArrayDeque<T, N>>,
+        condvar: &Condvar,
+        deadline: Option<Instant>,
+        ready: F,
+    ) -> Option<MutexGuard<'g, ArrayDeque<T, N>>>
+    where
+        F: Fn(&ArrayDeque<T, N>) -> bool,
+    {
+        while !ready(&guard) {
+            guard = match deadline {
+                Some(deadline) => {
+                    let timeout = deadline
+                        .checked_duration_since(Instant::now())
+                        .filter(|timeout| !timeout.is_zero())?;
+                    condvar
+                        .wait_timeout(guard, timeout)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+
+                None => condvar.wait(guard).unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+
+        Some(guard)
+    }
+
+    fn push_with<F>(
+        &self,
+        item: T,
+        deadline: Option<Instant>,
+        push: F,
+    ) -> Result<(), CapacityError<T>>
+    where
+        F: FnOnce(&mut ArrayDeque<T, N>, T) -> Result<(), CapacityError<T>>,
+    {
+        let guard = self.lock();
+
+        let mut guard = match self.wait_until(guard, &self.not_full, deadline, |d| !d.is_full()) {
+            Some(guard) => guard,
+            None => return Err(CapacityError { item }),
+        };
+
+        push(&mut guard, item)?;
+        drop(guard);
+        self.not_empty.notify_one();
+
+        Ok(())
+    }
+
+    fn pop_with(&self, deadline: Option<Instant>) -> Option<T> {
+        let guard = self.lock();
+        let mut guard = self.wait_until(guard, &self.not_empty, deadline, |d| !d.is_empty())?;
+
+        let item = guard.pop_front();
+        drop(guard);
+        self.not_full.notify_one();
+
+        item
+    }
+
+    /// Appends an element to the back of the deque, blocking until there is
+    /// room.
+    pub fn push_back(&self, item: T) {
+        // Without a deadline, the push waits until it succeeds.
+        let _ = self.push_with(item, None, ArrayDeque::push_back);
+    }
+
+    /// Prepends an element to the front of the deque, blocking until there is
+    /// room.
+    ///
+    /// The element will be the next one returned by [`pop_front`].
+    ///
+    /// [`pop_front`]: SyncDeque::pop_front
+    pub fn push_front(&self, item: T) {
+        let _ = self.push_with(item, None, ArrayDeque::push_front);
+    }
+
+    /// Appends an element to the back of the deque if there is room.
+    ///
+    /// If the deque is full, the element is returned in a [`CapacityError`].
+    pub fn try_push_back(&self, item: T) -> Result<(), CapacityError<T>> {
+        self.push_with(item, Some(Instant::now()), ArrayDeque::push_back)
+    }
+
+    /// Prepends an element to the front of the deque if there is room.
+    ///
+    /// If the deque is full, the element is returned in a [`CapacityError`].
+    pub fn try_push_front(&self, item: T) -> Result<(), CapacityError<T>> {
+        self.push_with(item, Some(Instant::now()), ArrayDeque::push_front)
+    }
+
+    /// Appends an element to the back of the deque, blocking for at most
+    /// `timeout` until there is room.
+    ///
+    /// If the deque is still full when the timeout expires, the element is
+    /// returned in a [`CapacityError`].
+    /// A timeout too long to be represented as an [`Instant`] never expires.
+    pub fn push_back_timeout(&self, item: T, timeout: Duration) -> Result<(), CapacityError<T>> {
+        self.push_with(item, deadline_after(timeout), ArrayDeque::push_back)
+    }
+
+    /// Prepends an element to the front of the deque, blocking for at most
+    /// `timeout` until there is room.
+    ///
+    /// If the deque is still full when the timeout expires, the element is
+    /// returned in a [`CapacityError`].
+    /// A timeout too long to be represented as an [`Instant`] never expires.
+    pub fn push_front_timeout(&self, item: T, timeout: Duration) -> Result<(), CapacityError<T>> {
+        self.push_with(item, deadline_after(timeout), ArrayDeque::push_front)
+    }
+
+    /// Removes the first element and returns it, blocking until the deque is
+    /// not empty.
+    pub fn pop_front(&self) -> T {
+        // Without a deadline, the pop waits until there is an element.
+        self.pop_with(None).unwrap()
+    }
+
+    /// Removes the first element and returns it, or `None` if the deque is
+    /// empty.
+    pub fn try_pop_front(&self) -> Option<T> {
+        self.pop_with(Some(Instant::now()))
+    }
+
+    /// Removes the first element and returns it, blocking for at most
+    /// `timeout` until the deque is not empty.
+    ///
+    /// If the deque is still empty when the timeout expires, `None` is
+    /// returned.
+    /// A timeout too long to be represented as an [`Instant`] never expires.
+    pub fn pop_front_timeout(&self, timeout: Duration) -> Option<T> {
+        self.pop_with(deadline_after(timeout))
+    }
+}
+
+/// Returns the instant `timeout` from now, or `None` if it cannot be
+/// represented, in which case the timeout is treated as never expiring.
+fn deadline_after(timeout: Duration) -> Option<Instant> {
+    Instant::now().checked_add(timeout)
+}
+
+impl<T, const N: usize> Default for SyncDeque<T, N>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        SyncDeque::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+
+    #[test]
+    fn try_operations() {
+        let deque: SyncDeque<u32, 2> = SyncDeque::new();
+
+        assert_eq!(deque.try_pop_front(), None);
+        deque.try_push_back(1).unwrap();
+        deque.try_push_back(2).unwrap();
+        assert_eq!(deque.try_push_front(0).unwrap_err().into_inner(), 0);
+        assert!(deque.is_full());
+
+        assert_eq!(deque.try_pop_front(), Some(1));
+        deque.try_push_front(0).unwrap();
+        assert_eq!(deque.try_pop_front(), Some(0));
+        assert_eq!(deque.try_pop_front(), Some(2));
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn timeouts_expire() {
+        let deque: SyncDeque<u32, 1> = SyncDeque::new();
+        let timeout = Duration::from_millis(10);
+
+        assert_eq!(deque.pop_front_timeout(timeout), None);
+        deque.push_back_timeout(1, timeout).unwrap();
+        assert!(deque.push_back_timeout(2, timeout).is_err());
+        assert!(deque.push_front_timeout(2, timeout).is_err());
+        assert_eq!(deque.pop_front_timeout(timeout), Some(1));
+    }
+
+    #[test]
+    fn unrepresentable_timeout_does_not_panic() {
+        let deque: SyncDeque<u32, 1> = SyncDeque::new();
+
+        deque.push_back_timeout(1, Duration::MAX).unwrap();
+        assert_eq!(deque.pop_front_timeout(Duration::MAX), Some(1));
+        deque.push_front_timeout(2, Duration::MAX).unwrap();
+        assert_eq!(deque.try_pop_front(), Some(2));
+    }
+
+    #[test]
+    fn blocking_push_waits_for_pop() {
+        let deque: SyncDeque<u32, 1> = SyncDeque::new();
+        deque.push_back(1);
+
+        thread::scope(|s| {
+            let pusher = s.spawn(|| deque.push_back(2));
+
+            assert_eq!(deque.pop_front(), 1);
+            pusher.join().unwrap();
+            assert_eq!(deque.pop_front(), 2);
+        });
+    }
+
+    #[test]
+    fn priority_push_front() {
+        let deque: SyncDeque<u32, 4> = SyncDeque::new();
+
+        thread::scope(|s| {
+            s.spawn(|| {
+                deque.push_back(1);
+                deque.push_back(2);
+                deque.push_front(0);
+            })
+            .join()
+            .unwrap();
+        });
+
+        assert_eq!(deque.pop_front(), 0);
+        assert_eq!(deque.pop_front(), 1);
+        assert_eq!(deque.pop_front(), 2);
+    }
+}
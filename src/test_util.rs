@@ -0,0 +1,257 @@
+//! Utilities for differential testing of deques.
+//!
+//! [`DequeOp`] describes one operation of the [`Deque`] trait. Applying the
+//! same sequence of operations to a deque under test, with [`apply`], and to
+//! a [`Model`] backed by a [`VecDeque`] should produce the same results and
+//! leave both with the same contents. With the `quickcheck` or `arbitrary`
+//! features, sequences of operations can be generated by a property tester
+//! or a fuzzer.
+//!
+//! # Example
+//!
+//! ```
+//! use holodeque::{
+//!     test_util::{apply, DequeOp, Model},
+//!     ArrayDeque,
+//! };
+//!
+//! let ops = [
+//!     DequeOp::PushBack(1),
+//!     DequeOp::PushFront(2),
+//!     DequeOp::PushBack(3),
+//!     DequeOp::PopBack,
+//!     DequeOp::PushFront(4),
+//! ];
+//!
+//! let mut deque: ArrayDeque<u32, 3> = ArrayDeque::new();
+//! let mut model = Model::new(deque.capacity());
+//!
+//! for op in ops.iter().cloned() {
+//!     assert_eq!(apply(&mut deque, op.clone()), model.apply(op));
+//!     assert!(model.matches(&deque));
+//! }
+//! ```
+//!
+//! [`VecDeque`]: alloc::collections::VecDeque
+
+use alloc::collections::VecDeque;
+
+use crate::Deque;
+
+/// An operation on a [`Deque`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DequeOp<T> {
+    /// Prepends an element with [`Deque::push_front`].
+    PushFront(T),
+
+    /// Appends an element with [`Deque::push_back`].
+    PushBack(T),
+
+    /// Removes the first element with [`Deque::pop_front`].
+    PopFront,
+
+    /// Removes the last element with [`Deque::pop_back`].
+    PopBack,
+
+    /// Keeps the last elements with [`Deque::truncate_front`].
+    TruncateFront(usize),
+
+    /// Removes all elements with [`Deque::clear`].
+    Clear,
+}
+
+/// Applies `op` to `deque`, returning the element the operation gave back.
+///
+/// This is the removed element for `PopFront` and `PopBack`, and the element
+/// itself for `PushFront` and `PushBack` if the deque was full. Other
+/// operations return `None`.
+pub fn apply<T, D>(deque: &mut D, op: DequeOp<T>) -> Option<T>
+where
+    D: Deque<T> + ?Sized,
+{
+    match op {
+        DequeOp::PushFront(item) => deque.push_front(item).err().map(|e| e.into_inner()),
+        DequeOp::PushBack(item) => deque.push_back(item).err().map(|e| e.into_inner()),
+        DequeOp::PopFront => deque.pop_front(),
+        DequeOp::PopBack => deque.pop_back(),
+        DequeOp::TruncateFront(len) => {
+            deque.truncate_front(len);
+            None
+        }
+        DequeOp::Clear => {
+            deque.clear();
+            None
+        }
+    }
+}
+
+/// A reference model of a fixed-capacity deque, backed by a [`VecDeque`].
+///
+/// [`VecDeque`]: alloc::collections::VecDeque
+#[derive(Clone, Debug)]
+pub struct Model<T> {
+    items: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> Model<T> {
+    /// Creates an empty model of a deque with the given capacity.
+    pub fn new(capacity: usize) -> Model<T> {
+        Model {
+            items: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Creates a model with the same capacity and contents as `deque`.
+    pub fn from_deque<D>(deque: &D) -> Model<T>
+    where
+        D: Deque<T> + ?Sized,
+        T: Clone,
+    {
+        let (front, back) = deque.as_slices();
+
+        Model {
+            items: front.iter().chain(back).cloned().collect(),
+            capacity: deque.capacity(),
+        }
+    }
+
+    /// Returns the capacity of the modeled deque.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the elements of the modeled deque.
+    #[inline]
+    pub fn items(&self) -> &VecDeque<T> {
+        &self.items
+    }
+
+    /// Applies `op` to the model, returning the element the operation gave
+    /// back.
+    ///
+    /// See [`apply`] for the returned elements.
+    pub fn apply(&mut self, op: DequeOp<T>) -> Option<T> {
+        match op {
+            DequeOp::PushFront(item) if self.items.len() == self.capacity => Some(item),
+            DequeOp::PushFront(item) => {
+                self.items.push_front(item);
+                None
+            }
+            DequeOp::PushBack(item) if self.items.len() == self.capacity => Some(item),
+            DequeOp::PushBack(item) => {
+                self.items.push_back(item);
+                None
+            }
+            DequeOp::PopFront => self.items.pop_front(),
+            DequeOp::PopBack => self.items.pop_back(),
+            DequeOp::TruncateFront(len) => {
+                let excess = self.items.len().saturating_sub(len);
+                self.items.drain(..excess);
+                None
+            }
+            DequeOp::Clear => {
+                self.items.clear();
+                None
+            }
+        }
+    }
+
+    /// Returns `true` if `deque` has the same capacity and contents as the
+    /// model.
+    pub fn matches<D>(&self, deque: &D) -> bool
+    where
+        D: Deque<T> + ?Sized,
+        T: PartialEq,
+    {
+        let (front, back) = deque.as_slices();
+
+        deque.capacity() == self.capacity && front.iter().chain(back).eq(&self.items)
+    }
+}
+
+#[cfg(any(feature = "quickcheck", test))]
+impl<T> quickcheck::Arbitrary for DequeOp<T>
+where
+    T: quickcheck::Arbitrary,
+{
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        match u8::arbitrary(g) % 8 {
+            0 | 1 => DequeOp::PushFront(T::arbitrary(g)),
+            2 | 3 => DequeOp::PushBack(T::arbitrary(g)),
+            4 => DequeOp::PopFront,
+            5 => DequeOp::PopBack,
+            6 => DequeOp::TruncateFront(usize::arbitrary(g) % (g.size() + 1)),
+            _ => DequeOp::Clear,
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T> arbitrary::Arbitrary<'a> for DequeOp<T>
+where
+    T: arbitrary::Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=5)? {
+            0 => DequeOp::PushFront(T::arbitrary(u)?),
+            1 => DequeOp::PushBack(T::arbitrary(u)?),
+            2 => DequeOp::PopFront,
+            3 => DequeOp::PopBack,
+            4 => DequeOp::TruncateFront(usize::arbitrary(u)?),
+            _ => DequeOp::Clear,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    use crate::{ArrayDeque, SliceDeque};
+
+    quickcheck::quickcheck! {
+        fn qc_array_deque_matches_model(ops: Vec<DequeOp<u8>>) -> bool {
+            let mut deque: ArrayDeque<u8, 8> = ArrayDeque::new();
+            let mut model = Model::new(8);
+
+            ops.into_iter().all(|op| {
+                apply(&mut deque, op.clone()) == model.apply(op) && model.matches(&deque)
+            })
+        }
+    }
+
+    #[test]
+    fn slice_deque_matches_model() {
+        let mut slice = [0; 3];
+        let mut deque = SliceDeque::new_in(&mut slice);
+        deque.push_back(7).unwrap();
+        let mut model = Model::from_deque(&deque);
+
+        let ops = [
+            DequeOp::PushFront(1),
+            DequeOp::PushBack(2),
+            DequeOp::PushBack(3),
+            DequeOp::TruncateFront(2),
+            DequeOp::PopFront,
+            DequeOp::Clear,
+            DequeOp::PopBack,
+        ];
+        let results: Vec<_> = ops
+            .iter()
+            .cloned()
+            .map(|op| {
+                let result = apply(&mut deque, op.clone());
+                assert_eq!(result, model.apply(op));
+                assert!(model.matches(&deque));
+                result
+            })
+            .collect();
+
+        assert_eq!(results, [None, None, Some(3), None, Some(7), None, None]);
+    }
+}
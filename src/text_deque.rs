@@ -0,0 +1,519 @@
+//! A double-ended queue of UTF-8 text with fixed capacity, backed by an
+//! array.
+
+use core::{fmt, str};
+
+use crate::{ArrayDeque, BaseDeque, CapacityError};
+
+/// A double-ended queue of UTF-8 text with fixed capacity, backed by an array.
+///
+/// The capacity of the deque, in bytes, is determined by the generic parameter
+/// `N`. Characters are added and removed whole, so the contents of the deque
+/// are always valid UTF-8.
+///
+/// The contents are stored in a ring buffer. If appending or prepending a
+/// character would split its encoding across the end of the backing array, the
+/// contents are first rotated to the start of the array so that
+/// [`as_strs`](TextDeque::as_strs) can always return two valid string slices.
+#[derive(Clone, Default)]
+pub struct TextDeque<const N: usize> {
+    bytes: ArrayDeque<u8, N>,
+}
+
+impl<const N: usize> TextDeque<N> {
+    /// Constructs a new, empty `TextDeque<N>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::TextDeque;
+    /// let text: TextDeque<16> = TextDeque::new();
+    ///
+    /// assert!(text.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        TextDeque {
+            bytes: ArrayDeque::new_copied(),
+        }
+    }
+
+    /// Returns the maximum number of bytes the deque may hold.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::TextDeque;
+    /// let text: TextDeque<16> = TextDeque::new();
+    ///
+    /// assert_eq!(text.capacity(), 16);
+    /// ```
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the length of the deque's contents, in bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, TextDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut text: TextDeque<16> = TextDeque::new();
+    ///
+    /// text.push_str("añb")?;
+    /// assert_eq!(text.len(), 4);
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns `true` if the deque contains no text.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns `true` if the deque is at capacity.
+    ///
+    /// Note that a deque which is not full may still lack room for a
+    /// particular character, if that character's encoding is longer than the
+    /// remaining space.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.bytes.is_full()
+    }
+
+    /// Returns a pair of string slices which contain, in order, the contents
+    /// of the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, TextDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut text: TextDeque<8> = TextDeque::new();
+    ///
+    /// text.push_str("world")?;
+    /// text.push_front_str("hi ")?;
+    ///
+    /// let (first, second) = text.as_strs();
+    /// assert_eq!(first, "hi ");
+    /// assert_eq!(second, "world");
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    pub fn as_strs(&self) -> (&str, &str) {
+        let (first, second) = self.bytes.as_slices();
+
+        (
+            str::from_utf8(first).expect("TextDeque contents are not UTF-8"),
+            str::from_utf8(second).expect("TextDeque contents are not UTF-8"),
+        )
+    }
+
+    /// Returns an iterator over the characters of the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::{CapacityError, TextDeque};
+    /// # fn main() {
+    /// # (|| -> Result<(), CapacityError<_>> {
+    /// let mut text: TextDeque<8> = TextDeque::new();
+    ///
+    /// text.push_str("bc")?;
+    /// text.push_front_str("a")?;
+    ///
+    /// assert!(text.chars().eq("abc".chars()));
+    /// # Ok(())
+    /// # })().unwrap();
+    /// # }
+    /// ```
+    pub fn chars(&self) -> impl DoubleEndedIterator<Item = char> + '_ {
+        let (first, second) = self.as_strs();
+
+        first.chars().chain(second.chars())
+    }
+
+    /// Appends a string slice to the back of the deque.
+    ///
+    /// If the deque does not have room for all of `s`, nothing is appended and
+    /// an `Err` containing `s` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::TextDeque;
+    /// let mut text: TextDeque<4> = TextDeque::new();
+    ///
+    /// text.push_str("ab").unwrap();
+    /// assert_eq!(text.push_str("cde").unwrap_err().into_inner(), "cde");
+    /// assert_eq!(text.as_strs(), ("ab", ""));
+    /// ```
+    pub fn push_str<'s>(&mut self, s: &'s str) -> Result<(), CapacityError<&'s str>> {
+        if s.len() > N - self.len() {
+            return Err(CapacityError { item: s });
+        }
+
+        self.bytes.try_extend(s.bytes()).unwrap();
+        self.fix_split();
+
+        Ok(())
+    }
+
+    /// Prepends a string slice to the front of the deque.
+    ///
+    /// If the deque does not have room for all of `s`, nothing is prepended
+    /// and an `Err` containing `s` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::TextDeque;
+    /// let mut text: TextDeque<8> = TextDeque::new();
+    ///
+    /// text.push_str("def").unwrap();
+    /// text.push_front_str("abc").unwrap();
+    ///
+    /// assert!(text.chars().eq("abcdef".chars()));
+    /// ```
+    pub fn push_front_str<'s>(&mut self, s: &'s str) -> Result<(), CapacityError<&'s str>> {
+        if s.len() > N - self.len() {
+            return Err(CapacityError { item: s });
+        }
+
+        for byte in s.bytes().rev() {
+            self.bytes.push_front(byte).unwrap();
+        }
+        self.fix_split();
+
+        Ok(())
+    }
+
+    /// Appends a character to the back of the deque.
+    ///
+    /// If the deque does not have room for `ch`, an `Err` containing `ch` is
+    /// returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::TextDeque;
+    /// let mut text: TextDeque<4> = TextDeque::new();
+    ///
+    /// text.push('a').unwrap();
+    /// text.push('é').unwrap();
+    /// assert!(text.push('€').is_err());
+    /// ```
+    pub fn push(&mut self, ch: char) -> Result<(), CapacityError<char>> {
+        self.push_str(ch.encode_utf8(&mut [0; 4]))
+            .map_err(|_| CapacityError { item: ch })
+    }
+
+    /// Prepends a character to the front of the deque.
+    ///
+    /// If the deque does not have room for `ch`, an `Err` containing `ch` is
+    /// returned.
+    pub fn push_front(&mut self, ch: char) -> Result<(), CapacityError<char>> {
+        self.push_front_str(ch.encode_utf8(&mut [0; 4]))
+            .map_err(|_| CapacityError { item: ch })
+    }
+
+    /// Removes and returns the first character of the deque.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::TextDeque;
+    /// let mut text: TextDeque<8> = TextDeque::new();
+    /// text.push_str("ñu").unwrap();
+    ///
+    /// assert_eq!(text.pop_char_front(), Some('ñ'));
+    /// assert_eq!(text.pop_char_front(), Some('u'));
+    /// assert_eq!(text.pop_char_front(), None);
+    /// ```
+    pub fn pop_char_front(&mut self) -> Option<char> {
+        let width = utf8_width(*self.bytes.front()?);
+
+        let mut buf = [0; 4];
+        for byte in &mut buf[..width] {
+            *byte = self.bytes.pop_front().unwrap();
+        }
+
+        Some(decode(&buf[..width]))
+    }
+
+    /// Removes and returns the last character of the deque.
+    ///
+    /// If the deque is empty, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::TextDeque;
+    /// let mut text: TextDeque<8> = TextDeque::new();
+    /// text.push_str("ñu").unwrap();
+    ///
+    /// assert_eq!(text.pop_char_back(), Some('u'));
+    /// assert_eq!(text.pop_char_back(), Some('ñ'));
+    /// assert_eq!(text.pop_char_back(), None);
+    /// ```
+    pub fn pop_char_back(&mut self) -> Option<char> {
+        let mut buf = [0; 4];
+        let mut start = 4;
+
+        loop {
+            let byte = self.bytes.pop_back()?;
+            start -= 1;
+            buf[start] = byte;
+
+            if !is_continuation(byte) {
+                break;
+            }
+        }
+
+        Some(decode(&buf[start..]))
+    }
+
+    /// Shortens the deque to at most `new_len` bytes, keeping the text at the
+    /// front.
+    ///
+    /// If `new_len` does not lie on a character boundary, the character
+    /// containing it is removed as well, so the resulting length may be less
+    /// than `new_len`. If `new_len` is greater than the current length, this
+    /// has no effect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::TextDeque;
+    /// let mut text: TextDeque<8> = TextDeque::new();
+    /// text.push_str("añb").unwrap();
+    ///
+    /// // Byte 2 is in the middle of 'ñ'.
+    /// text.truncate(2);
+    /// assert_eq!(text.as_strs(), ("a", ""));
+    /// ```
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len >= self.len() {
+            return;
+        }
+
+        let mut new_len = new_len;
+        while is_continuation(self.byte(new_len)) {
+            new_len -= 1;
+        }
+
+        self.bytes.truncate(new_len);
+    }
+
+    /// Shortens the deque to at most `new_len` bytes, keeping the text at the
+    /// back.
+    ///
+    /// If removing text from the front would split a character, that
+    /// character is removed as well, so the resulting length may be less than
+    /// `new_len`. If `new_len` is greater than the current length, this has
+    /// no effect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use holodeque::TextDeque;
+    /// let mut text: TextDeque<8> = TextDeque::new();
+    /// text.push_str("añb").unwrap();
+    ///
+    /// // Keeping 2 bytes would split 'ñ'.
+    /// text.truncate_front(2);
+    /// assert_eq!(text.as_strs(), ("b", ""));
+    /// ```
+    pub fn truncate_front(&mut self, new_len: usize) {
+        let len = self.len();
+        if new_len >= len {
+            return;
+        }
+
+        let mut remove = len - new_len;
+        while remove < len && is_continuation(self.byte(remove)) {
+            remove += 1;
+        }
+
+        drop(self.bytes.drain_front(remove));
+    }
+
+    /// Clears the deque, removing all text.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+
+    /// Returns the byte at logical index `index`.
+    fn byte(&self, index: usize) -> u8 {
+        let (first, second) = self.bytes.as_slices();
+
+        match first.get(index) {
+            Some(&byte) => byte,
+            None => second[index - first.len()],
+        }
+    }
+
+    /// Rotates the contents to the start of the backing array if a character
+    /// is split between the two slices of the deque.
+    fn fix_split(&mut self) {
+        if let Some(&byte) = self.bytes.as_slices().1.first() {
+            if is_continuation(byte) {
+                BaseDeque::make_contiguous(&mut self.bytes);
+            }
+        }
+    }
+}
+
+#[inline]
+fn is_continuation(byte: u8) -> bool {
+    byte & 0b1100_0000 == 0b1000_0000
+}
+
+#[inline]
+fn utf8_width(first_byte: u8) -> usize {
+    match first_byte.leading_ones() {
+        0 => 1,
+        n => n as usize,
+    }
+}
+
+fn decode(bytes: &[u8]) -> char {
+    str::from_utf8(bytes)
+        .ok()
+        .and_then(|s| s.chars().next())
+        .expect("TextDeque contents are not UTF-8")
+}
+
+impl<const N: usize> fmt::Write for TextDeque<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const N: usize> fmt::Display for TextDeque<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (first, second) = self.as_strs();
+
+        f.write_str(first)?;
+        f.write_str(second)
+    }
+}
+
+impl<const N: usize> fmt::Debug for TextDeque<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (first, second) = self.as_strs();
+
+        write!(f, "\"{}{}\"", first.escape_debug(), second.escape_debug())
+    }
+}
+
+impl<const N: usize> PartialEq for TextDeque<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl<const N: usize> Eq for TextDeque<N> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate alloc;
+    use alloc::{format, string::String};
+    use core::fmt::Write;
+
+    fn collect<const N: usize>(text: &TextDeque<N>) -> String {
+        let (first, second) = text.as_strs();
+        let mut s = String::from(first);
+        s.push_str(second);
+        s
+    }
+
+    #[test]
+    fn push_str_across_wrap_stays_valid() {
+        let mut text: TextDeque<6> = TextDeque::new();
+
+        text.push_str("abcd").unwrap();
+        assert_eq!(text.pop_char_front(), Some('a'));
+        assert_eq!(text.pop_char_front(), Some('b'));
+        assert_eq!(text.pop_char_front(), Some('c'));
+
+        // '€' is three bytes and would straddle the end of the array.
+        text.push_str("€").unwrap();
+        assert_eq!(collect(&text), "d€");
+
+        text.push('😀').unwrap_err();
+        text.push('x').unwrap();
+        text.push('y').unwrap();
+        assert!(text.is_full());
+        assert_eq!(collect(&text), "d€xy");
+    }
+
+    #[test]
+    fn push_front_across_wrap_stays_valid() {
+        let mut text: TextDeque<5> = TextDeque::new();
+
+        text.push_str("ab").unwrap();
+        text.push_front('ñ').unwrap();
+        assert_eq!(collect(&text), "ñab");
+        assert_eq!(text.pop_char_back(), Some('b'));
+        assert_eq!(text.pop_char_front(), Some('ñ'));
+    }
+
+    #[test]
+    fn pop_multibyte_chars() {
+        let mut text: TextDeque<16> = TextDeque::new();
+        text.push_str("a€😀").unwrap();
+
+        assert_eq!(text.pop_char_back(), Some('😀'));
+        assert_eq!(text.pop_char_front(), Some('a'));
+        assert_eq!(text.pop_char_front(), Some('€'));
+        assert!(text.is_empty());
+    }
+
+    #[test]
+    fn truncate_on_boundary() {
+        let mut text: TextDeque<16> = TextDeque::new();
+        text.push_str("a€b").unwrap();
+
+        text.truncate(4);
+        assert_eq!(collect(&text), "a€");
+        text.truncate(10);
+        assert_eq!(collect(&text), "a€");
+        text.truncate(0);
+        assert!(text.is_empty());
+    }
+
+    #[test]
+    fn truncate_front_removes_partial_char() {
+        let mut text: TextDeque<16> = TextDeque::new();
+        text.push_str("€b").unwrap();
+
+        text.truncate_front(3);
+        assert_eq!(collect(&text), "b");
+    }
+
+    #[test]
+    fn fmt_write_and_display() {
+        let mut text: TextDeque<8> = TextDeque::new();
+
+        write!(text, "{}-{}", 1, 2).unwrap();
+        assert!(write!(text, "{}", 123456).is_err());
+        assert_eq!(format!("{}", text), "1-2");
+        assert_eq!(format!("{:?}", text), "\"1-2\"");
+    }
+}
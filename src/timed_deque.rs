@@ -0,0 +1,400 @@
+//! A fixed-capacity deque which records when each element was added.
+
+use core::fmt;
+
+use crate::{array_deque, ArrayDeque, CapacityError};
+
+/// A source of timestamps for a [`TimedDeque`].
+///
+/// The instants returned by [`now`] must never decrease. Any closure
+/// returning a `u64` tick count is a clock, with windows measured in the same
+/// ticks; with the `std` feature enabled, [`StdClock`] measures time with
+/// [`std::time::Instant`].
+///
+/// [`now`]: Clock::now
+pub trait Clock {
+    /// A point in time.
+    ///
+    /// The [`Default`] value is only used to fill unused slots.
+    type Instant: Copy + Ord + Default;
+
+    /// A span of time between two instants.
+    type Duration: Copy;
+
+    /// Returns the current instant.
+    fn now(&self) -> Self::Instant;
+
+    /// Returns the instant `duration` before `instant`, or `None` if it is
+    /// earlier than any instant the clock can return.
+    fn checked_sub(
+        &self,
+        instant: Self::Instant,
+        duration: Self::Duration,
+    ) -> Option<Self::Instant>;
+}
+
+impl<F> Clock for F
+where
+    F: Fn() -> u64,
+{
+    type Instant = u64;
+    type Duration = u64;
+
+    #[inline]
+    fn now(&self) -> u64 {
+        self()
+    }
+
+    #[inline]
+    fn checked_sub(&self, instant: u64, duration: u64) -> Option<u64> {
+        instant.checked_sub(duration)
+    }
+}
+
+/// A [`Clock`] which measures the time elapsed since it was constructed.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct StdClock {
+    start: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl StdClock {
+    /// Constructs a new `StdClock` which starts counting from the present.
+    pub fn new() -> StdClock {
+        StdClock {
+            start: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for StdClock {
+    fn default() -> Self {
+        StdClock::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for StdClock {
+    // `std::time::Instant` has no `Default` value, so instants are stored as
+    // the time elapsed since the clock was constructed.
+    type Instant = std::time::Duration;
+    type Duration = std::time::Duration;
+
+    #[inline]
+    fn now(&self) -> std::time::Duration {
+        self.start.elapsed()
+    }
+
+    #[inline]
+    fn checked_sub(
+        &self,
+        instant: std::time::Duration,
+        duration: std::time::Duration,
+    ) -> Option<std::time::Duration> {
+        instant.checked_sub(duration)
+    }
+}
+
+#[derive(Default)]
+struct Stamped<I, T> {
+    at: I,
+    item: T,
+}
+
+/// A deque holding up to `N` elements, each stamped with the instant it was
+/// added, for answering questions about a sliding window of time.
+///
+/// Elements are added to the back with the current time from the clock `C`,
+/// so they are ordered from oldest to newest. An element is within a window
+/// of length `d` if it was added no more than `d` before the present.
+/// Because the timestamps are sorted, [`count_within`] takes logarithmic
+/// time.
+///
+/// [`count_within`]: TimedDeque::count_within
+///
+/// # Example
+///
+/// A rate limiter which allows at most two requests in any ten ticks:
+///
+/// ```
+/// # use holodeque::timed_deque::TimedDeque;
+/// use core::cell::Cell;
+///
+/// let ticks = Cell::new(0);
+/// let mut recent: TimedDeque<&str, _, 2> = TimedDeque::new(|| ticks.get());
+///
+/// let mut try_request = |name| {
+///     recent.evict_older_than(10);
+///     recent.push_back(name).is_ok()
+/// };
+///
+/// assert!(try_request("a"));
+/// ticks.set(4);
+/// assert!(try_request("b"));
+/// ticks.set(8);
+/// assert!(!try_request("c"));
+/// ticks.set(11);
+/// assert!(try_request("d"));
+///
+/// assert!(recent.iter().eq([(4, &"b"), (11, &"d")]));
+/// ```
+pub struct TimedDeque<T, C, const N: usize>
+where
+    T: Default,
+    C: Clock,
+{
+    entries: ArrayDeque<Stamped<C::Instant, T>, N>,
+    clock: C,
+}
+
+impl<T, C, const N: usize> TimedDeque<T, C, N>
+where
+    T: Default,
+    C: Clock,
+{
+    /// Constructs a new, empty `TimedDeque` which reads the time from
+    /// `clock`.
+    pub fn new(clock: C) -> Self {
+        TimedDeque {
+            entries: ArrayDeque::new(),
+            clock,
+        }
+    }
+
+    /// Returns a reference to the clock.
+    #[inline]
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Returns the maximum number of elements the deque may hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of elements in the deque.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the deque is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if the deque is full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.entries.is_full()
+    }
+
+    /// Returns the oldest element and the instant it was added.
+    ///
+    /// If the deque is empty, `None` is returned.
+    pub fn front(&self) -> Option<(C::Instant, &T)> {
+        self.entries.front().map(|entry| (entry.at, &entry.item))
+    }
+
+    /// Adds an element to the back of the deque, stamped with the current
+    /// instant.
+    ///
+    /// If the deque is full, the element is returned in a [`CapacityError`].
+    pub fn push_back(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        let at = self.clock.now();
+
+        self.entries
+            .push_back(Stamped { at, item })
+            .map_err(|e| e.map(|entry| entry.item))
+    }
+
+    /// Removes the oldest element and returns it with the instant it was
+    /// added.
+    ///
+    /// If the deque is empty, `None` is returned.
+    pub fn pop_front(&mut self) -> Option<(C::Instant, T)> {
+        self.entries.pop_front().map(|entry| (entry.at, entry.item))
+    }
+
+    /// Removes every element added more than `window` before the present,
+    /// returning how many were removed.
+    pub fn evict_older_than(&mut self, window: C::Duration) -> usize {
+        let n = self.first_within(window);
+
+        if let Some(drain) = self.entries.drain_front(n) {
+            drain.for_each(drop);
+        }
+
+        n
+    }
+
+    /// Returns the number of elements added no more than `window` before the
+    /// present.
+    pub fn count_within(&self, window: C::Duration) -> usize {
+        self.len() - self.first_within(window)
+    }
+
+    /// Returns an iterator over the elements and the instants they were
+    /// added, from oldest to newest.
+    pub fn iter(&self) -> Iter<'_, C::Instant, T, N> {
+        Iter {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Returns an iterator over the elements added no more than `window`
+    /// before the present, from oldest to newest.
+    pub fn iter_within(&self, window: C::Duration) -> Iter<'_, C::Instant, T, N> {
+        let mut iter = self.iter();
+        for _ in 0..self.first_within(window) {
+            iter.inner.next();
+        }
+
+        iter
+    }
+
+    /// Removes all elements from the deque.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns the index of the oldest element within `window`, or the
+    /// length of the deque if there is none.
+    fn first_within(&self, window: C::Duration) -> usize {
+        let cutoff = match self.clock.checked_sub(self.clock.now(), window) {
+            Some(cutoff) => cutoff,
+            // The window reaches back before the clock began.
+            None => return 0,
+        };
+        let is_old = |entry: &Stamped<C::Instant, T>| entry.at < cutoff;
+
+        let (front, back) = self.entries.as_slices();
+        match back.first() {
+            Some(entry) if is_old(entry) => front.len() + back.partition_point(is_old),
+            _ => front.partition_point(is_old),
+        }
+    }
+}
+
+impl<T, C, const N: usize> Default for TimedDeque<T, C, N>
+where
+    T: Default,
+    C: Clock + Default,
+{
+    fn default() -> Self {
+        TimedDeque::new(C::default())
+    }
+}
+
+impl<T, C, const N: usize> fmt::Debug for TimedDeque<T, C, N>
+where
+    T: Default,
+    C: Clock,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimedDeque")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An iterator over the elements of a [`TimedDeque`] and the instants they
+/// were added.
+///
+/// This struct is created by the [`iter`] and [`iter_within`] methods on
+/// [`TimedDeque`].
+///
+/// [`iter`]: TimedDeque::iter
+/// [`iter_within`]: TimedDeque::iter_within
+pub struct Iter<'a, I, T, const N: usize>
+where
+    I: Default,
+    T: Default,
+{
+    inner: array_deque::Iter<'a, Stamped<I, T>, N>,
+}
+
+impl<'a, I, T, const N: usize> Iterator for Iter<'a, I, T, N>
+where
+    I: Copy + Default,
+    T: Default,
+{
+    type Item = (I, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| (entry.at, &entry.item))
+    }
+}
+
+impl<'a, I, T, const N: usize> DoubleEndedIterator for Iter<'a, I, T, N>
+where
+    I: Copy + Default,
+    T: Default,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|entry| (entry.at, &entry.item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::cell::Cell;
+
+    #[test]
+    fn window_spans_wrapped_storage() {
+        let ticks = Cell::new(0);
+        let mut deque: TimedDeque<u32, _, 4> = TimedDeque::new(|| ticks.get());
+
+        for i in 0..4 {
+            ticks.set(i * 10);
+            deque.push_back(i as u32).unwrap();
+        }
+        deque.pop_front();
+        deque.pop_front();
+        ticks.set(40);
+        deque.push_back(4).unwrap();
+        ticks.set(50);
+        deque.push_back(5).unwrap();
+
+        // Stamps 20 and 30 are at the end of the backing array, 40 and 50 at
+        // the start.
+        assert_eq!(deque.count_within(100), 4);
+        assert_eq!(deque.count_within(25), 3);
+        assert_eq!(deque.count_within(15), 2);
+        assert_eq!(deque.count_within(5), 1);
+        assert!(deque.iter_within(25).map(|(_, &i)| i).eq([3, 4, 5]));
+
+        assert_eq!(deque.evict_older_than(15), 2);
+        assert_eq!(deque.front(), Some((40, &4)));
+    }
+
+    #[test]
+    fn window_boundary_is_inclusive() {
+        let ticks = Cell::new(5);
+        let mut deque: TimedDeque<u32, _, 2> = TimedDeque::new(|| ticks.get());
+
+        deque.push_back(1).unwrap();
+        ticks.set(8);
+
+        assert_eq!(deque.count_within(3), 1);
+        assert_eq!(deque.count_within(2), 0);
+        assert_eq!(deque.evict_older_than(3), 0);
+    }
+
+    #[test]
+    fn full_deque_rejects() {
+        let mut deque: TimedDeque<u32, _, 1> = TimedDeque::new(|| 0);
+
+        deque.push_back(1).unwrap();
+        assert_eq!(deque.push_back(2).unwrap_err().into_inner(), 2);
+        assert_eq!(deque.pop_front(), Some((0, 1)));
+    }
+}
@@ -0,0 +1,335 @@
+//! A fixed-capacity hierarchical timer wheel.
+
+use core::{array, fmt};
+
+use crate::{ArrayDeque, CapacityError};
+
+#[derive(Default)]
+struct Timer<T> {
+    /// The tick on which the timer expires.
+    deadline: u64,
+    item: T,
+}
+
+/// A hierarchical timer wheel with `LEVELS` levels of `SLOTS` slots, each
+/// slot holding up to `N` timers.
+///
+/// Each slot is an [`ArrayDeque`], so the wheel never allocates. A slot on
+/// level 0 covers a single tick, and a slot on each higher level covers as
+/// many ticks as a whole revolution of the level below it, so the wheel spans
+/// `SLOTS.pow(LEVELS)` ticks. A timer is placed on the lowest level which
+/// reaches its deadline. When the wheel reaches the start of a slot on a
+/// higher level, the timers in that slot cascade down to the levels below,
+/// so each tick only visits timers which expire or cascade on that tick,
+/// rather than every timer sharing a slot.
+///
+/// Timers further in the future than the wheel spans are held on the top
+/// level and placed again each time they cascade. If a timer cascades into a
+/// slot which is full, it stays on its level until that level's next
+/// revolution, and expires late. Timers which expire on the same tick are
+/// returned in no particular order.
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::timer_wheel::TimerWheel;
+/// let mut wheel: TimerWheel<&str, 4, 8> = TimerWheel::new();
+///
+/// wheel.insert_after(1, "retransmit").unwrap();
+/// wheel.insert_after(6, "keepalive").unwrap();
+/// wheel.insert_after(2, "ack").unwrap();
+///
+/// assert!(wheel.tick().eq(["retransmit"]));
+/// assert!(wheel.tick().eq(["ack"]));
+/// for _ in 2..5 {
+///     assert_eq!(wheel.tick().count(), 0);
+/// }
+/// assert!(wheel.tick().eq(["keepalive"]));
+/// assert!(wheel.is_empty());
+/// ```
+pub struct TimerWheel<T, const SLOTS: usize, const N: usize, const LEVELS: usize = 4>
+where
+    T: Default,
+{
+    levels: [[ArrayDeque<Timer<T>, N>; SLOTS]; LEVELS],
+    /// The number of ticks since the wheel was created.
+    now: u64,
+    len: usize,
+}
+
+impl<T, const SLOTS: usize, const N: usize, const LEVELS: usize> TimerWheel<T, SLOTS, N, LEVELS>
+where
+    T: Default,
+{
+    /// Constructs a new, empty `TimerWheel`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `SLOTS` is less than two or `LEVELS` is zero.
+    pub fn new() -> Self {
+        assert!(SLOTS >= 2, "a timer wheel must have at least two slots");
+        assert!(LEVELS > 0, "a timer wheel must have at least one level");
+
+        TimerWheel {
+            levels: array::from_fn(|_| array::from_fn(|_| ArrayDeque::new())),
+            now: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of pending timers.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no pending timers.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of ticks covered by one slot on `level`, saturating
+    /// at `u64::MAX`.
+    fn span(level: usize) -> u64 {
+        (SLOTS as u64).saturating_pow(level as u32)
+    }
+
+    /// Returns the level and slot in which a timer expiring on `deadline`
+    /// belongs.
+    fn position(&self, deadline: u64) -> (usize, usize) {
+        // Overdue timers expire on the current tick.
+        let deadline = deadline.max(self.now);
+        let delta = deadline - self.now;
+
+        let (level, placed) = match (0..LEVELS).find(|&level| delta < Self::span(level + 1)) {
+            Some(level) => (level, deadline),
+            // Beyond the reach of the wheel, the timer waits in the top-level
+            // slot reached last, and is placed again when it cascades.
+            None => (LEVELS - 1, self.now.saturating_add(Self::span(LEVELS) - 1)),
+        };
+
+        (level, (placed / Self::span(level) % SLOTS as u64) as usize)
+    }
+
+    /// Schedules `item` to be returned by the `ticks`-th subsequent call to
+    /// [`tick`].
+    ///
+    /// A `ticks` of zero is treated as one, so the item is returned by the
+    /// next call to `tick`. If the slot the timer belongs in is full, the item
+    /// is returned in a [`CapacityError`].
+    ///
+    /// [`tick`]: TimerWheel::tick
+    pub fn insert_after(&mut self, ticks: usize, item: T) -> Result<(), CapacityError<T>> {
+        let deadline = self.now.saturating_add(ticks.max(1) as u64);
+        let (level, slot) = self.position(deadline);
+
+        self.levels[level][slot]
+            .push_back(Timer { deadline, item })
+            .map_err(|e| e.map(|timer| timer.item))?;
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Moves the timers in the current slot of `level` to the levels below.
+    fn cascade(&mut self, level: usize) {
+        let slot = (self.now / Self::span(level) % SLOTS as u64) as usize;
+
+        for _ in 0..self.levels[level][slot].len() {
+            let timer = self.levels[level][slot].pop_front().unwrap();
+            let (to_level, to_slot) = self.position(timer.deadline);
+
+            // A timer which does not fit stays in this slot, which has room
+            // as it was just popped, until the level's next revolution.
+            if let Err(e) = self.levels[to_level][to_slot].push_back(timer) {
+                self.levels[level][slot].push_back_expect(e.into_inner());
+            }
+        }
+    }
+
+    /// Advances the wheel by one tick, returning an iterator over the timers
+    /// which expired.
+    ///
+    /// If the iterator is dropped before it is exhausted, the remaining
+    /// expired items are dropped.
+    pub fn tick(&mut self) -> Expired<'_, T, N> {
+        self.now += 1;
+
+        for level in (1..LEVELS).rev() {
+            if self.now % Self::span(level) == 0 {
+                self.cascade(level);
+            }
+        }
+
+        let slot = (self.now % SLOTS as u64) as usize;
+
+        Expired {
+            slot: &mut self.levels[0][slot],
+            wheel_len: &mut self.len,
+        }
+    }
+
+    /// Removes all pending timers.
+    pub fn clear(&mut self) {
+        for slot in self.levels.iter_mut().flatten() {
+            slot.clear();
+        }
+        self.len = 0;
+    }
+}
+
+impl<T, const SLOTS: usize, const N: usize, const LEVELS: usize> Default
+    for TimerWheel<T, SLOTS, N, LEVELS>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        TimerWheel::new()
+    }
+}
+
+impl<T, const SLOTS: usize, const N: usize, const LEVELS: usize> fmt::Debug
+    for TimerWheel<T, SLOTS, N, LEVELS>
+where
+    T: Default,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimerWheel")
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An iterator over the timers expired by a tick of a [`TimerWheel`].
+///
+/// This struct is created by the [`tick`] method on [`TimerWheel`].
+///
+/// [`tick`]: TimerWheel::tick
+pub struct Expired<'a, T, const N: usize>
+where
+    T: Default,
+{
+    slot: &'a mut ArrayDeque<Timer<T>, N>,
+    wheel_len: &'a mut usize,
+}
+
+impl<'a, T, const N: usize> Iterator for Expired<'a, T, N>
+where
+    T: Default,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        // Every timer in a level 0 slot expires when the wheel reaches it.
+        let timer = self.slot.pop_front()?;
+        *self.wheel_len -= 1;
+
+        Some(timer.item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.slot.len(), Some(self.slot.len()))
+    }
+}
+
+impl<'a, T, const N: usize> Drop for Expired<'a, T, N>
+where
+    T: Default,
+{
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    fn expire_all<const SLOTS: usize, const N: usize, const LEVELS: usize>(
+        wheel: &mut TimerWheel<u32, SLOTS, N, LEVELS>,
+        ticks: u32,
+    ) -> Vec<(u32, u32)> {
+        let mut expired = Vec::new();
+        for tick in 1..=ticks {
+            for item in wheel.tick() {
+                expired.push((tick, item));
+            }
+        }
+
+        expired
+    }
+
+    #[test]
+    fn expires_in_order() {
+        let mut wheel: TimerWheel<u32, 3, 4> = TimerWheel::new();
+
+        for ticks in [7, 0, 3, 1, 3, 10].iter().copied() {
+            wheel.insert_after(ticks as usize, ticks).unwrap();
+        }
+        assert_eq!(wheel.len(), 6);
+
+        assert_eq!(
+            expire_all(&mut wheel, 10),
+            [(1, 0), (1, 1), (3, 3), (3, 3), (7, 7), (10, 10)]
+        );
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn timers_cascade_through_levels() {
+        let mut wheel: TimerWheel<u32, 2, 4, 2> = TimerWheel::new();
+
+        // The wheel spans 4 ticks, so 11 is placed again after cascading.
+        for ticks in [11, 5, 2, 4].iter().copied() {
+            wheel.insert_after(ticks as usize, ticks).unwrap();
+        }
+        wheel.tick();
+        wheel.insert_after(5, 6).unwrap();
+
+        let mut expired = expire_all(&mut wheel, 11);
+        expired.sort_unstable();
+        let expected: Vec<_> = [2, 4, 5, 6, 11].iter().map(|&t| (t - 1, t)).collect();
+        assert_eq!(expired, expected);
+    }
+
+    #[test]
+    fn full_slot_rejects() {
+        let mut wheel: TimerWheel<u32, 2, 1> = TimerWheel::new();
+
+        wheel.insert_after(1, 1).unwrap();
+        assert_eq!(wheel.insert_after(1, 2).unwrap_err().into_inner(), 2);
+        wheel.insert_after(3, 3).unwrap();
+        assert_eq!(wheel.len(), 2);
+    }
+
+    #[test]
+    fn full_slot_delays_cascade() {
+        let mut wheel: TimerWheel<u32, 2, 1, 2> = TimerWheel::new();
+
+        wheel.insert_after(2, 1).unwrap();
+        assert_eq!(wheel.tick().count(), 0);
+        wheel.insert_after(1, 2).unwrap();
+
+        // Timer 1 cannot cascade into the slot held by timer 2, so it waits
+        // for the next revolution of level 1.
+        assert_eq!(expire_all(&mut wheel, 5), [(1, 2), (5, 1)]);
+    }
+
+    #[test]
+    fn dropped_iterator_expires_timers() {
+        let mut wheel: TimerWheel<u32, 2, 4> = TimerWheel::new();
+
+        wheel.insert_after(1, 1).unwrap();
+        wheel.insert_after(1, 2).unwrap();
+        wheel.insert_after(3, 3).unwrap();
+        drop(wheel.tick());
+
+        assert_eq!(wheel.len(), 1);
+        assert_eq!(wheel.tick().count(), 0);
+        assert!(wheel.tick().eq([3]));
+    }
+}
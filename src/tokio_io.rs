@@ -0,0 +1,256 @@
+//! A bounded byte buffer which implements Tokio's asynchronous I/O traits.
+
+use std::{
+    io,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll, Waker},
+};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::{io as deque_io, ArrayDeque};
+
+struct State<const N: usize> {
+    bytes: ArrayDeque<u8, N>,
+    // Woken when bytes are written or the writer shuts down.
+    read_waker: Option<Waker>,
+    // Woken when bytes are read.
+    write_waker: Option<Waker>,
+    shutdown: bool,
+}
+
+/// A fixed-capacity byte buffer shared between a reader and a writer.
+///
+/// Writing with [`AsyncWrite`] appends bytes to the back of the buffer, and
+/// returns `Pending` while the buffer is full. Reading with [`AsyncRead`]
+/// drains bytes from the front, and returns `Pending` while the buffer is
+/// empty. This allows a `SharedByteDeque` to sit between a producer, such as a
+/// codec, and a consumer, such as a socket, as a bounded staging buffer.
+///
+/// Cloning a `SharedByteDeque` produces another handle to the same buffer.
+/// Once any handle is shut down with [`AsyncWrite::poll_shutdown`], reads
+/// return end-of-file after the buffer is drained, and further writes fail
+/// with [`io::ErrorKind::BrokenPipe`].
+///
+/// Only the most recently blocked reader and the most recently blocked
+/// writer are woken, so the buffer is intended to be used by one task on
+/// each side.
+///
+/// # Example
+///
+/// ```
+/// # use holodeque::tokio_io::SharedByteDeque;
+/// use tokio::io::{AsyncReadExt, AsyncWriteExt};
+///
+/// # futures::executor::block_on(async {
+/// let mut writer: SharedByteDeque<4> = SharedByteDeque::new();
+/// let mut reader = writer.clone();
+///
+/// let produce = async move {
+///     writer.write_all(b"hello, world").await.unwrap();
+///     writer.shutdown().await.unwrap();
+/// };
+///
+/// let consume = async move {
+///     let mut received = Vec::new();
+///     reader.read_to_end(&mut received).await.unwrap();
+///     received
+/// };
+///
+/// let (_, received) = futures::join!(produce, consume);
+/// assert_eq!(received, b"hello, world");
+/// # });
+/// ```
+pub struct SharedByteDeque<const N: usize> {
+    shared: Arc<Mutex<State<N>>>,
+}
+
+impl<const N: usize> SharedByteDeque<N> {
+    /// Constructs a new, empty `SharedByteDeque<N>`.
+    pub fn new() -> Self {
+        SharedByteDeque {
+            shared: Arc::new(Mutex::new(State {
+                bytes: ArrayDeque::new_copied(),
+                read_waker: None,
+                write_waker: None,
+                shutdown: false,
+            })),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<N>> {
+        // Every operation leaves the state consistent, so poisoning can be
+        // ignored.
+        self.shared.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the maximum number of bytes the buffer may hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of bytes in the buffer.
+    pub fn len(&self) -> usize {
+        self.lock().bytes.len()
+    }
+
+    /// Returns `true` if the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().bytes.is_empty()
+    }
+
+    /// Returns `true` if the buffer has been shut down for writing.
+    pub fn is_shutdown(&self) -> bool {
+        self.lock().shutdown
+    }
+}
+
+impl<const N: usize> Clone for SharedByteDeque<N> {
+    fn clone(&self) -> Self {
+        SharedByteDeque {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<const N: usize> Default for SharedByteDeque<N> {
+    fn default() -> Self {
+        SharedByteDeque::new()
+    }
+}
+
+impl<const N: usize> AsyncRead for SharedByteDeque<N> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let mut state = self.lock();
+
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        if state.bytes.is_empty() {
+            if state.shutdown {
+                // End of file.
+                return Poll::Ready(Ok(()));
+            }
+
+            state.read_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        let copied = deque_io::copy_front_into(&mut state.bytes, buf.initialize_unfilled());
+        buf.advance(copied);
+
+        if let Some(waker) = state.write_waker.take() {
+            waker.wake();
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<const N: usize> AsyncWrite for SharedByteDeque<N> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut state = self.lock();
+
+        if state.shutdown {
+            return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+        }
+
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        if state.bytes.is_full() {
+            state.write_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        let copied = deque_io::copy_back_from(&mut state.bytes, buf);
+
+        if let Some(waker) = state.read_waker.take() {
+            waker.wake();
+        }
+
+        Poll::Ready(Ok(copied))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // Bytes are available to the reader as soon as they are written.
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut state = self.lock();
+        state.shutdown = true;
+
+        if let Some(waker) = state.read_waker.take() {
+            waker.wake();
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::{executor::block_on, future::poll_fn};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn write_pending_when_full() {
+        let mut writer: SharedByteDeque<3> = SharedByteDeque::new();
+        let mut reader = writer.clone();
+
+        block_on(async {
+            assert_eq!(writer.write(b"abcd").await.unwrap(), 3);
+
+            let poll = poll_fn(|cx| Poll::Ready(Pin::new(&mut writer).poll_write(cx, b"d"))).await;
+            assert!(poll.is_pending());
+
+            let mut buf = [0; 2];
+            assert_eq!(reader.read(&mut buf).await.unwrap(), 2);
+            assert_eq!(&buf, b"ab");
+
+            assert_eq!(writer.write(b"de").await.unwrap(), 2);
+            assert_eq!(reader.len(), 3);
+        });
+    }
+
+    #[test]
+    fn shutdown_ends_reads_and_rejects_writes() {
+        let mut writer: SharedByteDeque<4> = SharedByteDeque::new();
+        let mut reader = writer.clone();
+
+        block_on(async {
+            let mut buf = [0; 4];
+            let poll = poll_fn(|cx| {
+                let mut buf = ReadBuf::new(&mut buf);
+                Poll::Ready(Pin::new(&mut reader).poll_read(cx, &mut buf))
+            })
+            .await;
+            assert!(poll.is_pending());
+
+            writer.write_all(b"ok").await.unwrap();
+            writer.shutdown().await.unwrap();
+            assert!(reader.is_shutdown());
+
+            let err = writer.write(b"late").await.unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+
+            assert_eq!(reader.read(&mut buf).await.unwrap(), 2);
+            assert_eq!(reader.read(&mut buf).await.unwrap(), 0);
+        });
+    }
+}